@@ -26,18 +26,61 @@
 
 use std::{env, path::PathBuf};
 
+/// The parts of the Swiss Ephemeris the engine calls into.
+const SWE_SOURCES: [&str; 9] = [
+    "swecl.c",
+    "swedate.c",
+    "swehel.c",
+    "swehouse.c",
+    "swejpl.c",
+    "swemmoon.c",
+    "swemplan.c",
+    "sweph.c",
+    "swephlib.c",
+];
+
 fn main() {
+    // Only rerun if build.rs changes
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // The data model has no FFI, so there is nothing to link.
+    if env::var_os("CARGO_FEATURE_MODEL_ONLY").is_some() {
+        return;
+    }
+
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
-    
+
+    #[cfg(feature = "capi")]
+    write_c_header(&manifest_dir);
+
+    // In the browser the Swiss Ephemeris is compiled from source with Emscripten. Its
+    // in-memory filesystem takes the ephemeris files the engine writes out, so nothing
+    // touches a real disk.
+    if env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32") {
+        if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("emscripten") {
+            panic!(
+                "The Swiss Ephemeris needs a C library: build for wasm32-unknown-emscripten, \
+                 or with the `model-only` feature for other wasm targets"
+            );
+        }
+        let vendor = PathBuf::from(&manifest_dir).join("vendor");
+        for source in SWE_SOURCES {
+            println!("cargo:rerun-if-changed=vendor/{}", source);
+        }
+        cc::Build::new()
+            .files(SWE_SOURCES.iter().map(|source| vendor.join(source)))
+            .include(&vendor)
+            .warnings(false)
+            .compile("swe");
+        return;
+    }
+
     // Specify the exact path to swe.lib
     let lib_path = PathBuf::from(&manifest_dir).join("build").join("swe.lib");
 
     // Link the pre-compiled library
     println!("cargo:rustc-link-search=native={}", lib_path.parent().unwrap().display());
     println!("cargo:rustc-link-lib=static=swe");
-
-    // Only rerun if build.rs changes
-    println!("cargo:rerun-if-changed=build.rs");
 }
 
 //     //to avoid unecessary rebuilds. only rebuild if the ./src is modified
@@ -104,4 +147,35 @@ fn main() {
 //     bindings
 //         .write_to_file(out_path.join("bindings.rs"))
 //         .expect("Unable to write bindings.");
-// }
\ No newline at end of file
+// }
+/// Writes the header for the C interface in `src/capi.rs` to `OUT_DIR`. A build never
+/// writes into the source tree; a test checks `include/aztro_core.h` against this one.
+#[cfg(feature = "capi")]
+fn write_c_header(manifest_dir: &str) {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    let manifest_dir = PathBuf::from(manifest_dir);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    cbindgen::Builder::new()
+        .with_src(manifest_dir.join("src").join("capi.rs"))
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("AZTRO_CORE_H")
+        .with_autogen_warning("/* Generated from src/capi.rs by cbindgen; do not edit. */")
+        .with_header(THREADING)
+        .with_documentation(true)
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(out_dir.join("aztro_core.h"));
+}
+
+/// The threading contract of the C interface, at the top of its header.
+#[cfg(feature = "capi")]
+const THREADING: &str = "\
+/*
+ * Threads: any thread may call any function. Each calling thread gets an engine of its
+ * own on its first call, kept until the thread ends. On Apple platforms and Windows the
+ * Swiss Ephemeris keeps one state for the whole process, so there calls from different
+ * threads take turns behind a lock instead of running at once.
+ *
+ * Strings returned by any call belong to the caller until handed to aztro_free_string,
+ * which may be called from any thread.
+ */";
@@ -0,0 +1,64 @@
+/*
+ * Threads: any thread may call any function. Each calling thread gets an engine of its
+ * own on its first call, kept until the thread ends. On Apple platforms and Windows the
+ * Swiss Ephemeris keeps one state for the whole process, so there calls from different
+ * threads take turns behind a lock instead of running at once.
+ *
+ * Strings returned by any call belong to the caller until handed to aztro_free_string,
+ * which may be called from any thread.
+ */
+
+#ifndef AZTRO_CORE_H
+#define AZTRO_CORE_H
+
+/* Generated from src/capi.rs by cbindgen; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The crate version, such as `"0.1.17"`. The string is static and must not be freed.
+ */
+const char *aztro_version(void);
+
+/**
+ * Frees a string returned by any other `aztro_` call. Does nothing given null.
+ *
+ * # Safety
+ *
+ * `string` must be null or a string from this library not already freed.
+ */
+void aztro_free_string(char *string);
+
+/**
+ * The [`ChartInfo`] for `birth_info`, cast with `options`, a [`ChartOptions`] in JSON, or
+ * with the default options when `options` is null.
+ *
+ * # Safety
+ *
+ * `birth_info` and `options` must each be null or a NUL-terminated string.
+ */
+char *aztro_calculate_chart_json(const char *birth_info, const char *options);
+
+/**
+ * The full [`Report`] for `birth_info`, named and gendered after its person if it has one.
+ *
+ * # Safety
+ *
+ * `birth_info` must be null or a NUL-terminated string.
+ */
+char *aztro_calculate_report_json(const char *birth_info);
+
+/**
+ * Reports for `births`, a JSON array of [`BirthInfo`], as an array holding each report or
+ * the error that stopped it, in the order given. See [`Report::calculate_batch`].
+ *
+ * # Safety
+ *
+ * `births` must be null or a NUL-terminated string.
+ */
+char *aztro_calculate_batch_json(const char *births);
+
+#endif  /* AZTRO_CORE_H */
@@ -0,0 +1,322 @@
+use super::*;
+
+/// The eight sources of bindus: the seven planets and the ascendant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AshtakavargaContributor {
+    Sun,
+    Moon,
+    Mars,
+    Mercury,
+    Jupiter,
+    Venus,
+    Saturn,
+    Lagna,
+}
+
+impl AshtakavargaContributor {
+    pub fn all() -> [AshtakavargaContributor; 8] {
+        [
+            AshtakavargaContributor::Sun,
+            AshtakavargaContributor::Moon,
+            AshtakavargaContributor::Mars,
+            AshtakavargaContributor::Mercury,
+            AshtakavargaContributor::Jupiter,
+            AshtakavargaContributor::Venus,
+            AshtakavargaContributor::Saturn,
+            AshtakavargaContributor::Lagna,
+        ]
+    }
+
+    pub fn planet(&self) -> Option<CelestialBody> {
+        match self {
+            AshtakavargaContributor::Sun => Some(CelestialBody::Sun),
+            AshtakavargaContributor::Moon => Some(CelestialBody::Moon),
+            AshtakavargaContributor::Mars => Some(CelestialBody::Mars),
+            AshtakavargaContributor::Mercury => Some(CelestialBody::Mercury),
+            AshtakavargaContributor::Jupiter => Some(CelestialBody::Jupiter),
+            AshtakavargaContributor::Venus => Some(CelestialBody::Venus),
+            AshtakavargaContributor::Saturn => Some(CelestialBody::Saturn),
+            AshtakavargaContributor::Lagna => None,
+        }
+    }
+
+    /// Lord of the kaksha (3°45' division of a sign) at `longitude`. Kakshas are ruled in
+    /// descending order of orbital speed: Saturn, Jupiter, Mars, Sun, Venus, Mercury, Moon,
+    /// then the ascendant.
+    pub fn kaksha_lord(longitude: f64) -> (u8, AshtakavargaContributor) {
+        const KAKSHA_LORDS: [AshtakavargaContributor; 8] = [
+            AshtakavargaContributor::Saturn,
+            AshtakavargaContributor::Jupiter,
+            AshtakavargaContributor::Mars,
+            AshtakavargaContributor::Sun,
+            AshtakavargaContributor::Venus,
+            AshtakavargaContributor::Mercury,
+            AshtakavargaContributor::Moon,
+            AshtakavargaContributor::Lagna,
+        ];
+        let index = ((longitude.rem_euclid(30.0) / 3.75).floor() as usize).min(7);
+        (index as u8 + 1, KAKSHA_LORDS[index])
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+/// Houses, counted from each contributor, in which it gives a bindu to `planet`. Rows follow
+/// the order of `AshtakavargaContributor`.
+fn benefic_houses(planet: CelestialBody) -> [&'static [usize]; 8] {
+    match planet {
+        CelestialBody::Sun => [
+            &[1, 2, 4, 7, 8, 9, 10, 11],
+            &[3, 6, 10, 11],
+            &[1, 2, 4, 7, 8, 9, 10, 11],
+            &[3, 5, 6, 9, 10, 11, 12],
+            &[5, 6, 9, 11],
+            &[6, 7, 12],
+            &[1, 2, 4, 7, 8, 9, 10, 11],
+            &[3, 4, 6, 10, 11, 12],
+        ],
+        CelestialBody::Moon => [
+            &[3, 6, 7, 8, 10, 11],
+            &[1, 3, 6, 7, 10, 11],
+            &[2, 3, 5, 6, 9, 10, 11],
+            &[1, 3, 4, 5, 7, 8, 10, 11],
+            &[1, 4, 7, 8, 10, 11, 12],
+            &[3, 4, 5, 7, 9, 10, 11],
+            &[3, 5, 6, 11],
+            &[3, 6, 10, 11],
+        ],
+        CelestialBody::Mars => [
+            &[3, 5, 6, 10, 11],
+            &[3, 6, 11],
+            &[1, 2, 4, 7, 8, 10, 11],
+            &[3, 5, 6, 11],
+            &[6, 10, 11, 12],
+            &[6, 8, 11, 12],
+            &[1, 4, 7, 8, 9, 10, 11],
+            &[1, 3, 6, 10, 11],
+        ],
+        CelestialBody::Mercury => [
+            &[5, 6, 9, 11, 12],
+            &[2, 4, 6, 8, 10, 11],
+            &[1, 2, 4, 7, 8, 9, 10, 11],
+            &[1, 3, 5, 6, 9, 10, 11, 12],
+            &[6, 8, 11, 12],
+            &[1, 2, 3, 4, 5, 8, 9, 11],
+            &[1, 2, 4, 7, 8, 9, 10, 11],
+            &[1, 2, 4, 6, 8, 10, 11],
+        ],
+        CelestialBody::Jupiter => [
+            &[1, 2, 3, 4, 7, 8, 9, 10, 11],
+            &[2, 5, 7, 9, 11],
+            &[1, 2, 4, 7, 8, 10, 11],
+            &[1, 2, 4, 5, 6, 9, 10, 11],
+            &[1, 2, 3, 4, 7, 8, 10, 11],
+            &[2, 5, 6, 9, 10, 11],
+            &[3, 5, 6, 12],
+            &[1, 2, 4, 5, 6, 7, 9, 10, 11],
+        ],
+        CelestialBody::Venus => [
+            &[8, 11, 12],
+            &[1, 2, 3, 4, 5, 8, 9, 11, 12],
+            &[3, 5, 6, 9, 11, 12],
+            &[3, 5, 6, 9, 11],
+            &[5, 8, 9, 10, 11],
+            &[1, 2, 3, 4, 5, 8, 9, 10, 11],
+            &[3, 4, 5, 8, 9, 10, 11],
+            &[1, 2, 3, 4, 5, 8, 9, 11],
+        ],
+        CelestialBody::Saturn => [
+            &[1, 2, 4, 7, 8, 10, 11],
+            &[3, 6, 11],
+            &[3, 5, 6, 10, 11, 12],
+            &[6, 8, 9, 10, 11, 12],
+            &[5, 6, 11, 12],
+            &[6, 11, 12],
+            &[3, 5, 6, 11],
+            &[1, 3, 4, 6, 10, 11],
+        ],
+        // Only the seven planets have an Ashtakavarga.
+        _ => [&[]; 8],
+    }
+}
+
+/// Bindus a single planet receives in each sign.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Bhinnashtakavarga {
+    pub planet: CelestialBody,
+    /// Bindus per sign, Aries first.
+    pub bindus: [u8; 12],
+    /// Whether each contributor gave a bindu to each sign, in `AshtakavargaContributor`
+    /// order.
+    pub contributions: [[bool; 12]; 8],
+}
+
+impl Bhinnashtakavarga {
+    pub fn total(&self) -> u32 {
+        self.bindus.iter().map(|&b| b as u32).sum()
+    }
+
+    pub fn bindus_in(&self, sign: ZodiacSign) -> u8 {
+        self.bindus[sign as usize]
+    }
+
+    pub fn contributed(&self, contributor: AshtakavargaContributor, sign: ZodiacSign) -> bool {
+        self.contributions[contributor as usize][sign as usize]
+    }
+}
+
+/// The kaksha a planet occupies and whether its lord gave a bindu there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KakshaInfo {
+    pub planet: CelestialBody,
+    pub sign: ZodiacSign,
+    pub kaksha: u8,
+    pub lord: AshtakavargaContributor,
+    pub has_bindu: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AshtakavargaReport {
+    pub bhinnashtakavarga: Vec<Bhinnashtakavarga>,
+    /// Sum of all seven Bhinnashtakavargas per sign, Aries first.
+    pub sarvashtakavarga: [u8; 12],
+    pub kakshas: Vec<KakshaInfo>,
+}
+
+impl AshtakavargaReport {
+    pub fn for_planet(&self, planet: CelestialBody) -> Option<&Bhinnashtakavarga> {
+        self.bhinnashtakavarga.iter().find(|b| b.planet == planet)
+    }
+
+    pub fn sarva_total(&self) -> u32 {
+        self.sarvashtakavarga.iter().map(|&b| b as u32).sum()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    pub fn calculate_ashtakavarga(&self, chart: &ChartInfo) -> AshtakavargaReport {
+        let contributor_sign = |contributor: AshtakavargaContributor| match contributor.planet() {
+            Some(body) => chart
+                .planets
+                .iter()
+                .find(|p| p.planet == body)
+                .map(|p| p.sign),
+            None => Some(chart.ascendant.sign),
+        };
+
+        let bhinnashtakavarga: Vec<Bhinnashtakavarga> = AshtakavargaContributor::all()
+            .iter()
+            .filter_map(|c| c.planet())
+            .map(|planet| {
+                let mut contributions = [[false; 12]; 8];
+                let rules = benefic_houses(planet);
+                for contributor in AshtakavargaContributor::all() {
+                    if let Some(from) = contributor_sign(contributor) {
+                        for &house in rules[contributor as usize] {
+                            contributions[contributor as usize][(from as usize + house - 1) % 12] =
+                                true;
+                        }
+                    }
+                }
+
+                let bindus = std::array::from_fn(|sign| {
+                    contributions.iter().filter(|row| row[sign]).count() as u8
+                });
+
+                Bhinnashtakavarga {
+                    planet,
+                    bindus,
+                    contributions,
+                }
+            })
+            .collect();
+
+        let sarvashtakavarga =
+            std::array::from_fn(|sign| bhinnashtakavarga.iter().map(|b| b.bindus[sign]).sum());
+
+        let kakshas = bhinnashtakavarga
+            .iter()
+            .filter_map(|bav| {
+                let position = chart.planets.iter().find(|p| p.planet == bav.planet)?;
+                let (kaksha, lord) =
+                    AshtakavargaContributor::kaksha_lord(position.longitude.degrees());
+                Some(KakshaInfo {
+                    planet: bav.planet,
+                    sign: position.sign,
+                    kaksha,
+                    lord,
+                    has_bindu: bav.contributed(lord, position.sign),
+                })
+            })
+            .collect();
+
+        AshtakavargaReport {
+            bhinnashtakavarga,
+            sarvashtakavarga,
+            kakshas,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kakshas_are_ruled_from_saturn_down_to_the_lagna() {
+        assert_eq!(
+            AshtakavargaContributor::kaksha_lord(0.0),
+            (1, AshtakavargaContributor::Saturn)
+        );
+        assert_eq!(
+            AshtakavargaContributor::kaksha_lord(41.0),
+            (3, AshtakavargaContributor::Mars)
+        );
+        assert_eq!(
+            AshtakavargaContributor::kaksha_lord(359.9),
+            (8, AshtakavargaContributor::Lagna)
+        );
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn every_chart_has_the_same_bindus_in_all() {
+        use CelestialBody::*;
+
+        let ephemeris = SwissEph::new().unwrap();
+        for birth in [
+            crate::examples::native().unwrap(),
+            crate::examples::partner().unwrap(),
+        ] {
+            let chart = ephemeris
+                .calculate_chart(&birth, HouseSystem::WholeSign)
+                .unwrap();
+            let report = ephemeris.calculate_ashtakavarga(&chart);
+
+            // Each planet's tables hold a fixed number of benefic places, wherever the
+            // contributors stand
+            for (planet, total) in [
+                (Sun, 48),
+                (Moon, 49),
+                (Mars, 39),
+                (Mercury, 54),
+                (Jupiter, 56),
+                (Venus, 52),
+                (Saturn, 39),
+            ] {
+                assert_eq!(
+                    report.for_planet(planet).unwrap().total(),
+                    total,
+                    "{:?}",
+                    planet
+                );
+            }
+            assert_eq!(report.sarva_total(), 337);
+            assert_eq!(report.kakshas.len(), 7);
+        }
+    }
+}
@@ -0,0 +1,633 @@
+use super::*;
+
+impl Aspect {
+    /// Exact angular separation of the aspect in degrees.
+    pub fn angle(&self) -> f64 {
+        match self {
+            Aspect::Conjunction => 0.0,
+            Aspect::Opposition => 180.0,
+            Aspect::Trine => 120.0,
+            Aspect::Square => 90.0,
+            Aspect::Sextile => 60.0,
+            Aspect::Quincunx => 150.0,
+            Aspect::SemiSextile => 30.0,
+        }
+    }
+
+    /// The five major aspects, those found unless minor ones are asked for.
+    pub fn all() -> impl Iterator<Item = Aspect> {
+        [
+            Aspect::Conjunction,
+            Aspect::Opposition,
+            Aspect::Trine,
+            Aspect::Square,
+            Aspect::Sextile,
+        ]
+        .into_iter()
+    }
+
+    pub fn minor() -> impl Iterator<Item = Aspect> {
+        [Aspect::Quincunx, Aspect::SemiSextile].into_iter()
+    }
+}
+
+// ---------------------------
+// ## Orb Strategies
+// ---------------------------
+
+/// Decides how far from exact an aspect between two planets may be and still count.
+pub trait OrbStrategy {
+    fn orb(&self, aspect: &Aspect, planet1: &PlanetPosition, planet2: &PlanetPosition) -> f64;
+
+    /// The aspects looked for, the five major ones unless overridden.
+    fn aspects(&self) -> Vec<Aspect> {
+        Aspect::all().collect()
+    }
+}
+
+/// The same orb for every planet pair, set per aspect.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FixedOrbs {
+    pub conjunction: f64,
+    pub opposition: f64,
+    pub trine: f64,
+    pub square: f64,
+    pub sextile: f64,
+}
+
+impl Default for FixedOrbs {
+    fn default() -> Self {
+        FixedOrbs {
+            conjunction: 10.0,
+            opposition: 10.0,
+            trine: 8.0,
+            square: 8.0,
+            sextile: 6.0,
+        }
+    }
+}
+
+impl FixedOrbs {
+    /// The orb of `aspect`. Minor aspects have none here; see [`AspectConfig`].
+    pub fn orb_for(&self, aspect: &Aspect) -> f64 {
+        match aspect {
+            Aspect::Conjunction => self.conjunction,
+            Aspect::Opposition => self.opposition,
+            Aspect::Trine => self.trine,
+            Aspect::Square => self.square,
+            Aspect::Sextile => self.sextile,
+            Aspect::Quincunx | Aspect::SemiSextile => 0.0,
+        }
+    }
+}
+
+impl OrbStrategy for FixedOrbs {
+    fn orb(&self, aspect: &Aspect, _planet1: &PlanetPosition, _planet2: &PlanetPosition) -> f64 {
+        self.orb_for(aspect)
+    }
+}
+
+/// Widens the orbs of strong and luminous planets and narrows those of weak ones.
+///
+/// Each planet gets a factor of `luminosity × (strength / reference_strength)`, clamped to
+/// `min_factor..=max_factor`; the orb for a pair is the base orb times the mean of the two
+/// factors. Planets without a strength entry are treated as having the reference strength.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct StrengthScaledOrbs {
+    pub base: FixedOrbs,
+    pub strengths: HashMap<CelestialBody, f64>,
+    pub reference_strength: f64,
+    pub min_factor: f64,
+    pub max_factor: f64,
+}
+
+impl StrengthScaledOrbs {
+    pub fn new(strengths: HashMap<CelestialBody, f64>, reference_strength: f64) -> Self {
+        StrengthScaledOrbs {
+            base: FixedOrbs::default(),
+            strengths,
+            reference_strength,
+            min_factor: 0.5,
+            max_factor: 1.5,
+        }
+    }
+
+    /// Scales by Shadbala, using the mean Shadbala of the chart as the reference.
+    pub fn from_strengths(strengths: &HashMap<CelestialBody, StrengthInfo>) -> Self {
+        let shad_bala: HashMap<CelestialBody, f64> = strengths
+            .iter()
+            .map(|(planet, info)| (*planet, info.shad_bala))
+            .collect();
+        let reference_strength = if shad_bala.is_empty() {
+            1.0
+        } else {
+            shad_bala.values().sum::<f64>() / shad_bala.len() as f64
+        };
+        StrengthScaledOrbs::new(shad_bala, reference_strength)
+    }
+
+    fn luminosity(planet: CelestialBody) -> f64 {
+        match planet {
+            CelestialBody::Sun => 1.25,
+            CelestialBody::Moon => 1.2,
+            CelestialBody::Rahu | CelestialBody::Ketu => 0.8,
+            _ => 1.0,
+        }
+    }
+
+    pub fn factor(&self, planet: CelestialBody) -> f64 {
+        let relative_strength = match self.strengths.get(&planet) {
+            Some(strength) if self.reference_strength > 0.0 => strength / self.reference_strength,
+            _ => 1.0,
+        };
+        (Self::luminosity(planet) * relative_strength).clamp(self.min_factor, self.max_factor)
+    }
+}
+
+impl OrbStrategy for StrengthScaledOrbs {
+    fn orb(&self, aspect: &Aspect, planet1: &PlanetPosition, planet2: &PlanetPosition) -> f64 {
+        let factor = (self.factor(planet1.planet) + self.factor(planet2.planet)) / 2.0;
+        self.base.orb_for(aspect) * factor
+    }
+}
+
+/// Orbs set aspect by aspect and widened or narrowed planet by planet, with the minor
+/// aspects if wanted.
+///
+/// The orb for a pair is the aspect's orb times the mean of the two planets' factors.
+/// Planets without a factor have 1.
+///
+/// ```
+/// use aztro_core::*;
+///
+/// let config = AspectConfig::default()
+///     .with_minor_aspects()
+///     .with_orb(Aspect::Trine, 6.0)
+///     .with_planet_factor(CelestialBody::Sun, 1.5);
+/// assert!(config.aspects().contains(&Aspect::Quincunx));
+/// assert_eq!(config.orb_for(&Aspect::Trine), 6.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AspectConfig {
+    /// Orbs of the five major aspects.
+    pub orbs: FixedOrbs,
+    /// Looks for the quincunx and the semi-sextile as well.
+    pub include_minor: bool,
+    pub quincunx: f64,
+    pub semi_sextile: f64,
+    pub planet_factors: HashMap<CelestialBody, f64>,
+}
+
+impl Default for AspectConfig {
+    /// The default fixed orbs, with 3° for the quincunx and 2° for the semi-sextile when
+    /// they are included.
+    fn default() -> Self {
+        AspectConfig {
+            orbs: FixedOrbs::default(),
+            include_minor: false,
+            quincunx: 3.0,
+            semi_sextile: 2.0,
+            planet_factors: HashMap::new(),
+        }
+    }
+}
+
+impl AspectConfig {
+    pub fn with_minor_aspects(mut self) -> Self {
+        self.include_minor = true;
+        self
+    }
+
+    pub fn with_orb(mut self, aspect: Aspect, orb: f64) -> Self {
+        match aspect {
+            Aspect::Conjunction => self.orbs.conjunction = orb,
+            Aspect::Opposition => self.orbs.opposition = orb,
+            Aspect::Trine => self.orbs.trine = orb,
+            Aspect::Square => self.orbs.square = orb,
+            Aspect::Sextile => self.orbs.sextile = orb,
+            Aspect::Quincunx => self.quincunx = orb,
+            Aspect::SemiSextile => self.semi_sextile = orb,
+        }
+        self
+    }
+
+    /// Scales the orb of every aspect `planet` makes by `factor`, averaged with the other
+    /// planet's.
+    pub fn with_planet_factor(mut self, planet: CelestialBody, factor: f64) -> Self {
+        self.planet_factors.insert(planet, factor);
+        self
+    }
+
+    /// The orb of `aspect` before the planets' factors.
+    pub fn orb_for(&self, aspect: &Aspect) -> f64 {
+        match aspect {
+            Aspect::Quincunx => self.quincunx,
+            Aspect::SemiSextile => self.semi_sextile,
+            major => self.orbs.orb_for(major),
+        }
+    }
+
+    pub fn factor(&self, planet: CelestialBody) -> f64 {
+        self.planet_factors.get(&planet).copied().unwrap_or(1.0)
+    }
+}
+
+impl OrbStrategy for AspectConfig {
+    fn orb(&self, aspect: &Aspect, planet1: &PlanetPosition, planet2: &PlanetPosition) -> f64 {
+        let factor = (self.factor(planet1.planet) + self.factor(planet2.planet)) / 2.0;
+        self.orb_for(aspect) * factor
+    }
+
+    fn aspects(&self) -> Vec<Aspect> {
+        let mut aspects: Vec<Aspect> = Aspect::all().collect();
+        if self.include_minor {
+            aspects.extend(Aspect::minor());
+        }
+        aspects
+    }
+}
+
+// ---------------------------
+// ## Aspect Calculations
+// ---------------------------
+
+/// Whether the aspect `angle` between two planets is growing closer or moving apart, from
+/// their longitudes and daily speeds. None when the distance between them holds.
+pub(crate) fn aspect_phase(
+    angle: f64,
+    planet1: &PlanetPosition,
+    planet2: &PlanetPosition,
+) -> Option<AspectPhase> {
+    let raw = planet2.longitude - planet1.longitude;
+    // The separation as measured the short way round, and how fast it grows
+    let (separation, rate) = if raw <= 180.0 {
+        (raw, planet2.speed - planet1.speed)
+    } else {
+        (360.0 - raw, planet1.speed - planet2.speed)
+    };
+    if rate == 0.0 {
+        None
+    } else if (separation - angle) * rate < 0.0 {
+        Some(AspectPhase::Applying)
+    } else {
+        Some(AspectPhase::Separating)
+    }
+}
+
+/// The aspect between two planets closest to exact, if any falls within its orb.
+pub(crate) fn closest_aspect(
+    planet1: &PlanetPosition,
+    planet2: &PlanetPosition,
+    orbs: &dyn OrbStrategy,
+) -> Option<AspectInfo> {
+    closest_aspect_to(planet1, planet2, AspectTarget::Planet(planet2.planet), orbs)
+}
+
+/// The aspect from `planet1` to `target` closest to exact, with `planet2` standing at the
+/// target's place.
+fn closest_aspect_to(
+    planet1: &PlanetPosition,
+    planet2: &PlanetPosition,
+    target: AspectTarget,
+    orbs: &dyn OrbStrategy,
+) -> Option<AspectInfo> {
+    let mut separation = planet2.longitude - planet1.longitude;
+    if separation > 180.0 {
+        separation = 360.0 - separation;
+    }
+
+    orbs.aspects()
+        .into_iter()
+        .map(|aspect| {
+            let deviation = (separation - aspect.angle()).abs();
+            (aspect, deviation)
+        })
+        .filter(|(aspect, deviation)| *deviation <= orbs.orb(aspect, planet1, planet2))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(aspect, deviation)| AspectInfo {
+            aspect,
+            planet1: planet1.planet,
+            planet2: planet2.planet,
+            orb: deviation,
+            phase: aspect_phase(aspect.angle(), planet1, planet2),
+            target,
+        })
+}
+
+impl ChartInfo {
+    /// Aspects from every planet to the four `angles` and to the chart's house cusps. The
+    /// angles stand still, so the phase follows the planet's own motion, and the orbs are
+    /// those of the planet with itself. Cusps falling on the ascendant or the midheaven, as
+    /// the 1st and 10th do in quadrant systems, are left to the angles.
+    pub fn aspects_to_points(&self, angles: &Angles, orbs: &dyn OrbStrategy) -> Vec<AspectInfo> {
+        let mut points = vec![
+            (
+                AspectTarget::Angle(Angle::Ascendant),
+                angles.ascendant.longitude,
+            ),
+            (
+                AspectTarget::Angle(Angle::Midheaven),
+                angles.midheaven.longitude,
+            ),
+            (AspectTarget::Angle(Angle::Vertex), angles.vertex.longitude),
+            (
+                AspectTarget::Angle(Angle::EastPoint),
+                angles.east_point.longitude,
+            ),
+        ];
+        for cusp in &self.houses {
+            let longitude = Longitude::new(cusp.longitude());
+            let on_angle = [angles.ascendant.longitude, angles.midheaven.longitude]
+                .iter()
+                .any(|angle| longitude.separation(*angle) < 1e-6);
+            if !on_angle {
+                points.push((AspectTarget::Cusp(cusp.house), longitude));
+            }
+        }
+
+        let mut aspects = Vec::new();
+        for planet in &self.planets {
+            for &(target, longitude) in &points {
+                let point = PlanetPosition {
+                    longitude,
+                    speed: 0.0,
+                    ..planet.clone()
+                };
+                aspects.extend(closest_aspect_to(planet, &point, target, orbs));
+            }
+        }
+        aspects
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Finds aspects between every pair of planets using the default fixed orbs.
+    pub fn calculate_aspects(&self, chart: &ChartInfo) -> Vec<AspectInfo> {
+        self.calculate_aspects_with(chart, &FixedOrbs::default())
+    }
+
+    /// Finds aspects between every pair of planets, taking the aspects and their orbs from
+    /// `orbs`, such as an [`AspectConfig`]. When a pair falls within the orb of more than
+    /// one aspect the closest one is reported, and `AspectInfo::orb` holds the distance from
+    /// exact.
+    pub fn calculate_aspects_with(
+        &self,
+        chart: &ChartInfo,
+        orbs: &dyn OrbStrategy,
+    ) -> Vec<AspectInfo> {
+        let mut aspects = Vec::new();
+
+        for (i, planet1) in chart.planets.iter().enumerate() {
+            for planet2 in chart.planets.iter().skip(i + 1) {
+                aspects.extend(closest_aspect(planet1, planet2, orbs));
+            }
+        }
+
+        aspects
+    }
+
+    /// Aspects from the chart's planets to its angles and house cusps, as
+    /// [`ChartInfo::aspects_to_points`], with the angles found again for `birth_info` in
+    /// the chart's house system.
+    pub fn calculate_point_aspects(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+        orbs: &dyn OrbStrategy,
+    ) -> Result<Vec<AspectInfo>, AstrologyError> {
+        let houses = self.calculate_houses(
+            CoordinateSystem::Sidereal,
+            date_to_julian_day(birth_info.date_time),
+            birth_info.location.latitude,
+            birth_info.location.longitude,
+            chart.house_system,
+        )?;
+        Ok(chart.aspects_to_points(&houses.angles, orbs))
+    }
+}
+
+// ---------------------------
+// ## Graha Drishti
+// ---------------------------
+
+/// A Vedic aspect of one graha on another, cast sign to sign.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GrahaDrishtiInfo {
+    pub from: CelestialBody,
+    pub to: CelestialBody,
+    /// Sign of `to` counted from the sign of `from`.
+    pub house: House,
+    /// Sputa drishti in virupas, from 0 to a full 60, when asked for.
+    pub strength: Option<f64>,
+}
+
+impl CelestialBody {
+    /// Signs a graha casts full aspect on, counted from its own: the 7th for all, with the
+    /// 4th and 8th for Mars, the 5th and 9th for Jupiter and the 3rd and 10th for Saturn.
+    /// The nodes are given the 7th alone. Bodies outside the nine grahas cast none.
+    pub fn drishti_houses(&self) -> &'static [House] {
+        match self {
+            CelestialBody::Mars => &[House::Fourth, House::Seventh, House::Eighth],
+            CelestialBody::Jupiter => &[House::Fifth, House::Seventh, House::Ninth],
+            CelestialBody::Saturn => &[House::Third, House::Seventh, House::Tenth],
+            CelestialBody::Sun
+            | CelestialBody::Moon
+            | CelestialBody::Mercury
+            | CelestialBody::Venus
+            | CelestialBody::Rahu
+            | CelestialBody::Ketu => &[House::Seventh],
+            _ => &[],
+        }
+    }
+}
+
+/// Sputa drishti of `planet` on a point `distance` degrees ahead of it, in virupas, after
+/// Parashara. Mars is full on the 90° to 120° and 210° to 240° arcs, Jupiter on 120° to
+/// 150° and 240° to 270°, and Saturn on 60° to 90° and 270° to 300°.
+pub fn sputa_drishti(planet: CelestialBody, distance: f64) -> f64 {
+    let distance = distance.rem_euclid(360.0);
+    let special = match planet {
+        CelestialBody::Mars => [(90.0, 120.0), (210.0, 240.0)],
+        CelestialBody::Jupiter => [(120.0, 150.0), (240.0, 270.0)],
+        CelestialBody::Saturn => [(60.0, 90.0), (270.0, 300.0)],
+        _ => [(0.0, 0.0); 2],
+    };
+    if special
+        .iter()
+        .any(|&(start, end)| distance >= start && distance < end)
+    {
+        return 60.0;
+    }
+
+    match distance {
+        d if d < 30.0 => 0.0,
+        d if d < 60.0 => (d - 30.0) / 2.0,
+        d if d < 90.0 => d - 60.0 + 15.0,
+        d if d < 120.0 => (120.0 - d) / 2.0 + 30.0,
+        d if d < 150.0 => 150.0 - d,
+        d if d < 180.0 => (d - 150.0) * 2.0,
+        d if d < 300.0 => (300.0 - d) / 2.0,
+        _ => 0.0,
+    }
+}
+
+impl ChartInfo {
+    /// Every full aspect between the chart's grahas, judged by sign. A pair can aspect each
+    /// other, or only one the other, so both directions are listed. `strength` is `None`.
+    pub fn graha_drishti(&self) -> Vec<GrahaDrishtiInfo> {
+        let mut drishti = Vec::new();
+        for from in &self.planets {
+            for to in &self.planets {
+                let counted = (to.sign as usize + 12 - from.sign as usize) % 12 + 1;
+                let Some(house) = House::from_index(counted) else {
+                    continue;
+                };
+                if from.planet != to.planet && from.planet.drishti_houses().contains(&house) {
+                    drishti.push(GrahaDrishtiInfo {
+                        from: from.planet,
+                        to: to.planet,
+                        house,
+                        strength: None,
+                    });
+                }
+            }
+        }
+        drishti
+    }
+
+    /// [`ChartInfo::graha_drishti`] with the sputa drishti of each aspect.
+    pub fn sputa_graha_drishti(&self) -> Vec<GrahaDrishtiInfo> {
+        let mut drishti = self.graha_drishti();
+        for aspect in &mut drishti {
+            if let (Some(from), Some(to)) = (self.planet(aspect.from), self.planet(aspect.to)) {
+                let distance = to.longitude.degrees() - from.longitude.degrees();
+                aspect.strength = Some(sputa_drishti(aspect.from, distance));
+            }
+        }
+        drishti
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sputa_drishti_is_full_at_the_seventh_and_the_special_aspects() {
+        assert_eq!(sputa_drishti(CelestialBody::Sun, 180.0), 60.0);
+        assert_eq!(sputa_drishti(CelestialBody::Sun, 100.0), 40.0);
+        assert_eq!(sputa_drishti(CelestialBody::Mars, 100.0), 60.0);
+        assert_eq!(sputa_drishti(CelestialBody::Jupiter, 250.0), 60.0);
+        assert_eq!(sputa_drishti(CelestialBody::Saturn, 70.0), 60.0);
+        assert_eq!(sputa_drishti(CelestialBody::Venus, 15.0), 0.0);
+        assert_eq!(sputa_drishti(CelestialBody::Venus, -60.0), 0.0);
+    }
+
+    #[test]
+    fn sputa_drishti_runs_on_without_jumps() {
+        for distance in [30.0, 60.0, 90.0, 120.0, 150.0, 180.0, 300.0] {
+            let before = sputa_drishti(CelestialBody::Moon, distance - 1e-9);
+            let after = sputa_drishti(CelestialBody::Moon, distance);
+            assert!((before - after).abs() < 1e-6, "{}°", distance);
+        }
+    }
+
+    fn planet(body: CelestialBody, longitude: f64, speed: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: speed < 0.0,
+        }
+    }
+
+    #[test]
+    fn aspect_config_sets_orbs_and_minor_aspects() {
+        let sun = planet(CelestialBody::Sun, 10.0, 1.0);
+        let moon = planet(CelestialBody::Moon, 158.0, 13.0);
+        let venus = planet(CelestialBody::Venus, 78.0, 1.2);
+
+        assert!(closest_aspect(&sun, &moon, &FixedOrbs::default()).is_none());
+        let minor = AspectConfig::default().with_minor_aspects();
+        let quincunx = closest_aspect(&sun, &moon, &minor).unwrap();
+        assert_eq!(quincunx.aspect, Aspect::Quincunx);
+        // The Moon runs on towards 150° from the Sun
+        assert_eq!(quincunx.phase, Some(AspectPhase::Applying));
+
+        let wide = AspectConfig::default().with_orb(Aspect::Sextile, 10.0);
+        let sextile = closest_aspect(&sun, &venus, &wide).unwrap();
+        assert_eq!(sextile.phase, Some(AspectPhase::Separating));
+        let narrow_sun = wide.with_planet_factor(CelestialBody::Sun, 0.5);
+        assert!(closest_aspect(&sun, &venus, &narrow_sun).is_none());
+
+        // Closing on a conjunction across 0° Aries
+        let mars = planet(CelestialBody::Mars, 350.0, 0.7);
+        let saturn = planet(CelestialBody::Saturn, 5.0, 0.0);
+        let wide_conjunction = FixedOrbs {
+            conjunction: 20.0,
+            ..FixedOrbs::default()
+        };
+        let conjunction = closest_aspect(&mars, &saturn, &wide_conjunction).unwrap();
+        assert_eq!(conjunction.phase, Some(AspectPhase::Applying));
+    }
+
+    #[test]
+    fn planets_aspect_angles_and_cusps() {
+        // Equal houses from 15° Aries, the midheaven on the 10th cusp
+        let houses = House::all()
+            .map(|house| {
+                let longitude = 15.0 + (house as usize - 1) as f64 * 30.0;
+                HouseCusp {
+                    house,
+                    sign: ZodiacSign::from_longitude(longitude),
+                    degree: longitude % 30.0,
+                }
+            })
+            .collect();
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::Equal,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 15.0,
+            },
+            houses,
+            planets: vec![planet(CelestialBody::Sun, 105.0, 1.0)],
+            warnings: Vec::new(),
+        };
+        let angles = Angles {
+            ascendant: AnglePoint::from_longitude(15.0),
+            midheaven: AnglePoint::from_longitude(285.0),
+            vertex: AnglePoint::from_longitude(250.0),
+            east_point: AnglePoint::from_longitude(40.0),
+            armc: 0.0,
+        };
+        let aspects = chart.aspects_to_points(&angles, &FixedOrbs::default());
+        let to = |target| aspects.iter().find(|aspect| aspect.target == target);
+
+        let ascendant = to(AspectTarget::Angle(Angle::Ascendant)).unwrap();
+        assert_eq!(ascendant.aspect, Aspect::Square);
+        assert_eq!(ascendant.planet2, CelestialBody::Sun);
+        assert_eq!(
+            to(AspectTarget::Angle(Angle::Midheaven)).unwrap().aspect,
+            Aspect::Opposition
+        );
+        assert_eq!(
+            to(AspectTarget::Cusp(House::Fourth)).unwrap().aspect,
+            Aspect::Conjunction
+        );
+        assert!(to(AspectTarget::Cusp(House::First)).is_none());
+        assert!(to(AspectTarget::Cusp(House::Tenth)).is_none());
+        assert!(to(AspectTarget::Angle(Angle::Vertex)).is_none());
+    }
+}
@@ -0,0 +1,239 @@
+use super::*;
+
+/// Baladi avastha, a planet's age by its degree in the sign. Each spans six degrees, from
+/// infancy at the start of an odd sign and from death at the start of an even one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BaladiAvastha {
+    /// Infant, giving a quarter of its results.
+    Bala,
+    /// Youth, giving half.
+    Kumara,
+    /// Adult, giving them in full.
+    Yuva,
+    /// Old, giving little.
+    Vriddha,
+    /// Dead, giving none.
+    Mrita,
+}
+
+impl BaladiAvastha {
+    pub fn from_longitude(longitude: Longitude) -> Self {
+        const AGES: [BaladiAvastha; 5] = [
+            BaladiAvastha::Bala,
+            BaladiAvastha::Kumara,
+            BaladiAvastha::Yuva,
+            BaladiAvastha::Vriddha,
+            BaladiAvastha::Mrita,
+        ];
+        let part = ((longitude.degree_in_sign() / 6.0) as usize).min(4);
+        // Aries, the first sign, is odd
+        if (longitude.sign() as usize).is_multiple_of(2) {
+            AGES[part]
+        } else {
+            AGES[4 - part]
+        }
+    }
+}
+
+/// Jagradadi avastha, how awake a planet is to give its results.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum JagradadiAvastha {
+    /// Awake, in its own sign or exaltation.
+    Jagrat,
+    /// Dreaming, in the sign of a friend or a neutral.
+    Swapna,
+    /// Asleep, in the sign of an enemy or in debilitation.
+    Sushupti,
+}
+
+/// Deeptadi avastha, a planet's mood by its dignity and company.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DeeptadiAvastha {
+    /// Blazing, in exaltation.
+    Deepta,
+    /// At ease, in its own sign.
+    Swastha,
+    /// Delighted, in a great friend's sign.
+    Mudita,
+    /// Calm, in a friend's sign.
+    Shanta,
+    /// Wretched, in a neutral's sign.
+    Deena,
+    /// Crippled, in the same sign as a malefic.
+    Vikala,
+    /// Distressed, in an enemy's sign.
+    Dukhita,
+    /// Wicked, in a great enemy's sign or in debilitation.
+    Khala,
+    /// Angry, combust.
+    Kopa,
+}
+
+/// The avasthas of one planet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AvasthaInfo {
+    pub planet: CelestialBody,
+    pub baladi: BaladiAvastha,
+    pub jagradadi: JagradadiAvastha,
+    pub deeptadi: DeeptadiAvastha,
+}
+
+impl ChartInfo {
+    /// Baladi, Jagradadi and Deeptadi avasthas of the nine grahas in the chart, judging the
+    /// lords of their signs by five-fold relationship under `friendships`.
+    ///
+    /// A planet with more than one Deeptadi condition takes the first of combustion,
+    /// exaltation, own sign, debilitation and a malefic beside it, then its relationship
+    /// to the lord of its sign.
+    pub fn avasthas(
+        &self,
+        friendships: &FriendshipMatrix,
+        combustion_orbs: &CombustionOrbs,
+    ) -> HashMap<CelestialBody, AvasthaInfo> {
+        self.planets
+            .iter()
+            .filter(|position| {
+                position.planet.exaltation_degree().is_some()
+                    || matches!(position.planet, CelestialBody::Rahu | CelestialBody::Ketu)
+            })
+            .map(|position| {
+                let planet = position.planet;
+                let dignity = DignityInfo::of(position, Some(self), friendships);
+                let relationship = dignity.sign_lord_relationship;
+
+                let jagradadi = if dignity.exalted || dignity.own_sign {
+                    JagradadiAvastha::Jagrat
+                } else if dignity.debilitated
+                    || matches!(
+                        relationship,
+                        Some(CompoundRelationship::Enemy | CompoundRelationship::GreatEnemy)
+                    )
+                {
+                    JagradadiAvastha::Sushupti
+                } else {
+                    JagradadiAvastha::Swapna
+                };
+
+                let with_malefic = self.planets.iter().any(|other| {
+                    other.planet != planet
+                        && other.sign == position.sign
+                        && other.planet.exaltation_degree().is_some()
+                        && !self.is_benefic(other.planet)
+                });
+                let deeptadi = if self.is_combust(planet, combustion_orbs) {
+                    DeeptadiAvastha::Kopa
+                } else if dignity.exalted {
+                    DeeptadiAvastha::Deepta
+                } else if dignity.own_sign {
+                    DeeptadiAvastha::Swastha
+                } else if dignity.debilitated {
+                    DeeptadiAvastha::Khala
+                } else if with_malefic {
+                    DeeptadiAvastha::Vikala
+                } else {
+                    match relationship {
+                        Some(CompoundRelationship::GreatFriend) => DeeptadiAvastha::Mudita,
+                        Some(CompoundRelationship::Friend) => DeeptadiAvastha::Shanta,
+                        Some(CompoundRelationship::Enemy) => DeeptadiAvastha::Dukhita,
+                        Some(CompoundRelationship::GreatEnemy) => DeeptadiAvastha::Khala,
+                        _ => DeeptadiAvastha::Deena,
+                    }
+                };
+
+                let avastha = AvasthaInfo {
+                    planet,
+                    baladi: BaladiAvastha::from_longitude(position.longitude),
+                    jagradadi,
+                    deeptadi,
+                };
+                (planet, avastha)
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Avasthas of the chart's grahas under the engine's friendships and combustion orbs.
+    pub fn calculate_avasthas(&self, chart: &ChartInfo) -> HashMap<CelestialBody, AvasthaInfo> {
+        chart.avasthas(&self.friendships, &self.conventions.combustion_orbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn avasthas_follow_degree_dignity_and_company() {
+        // Odd signs run from infancy, even signs from death
+        assert_eq!(
+            BaladiAvastha::from_longitude(Longitude::new(2.0)),
+            BaladiAvastha::Bala
+        );
+        assert_eq!(
+            BaladiAvastha::from_longitude(Longitude::new(32.0)),
+            BaladiAvastha::Mrita
+        );
+        assert_eq!(
+            BaladiAvastha::from_longitude(Longitude::new(75.0)),
+            BaladiAvastha::Yuva
+        );
+
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 0.0,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                planet(CelestialBody::Sun, 10.0),
+                planet(CelestialBody::Jupiter, 95.0),
+                planet(CelestialBody::Saturn, 200.0),
+                planet(CelestialBody::Mars, 205.0),
+                planet(CelestialBody::Uranus, 300.0),
+            ],
+            warnings: Vec::new(),
+        };
+        let avasthas = chart.avasthas(&FriendshipMatrix::natural(), &CombustionOrbs::default());
+        assert!(!avasthas.contains_key(&CelestialBody::Uranus));
+
+        let sun = &avasthas[&CelestialBody::Sun];
+        assert_eq!(sun.jagradadi, JagradadiAvastha::Jagrat);
+        assert_eq!(sun.deeptadi, DeeptadiAvastha::Deepta);
+        // Cancer is even, so Jupiter at 5° is dead though exalted
+        assert_eq!(
+            avasthas[&CelestialBody::Jupiter].baladi,
+            BaladiAvastha::Mrita
+        );
+        // Exalted Saturn shares Libra with Mars but is still blazing; Mars, in the sign of
+        // Venus beside a malefic, is crippled
+        assert_eq!(
+            avasthas[&CelestialBody::Saturn].deeptadi,
+            DeeptadiAvastha::Deepta
+        );
+        let mars = &avasthas[&CelestialBody::Mars];
+        assert_eq!(mars.deeptadi, DeeptadiAvastha::Vikala);
+        assert_eq!(mars.jagradadi, JagradadiAvastha::Swapna);
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+
+/// Mean obliquity of the ecliptic, close enough for the Sun's declination in ayana bala.
+const OBLIQUITY: f64 = 23.44;
+
+/// Ishta (good) and Kashta (bad) phala of a planet, the share of its results that will be
+/// good or bad, each out of 60 virupas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct IshtaKashta {
+    /// Out of 60, full at the point of deepest exaltation.
+    pub uchcha_bala: f64,
+    /// Out of 60. The Sun takes its ayana bala and the Moon its paksha bala.
+    pub cheshta_bala: f64,
+    /// Geometric mean of the uchcha and cheshta balas.
+    pub ishta_phala: f64,
+    /// Geometric mean of what the uchcha and cheshta balas fall short of 60.
+    pub kashta_phala: f64,
+}
+
+impl IshtaKashta {
+    pub fn new(uchcha_bala: f64, cheshta_bala: f64) -> Self {
+        IshtaKashta {
+            uchcha_bala,
+            cheshta_bala,
+            ishta_phala: (uchcha_bala * cheshta_bala).sqrt(),
+            kashta_phala: ((60.0 - uchcha_bala) * (60.0 - cheshta_bala)).sqrt(),
+        }
+    }
+}
+
+/// Strength of a bhava in virupas, from its lord, its direction and the aspects on its
+/// madhya.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BhavaBala {
+    pub bhava: House,
+    /// The dignity score of the bhava's lord, standing in for its Shadbala, which the
+    /// crate does not compute.
+    pub bhavadhipati_bala: f64,
+    /// Out of 60, full when the sign of the madhya is in its strongest bhava: human signs
+    /// in the 1st, watery signs in the 4th, Scorpio in the 7th and four-footed signs in the
+    /// 10th.
+    pub digbala: f64,
+    /// A quarter of the sputa drishti of each benefic on the madhya less that of each
+    /// malefic, with Jupiter's and Mercury's counted in full.
+    pub drishti_bala: f64,
+    pub total: f64,
+}
+
+impl ChartInfo {
+    /// Ishta and Kashta phala of the seven planets in the chart. The Sun's declination,
+    /// for its ayana bala, is found from its sidereal longitude and `ayanamsa`.
+    ///
+    /// The five star planets take their cheshta bala from their [`Motion`]: 60 when
+    /// retrograde, 15 when stationary or very slow, 30 when slow, 7.5 at the average, and
+    /// when fast 30, or 45 above half as fast again as the mean.
+    pub fn ishta_kashta(&self, ayanamsa: f64) -> HashMap<CelestialBody, IshtaKashta> {
+        self.planets
+            .iter()
+            .filter_map(|position| {
+                let exaltation = position.planet.exaltation_degree()?;
+                let distance = position.longitude.separation(Longitude::new(exaltation));
+                let uchcha_bala = (180.0 - distance) / 3.0;
+                let cheshta_bala = self.cheshta_bala(position, ayanamsa)?;
+                Some((position.planet, IshtaKashta::new(uchcha_bala, cheshta_bala)))
+            })
+            .collect()
+    }
+
+    fn cheshta_bala(&self, position: &PlanetPosition, ayanamsa: f64) -> Option<f64> {
+        match position.planet {
+            CelestialBody::Sun => {
+                let tropical = (position.longitude.degrees() + ayanamsa).to_radians();
+                let declination = (OBLIQUITY.to_radians().sin() * tropical.sin())
+                    .asin()
+                    .to_degrees();
+                Some((24.0 + declination) / 48.0 * 60.0)
+            }
+            CelestialBody::Moon => {
+                let sun = self.planet(CelestialBody::Sun)?;
+                Some(position.longitude.separation(sun.longitude) / 3.0)
+            }
+            CelestialBody::Mars
+            | CelestialBody::Mercury
+            | CelestialBody::Jupiter
+            | CelestialBody::Venus
+            | CelestialBody::Saturn => {
+                let ratio = position.speed_ratio()?;
+                Some(match Motion::from_speed_ratio(ratio) {
+                    _ if ratio < 0.0 => 60.0,
+                    Motion::Stationary | Motion::VerySlow => 15.0,
+                    Motion::Slow => 30.0,
+                    Motion::Average => 7.5,
+                    Motion::Fast if ratio <= 1.5 => 30.0,
+                    Motion::Fast => 45.0,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Bhava bala of the twelve bhavas on the chart's cusps, the 1st first, with the lords
+    /// judged by `friendships`. Empty if the chart has no cusps.
+    pub fn bhava_bala(&self, friendships: &FriendshipMatrix) -> Vec<BhavaBala> {
+        self.bhavas()
+            .into_iter()
+            .map(|bhava| {
+                let bhavadhipati_bala = self.planet(bhava.lord).map_or(0.0, |lord| {
+                    DignityInfo::of(lord, Some(self), friendships).score
+                });
+
+                let madhya = bhava.madhya;
+                let strongest = match (madhya.sign(), madhya.degree_in_sign() < 15.0) {
+                    (
+                        ZodiacSign::Gemini
+                        | ZodiacSign::Virgo
+                        | ZodiacSign::Libra
+                        | ZodiacSign::Aquarius,
+                        _,
+                    )
+                    | (ZodiacSign::Sagittarius, true) => 1,
+                    (ZodiacSign::Cancer | ZodiacSign::Pisces, _)
+                    | (ZodiacSign::Capricorn, false) => 4,
+                    (ZodiacSign::Scorpio, _) => 7,
+                    _ => 10,
+                };
+                let apart = (bhava.bhava as usize + 12 - strongest) % 12;
+                let digbala = (6.0 - apart.min(12 - apart) as f64) * 10.0;
+
+                let drishti_bala = self
+                    .planets
+                    .iter()
+                    .filter(|planet| planet.planet.exaltation_degree().is_some())
+                    .map(|planet| {
+                        let distance = madhya.degrees() - planet.longitude.degrees();
+                        let drishti = sputa_drishti(planet.planet, distance);
+                        match planet.planet {
+                            CelestialBody::Jupiter | CelestialBody::Mercury => drishti,
+                            body if self.is_benefic(body) => drishti / 4.0,
+                            _ => -drishti / 4.0,
+                        }
+                    })
+                    .sum();
+
+                BhavaBala {
+                    bhava: bhava.bhava,
+                    bhavadhipati_bala,
+                    digbala,
+                    drishti_bala,
+                    total: bhavadhipati_bala + digbala + drishti_bala,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ishta_and_kashta_are_complementary() {
+        let full = IshtaKashta::new(60.0, 60.0);
+        assert_eq!((full.ishta_phala, full.kashta_phala), (60.0, 0.0));
+        let none = IshtaKashta::new(0.0, 45.0);
+        assert_eq!(
+            (none.ishta_phala, none.kashta_phala),
+            (0.0, (60.0f64 * 15.0).sqrt())
+        );
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn the_report_ranks_the_bhavas() {
+        let report = crate::examples::natal_report().unwrap();
+        assert_eq!(report.bhava_bala.len(), 12);
+        assert_eq!(report.ishta_kashta.len(), 7);
+        for phala in report.ishta_kashta.values() {
+            assert!((0.0..=60.0).contains(&phala.ishta_phala));
+            assert!((0.0..=60.0).contains(&phala.kashta_phala));
+        }
+        let ranked = report.bhavas_by_strength();
+        assert!(ranked.windows(2).all(|pair| pair[0].total >= pair[1].total));
+    }
+}
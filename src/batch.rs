@@ -0,0 +1,86 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A browser gives a WebAssembly module no threads of its own to spawn.
+const THREADED: bool = !cfg!(target_family = "wasm");
+
+impl Report {
+    /// Reports for many births, in the order given. Names and genders come from each
+    /// birth's [`Person`]; without one, a report has an empty name and
+    /// [`Gender::Unspecified`].
+    ///
+    /// The work is shared among one worker thread per CPU, each with its own engine. Where
+    /// Swiss Ephemeris cannot run on several threads at once, a single worker is used, and
+    /// on WebAssembly the reports are worked out one by one on the calling thread.
+    pub fn calculate_batch(births: &[BirthInfo]) -> Vec<Result<Report, AstrologyError>> {
+        let _timed = Timed::start(
+            TimedWork::BatchReport,
+            tracing::debug_span!("batch_report", births = births.len()),
+        );
+        if !THREADED {
+            let ephemeris = SwissEph::new();
+            return births
+                .iter()
+                .map(|birth| batch_report(&ephemeris, birth))
+                .collect();
+        }
+
+        let workers = if THREAD_LOCAL_EPHEMERIS {
+            std::thread::available_parallelism()
+                .map_or(1, |count| count.get())
+                .min(births.len())
+        } else {
+            1
+        };
+        let next = AtomicUsize::new(0);
+
+        let finished: Vec<Vec<(usize, Result<Report, AstrologyError>)>> =
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..workers)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let ephemeris = SwissEph::new();
+                            let mut done = Vec::new();
+                            loop {
+                                let index = next.fetch_add(1, Ordering::Relaxed);
+                                let Some(birth) = births.get(index) else {
+                                    break;
+                                };
+                                done.push((index, batch_report(&ephemeris, birth)));
+                            }
+                            done
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                    .collect()
+            });
+
+        let mut reports: Vec<Option<Result<Report, AstrologyError>>> =
+            births.iter().map(|_| None).collect();
+        for (index, report) in finished.into_iter().flatten() {
+            reports[index] = Some(report);
+        }
+        reports.into_iter().flatten().collect()
+    }
+}
+
+fn batch_report(
+    ephemeris: &Result<SwissEph, AstrologyError>,
+    birth: &BirthInfo,
+) -> Result<Report, AstrologyError> {
+    let name = birth
+        .person
+        .as_ref()
+        .map_or("", |person| person.name.as_str());
+    match ephemeris {
+        Ok(ephemeris) => Report::calculate_with(ephemeris, name, birth.clone(), birth.gender()),
+        Err(error) => Err(error.clone()),
+    }
+}
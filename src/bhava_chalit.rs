@@ -0,0 +1,259 @@
+use super::*;
+
+/// Where the middle of each bhava falls in a Bhava Chalit chart.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BhavaChalitMethod {
+    /// The lagna and the midheaven are the middles of the 1st and 10th bhavas, and each
+    /// quadrant between the angles is trisected.
+    #[default]
+    Sripati,
+    /// The middle of each bhava is 30° on from the last, starting at the lagna degree.
+    EqualFromLagna,
+}
+
+/// One bhava, from the sandhi before its middle to the sandhi after.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BhavaChalitHouse {
+    pub house: House,
+    /// Sandhi where the bhava begins, midway between its middle and the one before.
+    pub start: Longitude,
+    /// Bhava madhya, the bhava's most sensitive point.
+    pub madhya: Longitude,
+    pub end: Longitude,
+}
+
+/// A planet's house by sign and by bhava. The two often differ for planets near the start
+/// or end of a sign.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BhavaChalitPlanet {
+    pub planet: CelestialBody,
+    /// Sign counted from the lagna's sign.
+    pub rasi_house: House,
+    /// Bhava whose sandhis enclose the planet.
+    pub bhava: House,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BhavaChalitChart {
+    pub method: BhavaChalitMethod,
+    /// From the 1st bhava to the 12th.
+    pub bhavas: Vec<BhavaChalitHouse>,
+    pub planets: Vec<BhavaChalitPlanet>,
+}
+
+impl BhavaChalitChart {
+    /// The chalit chart of `chart`'s planets given the twelve bhava madhyas, the 1st
+    /// first. Sandhis fall halfway along the arc from one madhya to the next.
+    pub fn from_madhyas(
+        method: BhavaChalitMethod,
+        chart: &ChartInfo,
+        madhyas: &[f64; 12],
+    ) -> BhavaChalitChart {
+        let sandhi =
+            |from: f64, to: f64| Longitude::new(from + (to - from).rem_euclid(360.0) / 2.0);
+        let bhavas: Vec<BhavaChalitHouse> = House::all()
+            .enumerate()
+            .map(|(index, house)| BhavaChalitHouse {
+                house,
+                start: sandhi(madhyas[(index + 11) % 12], madhyas[index]),
+                madhya: Longitude::new(madhyas[index]),
+                end: sandhi(madhyas[index], madhyas[(index + 1) % 12]),
+            })
+            .collect();
+
+        let lagna = chart.ascendant.sign as usize;
+        let planets = chart
+            .planets
+            .iter()
+            .map(|position| {
+                let longitude = position.longitude.degrees();
+                let bhava = bhavas
+                    .iter()
+                    .find(|bhava| {
+                        let start = bhava.start.degrees();
+                        (longitude - start).rem_euclid(360.0)
+                            < (bhava.end.degrees() - start).rem_euclid(360.0)
+                    })
+                    .map_or(House::First, |bhava| bhava.house);
+                BhavaChalitPlanet {
+                    planet: position.planet,
+                    rasi_house: House::from_index((position.sign as usize + 12 - lagna) % 12 + 1)
+                        .unwrap_or(House::First),
+                    bhava,
+                }
+            })
+            .collect();
+
+        BhavaChalitChart {
+            method,
+            bhavas,
+            planets,
+        }
+    }
+
+    /// Planets whose bhava is not the house their sign gives them.
+    pub fn shifted_planets(&self) -> impl Iterator<Item = &BhavaChalitPlanet> {
+        self.planets
+            .iter()
+            .filter(|planet| planet.rasi_house != planet.bhava)
+    }
+}
+
+/// Degrees either side of a sandhi within which a planet is held to sit in it.
+pub const BHAVA_SANDHI_ORB: f64 = 1.0;
+
+impl ChartInfo {
+    /// The chart's bhavas on its own house cusps, each running from its cusp to the next
+    /// with the madhya halfway between. Empty if the chart has no cusps.
+    pub fn bhavas(&self) -> Vec<BhavaInfo> {
+        if self.houses.len() != 12 {
+            return Vec::new();
+        }
+        self.houses
+            .iter()
+            .enumerate()
+            .map(|(index, cusp)| {
+                let start = cusp.longitude();
+                let width = (self.houses[(index + 1) % 12].longitude() - start).rem_euclid(360.0);
+                let mut planets = Vec::new();
+                let mut sandhi_planets = Vec::new();
+                for position in &self.planets {
+                    let offset = (position.longitude.degrees() - start).rem_euclid(360.0);
+                    if offset < width {
+                        planets.push(position.planet);
+                        if offset < BHAVA_SANDHI_ORB || width - offset < BHAVA_SANDHI_ORB {
+                            sandhi_planets.push(position.planet);
+                        }
+                    }
+                }
+                BhavaInfo {
+                    bhava: cusp.house,
+                    sign: cusp.sign,
+                    degree: cusp.degree,
+                    lord: cusp.sign.lord(),
+                    planets,
+                    start: Longitude::new(start),
+                    madhya: Longitude::new(start + width / 2.0),
+                    end: Longitude::new(start + width),
+                    sandhi_planets,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Bhava Chalit chart of a birth in the sidereal zodiac.
+    pub fn calculate_bhava_chalit(
+        &self,
+        birth_info: &BirthInfo,
+        method: BhavaChalitMethod,
+    ) -> Result<BhavaChalitChart, AstrologyError> {
+        let chart = self.calculate_chart(birth_info, self.conventions.house_system)?;
+        let lagna = chart.ascendant.longitude();
+        let mut madhyas = [0.0; 12];
+        match method {
+            BhavaChalitMethod::Sripati => {
+                // Porphyry cusps are the Sripati bhava madhyas
+                let houses = self.calculate_houses(
+                    CoordinateSystem::Sidereal,
+                    date_to_julian_day(birth_info.date_time),
+                    birth_info.location.latitude,
+                    birth_info.location.longitude,
+                    HouseSystem::Porphyry,
+                )?;
+                for (madhya, cusp) in madhyas.iter_mut().zip(&houses.cusps) {
+                    *madhya = cusp.longitude();
+                }
+            }
+            BhavaChalitMethod::EqualFromLagna => {
+                for (index, madhya) in madhyas.iter_mut().enumerate() {
+                    *madhya = (lagna + index as f64 * 30.0).rem_euclid(360.0);
+                }
+            }
+        }
+        Ok(BhavaChalitChart::from_madhyas(method, &chart, &madhyas))
+    }
+
+    /// Bhavas of a birth on the engine's house system.
+    pub fn calculate_bhavas(
+        &self,
+        birth_info: &BirthInfo,
+    ) -> Result<Vec<BhavaInfo>, AstrologyError> {
+        Ok(self
+            .calculate_chart(birth_info, self.conventions.house_system)?
+            .bhavas())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn bhavas_run_from_cusp_to_cusp() {
+        // Cusps 30° apart from 10° Aries, except the 2nd, pulled on to 15° Taurus
+        let houses = House::all()
+            .enumerate()
+            .map(|(index, house)| {
+                let longitude = if index == 1 {
+                    45.0
+                } else {
+                    10.0 + index as f64 * 30.0
+                };
+                HouseCusp {
+                    house,
+                    sign: ZodiacSign::from_longitude(longitude),
+                    degree: longitude % 30.0,
+                }
+            })
+            .collect();
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::Placidus,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 10.0,
+            },
+            houses,
+            planets: vec![
+                planet(CelestialBody::Sun, 44.5),
+                planet(CelestialBody::Moon, 60.0),
+                planet(CelestialBody::Mars, 9.5),
+            ],
+            warnings: Vec::new(),
+        };
+
+        let bhavas = chart.bhavas();
+        assert_eq!(bhavas.len(), 12);
+        let first = &bhavas[0];
+        assert_eq!(first.madhya.degrees(), 27.5);
+        assert_eq!(first.end.degrees(), 45.0);
+        assert_eq!(first.planets, vec![CelestialBody::Sun]);
+        assert_eq!(first.sandhi_planets, vec![CelestialBody::Sun]);
+        assert_eq!(bhavas[1].planets, vec![CelestialBody::Moon]);
+        assert!(bhavas[1].sandhi_planets.is_empty());
+        // Mars sits just before the lagna, at the end of the 12th
+        assert_eq!(bhavas[11].lord, CelestialBody::Jupiter);
+        assert_eq!(bhavas[11].sandhi_planets, vec![CelestialBody::Mars]);
+    }
+}
@@ -0,0 +1,254 @@
+//! An HTTP server answering in JSON. Run `aztro_server [address]`, by default on
+//! `127.0.0.1:8080`.
+//!
+//! - `POST /chart` with birth info in the body casts the chart. The query can set
+//!   `house_system` (such as `WholeSign`), `outer_planets=true` and `asteroids=true`.
+//! - `POST /report` with birth info in the body gives the full report.
+//! - `POST /compatibility` with a JSON array of two [`BirthInfo`]s matches them, the groom
+//!   and bride taken from their genders.
+//! - `GET /panchanga?lat=&lon=&tz=&date=` gives the panchanga at `date`, an RFC 3339 time,
+//!   or now if it is left out, for a place `tz` hours ahead of UTC.
+//!
+//! Birth info is either a [`BirthInfo`] in JSON or a birth record such as
+//! `1991-06-18 07:10 +05:30 @ 11.2588,75.7804`. Failures answer with the
+//! [`AstrologyError`] in JSON.
+
+use aztro_core::{
+    AstrologyError, BirthInfo, ChartOptions, HouseSystem, Location, Report, SwissEph,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Swiss Ephemeris shares one state among all threads on Apple platforms and Windows, so
+/// requests there are answered one at a time, as in [`Report::calculate_batch`].
+const THREAD_LOCAL_EPHEMERIS: bool = !cfg!(any(target_vendor = "apple", windows));
+
+fn main() {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let server = match Server::http(&address) {
+        Ok(server) => Arc::new(server),
+        Err(error) => {
+            eprintln!("Cannot listen on {}: {}", address, error);
+            std::process::exit(1);
+        }
+    };
+    eprintln!("Listening on http://{}", address);
+
+    let workers = if THREAD_LOCAL_EPHEMERIS {
+        std::thread::available_parallelism().map_or(1, |count| count.get())
+    } else {
+        1
+    };
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            std::thread::spawn(move || {
+                let ephemeris = SwissEph::new();
+                for request in server.incoming_requests() {
+                    handle(&ephemeris, request);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+fn handle(ephemeris: &Result<SwissEph, AstrologyError>, mut request: Request) {
+    let mut body = String::new();
+    let (status, json) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => {
+            let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+            match ephemeris {
+                Ok(ephemeris) => route(ephemeris, request.method(), path, query, &body),
+                Err(error) => failure(error.clone()),
+            }
+        }
+        Err(error) => failure(AstrologyError::InvalidInput(error.to_string())),
+    };
+    let response = Response::from_string(json)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(error) = request.respond(response) {
+        eprintln!("Failed to answer a request: {}", error);
+    }
+}
+
+/// The status code and JSON body answering `method` on `path`.
+fn route(
+    ephemeris: &SwissEph,
+    method: &Method,
+    path: &str,
+    query: &str,
+    body: &str,
+) -> (u16, String) {
+    let result = match (method, path) {
+        (Method::Post, "/chart") => chart(ephemeris, query, body),
+        (Method::Post, "/report") => report(ephemeris, body),
+        (Method::Post, "/compatibility") => compatibility(ephemeris, body),
+        (Method::Get, "/panchanga") => panchanga(ephemeris, query),
+        (_, "/chart" | "/report" | "/compatibility" | "/panchanga") => {
+            return (405, error_json("Method not allowed"));
+        }
+        _ => return (404, error_json("Not found")),
+    };
+    match result {
+        Ok(json) => (200, json),
+        Err(error) => failure(error),
+    }
+}
+
+fn chart(ephemeris: &SwissEph, query: &str, body: &str) -> Result<String, AstrologyError> {
+    let birth_info = birth_info(body)?;
+    let mut options = ChartOptions::default();
+    if let Some(house_system) = parameter(query, "house_system") {
+        options.house_system = serde_json::from_value::<HouseSystem>(house_system.as_str().into())
+            .map_err(|_| invalid(format!("Unknown house system '{}'", house_system)))?;
+    }
+    options.include_outer_planets = parameter(query, "outer_planets").as_deref() == Some("true");
+    options.include_asteroids = parameter(query, "asteroids").as_deref() == Some("true");
+    to_json(&ephemeris.calculate_chart_with_options(&birth_info, &options)?)
+}
+
+fn report(ephemeris: &SwissEph, body: &str) -> Result<String, AstrologyError> {
+    let birth_info = birth_info(body)?;
+    let name = birth_info
+        .person
+        .as_ref()
+        .map_or(String::new(), |person| person.name.clone());
+    let gender = birth_info.gender();
+    to_json(&Report::calculate_with(
+        ephemeris, name, birth_info, gender,
+    )?)
+}
+
+fn compatibility(ephemeris: &SwissEph, body: &str) -> Result<String, AstrologyError> {
+    let [first, second]: [BirthInfo; 2] = serde_json::from_str(body)
+        .map_err(|error| invalid(format!("Expected two birth infos: {}", error)))?;
+    to_json(&ephemeris.calculate_marriage_match(&first, &second)?)
+}
+
+fn panchanga(ephemeris: &SwissEph, query: &str) -> Result<String, AstrologyError> {
+    let number = |name: &str| -> Result<f64, AstrologyError> {
+        let value = parameter(query, name).ok_or_else(|| invalid(format!("Missing '{}'", name)))?;
+        value
+            .parse()
+            .map_err(|_| invalid(format!("Invalid '{}': {}", name, value)))
+    };
+    let location = Location::new(number("lat")?, number("lon")?, number("tz")? as f32);
+    let date_time = match parameter(query, "date") {
+        Some(date) => DateTime::parse_from_rfc3339(&date)
+            .map_err(|error| invalid(format!("Invalid 'date': {}", error)))?
+            .with_timezone(&Utc),
+        None => Utc::now(),
+    };
+    to_json(&ephemeris.calculate_panchanga(date_time, &location)?)
+}
+
+/// A [`BirthInfo`] in JSON or a birth record in the form [`BirthInfo::parse`] takes.
+fn birth_info(body: &str) -> Result<BirthInfo, AstrologyError> {
+    if body.trim_start().starts_with('{') {
+        serde_json::from_str(body).map_err(|error| invalid(error.to_string()))
+    } else {
+        Ok(BirthInfo::parse(body.trim())?)
+    }
+}
+
+/// The percent-decoded value of `name` in `query`. A `+` is kept as it is, so offsets in
+/// times need not be escaped.
+fn parameter(query: &str, name: &str) -> Option<String> {
+    let value = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))?;
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| std::str::from_utf8(tail.get(..2)?).ok())
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn invalid(message: String) -> AstrologyError {
+    AstrologyError::InvalidInput(message)
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, AstrologyError> {
+    serde_json::to_string(value).map_err(|error| AstrologyError::Calculation(error.to_string()))
+}
+
+/// Bad input is the client's fault; a failing ephemeris is the server's. Anything else is
+/// input the calculation has no answer for.
+fn failure(error: AstrologyError) -> (u16, String) {
+    let status = match error {
+        AstrologyError::InvalidInput(_) | AstrologyError::BirthInfo(_) => 400,
+        AstrologyError::Ephemeris { .. } | AstrologyError::EphemerisFiles(_) => 500,
+        _ => 422,
+    };
+    (status, serde_json::to_string(&error).unwrap_or_default())
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_routed_and_bad_input_refused() {
+        let ephemeris = SwissEph::new().unwrap();
+        let birth = "1991-06-18 07:10 +05:30 @ 11.2588,75.7804";
+
+        let (status, json) = route(
+            &ephemeris,
+            &Method::Post,
+            "/chart",
+            "house_system=WholeSign",
+            birth,
+        );
+        assert_eq!(status, 200);
+        assert!(json.contains("\"house_system\":\"WholeSign\""));
+
+        let query = "lat=11.2588&lon=75.7804&tz=5.5&date=2024-03-01T06:00:00%2B05:30";
+        assert_eq!(
+            route(&ephemeris, &Method::Get, "/panchanga", query, "").0,
+            200
+        );
+        assert_eq!(
+            route(&ephemeris, &Method::Get, "/panchanga", "lat=1", "").0,
+            400
+        );
+        assert_eq!(route(&ephemeris, &Method::Get, "/chart", "", birth).0, 405);
+        assert_eq!(route(&ephemeris, &Method::Get, "/nowhere", "", "").0, 404);
+    }
+
+    #[test]
+    fn query_values_are_percent_decoded() {
+        let query = "date=2024-03-01T06:00:00%2B05:30&tz=5.5";
+        assert_eq!(
+            parameter(query, "date").as_deref(),
+            Some("2024-03-01T06:00:00+05:30")
+        );
+        assert_eq!(parameter(query, "tz").as_deref(), Some("5.5"));
+        assert_eq!(parameter(query, "lat"), None);
+    }
+}
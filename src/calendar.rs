@@ -0,0 +1,133 @@
+use super::*;
+use chrono::Datelike;
+
+/// The calendar a birth date is written in. Dates before the Gregorian reform were kept in
+/// the Julian calendar, ten days behind by 1582 and thirteen by 1900, and countries moved
+/// over at different times. Dates in the crate are otherwise proleptic Gregorian, as chrono
+/// keeps them; this only says how to read a date given as year, month and day.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum CalendarSystem {
+    /// Julian before 15 October 1582, when Rome adopted the Gregorian calendar, and
+    /// Gregorian from then on.
+    #[default]
+    Automatic,
+    /// Gregorian throughout, extended back before 1582.
+    Gregorian,
+    /// Julian throughout, extended past 1582.
+    Julian,
+    /// Julian before the given date, the first Gregorian one, and Gregorian from then on.
+    Cutover(NaiveDate),
+}
+
+impl CalendarSystem {
+    /// Britain and its colonies, which changed calendar on 14 September 1752.
+    pub fn british() -> Self {
+        CalendarSystem::Cutover(NaiveDate::from_ymd_opt(1752, 9, 14).unwrap())
+    }
+
+    /// The first day counted in the Gregorian calendar.
+    fn first_gregorian_date(&self) -> NaiveDate {
+        match self {
+            CalendarSystem::Automatic => NaiveDate::from_ymd_opt(1582, 10, 15).unwrap(),
+            CalendarSystem::Gregorian => NaiveDate::MIN,
+            CalendarSystem::Julian => NaiveDate::MAX,
+            CalendarSystem::Cutover(date) => *date,
+        }
+    }
+
+    /// The day written as `year`, `month` and `day` in this calendar, or `None` if there is
+    /// no such day, such as 29 February 1700 after the change or the days the change
+    /// skipped. Years are astronomical, 0 being 1 BC.
+    pub fn to_gregorian(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        let cutover = self.first_gregorian_date();
+        match NaiveDate::from_ymd_opt(year, month, day) {
+            Some(date) if date >= cutover => Some(date),
+            _ => julian_date(year, month, day).filter(|&date| date < cutover),
+        }
+    }
+
+    /// How this calendar writes `date`, as year, month and day.
+    pub fn from_gregorian(&self, date: NaiveDate) -> (i32, u32, u32) {
+        if date >= self.first_gregorian_date() {
+            return (date.year(), date.month(), date.day());
+        }
+        // Julian day numbers of the Julian calendar, after the Explanatory Supplement
+        let c = date.num_days_from_ce() as i64 + JDN_OF_CE_DAY_ZERO + 32082;
+        let d = (4 * c + 3) / 1461;
+        let e = c - 1461 * d / 4;
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = d - 4800 + m / 10;
+        (year as i32, month as u32, day as u32)
+    }
+
+    /// Julian day of `hour` o'clock UT, with fractions, on the day written as `year`,
+    /// `month` and `day` in this calendar.
+    pub fn julian_day(&self, year: i32, month: u32, day: u32, hour: f64) -> Option<JulianDay> {
+        let date = self.to_gregorian(year, month, day)?;
+        let day_number = date.num_days_from_ce() as i64 + JDN_OF_CE_DAY_ZERO;
+        Some(day_number as f64 - 0.5 + hour / 24.0)
+    }
+}
+
+/// Julian day number of the day before 1 January 1 in the proleptic Gregorian calendar,
+/// which chrono counts as day 0 of the common era.
+const JDN_OF_CE_DAY_ZERO: i64 = 1_721_425;
+
+/// The proleptic Gregorian date of the Julian calendar's `year`, `month` and `day`.
+fn julian_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let leap = year.rem_euclid(4) == 0;
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+    let a = (14 - month as i64) / 12;
+    let y = year as i64 + 4800 - a;
+    let m = month as i64 + 12 * a - 3;
+    let day_number = day as i64 + (153 * m + 2) / 5 + 365 * y + y / 4 - 32083;
+    NaiveDate::from_num_days_from_ce_opt((day_number - JDN_OF_CE_DAY_ZERO) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_reform_skips_ten_days() {
+        let calendar = CalendarSystem::Automatic;
+        // Thursday 4 October 1582 was followed by Friday 15 October
+        let last_julian = calendar.to_gregorian(1582, 10, 4).unwrap();
+        let first_gregorian = calendar.to_gregorian(1582, 10, 15).unwrap();
+        assert_eq!(first_gregorian - last_julian, chrono::Duration::days(1));
+        assert_eq!(calendar.to_gregorian(1582, 10, 10), None);
+        assert_eq!(calendar.from_gregorian(last_julian), (1582, 10, 4));
+
+        // 1500 was a leap year only in the Julian calendar
+        assert!(calendar.to_gregorian(1500, 2, 29).is_some());
+        assert_eq!(CalendarSystem::Gregorian.to_gregorian(1500, 2, 29), None);
+
+        // Newton was born on Christmas Day 1642 in England, still on the Julian calendar
+        let british = CalendarSystem::british();
+        let newton = british.to_gregorian(1642, 12, 25).unwrap();
+        assert_eq!(newton, NaiveDate::from_ymd_opt(1643, 1, 4).unwrap());
+        assert_eq!(british.from_gregorian(newton), (1642, 12, 25));
+    }
+
+    #[test]
+    fn julian_days_match_the_epochs() {
+        let calendar = CalendarSystem::Automatic;
+        assert_eq!(calendar.julian_day(2000, 1, 1, 12.0), Some(2_451_545.0));
+        // The Julian calendar's own epoch, noon of 1 January 4713 BC
+        assert_eq!(calendar.julian_day(-4712, 1, 1, 12.0), Some(0.0));
+        assert_eq!(calendar.julian_day(1582, 10, 4, 0.0), Some(2_299_159.5));
+        assert_eq!(calendar.julian_day(1582, 10, 15, 0.0), Some(2_299_160.5));
+    }
+}
@@ -0,0 +1,199 @@
+//! A C interface for embedding the engine in apps written in other languages, such as
+//! Swift, Kotlin or Dart. Requests and results cross as JSON in NUL-terminated UTF-8
+//! strings. Every call answers with `{"Ok": ...}` or `{"Err": ...}`, the error being an
+//! [`AstrologyError`], in a string the caller hands back to [`aztro_free_string`].
+//!
+//! Birth info is either a [`BirthInfo`] in JSON or a birth record in the form
+//! [`BirthInfo::parse`] takes, such as `"1991-06-18 07:10 +05:30 @ 11.2588,75.7804"`.
+//!
+//! Any thread may call in. Each calling thread gets an engine of its own, and where Swiss
+//! Ephemeris has no thread-local storage, on Apple platforms and Windows, calls from
+//! different threads take turns instead of running at once.
+//!
+//! The header, `aztro_core.h`, is generated into the build's `OUT_DIR` when the crate is
+//! built with the `capi` feature, and the copy in `include/` is checked against it.
+
+use super::*;
+use serde::de::DeserializeOwned;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+thread_local! {
+    /// Each calling thread gets an engine of its own the first time it asks for one.
+    static ENGINE: Result<SwissEph, AstrologyError> = SwissEph::new();
+}
+
+/// Held through every call where all threads share one Swiss Ephemeris state.
+static SHARED_LIBRARY: Mutex<()> = Mutex::new(());
+
+/// The crate version, such as `"0.1.17"`. The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn aztro_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Frees a string returned by any other `aztro_` call. Does nothing given null.
+///
+/// # Safety
+///
+/// `string` must be null or a string from this library not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn aztro_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// The [`ChartInfo`] for `birth_info`, cast with `options`, a [`ChartOptions`] in JSON, or
+/// with the default options when `options` is null.
+///
+/// # Safety
+///
+/// `birth_info` and `options` must each be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aztro_calculate_chart_json(
+    birth_info: *const c_char,
+    options: *const c_char,
+) -> *mut c_char {
+    respond(|ephemeris| {
+        let birth_info = read_birth_info(birth_info)?;
+        let options = if options.is_null() {
+            ChartOptions::default()
+        } else {
+            read_json(options)?
+        };
+        ephemeris.calculate_chart_with_options(&birth_info, &options)
+    })
+}
+
+/// The full [`Report`] for `birth_info`, named and gendered after its person if it has one.
+///
+/// # Safety
+///
+/// `birth_info` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aztro_calculate_report_json(birth_info: *const c_char) -> *mut c_char {
+    respond(|ephemeris| {
+        let birth_info = read_birth_info(birth_info)?;
+        let name = birth_info
+            .person
+            .as_ref()
+            .map_or(String::new(), |person| person.name.clone());
+        let gender = birth_info.gender();
+        Report::calculate_with(ephemeris, name, birth_info, gender)
+    })
+}
+
+/// Reports for `births`, a JSON array of [`BirthInfo`], as an array holding each report or
+/// the error that stopped it, in the order given. See [`Report::calculate_batch`].
+///
+/// # Safety
+///
+/// `births` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aztro_calculate_batch_json(births: *const c_char) -> *mut c_char {
+    respond(|_| {
+        let births: Vec<BirthInfo> = read_json(births)?;
+        Ok(Report::calculate_batch(&births))
+    })
+}
+
+/// Runs `work` on this thread's engine and answers with its result in JSON. A panic is
+/// caught and answered as an error rather than unwinding into the caller.
+fn respond<T: Serialize>(work: impl FnOnce(&SwissEph) -> Result<T, AstrologyError>) -> *mut c_char {
+    // A panic is caught before the guard drops, so the lock is never poisoned by one
+    let _turn = (!THREAD_LOCAL_EPHEMERIS).then(|| {
+        SHARED_LIBRARY
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    });
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        ENGINE.with(|ephemeris| match ephemeris {
+            Ok(ephemeris) => work(ephemeris),
+            Err(error) => Err(error.clone()),
+        })
+    }))
+    .unwrap_or_else(|_| {
+        Err(AstrologyError::Calculation(
+            "the calculation panicked".into(),
+        ))
+    });
+
+    let json = serde_json::to_string(&result).unwrap_or_else(|error| {
+        let error: Result<(), _> = Err(AstrologyError::Calculation(error.to_string()));
+        serde_json::to_string(&error).unwrap_or_default()
+    });
+    // JSON escapes NUL within strings, so there is none to refuse
+    CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// # Safety
+///
+/// `input` must be null or a NUL-terminated string.
+unsafe fn read_str<'a>(input: *const c_char) -> Result<&'a str, AstrologyError> {
+    if input.is_null() {
+        return Err(AstrologyError::InvalidInput("null string".into()));
+    }
+    CStr::from_ptr(input)
+        .to_str()
+        .map_err(|error| AstrologyError::InvalidInput(error.to_string()))
+}
+
+/// # Safety
+///
+/// `input` must be null or a NUL-terminated string.
+unsafe fn read_json<T: DeserializeOwned>(input: *const c_char) -> Result<T, AstrologyError> {
+    serde_json::from_str(read_str(input)?)
+        .map_err(|error| AstrologyError::InvalidInput(error.to_string()))
+}
+
+/// # Safety
+///
+/// `input` must be null or a NUL-terminated string.
+unsafe fn read_birth_info(input: *const c_char) -> Result<BirthInfo, AstrologyError> {
+    let text = read_str(input)?;
+    if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|error| AstrologyError::InvalidInput(error.to_string()))
+    } else {
+        Ok(BirthInfo::parse(text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(answer: *mut c_char) -> serde_json::Value {
+        let json = unsafe { CStr::from_ptr(answer) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        unsafe { aztro_free_string(answer) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn charts_and_errors_come_back_as_json() {
+        let birth = CString::new("1991-06-18 07:10 +05:30 @ 11.2588,75.7804").unwrap();
+        let chart = call(unsafe { aztro_calculate_chart_json(birth.as_ptr(), std::ptr::null()) });
+        assert_eq!(chart["Ok"]["planets"].as_array().unwrap().len(), 9);
+
+        let garbage = CString::new("{\"date_time\": 3}").unwrap();
+        let error = call(unsafe { aztro_calculate_report_json(garbage.as_ptr()) });
+        assert!(error["Err"]["InvalidInput"].is_string());
+        let error = call(unsafe { aztro_calculate_report_json(std::ptr::null()) });
+        assert!(error["Err"]["InvalidInput"].is_string());
+    }
+
+    #[test]
+    fn the_header_in_the_tree_is_the_generated_one() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aztro_core.h"));
+        let committed = include_str!("../include/aztro_core.h");
+        assert!(
+            generated == committed,
+            "include/aztro_core.h is out of date; copy it from {}",
+            env!("OUT_DIR")
+        );
+    }
+}
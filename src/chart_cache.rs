@@ -0,0 +1,204 @@
+use super::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Reports a [`ChartCache`] keeps unless told otherwise.
+pub const CHART_CACHE_CAPACITY: usize = 64;
+
+#[derive(Debug, Default)]
+struct CachedReports {
+    /// Each report with the tick of its last use, keyed by the hash of what it was
+    /// calculated from.
+    reports: HashMap<u64, (Arc<Report>, u64)>,
+    tick: u64,
+}
+
+/// Reports already calculated, for apps that show the same chart again and again. Opt in
+/// by calling [`ChartCache::report`] in place of [`Report::calculate_with_options`].
+///
+/// A report is keyed by its birth, name and gender, the engine's [`Conventions`] and the
+/// [`ReportOptions`], so changing any of them calculates afresh rather than returning a
+/// report made with the old ones. Once full, the least recently used report is dropped.
+/// The cache can be shared between threads.
+#[derive(Debug)]
+pub struct ChartCache {
+    capacity: usize,
+    entries: Mutex<CachedReports>,
+}
+
+impl Default for ChartCache {
+    fn default() -> Self {
+        ChartCache::new(CHART_CACHE_CAPACITY)
+    }
+}
+
+impl ChartCache {
+    /// A cache holding up to `capacity` reports. Zero keeps none.
+    pub fn new(capacity: usize) -> Self {
+        ChartCache {
+            capacity,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// The report [`Report::calculate_with_options`] would give, from the cache if it has
+    /// been calculated before with the same inputs and conventions.
+    pub fn report(
+        &self,
+        ephemeris: &SwissEph,
+        name: impl AsRef<str>,
+        birth: impl Into<BirthInfo>,
+        gender: impl Into<Gender>,
+        options: ReportOptions,
+    ) -> Result<Arc<Report>, AstrologyError> {
+        let name = name.as_ref();
+        let gender = gender.into();
+        let birth_info = birth.into().for_person(name, gender);
+        let key = report_key(ephemeris, &birth_info, options)?;
+        if let Some(report) = self.get(key) {
+            return Ok(report);
+        }
+
+        // The lock is not held while the report is worked out, so other threads can read
+        // the cache meanwhile.
+        let report = Arc::new(Report::calculate_with_options(
+            ephemeris, name, birth_info, gender, options,
+        )?);
+        self.insert(key, report.clone());
+        Ok(report)
+    }
+
+    /// Calculates and keeps the reports for `births` that are not cached yet, so showing
+    /// them later is immediate. Names and genders come from each birth's [`Person`], as in
+    /// [`Report::calculate_batch`]. Stops at the first birth that fails.
+    pub fn precompute(
+        &self,
+        ephemeris: &SwissEph,
+        births: &[BirthInfo],
+        options: ReportOptions,
+    ) -> Result<(), AstrologyError> {
+        for birth in births {
+            let name = birth
+                .person
+                .as_ref()
+                .map_or("", |person| person.name.as_str());
+            self.report(ephemeris, name, birth.clone(), birth.gender(), options)?;
+        }
+        Ok(())
+    }
+
+    /// Reports held now.
+    pub fn len(&self) -> usize {
+        self.lock().reports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets every report.
+    pub fn clear(&self) {
+        self.lock().reports.clear();
+    }
+
+    fn get(&self, key: u64) -> Option<Arc<Report>> {
+        let mut entries = self.lock();
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.reports.get_mut(&key).map(|(report, used)| {
+            *used = tick;
+            report.clone()
+        })
+    }
+
+    fn insert(&self, key: u64, report: Arc<Report>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if !entries.reports.contains_key(&key) && entries.reports.len() >= self.capacity {
+            let oldest = entries
+                .reports
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(&key, _)| key);
+            if let Some(oldest) = oldest {
+                entries.reports.remove(&oldest);
+            }
+        }
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.reports.insert(key, (report, tick));
+    }
+
+    /// A panic while the lock was held cannot leave a half-written entry, so a poisoned
+    /// lock is used as it is.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CachedReports> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Hash of everything a report depends on. Birth data holds floating point numbers, which
+/// do not hash, so their serialized form is hashed instead.
+fn report_key(
+    ephemeris: &SwissEph,
+    birth_info: &BirthInfo,
+    options: ReportOptions,
+) -> Result<u64, AstrologyError> {
+    let inputs = serde_json::to_string(&(birth_info, ephemeris.conventions(), options))
+        .map_err(|error| AstrologyError::InvalidInput(error.to_string()))?;
+    let mut hasher = DefaultHasher::new();
+    inputs.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_are_reused_until_the_inputs_change() {
+        let cache = ChartCache::new(2);
+        let ephemeris = SwissEph::new().unwrap();
+        let options = ReportOptions::at(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        let birth = BirthInfo::parse("1991-06-18 07:10 +05:30 @ 11.2588,75.7804").unwrap();
+        let report = |ephemeris: &SwissEph, birth: &BirthInfo| {
+            cache
+                .report(ephemeris, "Asha", birth.clone(), Gender::Female, options)
+                .unwrap()
+        };
+
+        let first = report(&ephemeris, &birth);
+        assert!(Arc::ptr_eq(&first, &report(&ephemeris, &birth)));
+        assert_eq!(cache.len(), 1);
+
+        // Other conventions make another report
+        let raman = SwissEph::new().unwrap().with_conventions(Conventions {
+            ayanamsa: Ayanamsa::Raman,
+            ..*ephemeris.conventions()
+        });
+        let other = report(&raman, &birth);
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(other.settings.ayanamsa, Ayanamsa::Raman);
+        assert_eq!(cache.len(), 2);
+
+        // A third birth pushes out the least recently used, the first
+        let later = BirthInfo {
+            date_time: birth.date_time + ChronoDuration::hours(1),
+            ..birth.clone()
+        };
+        cache
+            .precompute(
+                &ephemeris,
+                &[later.for_person("Asha", Gender::Female)],
+                options,
+            )
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(Arc::ptr_eq(&other, &report(&raman, &birth)));
+        assert!(!Arc::ptr_eq(&first, &report(&ephemeris, &birth)));
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+use std::path::Path;
+
+/// Version of the layout [`SavedChart`] writes. It goes up whenever a change would stop an
+/// older reader from understanding the file; files from older versions still load.
+pub const CHART_FILE_VERSION: u32 = 1;
+
+/// A chart as archived on disk: the computed positions together with the birth they were
+/// cast for and the conventions they were cast under, so it can be reloaded without
+/// recalculating and compared with charts from other versions of the crate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SavedChart {
+    /// Layout of the file, [`CHART_FILE_VERSION`] when written by this version.
+    pub version: u32,
+    /// Version of the crate that cast the chart.
+    pub crate_version: String,
+    pub birth_info: Option<BirthInfo>,
+    /// Ayanamsa, house system and the other settings the chart was cast with, when known.
+    pub conventions: Option<Conventions>,
+    pub chart: ChartInfo,
+}
+
+impl SavedChart {
+    pub fn new(
+        chart: ChartInfo,
+        birth_info: Option<BirthInfo>,
+        conventions: Option<Conventions>,
+    ) -> Self {
+        SavedChart {
+            version: CHART_FILE_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            birth_info,
+            conventions,
+            chart,
+        }
+    }
+
+    /// Writes the chart as pretty-printed JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AstrologyError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|error| AstrologyError::ChartFile(error.to_string()))?;
+        std::fs::write(path, json)
+            .map_err(|error| AstrologyError::ChartFile(format!("{}: {}", path.display(), error)))
+    }
+
+    /// Reads a chart written by [`SavedChart::save`]. Fails on a file from a newer
+    /// version of the layout than this crate knows.
+    pub fn load(path: impl AsRef<Path>) -> Result<SavedChart, AstrologyError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|error| AstrologyError::ChartFile(format!("{}: {}", path.display(), error)))?;
+        SavedChart::from_json(&json)
+    }
+
+    /// Reads a chart from the JSON [`SavedChart::save`] writes.
+    pub fn from_json(json: &str) -> Result<SavedChart, AstrologyError> {
+        let saved: SavedChart = serde_json::from_str(json)
+            .map_err(|error| AstrologyError::ChartFile(error.to_string()))?;
+        if saved.version > CHART_FILE_VERSION {
+            return Err(AstrologyError::ChartFile(format!(
+                "written in layout version {} by aztro_core {}; this version reads up to {}",
+                saved.version, saved.crate_version, CHART_FILE_VERSION
+            )));
+        }
+        Ok(saved)
+    }
+}
+
+impl ChartInfo {
+    /// Saves the chart alone, with no birth data or conventions. Use [`SavedChart`] to
+    /// keep those too.
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), AstrologyError> {
+        SavedChart::new(self.clone(), None, None).save(path)
+    }
+
+    /// The chart from a file written by [`ChartInfo::to_file`] or [`SavedChart::save`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<ChartInfo, AstrologyError> {
+        Ok(SavedChart::load(path)?.chart)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Casts the chart for `birth_info` and keeps it with the birth and the engine's
+    /// conventions, ready to save.
+    pub fn calculate_saved_chart(
+        &self,
+        birth_info: &BirthInfo,
+        options: &ChartOptions,
+    ) -> Result<SavedChart, AstrologyError> {
+        let chart = self.calculate_chart_with_options(birth_info, options)?;
+        Ok(SavedChart::new(
+            chart,
+            Some(birth_info.clone()),
+            Some(self.conventions),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart() -> ChartInfo {
+        ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Leo,
+                degree: 4.5,
+            },
+            houses: Vec::new(),
+            planets: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn charts_survive_a_round_trip() {
+        let path = std::env::temp_dir().join(format!("aztro_chart_{}.json", std::process::id()));
+        let saved = SavedChart::new(chart(), None, Some(Conventions::default()));
+        saved.save(&path).unwrap();
+        let loaded = SavedChart::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), saved);
+    }
+
+    #[test]
+    fn newer_layouts_are_refused() {
+        let mut saved = SavedChart::new(chart(), None, None);
+        saved.version = CHART_FILE_VERSION + 1;
+        let json = serde_json::to_string(&saved).unwrap();
+        assert!(matches!(
+            SavedChart::from_json(&json),
+            Err(AstrologyError::ChartFile(_))
+        ));
+    }
+}
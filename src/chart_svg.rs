@@ -0,0 +1,141 @@
+use super::*;
+
+/// Side of one sign's square in the drawing.
+const CELL: usize = 100;
+
+/// Row and column of each sign, Aries first, in the South Indian square. The signs run
+/// clockwise round the border from Pisces in the top left corner.
+const SOUTH_INDIAN_CELLS: [(usize, usize); 12] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+    (3, 2),
+    (3, 1),
+    (3, 0),
+    (2, 0),
+    (1, 0),
+    (0, 0),
+];
+
+impl CelestialBody {
+    /// Two-letter abbreviation used where space is short, as in a drawn chart.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            CelestialBody::Sun => "Su",
+            CelestialBody::Moon => "Mo",
+            CelestialBody::Mercury => "Me",
+            CelestialBody::Venus => "Ve",
+            CelestialBody::Mars => "Ma",
+            CelestialBody::Jupiter => "Ju",
+            CelestialBody::Saturn => "Sa",
+            CelestialBody::Uranus => "Ur",
+            CelestialBody::Neptune => "Ne",
+            CelestialBody::Pluto => "Pl",
+            CelestialBody::Rahu => "Ra",
+            CelestialBody::Ketu => "Ke",
+            CelestialBody::Chiron => "Ch",
+            CelestialBody::Ceres => "Ce",
+            CelestialBody::Pallas => "Pa",
+            CelestialBody::Juno => "Jn",
+            CelestialBody::Vesta => "Vs",
+        }
+    }
+}
+
+impl ChartInfo {
+    /// The chart drawn as an SVG image in the South Indian style: signs in fixed squares
+    /// round a 4×4 grid, the ascendant marked "Asc" and retrograde planets with an "R".
+    /// The chart type is written in the empty middle.
+    pub fn to_svg(&self) -> String {
+        let size = CELL * 4;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+             viewBox=\"0 0 {size} {size}\" font-family=\"sans-serif\" font-size=\"14\">\n\
+             <rect width=\"{size}\" height=\"{size}\" fill=\"white\" stroke=\"black\" \
+             stroke-width=\"2\"/>\n"
+        );
+
+        for (index, &(row, column)) in SOUTH_INDIAN_CELLS.iter().enumerate() {
+            let (x, y) = (column * CELL, row * CELL);
+            svg += &format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"none\" \
+                 stroke=\"black\"/>\n<text x=\"{}\" y=\"{}\" font-size=\"10\" \
+                 fill=\"gray\">{}</text>\n",
+                x + 4,
+                y + 12,
+                ZodiacSign::from_longitude(index as f64 * 30.0)
+            );
+
+            let mut labels = Vec::new();
+            if self.ascendant.sign as usize == index {
+                labels.push("Asc".to_string());
+            }
+            labels.extend(
+                self.planets
+                    .iter()
+                    .filter(|planet| planet.sign as usize == index)
+                    .map(|planet| {
+                        format!(
+                            "{}{}",
+                            planet.planet.abbreviation(),
+                            if planet.retrograde { " R" } else { "" }
+                        )
+                    }),
+            );
+            // Two labels to a line, so a crowded sign still fits its square
+            for (line, pair) in labels.chunks(2).enumerate() {
+                svg += &format!(
+                    "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                    x + 8,
+                    y + 32 + line * 18,
+                    pair.join(" ")
+                );
+            }
+        }
+
+        svg += &format!(
+            "<text x=\"{0}\" y=\"{0}\" text-anchor=\"middle\" dominant-baseline=\"middle\" \
+             font-size=\"18\">{1:?}</text>\n</svg>\n",
+            size / 2,
+            self.chart_type
+        );
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn planets_are_drawn_in_their_signs() {
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Gemini,
+                degree: 16.0,
+            },
+            houses: Vec::new(),
+            planets: vec![PlanetPosition {
+                planet: CelestialBody::Saturn,
+                longitude: Longitude::new(290.0),
+                latitude: 0.0,
+                speed: -0.01,
+                sign: ZodiacSign::Capricorn,
+                house: House::Eighth,
+                nakshatra: NakshatraInfo::from_longitude(290.0),
+                retrograde: true,
+            }],
+            warnings: Vec::new(),
+        };
+        let svg = chart.to_svg();
+        // Gemini is the top right square and Capricorn the third down on the left
+        assert!(svg.contains("<text x=\"308\" y=\"32\">Asc</text>"));
+        assert!(svg.contains("<text x=\"8\" y=\"232\">Sa R</text>"));
+    }
+}
@@ -0,0 +1,262 @@
+use super::*;
+use chrono_tz::{OffsetComponents, Tz};
+
+/// A named place with its tz database zone.
+struct City {
+    name: &'static str,
+    /// ISO 3166 country code.
+    #[cfg_attr(not(feature = "geonames"), allow(dead_code))]
+    country: &'static str,
+    latitude: f64,
+    longitude: f64,
+    tz_name: &'static str,
+}
+
+const fn city(
+    name: &'static str,
+    country: &'static str,
+    latitude: f64,
+    longitude: f64,
+    tz_name: &'static str,
+) -> City {
+    City {
+        name,
+        country,
+        latitude,
+        longitude,
+        tz_name,
+    }
+}
+
+/// Cities with their own [`Location`] constructors, such as [`Location::kozhikode`].
+const PRESETS: &[City] = &[
+    city("Delhi", "IN", 28.6139, 77.2090, "Asia/Kolkata"),
+    city("Mumbai", "IN", 19.0760, 72.8777, "Asia/Kolkata"),
+    city("Bangalore", "IN", 12.9716, 77.5946, "Asia/Kolkata"),
+    city("Chennai", "IN", 13.0827, 80.2707, "Asia/Kolkata"),
+    city("Kannur", "IN", 11.8740, 75.3600, "Asia/Kolkata"),
+    city("Kolkata", "IN", 22.5052, 87.3616, "Asia/Kolkata"),
+    city("Abu Dhabi", "AE", 24.4667, 54.3667, "Asia/Dubai"),
+    city("Dubai", "AE", 25.276987, 55.296234, "Asia/Dubai"),
+    city("Sharjah", "AE", 25.3550, 55.4000, "Asia/Dubai"),
+    city("Malappuram", "IN", 10.7900, 76.0700, "Asia/Kolkata"),
+    city("Kochi", "IN", 9.9312, 76.2673, "Asia/Kolkata"),
+    city("Kollam", "IN", 8.8857, 76.5881, "Asia/Kolkata"),
+    city("Thrissur", "IN", 10.522, 76.2100, "Asia/Kolkata"),
+    city("Kozhikode", "IN", 11.2588, 75.7804, "Asia/Kolkata"),
+    city("Wayanad", "IN", 11.6900, 75.8900, "Asia/Kolkata"),
+    city("Munnar", "IN", 10.0000, 77.0667, "Asia/Kolkata"),
+    city("Idukki", "IN", 10.0000, 77.0667, "Asia/Kolkata"),
+    city("Kottayam", "IN", 10.0000, 76.5000, "Asia/Kolkata"),
+    city("Alappuzha", "IN", 9.4900, 76.3200, "Asia/Kolkata"),
+];
+
+/// A place this close to a known city is taken to be in that city.
+const SAME_CITY_KM: f64 = 30.0;
+
+/// No city of another zone may lie this close to a place for its zone to be certain.
+const OTHER_ZONE_KM: f64 = 150.0;
+
+/// Tab-separated name, country, latitude, longitude and zone of every other city
+/// [`Location::lookup`] knows; `#` starts a comment line.
+#[cfg(feature = "geonames")]
+const CITIES: &str = include_str!("../data/cities.tsv");
+
+impl City {
+    #[cfg(feature = "geonames")]
+    fn matches(&self, name: &str, country: Option<&str>) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            && country.is_none_or(|country| self.country.eq_ignore_ascii_case(country))
+    }
+
+    /// Great-circle distance to a point, in kilometres.
+    fn distance_km(&self, latitude: f64, longitude: f64) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), latitude.to_radians());
+        let half_lat = (lat2 - lat1) / 2.0;
+        let half_lon = (longitude - self.longitude).to_radians() / 2.0;
+        let a = half_lat.sin().powi(2) + lat1.cos() * lat2.cos() * half_lon.sin().powi(2);
+        2.0 * 6371.0 * a.sqrt().asin()
+    }
+
+    /// `None` if chrono-tz does not know the zone.
+    fn location(&self) -> Option<Location> {
+        let tz: Tz = self.tz_name.parse().ok()?;
+        // The zone's standard offset today; `born_at` finds the one in force at the birth
+        let offset = tz.offset_from_utc_datetime(&Utc::now().naive_utc());
+        let timezone = offset.base_utc_offset().num_seconds() as f32 / 3600.0;
+        Some(Location::new(self.latitude, self.longitude, timezone).with_tz_name(self.tz_name))
+    }
+}
+
+#[cfg(feature = "geonames")]
+fn parse_city(line: &'static str) -> Option<City> {
+    let mut fields = line.split('\t');
+    let city = City {
+        name: fields.next()?,
+        country: fields.next()?,
+        latitude: fields.next()?.parse().ok()?,
+        longitude: fields.next()?.parse().ok()?,
+        tz_name: fields.next()?,
+    };
+    Some(city)
+}
+
+impl Location {
+    /// One of the cities with a constructor of its own.
+    pub(crate) fn preset(name: &str) -> Self {
+        PRESETS
+            .iter()
+            .find(|city| city.name == name)
+            .and_then(City::location)
+            .unwrap_or_else(|| panic!("{} is not a preset city", name))
+    }
+
+    /// A place at sea level in the tz database zone of the known city it stands in, for
+    /// when only the coordinates are known. `timezone` is the zone's standard offset today,
+    /// and [`Location::born_at`] finds the one in force at a birth.
+    ///
+    /// The crate ships no zone boundaries, so a zone is only given where it is certain:
+    /// within 30 km of a known city, with no city of another zone within 150 km. Anywhere
+    /// else is [`BirthInfoError::UnresolvedTimeZone`], and the zone has to be named with
+    /// [`Location::with_tz_name`]. The preset cities are always known, and the `geonames`
+    /// feature adds the principal city of every zone and more cities in India.
+    ///
+    /// ```
+    /// use aztro_core::{BirthInfoError, Location};
+    ///
+    /// let calicut = Location::from_coordinates(11.25, 75.78).unwrap();
+    /// assert_eq!(calicut.tz_name.as_deref(), Some("Asia/Kolkata"));
+    /// assert_eq!(calicut.timezone, 5.5);
+    ///
+    /// // Lahore, across the border from the known Indian cities
+    /// assert!(matches!(
+    ///     Location::from_coordinates(31.55, 74.34),
+    ///     Err(BirthInfoError::UnresolvedTimeZone(_))
+    /// ));
+    /// ```
+    pub fn from_coordinates(latitude: f64, longitude: f64) -> Result<Location, BirthInfoError> {
+        check_coordinates(latitude, longitude)?;
+        let near = |city: &City| {
+            let distance = city.distance_km(latitude, longitude);
+            (distance <= OTHER_ZONE_KM).then_some((distance, city.tz_name))
+        };
+        #[allow(unused_mut)]
+        let mut nearby: Vec<(f64, &'static str)> = PRESETS.iter().filter_map(near).collect();
+        #[cfg(feature = "geonames")]
+        nearby.extend(
+            CITIES
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(parse_city)
+                .filter_map(|city| near(&city)),
+        );
+
+        let nearest = nearby
+            .iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .filter(|&&(distance, _)| distance <= SAME_CITY_KM)
+            .map(|&(_, tz_name)| tz_name);
+        nearest
+            .filter(|&tz_name| nearby.iter().all(|&(_, zone)| zone == tz_name))
+            .and_then(|tz_name| city("", "", latitude, longitude, tz_name).location())
+            .ok_or_else(|| {
+                BirthInfoError::UnresolvedTimeZone(format!("{}, {}", latitude, longitude))
+            })
+    }
+
+    /// Coordinates and zone of a city given as `"Name, CC"` with its ISO 3166 country
+    /// code, or as just `"Name"` for the first city of that name. Case is ignored.
+    ///
+    /// Knows the preset cities and the principal city of every tz database zone, with
+    /// more cities in India. `timezone` is the zone's standard offset today.
+    ///
+    /// ```
+    /// use aztro_core::Location;
+    ///
+    /// let kozhikode = Location::lookup("kozhikode, in").unwrap();
+    /// assert_eq!(kozhikode, Location::kozhikode());
+    /// assert_eq!(Location::lookup("Tokyo").unwrap().timezone, 9.0);
+    /// ```
+    #[cfg(feature = "geonames")]
+    pub fn lookup(query: &str) -> Option<Location> {
+        let (name, country) = match query.rsplit_once(',') {
+            Some((name, country)) => (name.trim(), Some(country.trim())),
+            None => (query.trim(), None),
+        };
+
+        if let Some(city) = PRESETS.iter().find(|city| city.matches(name, country)) {
+            return city.location();
+        }
+        CITIES
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(parse_city)
+            .find(|city| city.matches(name, country))
+            .and_then(|city| city.location())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_take_the_zone_only_of_a_city_they_stand_in() {
+        let calicut = Location::from_coordinates(11.25, 75.78).unwrap();
+        assert_eq!(calicut.tz_name.as_deref(), Some("Asia/Kolkata"));
+        assert_eq!(calicut.timezone, 5.5);
+        assert_eq!((calicut.latitude, calicut.longitude), (11.25, 75.78));
+
+        // Lahore, Karachi, Kathmandu, Dhaka, Colombo and Tehran are in zones of their own,
+        // however near an Indian or Emirati city, and the open sea is in none
+        for (latitude, longitude) in [
+            (31.55, 74.34),
+            (24.86, 67.01),
+            (27.72, 85.32),
+            (23.81, 90.41),
+            (6.93, 79.85),
+            (35.69, 51.39),
+            (-40.0, -120.0),
+        ] {
+            let location = Location::from_coordinates(latitude, longitude);
+            let resolved = location.as_ref().ok().and_then(|l| l.tz_name.as_deref());
+            assert!(
+                matches!(
+                    resolved,
+                    None | Some(
+                        "Asia/Karachi"
+                            | "Asia/Kathmandu"
+                            | "Asia/Dhaka"
+                            | "Asia/Colombo"
+                            | "Asia/Tehran"
+                    )
+                ),
+                "{}, {}: {:?}",
+                latitude,
+                longitude,
+                resolved
+            );
+        }
+
+        assert!(matches!(
+            Location::from_coordinates(91.0, 0.0),
+            Err(BirthInfoError::InvalidCoordinates(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "geonames")]
+    fn the_city_table_knows_the_neighbours() {
+        let zone = |latitude, longitude| {
+            Location::from_coordinates(latitude, longitude)
+                .ok()
+                .and_then(|location| location.tz_name)
+        };
+        assert_eq!(zone(24.86, 67.01).as_deref(), Some("Asia/Karachi"));
+        assert_eq!(zone(27.72, 85.32).as_deref(), Some("Asia/Kathmandu"));
+        assert_eq!(zone(6.93, 79.85).as_deref(), Some("Asia/Colombo"));
+        assert_eq!(zone(35.69, 51.39).as_deref(), Some("Asia/Tehran"));
+        // Lahore is not in the table, and Amritsar is only 50 km away
+        assert_eq!(zone(31.55, 74.34), None);
+    }
+}
@@ -0,0 +1,436 @@
+use super::*;
+
+// ---------------------------
+// ## Moon Sign Attributes
+// ---------------------------
+
+/// Varnas from highest to lowest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Varna {
+    Brahmin,
+    Kshatriya,
+    Vaishya,
+    Shudra,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Vasya {
+    Chatushpada,
+    Manava,
+    Jalachara,
+    Vanachara,
+    Keeta,
+}
+
+impl ZodiacSign {
+    /// Water signs are Brahmin, fire Kshatriya, earth Vaishya and air Shudra.
+    pub fn varna(&self) -> Varna {
+        match crate::core::sign_varna(*self as usize) {
+            0 => Varna::Brahmin,
+            1 => Varna::Kshatriya,
+            2 => Varna::Vaishya,
+            _ => Varna::Shudra,
+        }
+    }
+}
+
+/// Vasya group of a sidereal longitude. Sagittarius is human in its first half and
+/// four-footed in its second; Capricorn is four-footed in its first half and watery in its
+/// second.
+pub fn vasya_of(longitude: f64) -> Vasya {
+    match crate::core::vasya_index(longitude) {
+        0 => Vasya::Chatushpada,
+        1 => Vasya::Manava,
+        2 => Vasya::Jalachara,
+        3 => Vasya::Vanachara,
+        _ => Vasya::Keeta,
+    }
+}
+
+// ---------------------------
+// ## Kuta Scores
+// ---------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum MatchVerdict {
+    Favorable,
+    Neutral,
+    Unfavorable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KutaScore {
+    pub score: f64,
+    pub max: f64,
+}
+
+impl KutaScore {
+    pub fn verdict(&self) -> MatchVerdict {
+        let ratio = if self.max > 0.0 {
+            self.score / self.max
+        } else {
+            0.0
+        };
+        if ratio >= 0.75 {
+            MatchVerdict::Favorable
+        } else if ratio >= 0.5 {
+            MatchVerdict::Neutral
+        } else {
+            MatchVerdict::Unfavorable
+        }
+    }
+}
+
+/// Tara, Yoni and Gana agreement between two birth stars.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct QuickMatch {
+    pub tara: KutaScore,
+    pub yoni: KutaScore,
+    pub gana: KutaScore,
+}
+
+impl QuickMatch {
+    pub fn total(&self) -> KutaScore {
+        KutaScore {
+            score: self.tara.score + self.yoni.score + self.gana.score,
+            max: self.tara.max + self.yoni.max + self.gana.max,
+        }
+    }
+
+    pub fn verdict(&self) -> MatchVerdict {
+        self.total().verdict()
+    }
+}
+
+/// Compares two Moon nakshatras without needing either chart, for cases where only the
+/// birth star of one or both people is known.
+pub fn quick_match(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> QuickMatch {
+    QuickMatch {
+        tara: tara_kuta(nakshatra1, nakshatra2),
+        yoni: yoni_kuta(nakshatra1, nakshatra2),
+        gana: gana_kuta(nakshatra1, nakshatra2),
+    }
+}
+
+/// Counts the stars from each person to the other; a count falling on the 3rd, 5th or 7th
+/// tara (Vipat, Pratyak, Naidhana) is inauspicious. Each direction is worth 1.5 points.
+pub fn tara_kuta(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> KutaScore {
+    KutaScore {
+        score: crate::core::tara_points(nakshatra1 as usize, nakshatra2 as usize),
+        max: 3.0,
+    }
+}
+
+pub fn yoni_kuta(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> KutaScore {
+    KutaScore {
+        score: crate::core::yoni_points(nakshatra1 as usize, nakshatra2 as usize),
+        max: 4.0,
+    }
+}
+
+pub fn gana_kuta(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> KutaScore {
+    KutaScore {
+        score: crate::core::gana_points(nakshatra1 as usize, nakshatra2 as usize),
+        max: 6.0,
+    }
+}
+
+/// Full marks when the groom's varna is at least as high as the bride's.
+pub fn varna_kuta(groom_moon: ZodiacSign, bride_moon: ZodiacSign) -> KutaScore {
+    KutaScore {
+        score: crate::core::varna_points(groom_moon as usize, bride_moon as usize),
+        max: 1.0,
+    }
+}
+
+pub fn vasya_kuta(groom_moon: f64, bride_moon: f64) -> KutaScore {
+    KutaScore {
+        score: crate::core::vasya_points(groom_moon, bride_moon),
+        max: 2.0,
+    }
+}
+
+/// Graha Maitri from how the lords of the two Moon signs regard each other: 5 for mutual
+/// friends or a shared lord, 4 for friend and neutral, 3 for mutual neutrals, 1 for friend
+/// and enemy, 0.5 for neutral and enemy, otherwise 0.
+pub fn graha_maitri_kuta(
+    moon1: ZodiacSign,
+    moon2: ZodiacSign,
+    friendships: &FriendshipMatrix,
+) -> KutaScore {
+    let (lord1, lord2) = (moon1.lord(), moon2.lord());
+    let score = if lord1 == lord2 {
+        5.0
+    } else {
+        match (
+            friendships.relationship(lord1, lord2),
+            friendships.relationship(lord2, lord1),
+        ) {
+            (Relationship::Friend, Relationship::Friend) => 5.0,
+            (Relationship::Friend, Relationship::Neutral)
+            | (Relationship::Neutral, Relationship::Friend) => 4.0,
+            (Relationship::Neutral, Relationship::Neutral) => 3.0,
+            (Relationship::Friend, Relationship::Enemy)
+            | (Relationship::Enemy, Relationship::Friend) => 1.0,
+            (Relationship::Neutral, Relationship::Enemy)
+            | (Relationship::Enemy, Relationship::Neutral) => 0.5,
+            (Relationship::Enemy, Relationship::Enemy) => 0.0,
+        }
+    };
+    KutaScore { score, max: 5.0 }
+}
+
+/// Moon signs 2/12, 5/9 or 6/8 from each other score nothing.
+pub fn bhakut_kuta(moon1: ZodiacSign, moon2: ZodiacSign) -> KutaScore {
+    KutaScore {
+        score: crate::core::bhakut_points(moon1 as usize, moon2 as usize),
+        max: 7.0,
+    }
+}
+
+/// Birth stars of the same nadi score nothing.
+pub fn nadi_kuta(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> KutaScore {
+    KutaScore {
+        score: crate::core::nadi_points(nakshatra1 as usize, nakshatra2 as usize),
+        max: 8.0,
+    }
+}
+
+/// The eight kutas of the Ashtakoota match, each with its own score and maximum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Ashtakoota {
+    pub varna: KutaScore,
+    pub vasya: KutaScore,
+    pub tara: KutaScore,
+    pub yoni: KutaScore,
+    pub graha_maitri: KutaScore,
+    pub gana: KutaScore,
+    pub bhakut: KutaScore,
+    pub nadi: KutaScore,
+}
+
+impl Ashtakoota {
+    /// Scores the match from the sidereal Moon longitudes of the groom and the bride.
+    pub fn from_moons(groom_moon: f64, bride_moon: f64, friendships: &FriendshipMatrix) -> Self {
+        let (sign1, sign2) = (
+            ZodiacSign::from_longitude(groom_moon),
+            ZodiacSign::from_longitude(bride_moon),
+        );
+        let (nakshatra1, nakshatra2) = (
+            NakshatraInfo::from_longitude(groom_moon).nakshatra,
+            NakshatraInfo::from_longitude(bride_moon).nakshatra,
+        );
+
+        Ashtakoota {
+            varna: varna_kuta(sign1, sign2),
+            vasya: vasya_kuta(groom_moon, bride_moon),
+            tara: tara_kuta(nakshatra1, nakshatra2),
+            yoni: yoni_kuta(nakshatra1, nakshatra2),
+            graha_maitri: graha_maitri_kuta(sign1, sign2, friendships),
+            gana: gana_kuta(nakshatra1, nakshatra2),
+            bhakut: bhakut_kuta(sign1, sign2),
+            nadi: nadi_kuta(nakshatra1, nakshatra2),
+        }
+    }
+
+    /// Rescales each kuta so its maximum is the matching weight. A kuta keeps its share of
+    /// the classical maximum: half the Yoni points is half the Yoni weight.
+    pub fn weighted(&self, weights: &KutaWeights) -> Ashtakoota {
+        let scale = |kuta: KutaScore, weight: f64| KutaScore {
+            score: if kuta.max > 0.0 {
+                kuta.score / kuta.max * weight
+            } else {
+                0.0
+            },
+            max: weight,
+        };
+
+        Ashtakoota {
+            varna: scale(self.varna, weights.varna),
+            vasya: scale(self.vasya, weights.vasya),
+            tara: scale(self.tara, weights.tara),
+            yoni: scale(self.yoni, weights.yoni),
+            graha_maitri: scale(self.graha_maitri, weights.graha_maitri),
+            gana: scale(self.gana, weights.gana),
+            bhakut: scale(self.bhakut, weights.bhakut),
+            nadi: scale(self.nadi, weights.nadi),
+        }
+    }
+
+    pub fn kutas(&self) -> [KutaScore; 8] {
+        [
+            self.varna,
+            self.vasya,
+            self.tara,
+            self.yoni,
+            self.graha_maitri,
+            self.gana,
+            self.bhakut,
+            self.nadi,
+        ]
+    }
+
+    pub fn total(&self) -> KutaScore {
+        self.kutas().into_iter().fold(
+            KutaScore {
+                score: 0.0,
+                max: 0.0,
+            },
+            |total, kuta| KutaScore {
+                score: total.score + kuta.score,
+                max: total.max + kuta.max,
+            },
+        )
+    }
+}
+
+// ---------------------------
+// ## Mangal Dosha
+// ---------------------------
+
+/// Mars in the 1st, 2nd, 4th, 7th, 8th or 12th house, counted in whole signs from the
+/// lagna, the Moon and Venus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MangalDosha {
+    pub from_lagna: bool,
+    pub from_moon: bool,
+    pub from_venus: bool,
+    /// Rules that cancel the dosha in this chart.
+    pub cancellations: Vec<String>,
+}
+
+impl MangalDosha {
+    const HOUSES: [usize; 6] = [1, 2, 4, 7, 8, 12];
+
+    pub fn from_chart(chart: &ChartInfo) -> MangalDosha {
+        let position = |body: CelestialBody| chart.planets.iter().find(|p| p.planet == body);
+        let Some(mars) = position(CelestialBody::Mars) else {
+            return MangalDosha {
+                from_lagna: false,
+                from_moon: false,
+                from_venus: false,
+                cancellations: Vec::new(),
+            };
+        };
+        let house_from = |from: ZodiacSign| (mars.sign as usize + 12 - from as usize) % 12 + 1;
+        let afflicts = |from: Option<ZodiacSign>| {
+            from.is_some_and(|from| Self::HOUSES.contains(&house_from(from)))
+        };
+
+        let lagna_house = house_from(chart.ascendant.sign);
+        let mut cancellations = Vec::new();
+        if matches!(
+            mars.sign,
+            ZodiacSign::Aries | ZodiacSign::Scorpio | ZodiacSign::Capricorn
+        ) {
+            cancellations.push(format!("Mars in its own or exaltation sign, {}", mars.sign));
+        }
+        if matches!(mars.sign, ZodiacSign::Leo | ZodiacSign::Aquarius) {
+            cancellations.push(format!("Mars in {}", mars.sign));
+        }
+        let exempt_signs: &[ZodiacSign] = match lagna_house {
+            2 => &[ZodiacSign::Gemini, ZodiacSign::Virgo],
+            4 => &[ZodiacSign::Aries, ZodiacSign::Scorpio],
+            7 => &[ZodiacSign::Cancer, ZodiacSign::Capricorn],
+            8 => &[ZodiacSign::Sagittarius, ZodiacSign::Pisces],
+            12 => &[ZodiacSign::Taurus, ZodiacSign::Libra],
+            _ => &[],
+        };
+        if exempt_signs.contains(&mars.sign) {
+            cancellations.push(format!("Mars in {} in house {}", mars.sign, lagna_house));
+        }
+        if let Some(jupiter) = position(CelestialBody::Jupiter) {
+            let from_jupiter = (mars.sign as usize + 12 - jupiter.sign as usize) % 12 + 1;
+            if matches!(from_jupiter, 1 | 5 | 7 | 9) {
+                cancellations.push("Mars joined or aspected by Jupiter".to_string());
+            }
+        }
+
+        MangalDosha {
+            from_lagna: afflicts(Some(chart.ascendant.sign)),
+            from_moon: afflicts(position(CelestialBody::Moon).map(|p| p.sign)),
+            from_venus: afflicts(position(CelestialBody::Venus).map(|p| p.sign)),
+            cancellations,
+        }
+    }
+
+    /// Mars afflicts from at least one reference point.
+    pub fn is_formed(&self) -> bool {
+        self.from_lagna || self.from_moon || self.from_venus
+    }
+
+    /// Formed and not cancelled in the chart itself.
+    pub fn is_present(&self) -> bool {
+        self.is_formed() && self.cancellations.is_empty()
+    }
+}
+
+impl CompatibilityInfo {
+    /// Mangal Dosha does not stand in the way of the match: neither partner has it, or
+    /// both do and cancel each other out.
+    pub fn mangal_dosha_matched(&self) -> bool {
+        self.mangal_dosha1.is_present() == self.mangal_dosha2.is_present()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(nakshatra1: Nakshatra, nakshatra2: Nakshatra) -> (f64, f64, f64) {
+        let quick = quick_match(nakshatra1, nakshatra2);
+        (quick.tara.score, quick.yoni.score, quick.gana.score)
+    }
+
+    #[test]
+    fn quick_match_agrees_with_the_ashtakoota_tables() {
+        // The same star is Janma tara both ways, one animal and one gana: full marks
+        for nakshatra in [Nakshatra::Ashwini, Nakshatra::Rohini, Nakshatra::Revati] {
+            let quick = quick_match(nakshatra, nakshatra);
+            assert_eq!(
+                quick.total(),
+                KutaScore {
+                    score: 13.0,
+                    max: 13.0
+                }
+            );
+            assert_eq!(quick.verdict(), MatchVerdict::Favorable);
+        }
+
+        // Ashwini and Bharani: Sampat and Parama Mitra tara, horse and elephant, Deva and
+        // Manushya. Counting from either side gives the same points.
+        assert_eq!(
+            scores(Nakshatra::Ashwini, Nakshatra::Bharani),
+            (3.0, 2.0, 5.0)
+        );
+        assert_eq!(
+            scores(Nakshatra::Bharani, Nakshatra::Ashwini),
+            (3.0, 2.0, 5.0)
+        );
+
+        // Krittika is the 3rd star, Vipat, from Ashwini, and a Rakshasa one
+        assert_eq!(
+            scores(Nakshatra::Ashwini, Nakshatra::Krittika),
+            (1.5, 2.0, 1.0)
+        );
+
+        // Sworn enemies among the animals: horse and buffalo, tiger and cow
+        assert_eq!(scores(Nakshatra::Ashwini, Nakshatra::Hasta).1, 0.0);
+        assert_eq!(
+            scores(Nakshatra::Chitra, Nakshatra::UttaraBhadrapada).1,
+            0.0
+        );
+        assert_eq!(scores(Nakshatra::Swati, Nakshatra::Hasta).1, 4.0);
+        assert_eq!(scores(Nakshatra::Shatabhisha, Nakshatra::Ashwini).1, 4.0);
+
+        // Manushya and Rakshasa do not mix
+        assert_eq!(scores(Nakshatra::Bharani, Nakshatra::Krittika).2, 0.0);
+    }
+}
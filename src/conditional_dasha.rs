@@ -0,0 +1,329 @@
+use super::*;
+
+/// A dasha the texts prescribe only for charts meeting its conditions, counted from the
+/// chart as a whole rather than from the Moon alone. Every [`DashaSystem`] is one, counted
+/// from the chart's Moon; the sign dashas of Jaimini, such as [`ShoolaDasha`], start from
+/// houses of the chart instead.
+///
+/// Implement it and add the system to a [`DashaRegistry`] to have it offered for the
+/// charts it applies to.
+pub trait ConditionalDasha: Send + Sync {
+    fn name(&self) -> String;
+
+    /// Whether the conditions for the system hold in `chart`.
+    fn applies(&self, chart: &ChartInfo) -> bool;
+
+    /// Mahadashas in order from the one running at birth, each with its full length in
+    /// years, and the fraction of the first that had already passed at birth.
+    fn mahadashas(&self, chart: &ChartInfo) -> (Vec<(DashaLord, f64)>, f64);
+
+    /// The sub-periods of a period ruled by `lord`, in order, each with the fraction of
+    /// the period it takes.
+    fn antardashas(&self, chart: &ChartInfo, lord: DashaLord) -> Vec<(DashaLord, f64)>;
+}
+
+impl<T: DashaSystem + Send + Sync> ConditionalDasha for T {
+    fn name(&self) -> String {
+        format!("{:?}", self.kind())
+    }
+
+    fn applies(&self, chart: &ChartInfo) -> bool {
+        self.applies_to(chart) && chart.planet(CelestialBody::Moon).is_some()
+    }
+
+    fn mahadashas(&self, chart: &ChartInfo) -> (Vec<(DashaLord, f64)>, f64) {
+        self.maha_dashas(moon_longitude(chart))
+    }
+
+    fn antardashas(&self, chart: &ChartInfo, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        self.sub_periods(moon_longitude(chart), lord)
+    }
+}
+
+fn moon_longitude(chart: &ChartInfo) -> f64 {
+    chart
+        .planet(CelestialBody::Moon)
+        .map_or(0.0, |moon| moon.longitude.degrees())
+}
+
+/// The sign `houses` on from the lagna, the lagna being the first.
+fn house_sign(chart: &ChartInfo, house: usize) -> ZodiacSign {
+    ZodiacSign::from_longitude((chart.ascendant.sign as usize + house - 1) as f64 * 30.0)
+}
+
+/// Twelve signs from `first`, forward through the zodiac from an odd sign and backward
+/// from an even one, each with its years.
+fn sign_sequence(first: ZodiacSign, years: impl Fn(ZodiacSign) -> f64) -> Vec<(DashaLord, f64)> {
+    // Aries, the first sign, is odd
+    let direct = (first as usize).is_multiple_of(2);
+    (0..12)
+        .map(|step| {
+            let offset = if direct { step } else { 12 - step };
+            let sign = ZodiacSign::from_longitude(((first as usize + offset) % 12) as f64 * 30.0);
+            (DashaLord::Sign(sign), years(sign))
+        })
+        .collect()
+}
+
+/// The sign sequence from the period's own sign, in the mahadashas' direction, each
+/// sub-period in proportion to its sign's years.
+fn sign_antardashas(mahadashas: Vec<(DashaLord, f64)>, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+    let total: f64 = mahadashas.iter().map(|&(_, years)| years).sum();
+    let start = mahadashas
+        .iter()
+        .position(|&(sign, _)| sign == lord)
+        .unwrap_or(0);
+    (0..mahadashas.len())
+        .map(|step| mahadashas[(start + step) % mahadashas.len()])
+        .map(|(sign, years)| (sign, years / total))
+        .collect()
+}
+
+/// Jaimini's dasha for the timing of dangers and the end of life: nine years to each sign,
+/// from the stronger of the lagna and the 7th house.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ShoolaDasha;
+
+impl ShoolaDasha {
+    fn sequence(chart: &ChartInfo) -> Vec<(DashaLord, f64)> {
+        let first = chart.stronger_sign(house_sign(chart, 1), house_sign(chart, 7));
+        sign_sequence(first, |_| 9.0)
+    }
+}
+
+impl ConditionalDasha for ShoolaDasha {
+    fn name(&self) -> String {
+        "Shoola".to_string()
+    }
+
+    fn applies(&self, _chart: &ChartInfo) -> bool {
+        true
+    }
+
+    fn mahadashas(&self, chart: &ChartInfo) -> (Vec<(DashaLord, f64)>, f64) {
+        (Self::sequence(chart), 0.0)
+    }
+
+    fn antardashas(&self, chart: &ChartInfo, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        sign_antardashas(Self::sequence(chart), lord)
+    }
+}
+
+/// Jaimini's Shoola dasha counted from the stronger of the 2nd and 8th houses, with seven
+/// years to movable signs, eight to fixed and nine to dual.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NiryanaShoolaDasha;
+
+impl NiryanaShoolaDasha {
+    fn sequence(chart: &ChartInfo) -> Vec<(DashaLord, f64)> {
+        let first = chart.stronger_sign(house_sign(chart, 2), house_sign(chart, 8));
+        sign_sequence(first, |sign| 7.0 + (sign as usize % 3) as f64)
+    }
+}
+
+impl ConditionalDasha for NiryanaShoolaDasha {
+    fn name(&self) -> String {
+        "NiryanaShoola".to_string()
+    }
+
+    fn applies(&self, _chart: &ChartInfo) -> bool {
+        true
+    }
+
+    fn mahadashas(&self, chart: &ChartInfo) -> (Vec<(DashaLord, f64)>, f64) {
+        (Self::sequence(chart), 0.0)
+    }
+
+    fn antardashas(&self, chart: &ChartInfo, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        sign_antardashas(Self::sequence(chart), lord)
+    }
+}
+
+/// One mahadasha of a [`DashaTable`] with its antardashas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DashaTableEntry {
+    pub period: DashaPeriod,
+    pub antardashas: Vec<DashaPeriod>,
+}
+
+/// The periods of one dasha system over a native's life.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DashaTable {
+    /// [`ConditionalDasha::name`] of the system.
+    pub system: String,
+    pub periods: Vec<DashaTableEntry>,
+}
+
+/// The dasha systems to consider for a chart. The default holds every system built in;
+/// [`DashaRegistry::register`] adds others.
+pub struct DashaRegistry {
+    systems: Vec<Box<dyn ConditionalDasha>>,
+}
+
+impl Default for DashaRegistry {
+    fn default() -> Self {
+        DashaRegistry::empty()
+            .register(VimshottariDasha)
+            .register(YoginiDasha)
+            .register(AshtottariDasha)
+            .register(KalachakraDasha)
+            .register(DwisaptatiSamaDasha)
+            .register(ShoolaDasha)
+            .register(NiryanaShoolaDasha)
+    }
+}
+
+impl DashaRegistry {
+    /// A registry with no systems in it.
+    pub fn empty() -> Self {
+        DashaRegistry {
+            systems: Vec::new(),
+        }
+    }
+
+    pub fn register(mut self, system: impl ConditionalDasha + 'static) -> Self {
+        self.systems.push(Box::new(system));
+        self
+    }
+
+    pub fn systems(&self) -> impl Iterator<Item = &dyn ConditionalDasha> {
+        self.systems.iter().map(|system| system.as_ref())
+    }
+
+    /// The systems whose conditions `chart` meets, in the order registered.
+    pub fn applicable(&self, chart: &ChartInfo) -> Vec<&dyn ConditionalDasha> {
+        self.systems()
+            .filter(|system| system.applies(chart))
+            .collect()
+    }
+
+    /// Mahadashas and antardashas of every system applying to `chart`, for a native born
+    /// at `birth`, with years of `year_days` days.
+    pub fn tables(
+        &self,
+        chart: &ChartInfo,
+        birth: DateTime<Utc>,
+        year_days: f64,
+    ) -> Vec<DashaTable> {
+        self.applicable(chart)
+            .into_iter()
+            .map(|system| {
+                let (sequence, elapsed) = system.mahadashas(chart);
+                let periods = lay_out_periods(&sequence, elapsed, birth, year_days)
+                    .into_iter()
+                    .map(|period| DashaTableEntry {
+                        antardashas: split_by_fractions(
+                            &period,
+                            system.antardashas(chart, period.lord),
+                        ),
+                        period,
+                    })
+                    .collect();
+                DashaTable {
+                    system: system.name(),
+                    periods,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Dasha tables of every system in `registry` that applies to the birth chart, on the
+    /// engine's house system and dasha year.
+    pub fn calculate_applicable_dashas(
+        &self,
+        birth_info: &BirthInfo,
+        registry: &DashaRegistry,
+    ) -> Result<Vec<DashaTable>, AstrologyError> {
+        let chart = self.calculate_chart(birth_info, self.conventions.house_system)?;
+        Ok(registry.tables(
+            &chart,
+            birth_info.date_time,
+            self.conventions.dasha_year_days,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    /// Aries lagna with Mars, its lord, in Libra.
+    fn chart() -> ChartInfo {
+        ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 10.0,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                planet(CelestialBody::Mars, 190.0),
+                planet(CelestialBody::Venus, 200.0),
+                planet(CelestialBody::Moon, 250.0),
+            ],
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn conditional_systems_are_offered_only_when_they_apply() {
+        let registry = DashaRegistry::default();
+        let names: Vec<String> = registry
+            .applicable(&chart())
+            .iter()
+            .map(|system| system.name())
+            .collect();
+        // Mars, lord of the lagna, sits in the 7th
+        assert!(names.contains(&"DwisaptatiSama".to_string()));
+        assert!(names.contains(&"Shoola".to_string()));
+
+        let mut elsewhere = chart();
+        elsewhere.planets[0] = planet(CelestialBody::Mars, 100.0);
+        elsewhere.planets[1] = planet(CelestialBody::Venus, 100.0);
+        assert!(!DwisaptatiSamaDasha.applies(&elsewhere));
+    }
+
+    #[test]
+    fn shoola_starts_from_the_stronger_of_lagna_and_seventh() {
+        // Libra holds two planets to the lagna's none, and is odd, so the dasha runs on
+        // through Scorpio, nine years each
+        let birth = DateTime::from_timestamp(0, 0).unwrap();
+        let table = DashaRegistry::empty()
+            .register(ShoolaDasha)
+            .tables(&chart(), birth, 360.0)
+            .remove(0);
+        let first = &table.periods[0];
+        assert_eq!(first.period.lord, DashaLord::Sign(ZodiacSign::Libra));
+        assert_eq!(first.period.start, birth);
+        assert_eq!((first.period.end - first.period.start).num_days(), 9 * 360);
+        assert_eq!(
+            table.periods[1].period.lord,
+            DashaLord::Sign(ZodiacSign::Scorpio)
+        );
+        assert_eq!(first.antardashas.len(), 12);
+        assert_eq!(first.antardashas[0].lord, first.period.lord);
+        // Each antardasha is rounded down to the second
+        let shortfall = first.period.end - first.antardashas[11].end;
+        assert!((0..12).contains(&shortfall.num_seconds()));
+    }
+}
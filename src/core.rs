@@ -0,0 +1,326 @@
+//! The symbolic layer on plain numbers: signs, nakshatras and navamsas from a longitude,
+//! Vimshottari dasha arithmetic and the Ashtakoota kuta points.
+//!
+//! Nothing here needs the ephemeris, a clock or an allocator. Longitudes are sidereal
+//! degrees the caller supplies, from any source, and everything else is an index counted
+//! from zero: Aries is sign 0, Ashwini nakshatra 0, Ketu the first Vimshottari lord. The
+//! module uses only what the `core` library provides, so it can be lifted into a `no_std`
+//! build for embedded or WebAssembly targets as it is. The typed API of the rest of the
+//! crate is built on these functions.
+
+/// Degrees in a sign.
+pub const SIGN_SPAN: f64 = 30.0;
+
+/// Degrees in a nakshatra, 13°20'.
+pub const NAKSHATRA_SPAN: f64 = 360.0 / 27.0;
+
+/// Degrees in a pada, a quarter of a nakshatra.
+pub const PADA_SPAN: f64 = NAKSHATRA_SPAN / 4.0;
+
+/// Mahadasha years of the Vimshottari lords, from Ketu: Ketu, Venus, Sun, Moon, Mars,
+/// Rahu, Jupiter, Saturn and Mercury.
+pub const VIMSHOTTARI_YEARS: [f64; 9] = [7.0, 20.0, 6.0, 10.0, 7.0, 18.0, 16.0, 19.0, 17.0];
+
+/// Length of the whole Vimshottari cycle.
+pub const VIMSHOTTARI_CYCLE_YEARS: f64 = 120.0;
+
+/// `longitude` brought into `0..360`. Written without `f64::rem_euclid`, which needs the
+/// standard library.
+pub fn normalize(longitude: f64) -> f64 {
+    let longitude = longitude % 360.0;
+    if longitude < 0.0 {
+        longitude + 360.0
+    } else {
+        longitude
+    }
+}
+
+/// Whole `span`s in `degrees`, no more than `count - 1`. The cast truncates, which is the
+/// floor for the non-negative numbers it is given.
+fn part(degrees: f64, span: f64, count: usize) -> usize {
+    ((degrees / span) as usize).min(count - 1)
+}
+
+/// Sign of a longitude, 0 for Aries to 11 for Pisces.
+pub fn sign_index(longitude: f64) -> usize {
+    part(normalize(longitude), SIGN_SPAN, 12)
+}
+
+/// Degrees from the start of the sign.
+pub fn degree_in_sign(longitude: f64) -> f64 {
+    let longitude = normalize(longitude);
+    longitude - sign_index(longitude) as f64 * SIGN_SPAN
+}
+
+/// Nakshatra of a longitude, 0 for Ashwini to 26 for Revati.
+pub fn nakshatra_index(longitude: f64) -> usize {
+    part(normalize(longitude), NAKSHATRA_SPAN, 27)
+}
+
+/// Share of its nakshatra the longitude has passed through, from 0 to 1.
+pub fn nakshatra_elapsed(longitude: f64) -> f64 {
+    let position = normalize(longitude) / NAKSHATRA_SPAN;
+    position - position as usize as f64
+}
+
+/// Pada of a longitude within its nakshatra, 1 to 4.
+pub fn pada(longitude: f64) -> u8 {
+    part(normalize(longitude) % NAKSHATRA_SPAN, PADA_SPAN, 4) as u8 + 1
+}
+
+/// Maps a longitude into a varga of `divisions` equal parts per sign. `start_sign` gives,
+/// for a sign index, the sign index from which its divisions are counted.
+pub fn varga_longitude(longitude: f64, divisions: u32, start_sign: impl Fn(usize) -> usize) -> f64 {
+    let sign = sign_index(longitude);
+    let span = SIGN_SPAN / divisions as f64;
+    let degree_in_sign = degree_in_sign(longitude);
+    let part = part(degree_in_sign, span, divisions as usize);
+    let within_part = (degree_in_sign - part as f64 * span) / span;
+
+    let varga_sign = (start_sign(sign) + part) % 12;
+    varga_sign as f64 * SIGN_SPAN + within_part * SIGN_SPAN
+}
+
+/// Navamsa (D9) longitude. Each sign is cut into nine parts of 3°20', counted from the
+/// movable sign of its element: Aries for fire signs, Capricorn for earth, Libra for air
+/// and Cancer for water.
+pub fn navamsa_longitude(longitude: f64) -> f64 {
+    varga_longitude(longitude, 9, |sign| match sign % 4 {
+        0 => 0,
+        1 => 9,
+        2 => 6,
+        _ => 3,
+    })
+}
+
+/// Navamsa sign of a longitude.
+pub fn navamsa_sign_index(longitude: f64) -> usize {
+    sign_index(navamsa_longitude(longitude))
+}
+
+// ---------------------------
+// ## Vimshottari
+// ---------------------------
+
+/// Vimshottari lord of a nakshatra, an index into [`VIMSHOTTARI_YEARS`]. The nine lords
+/// repeat three times round the zodiac from Ashwini.
+pub fn vimshottari_lord(nakshatra: usize) -> usize {
+    nakshatra % 9
+}
+
+/// The mahadasha running at birth for the Moon at `moon_longitude`, and the years of it
+/// left to run.
+pub fn vimshottari_balance(moon_longitude: f64) -> (usize, f64) {
+    let lord = vimshottari_lord(nakshatra_index(moon_longitude));
+    let years = VIMSHOTTARI_YEARS[lord] * (1.0 - nakshatra_elapsed(moon_longitude));
+    (lord, years)
+}
+
+/// Years of the `antar` sub-period within the `maha` period. Each lord takes its share of
+/// the period in proportion to its own mahadasha years.
+pub fn antardasha_years(maha: usize, antar: usize) -> f64 {
+    VIMSHOTTARI_YEARS[maha] * VIMSHOTTARI_YEARS[antar] / VIMSHOTTARI_CYCLE_YEARS
+}
+
+// ---------------------------
+// ## Kutas
+// ---------------------------
+
+/// Yoni animal of each nakshatra, from Ashwini: Horse 0, Elephant 1, Sheep 2, Serpent 3,
+/// Dog 4, Cat 5, Rat 6, Cow 7, Buffalo 8, Tiger 9, Deer 10, Monkey 11, Mongoose 12 and
+/// Lion 13.
+const NAKSHATRA_YONIS: [usize; 27] = [
+    0, 1, 2, 3, 3, 4, 5, 2, 5, 6, 6, 7, 8, 9, 8, 9, 10, 10, 4, 11, 12, 11, 13, 0, 13, 7, 1,
+];
+
+/// Gana of each nakshatra, from Ashwini: Deva 0, Manushya 1 and Rakshasa 2.
+const NAKSHATRA_GANAS: [usize; 27] = [
+    0, 1, 2, 1, 0, 1, 0, 0, 2, 2, 1, 1, 0, 2, 0, 2, 0, 2, 2, 1, 1, 0, 2, 2, 1, 1, 0,
+];
+
+/// Yoni points, rows and columns in the order of [`nakshatra_yoni`].
+const YONI_POINTS: [[u8; 14]; 14] = [
+    [4, 2, 2, 3, 2, 2, 2, 1, 0, 1, 3, 3, 2, 1],
+    [2, 4, 3, 3, 2, 2, 2, 2, 3, 1, 2, 3, 2, 0],
+    [2, 3, 4, 2, 1, 2, 1, 3, 3, 1, 2, 0, 3, 1],
+    [3, 3, 2, 4, 2, 1, 1, 1, 1, 2, 2, 2, 0, 2],
+    [2, 2, 1, 2, 4, 2, 1, 2, 2, 1, 0, 2, 1, 1],
+    [2, 2, 2, 1, 2, 4, 0, 2, 2, 1, 3, 3, 2, 1],
+    [2, 2, 1, 1, 1, 0, 4, 2, 2, 2, 2, 2, 1, 2],
+    [1, 2, 3, 1, 2, 2, 2, 4, 3, 0, 3, 2, 2, 1],
+    [0, 3, 3, 1, 2, 2, 2, 3, 4, 1, 2, 2, 2, 1],
+    [1, 1, 1, 2, 1, 1, 2, 0, 1, 4, 1, 1, 2, 1],
+    [3, 2, 2, 2, 0, 3, 2, 3, 2, 1, 4, 2, 2, 1],
+    [3, 3, 0, 2, 2, 3, 2, 2, 2, 1, 2, 4, 3, 2],
+    [2, 2, 3, 0, 1, 2, 1, 2, 2, 2, 2, 3, 4, 2],
+    [1, 0, 1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 4],
+];
+
+/// Vasya points, rows and columns in the order of [`vasya_index`].
+const VASYA_POINTS: [[f64; 5]; 5] = [
+    [2.0, 1.0, 1.0, 0.5, 1.0],
+    [1.0, 2.0, 0.5, 0.0, 1.0],
+    [1.0, 0.5, 2.0, 1.0, 1.0],
+    [0.5, 0.0, 1.0, 2.0, 0.0],
+    [1.0, 1.0, 1.0, 0.0, 2.0],
+];
+
+/// Yoni animal of a nakshatra, 0 for the horse to 13 for the lion.
+pub fn nakshatra_yoni(nakshatra: usize) -> usize {
+    NAKSHATRA_YONIS[nakshatra % 27]
+}
+
+/// Gana of a nakshatra: 0 Deva, 1 Manushya, 2 Rakshasa.
+pub fn nakshatra_gana(nakshatra: usize) -> usize {
+    NAKSHATRA_GANAS[nakshatra % 27]
+}
+
+/// Nadi of a nakshatra: 0 Aadi, 1 Madhya, 2 Antya. They run there and back again, six
+/// stars to a round.
+pub fn nakshatra_nadi(nakshatra: usize) -> usize {
+    match nakshatra % 6 {
+        0 | 5 => 0,
+        1 | 4 => 1,
+        _ => 2,
+    }
+}
+
+/// Varna of a sign, highest first: 0 Brahmin for water signs, 1 Kshatriya for fire, 2
+/// Vaishya for earth and 3 Shudra for air.
+pub fn sign_varna(sign: usize) -> usize {
+    match sign % 4 {
+        3 => 0,
+        0 => 1,
+        1 => 2,
+        _ => 3,
+    }
+}
+
+/// Vasya group of a longitude: 0 Chatushpada, 1 Manava, 2 Jalachara, 3 Vanachara and 4
+/// Keeta. Sagittarius is human in its first half and four-footed in its second; Capricorn
+/// is four-footed in its first half and watery in its second.
+pub fn vasya_index(longitude: f64) -> usize {
+    let first_half = degree_in_sign(longitude) < 15.0;
+    match sign_index(longitude) {
+        0 | 1 => 0,
+        8 if first_half => 1,
+        8 => 0,
+        9 if first_half => 0,
+        9 => 2,
+        2 | 5 | 6 | 10 => 1,
+        3 | 11 => 2,
+        4 => 3,
+        _ => 4,
+    }
+}
+
+/// Tara of nakshatra `to` counted from `from`, 0 for Janma to 8 for Parama Mitra.
+pub fn tara_index(from: usize, to: usize) -> usize {
+    (to % 27 + 27 - from % 27) % 27 % 9
+}
+
+/// Tara kuta out of 3: 1.5 for each direction whose count does not fall on the 3rd, 5th
+/// or 7th tara (Vipat, Pratyak, Naidhana).
+pub fn tara_points(nakshatra1: usize, nakshatra2: usize) -> f64 {
+    let favourable = |from, to| !matches!(tara_index(from, to), 2 | 4 | 6);
+    let mut points = 0.0;
+    if favourable(nakshatra1, nakshatra2) {
+        points += 1.5;
+    }
+    if favourable(nakshatra2, nakshatra1) {
+        points += 1.5;
+    }
+    points
+}
+
+/// Yoni kuta out of 4, from the two nakshatras' animals.
+pub fn yoni_points(nakshatra1: usize, nakshatra2: usize) -> f64 {
+    YONI_POINTS[nakshatra_yoni(nakshatra1)][nakshatra_yoni(nakshatra2)] as f64
+}
+
+/// Gana kuta out of 6.
+pub fn gana_points(nakshatra1: usize, nakshatra2: usize) -> f64 {
+    match (nakshatra_gana(nakshatra1), nakshatra_gana(nakshatra2)) {
+        (g1, g2) if g1 == g2 => 6.0,
+        (0, 1) | (1, 0) => 5.0,
+        (0, 2) | (2, 0) => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Varna kuta out of 1: full marks when the groom's varna is at least as high as the
+/// bride's.
+pub fn varna_points(groom_sign: usize, bride_sign: usize) -> f64 {
+    if sign_varna(groom_sign) <= sign_varna(bride_sign) {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Vasya kuta out of 2, from the Moons' longitudes.
+pub fn vasya_points(groom_moon: f64, bride_moon: f64) -> f64 {
+    VASYA_POINTS[vasya_index(groom_moon)][vasya_index(bride_moon)]
+}
+
+/// Bhakut kuta out of 7: Moon signs 2/12, 5/9 or 6/8 from each other score nothing.
+pub fn bhakut_points(sign1: usize, sign2: usize) -> f64 {
+    let count = (sign2 % 12 + 12 - sign1 % 12) % 12 + 1;
+    if matches!(count, 2 | 5 | 6 | 8 | 9 | 12) {
+        0.0
+    } else {
+        7.0
+    }
+}
+
+/// Nadi kuta out of 8: birth stars of the same nadi score nothing.
+pub fn nadi_points(nakshatra1: usize, nakshatra2: usize) -> f64 {
+    if nakshatra_nadi(nakshatra1) == nakshatra_nadi(nakshatra2) {
+        0.0
+    } else {
+        8.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gana, Nakshatra, ZodiacSign};
+
+    #[test]
+    fn indices_agree_with_the_typed_api() {
+        for step in 0..1440 {
+            let longitude = step as f64 * 0.25 - 720.0 + 0.1;
+            assert_eq!(
+                sign_index(longitude),
+                ZodiacSign::from_longitude(longitude) as usize
+            );
+            let nakshatra = Nakshatra::from_longitude(longitude);
+            assert_eq!(nakshatra_index(longitude), nakshatra as usize);
+            assert_eq!(
+                nakshatra_yoni(nakshatra as usize),
+                nakshatra.yoni() as usize
+            );
+            assert_eq!(
+                nakshatra_gana(nakshatra as usize),
+                nakshatra.gana() as usize
+            );
+        }
+        assert_eq!(
+            nakshatra_gana(Nakshatra::Revati as usize),
+            Gana::Deva as usize
+        );
+        assert_eq!(pada(NAKSHATRA_SPAN - 1e-9), 4);
+        assert_eq!(navamsa_sign_index(31.0), 9);
+    }
+
+    #[test]
+    fn vimshottari_balance_is_what_is_left_of_the_first_lord() {
+        // Rohini, ruled by the Moon, half gone
+        let (lord, years) = vimshottari_balance(3.5 * NAKSHATRA_SPAN);
+        assert_eq!(lord, 3);
+        assert!((years - 5.0).abs() < 1e-9);
+        let total: f64 = (0..9).map(|antar| antardasha_years(5, antar)).sum();
+        assert!((total - 18.0).abs() < 1e-9);
+    }
+}
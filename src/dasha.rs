@@ -0,0 +1,205 @@
+use super::*;
+
+impl Dasha {
+    /// The planet whose period this is.
+    pub fn lord(&self) -> CelestialBody {
+        match self {
+            Dasha::Ketu => CelestialBody::Ketu,
+            Dasha::Venus => CelestialBody::Venus,
+            Dasha::Sun => CelestialBody::Sun,
+            Dasha::Moon => CelestialBody::Moon,
+            Dasha::Mars => CelestialBody::Mars,
+            Dasha::Rahu => CelestialBody::Rahu,
+            Dasha::Jupiter => CelestialBody::Jupiter,
+            Dasha::Saturn => CelestialBody::Saturn,
+            Dasha::Mercury => CelestialBody::Mercury,
+        }
+    }
+
+    /// The nine periods in Vimshottari order, starting from Ketu.
+    pub fn sequence() -> [Dasha; 9] {
+        [
+            Dasha::Ketu,
+            Dasha::Venus,
+            Dasha::Sun,
+            Dasha::Moon,
+            Dasha::Mars,
+            Dasha::Rahu,
+            Dasha::Jupiter,
+            Dasha::Saturn,
+            Dasha::Mercury,
+        ]
+    }
+
+    /// The Vimshottari period ruled by `body`, if it rules one.
+    pub fn from_lord(body: CelestialBody) -> Option<Dasha> {
+        match body {
+            CelestialBody::Ketu => Some(Dasha::Ketu),
+            CelestialBody::Venus => Some(Dasha::Venus),
+            CelestialBody::Sun => Some(Dasha::Sun),
+            CelestialBody::Moon => Some(Dasha::Moon),
+            CelestialBody::Mars => Some(Dasha::Mars),
+            CelestialBody::Rahu => Some(Dasha::Rahu),
+            CelestialBody::Jupiter => Some(Dasha::Jupiter),
+            CelestialBody::Saturn => Some(Dasha::Saturn),
+            CelestialBody::Mercury => Some(Dasha::Mercury),
+            _ => None,
+        }
+    }
+
+    /// Length of the full mahadasha in years.
+    pub fn years(&self) -> f64 {
+        crate::core::VIMSHOTTARI_YEARS[*self as usize]
+    }
+}
+
+// ---------------------------
+// ## Dasha Changes
+// ---------------------------
+
+/// Changes at different levels closer together than this are in sandhi.
+pub const DASHA_SANDHI_WINDOW_HOURS: i64 = 24;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DashaLevel {
+    Maha,
+    Antar,
+    Pratyantar,
+}
+
+/// One period giving way to the next.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DashaChange {
+    pub level: DashaLevel,
+    pub from: DashaLord,
+    pub to: DashaLord,
+    pub at: DateTime<Utc>,
+    /// A change at another level falls within [`DASHA_SANDHI_WINDOW_HOURS`] of this one, so
+    /// the junction is felt at more than one level.
+    pub sandhi: bool,
+}
+
+impl DashaInfo {
+    /// Every maha, antar and pratyantar change from [`DashaInfo::as_of`] until `within`
+    /// after it.
+    pub fn upcoming_changes(&self, within: ChronoDuration) -> Vec<DashaChange> {
+        self.changes_between(self.as_of, self.as_of + within)
+    }
+
+    /// Every maha, antar and pratyantar change after `start` and up to `end`, earliest
+    /// first, within the mahadashas laid out from birth.
+    pub fn changes_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<DashaChange> {
+        let maha_periods = &self.maha_dashas;
+        let antar_periods: Vec<_> = maha_periods
+            .iter()
+            .filter(|maha| maha.end > start && maha.start <= end)
+            .flat_map(|maha| self.sub_periods(maha))
+            .collect();
+        let pratyantar_periods: Vec<_> = antar_periods
+            .iter()
+            .filter(|antar| antar.end > start && antar.start <= end)
+            .flat_map(|antar| self.sub_periods(antar))
+            .collect();
+
+        let mut changes = Vec::new();
+        for (level, periods) in [
+            (DashaLevel::Maha, maha_periods),
+            (DashaLevel::Antar, &antar_periods),
+            (DashaLevel::Pratyantar, &pratyantar_periods),
+        ] {
+            for pair in periods.windows(2) {
+                let (from, to) = (&pair[0], &pair[1]);
+                if to.start > start && to.start <= end {
+                    changes.push(DashaChange {
+                        level,
+                        from: from.lord,
+                        to: to.lord,
+                        at: to.start,
+                        sandhi: false,
+                    });
+                }
+            }
+        }
+        changes.sort_by(|a, b| a.at.cmp(&b.at).then(a.level.cmp(&b.level)));
+
+        let window = ChronoDuration::hours(DASHA_SANDHI_WINDOW_HOURS);
+        let sandhi: Vec<bool> = changes
+            .iter()
+            .map(|change| {
+                changes.iter().any(|other| {
+                    other.level != change.level && (other.at - change.at).abs() <= window
+                })
+            })
+            .collect();
+        for (change, sandhi) in changes.iter_mut().zip(sandhi) {
+            change.sandhi = sandhi;
+        }
+
+        changes
+    }
+
+    /// Natal condition of the lord of `period`, at any level of the dasha tree.
+    pub fn lord_condition(&self, period: &DashaPeriod) -> Option<&LordCondition> {
+        self.lord_conditions.get(&period.lord.lord())
+    }
+}
+
+// ---------------------------
+// ## Dasha Lord Condition
+// ---------------------------
+
+/// Where a dasha lord stands in the natal chart, so a period can be read without looking
+/// the planet up again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LordCondition {
+    pub planet: CelestialBody,
+    pub house: House,
+    pub sign: ZodiacSign,
+    pub dignity: DignityInfo,
+    pub combust: bool,
+    pub retrograde: bool,
+    /// Aspects between the lord and the other planets, either way round.
+    pub aspects: Vec<AspectInfo>,
+    pub yogas: Vec<String>,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Snapshots the natal condition of every planet in `chart`.
+    pub fn calculate_lord_conditions(
+        &self,
+        chart: &ChartInfo,
+    ) -> HashMap<CelestialBody, LordCondition> {
+        let aspects = self.calculate_aspects(chart);
+        let yogas = self.calculate_yogas(chart);
+        chart
+            .planets
+            .iter()
+            .map(|position| {
+                let planet = position.planet;
+                let condition = LordCondition {
+                    planet,
+                    house: position.house,
+                    sign: position.sign,
+                    dignity: self.calculate_dignity(position),
+                    combust: chart.is_combust(planet, &self.conventions.combustion_orbs),
+                    retrograde: position.retrograde,
+                    aspects: aspects
+                        .iter()
+                        .filter(|aspect| aspect.planet1 == planet || aspect.planet2 == planet)
+                        .cloned()
+                        .collect(),
+                    yogas: yogas
+                        .iter()
+                        .filter(|yoga| yoga.involved_planets.contains(&planet))
+                        .map(|yoga| yoga.yoga.name.clone())
+                        .collect(),
+                };
+                (planet, condition)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,851 @@
+use super::*;
+
+/// Mahadashas are laid out from birth until they cover this many years.
+pub const DASHA_LIFESPAN_YEARS: f64 = 120.0;
+
+/// Ruler of a period in any dasha system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DashaLord {
+    Planet(CelestialBody),
+    Yogini(Yogini),
+    /// A sign, in the sign-based systems such as Kalachakra.
+    Sign(ZodiacSign),
+}
+
+impl DashaLord {
+    /// The planet whose condition colours the period: the planet itself, the yogini's
+    /// planet or the sign's lord.
+    pub fn lord(&self) -> CelestialBody {
+        match self {
+            DashaLord::Planet(planet) => *planet,
+            DashaLord::Yogini(yogini) => yogini.lord(),
+            DashaLord::Sign(sign) => sign.lord(),
+        }
+    }
+}
+
+impl From<Dasha> for DashaLord {
+    fn from(dasha: Dasha) -> Self {
+        DashaLord::Planet(dasha.lord())
+    }
+}
+
+impl fmt::Display for DashaLord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DashaLord::Planet(planet) => f.pad(&format!("{:?}", planet)),
+            DashaLord::Yogini(yogini) => f.pad(&format!("{:?}", yogini)),
+            DashaLord::Sign(sign) => f.pad(&sign.to_string()),
+        }
+    }
+}
+
+/// The eight yoginis of Yogini dasha, in order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Yogini {
+    Mangala,
+    Pingala,
+    Dhanya,
+    Bhramari,
+    Bhadrika,
+    Ulka,
+    Siddha,
+    Sankata,
+}
+
+impl Yogini {
+    pub fn sequence() -> [Yogini; 8] {
+        [
+            Yogini::Mangala,
+            Yogini::Pingala,
+            Yogini::Dhanya,
+            Yogini::Bhramari,
+            Yogini::Bhadrika,
+            Yogini::Ulka,
+            Yogini::Siddha,
+            Yogini::Sankata,
+        ]
+    }
+
+    pub fn lord(&self) -> CelestialBody {
+        match self {
+            Yogini::Mangala => CelestialBody::Moon,
+            Yogini::Pingala => CelestialBody::Sun,
+            Yogini::Dhanya => CelestialBody::Jupiter,
+            Yogini::Bhramari => CelestialBody::Mars,
+            Yogini::Bhadrika => CelestialBody::Mercury,
+            Yogini::Ulka => CelestialBody::Saturn,
+            Yogini::Siddha => CelestialBody::Venus,
+            Yogini::Sankata => CelestialBody::Rahu,
+        }
+    }
+
+    /// Length of the full mahadasha in years, one more than the one before.
+    pub fn years(&self) -> f64 {
+        (*self as usize + 1) as f64
+    }
+}
+
+/// One period of a dasha system.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DashaPeriod {
+    pub lord: DashaLord,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// The dasha systems built in, so [`DashaInfo`] can name the one it was calculated with.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DashaSystemKind {
+    #[default]
+    Vimshottari,
+    Yogini,
+    Ashtottari,
+    Kalachakra,
+    DwisaptatiSama,
+}
+
+impl DashaSystemKind {
+    pub fn system(self) -> &'static dyn DashaSystem {
+        match self {
+            DashaSystemKind::Vimshottari => &VimshottariDasha,
+            DashaSystemKind::Yogini => &YoginiDasha,
+            DashaSystemKind::Ashtottari => &AshtottariDasha,
+            DashaSystemKind::Kalachakra => &KalachakraDasha,
+            DashaSystemKind::DwisaptatiSama => &DwisaptatiSamaDasha,
+        }
+    }
+}
+
+/// A way of dividing life into planetary periods from the Moon's place at birth.
+pub trait DashaSystem {
+    fn kind(&self) -> DashaSystemKind;
+
+    /// Mahadashas in order from the one running at birth, each with its full length in
+    /// years, and the fraction of the first that had already passed at birth. The order
+    /// repeats if the periods run out before [`DASHA_LIFESPAN_YEARS`].
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64);
+
+    /// The sub-periods of a period ruled by `lord`, in order, each with the fraction of
+    /// the period it takes. The fractions add up to one.
+    fn sub_periods(&self, moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)>;
+
+    /// Whether the texts apply the system to `chart`. Most apply to every chart.
+    fn applies_to(&self, _chart: &ChartInfo) -> bool {
+        true
+    }
+}
+
+/// Index of the nakshatra holding `longitude`, and the fraction of it already traversed.
+fn nakshatra_position(longitude: f64) -> (usize, f64) {
+    (
+        crate::core::nakshatra_index(longitude),
+        crate::core::nakshatra_elapsed(longitude),
+    )
+}
+
+/// `sequence` from the entry ruled by `first`, each with its share of `total` years.
+fn rotated_shares<T: Copy + PartialEq>(
+    sequence: &[T],
+    first: T,
+    years: impl Fn(T) -> f64,
+    total: f64,
+    lord: impl Fn(T) -> DashaLord,
+) -> Vec<(DashaLord, f64)> {
+    let start = sequence.iter().position(|&item| item == first).unwrap_or(0);
+    (0..sequence.len())
+        .map(|i| sequence[(start + i) % sequence.len()])
+        .map(|item| (lord(item), years(item) / total))
+        .collect()
+}
+
+// ---------------------------
+// ## Vimshottari
+// ---------------------------
+
+/// The 120-year cycle of nine planets, entered at the lord of the Moon's nakshatra.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct VimshottariDasha;
+
+impl DashaSystem for VimshottariDasha {
+    fn kind(&self) -> DashaSystemKind {
+        DashaSystemKind::Vimshottari
+    }
+
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64) {
+        let (_, elapsed) = nakshatra_position(moon_longitude);
+        let first = Dasha::from_lord(NakshatraInfo::from_longitude(moon_longitude).lord)
+            .unwrap_or(Dasha::Ketu);
+        let sequence = Dasha::sequence();
+        let start = sequence
+            .iter()
+            .position(|&dasha| dasha == first)
+            .unwrap_or(0);
+        let periods = (0..sequence.len())
+            .map(|i| sequence[(start + i) % sequence.len()])
+            .map(|dasha| (DashaLord::from(dasha), dasha.years()))
+            .collect();
+        (periods, elapsed)
+    }
+
+    fn sub_periods(&self, _moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        let first = Dasha::from_lord(lord.lord()).unwrap_or(Dasha::Ketu);
+        rotated_shares(
+            &Dasha::sequence(),
+            first,
+            |dasha| dasha.years(),
+            crate::core::VIMSHOTTARI_CYCLE_YEARS,
+            DashaLord::from,
+        )
+    }
+}
+
+// ---------------------------
+// ## Yogini
+// ---------------------------
+
+/// The 36-year cycle of eight yoginis, entered at the yogini of the Moon's nakshatra.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct YoginiDasha;
+
+impl DashaSystem for YoginiDasha {
+    fn kind(&self) -> DashaSystemKind {
+        DashaSystemKind::Yogini
+    }
+
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64) {
+        let (nakshatra, elapsed) = nakshatra_position(moon_longitude);
+        // Counting Ashwini as 1, the nakshatra's number plus three leaves the yogini's
+        // number when divided by eight, with 0 for Sankata. Counting from 0 as here gives
+        // its index in the sequence instead, so Ashwini starts in Bhramari.
+        let first = Yogini::sequence()[(nakshatra + 3) % 8];
+        let periods = rotated_shares(
+            &Yogini::sequence(),
+            first,
+            |y| y.years(),
+            1.0,
+            DashaLord::Yogini,
+        );
+        (periods, elapsed)
+    }
+
+    fn sub_periods(&self, _moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        let first = match lord {
+            DashaLord::Yogini(yogini) => yogini,
+            _ => Yogini::Mangala,
+        };
+        rotated_shares(
+            &Yogini::sequence(),
+            first,
+            |y| y.years(),
+            36.0,
+            DashaLord::Yogini,
+        )
+    }
+}
+
+// ---------------------------
+// ## Ashtottari
+// ---------------------------
+
+/// Ashtottari lords in order, with their years and the number of nakshatras each rules
+/// counting on from Ardra.
+const ASHTOTTARI: [(CelestialBody, f64, usize); 8] = [
+    (CelestialBody::Sun, 6.0, 4),
+    (CelestialBody::Moon, 15.0, 3),
+    (CelestialBody::Mars, 8.0, 4),
+    (CelestialBody::Mercury, 17.0, 3),
+    (CelestialBody::Saturn, 10.0, 3),
+    (CelestialBody::Jupiter, 19.0, 3),
+    (CelestialBody::Rahu, 12.0, 4),
+    (CelestialBody::Venus, 21.0, 3),
+];
+
+/// The 108-year cycle of eight planets, Ketu left out. Meant for charts with Rahu in a
+/// kendra or trikona from the lagna lord, Rahu not in the lagna itself.
+///
+/// Abhijit is not counted, so Saturn rules the three nakshatras from Purva Ashadha to
+/// Shravana.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct AshtottariDasha;
+
+impl AshtottariDasha {
+    fn years(planet: CelestialBody) -> f64 {
+        ASHTOTTARI
+            .iter()
+            .find(|&&(lord, _, _)| lord == planet)
+            .map_or(0.0, |&(_, years, _)| years)
+    }
+}
+
+impl DashaSystem for AshtottariDasha {
+    fn kind(&self) -> DashaSystemKind {
+        DashaSystemKind::Ashtottari
+    }
+
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64) {
+        let (nakshatra, fraction) = nakshatra_position(moon_longitude);
+        // Ardra is the sixth nakshatra
+        let mut counted = (nakshatra + 27 - 5) % 27;
+        let mut first = 0;
+        for (index, &(_, _, count)) in ASHTOTTARI.iter().enumerate() {
+            if counted < count {
+                first = index;
+                break;
+            }
+            counted -= count;
+        }
+        let (lord, _, count) = ASHTOTTARI[first];
+        let elapsed = (counted as f64 + fraction) / count as f64;
+
+        let lords: Vec<CelestialBody> = ASHTOTTARI.iter().map(|&(lord, _, _)| lord).collect();
+        let periods = rotated_shares(&lords, lord, Self::years, 1.0, DashaLord::Planet);
+        (periods, elapsed)
+    }
+
+    fn sub_periods(&self, _moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        let lords: Vec<CelestialBody> = ASHTOTTARI.iter().map(|&(lord, _, _)| lord).collect();
+        rotated_shares(&lords, lord.lord(), Self::years, 108.0, DashaLord::Planet)
+    }
+
+    fn applies_to(&self, chart: &ChartInfo) -> bool {
+        let house_of = |body: CelestialBody| {
+            chart
+                .planets
+                .iter()
+                .find(|planet| planet.planet == body)
+                .map(|planet| planet.sign as usize)
+        };
+        let lagna = chart.ascendant.sign;
+        let (Some(rahu), Some(lagna_lord)) =
+            (house_of(CelestialBody::Rahu), house_of(lagna.lord()))
+        else {
+            return false;
+        };
+        let from_lagna_lord = (rahu + 12 - lagna_lord) % 12 + 1;
+        rahu != lagna as usize && matches!(from_lagna_lord, 1 | 4 | 5 | 7 | 9 | 10)
+    }
+}
+
+// ---------------------------
+// ## Dwisaptati Sama
+// ---------------------------
+
+/// Dwisaptati Sama lords in order, nine years each.
+const DWISAPTATI_SAMA: [CelestialBody; 8] = [
+    CelestialBody::Sun,
+    CelestialBody::Moon,
+    CelestialBody::Mars,
+    CelestialBody::Mercury,
+    CelestialBody::Jupiter,
+    CelestialBody::Venus,
+    CelestialBody::Saturn,
+    CelestialBody::Rahu,
+];
+
+/// The 72-year cycle of eight planets at nine years each, entered by counting the Moon's
+/// nakshatra from Moola round the eight lords. Meant for charts with the lagna lord in
+/// the 7th house or the 7th lord in the lagna.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DwisaptatiSamaDasha;
+
+impl DashaSystem for DwisaptatiSamaDasha {
+    fn kind(&self) -> DashaSystemKind {
+        DashaSystemKind::DwisaptatiSama
+    }
+
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64) {
+        let (nakshatra, elapsed) = nakshatra_position(moon_longitude);
+        // Moola is the nineteenth nakshatra
+        let first = DWISAPTATI_SAMA[(nakshatra + 27 - 18) % 27 % 8];
+        let periods = rotated_shares(&DWISAPTATI_SAMA, first, |_| 9.0, 1.0, DashaLord::Planet);
+        (periods, elapsed)
+    }
+
+    fn sub_periods(&self, _moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        rotated_shares(
+            &DWISAPTATI_SAMA,
+            lord.lord(),
+            |_| 1.0,
+            8.0,
+            DashaLord::Planet,
+        )
+    }
+
+    fn applies_to(&self, chart: &ChartInfo) -> bool {
+        let lagna = chart.ascendant.sign;
+        let seventh = ZodiacSign::from_longitude((lagna as usize + 6) as f64 * 30.0);
+        let sits_in = |sign: ZodiacSign, house: ZodiacSign| {
+            chart
+                .planets
+                .iter()
+                .any(|planet| planet.planet == sign.lord() && planet.sign == house)
+        };
+        sits_in(lagna, seventh) || sits_in(seventh, lagna)
+    }
+}
+
+// ---------------------------
+// ## Kalachakra
+// ---------------------------
+
+/// Nine-sign sequences of the four padas of Ashwini, and of Bharani, in the savya
+/// (direct) chakra. Krittika repeats Ashwini, and every later nakshatra one of these three.
+const KALACHAKRA_SAVYA: [[[ZodiacSign; 9]; 4]; 2] = {
+    use ZodiacSign::*;
+    [
+        [
+            [
+                Aries,
+                Taurus,
+                Gemini,
+                Cancer,
+                Leo,
+                Virgo,
+                Libra,
+                Scorpio,
+                Sagittarius,
+            ],
+            [
+                Capricorn, Aquarius, Pisces, Scorpio, Libra, Virgo, Cancer, Leo, Gemini,
+            ],
+            [
+                Taurus,
+                Aries,
+                Pisces,
+                Aquarius,
+                Capricorn,
+                Sagittarius,
+                Aries,
+                Taurus,
+                Gemini,
+            ],
+            [
+                Cancer,
+                Leo,
+                Virgo,
+                Libra,
+                Scorpio,
+                Sagittarius,
+                Capricorn,
+                Aquarius,
+                Pisces,
+            ],
+        ],
+        [
+            [
+                Scorpio, Libra, Virgo, Cancer, Leo, Gemini, Taurus, Aries, Pisces,
+            ],
+            [
+                Aquarius,
+                Capricorn,
+                Sagittarius,
+                Aries,
+                Taurus,
+                Gemini,
+                Cancer,
+                Leo,
+                Virgo,
+            ],
+            [
+                Libra,
+                Scorpio,
+                Sagittarius,
+                Capricorn,
+                Aquarius,
+                Pisces,
+                Scorpio,
+                Libra,
+                Virgo,
+            ],
+            [
+                Cancer,
+                Leo,
+                Gemini,
+                Taurus,
+                Aries,
+                Pisces,
+                Aquarius,
+                Capricorn,
+                Sagittarius,
+            ],
+        ],
+    ]
+};
+
+fn kalachakra_years(sign: ZodiacSign) -> f64 {
+    match sign {
+        ZodiacSign::Aries | ZodiacSign::Scorpio => 7.0,
+        ZodiacSign::Taurus | ZodiacSign::Libra => 16.0,
+        ZodiacSign::Gemini | ZodiacSign::Virgo => 9.0,
+        ZodiacSign::Cancer => 21.0,
+        ZodiacSign::Leo => 5.0,
+        ZodiacSign::Sagittarius | ZodiacSign::Pisces => 10.0,
+        ZodiacSign::Capricorn | ZodiacSign::Aquarius => 4.0,
+    }
+}
+
+/// The nine signs whose periods make up nakshatra pada `pada` (0 to 107) counted from
+/// Ashwini. The apasavya (reverse) nakshatras run the savya sequences backwards.
+fn kalachakra_pada(pada: usize) -> [ZodiacSign; 9] {
+    let (nakshatra, quarter) = ((pada / 4) % 27, pada % 4);
+    match nakshatra % 6 {
+        0 | 2 => KALACHAKRA_SAVYA[0][quarter],
+        1 => KALACHAKRA_SAVYA[1][quarter],
+        group => {
+            let source = if group == 4 { 0 } else { 1 };
+            let mut signs = KALACHAKRA_SAVYA[source][3 - quarter];
+            signs.reverse();
+            signs
+        }
+    }
+}
+
+/// The sign-based dasha of the Moon's nakshatra pada. Each pada runs nine signs whose
+/// years add up to its lifespan of 83 to 100 years; periods go on into the following
+/// padas after that.
+///
+/// Sub-periods follow the Moon's pada sequence from the period's own sign, in proportion
+/// to the sign years.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct KalachakraDasha;
+
+impl KalachakraDasha {
+    fn moon_pada(moon_longitude: f64) -> (usize, f64) {
+        let position = crate::core::normalize(moon_longitude) / crate::core::PADA_SPAN;
+        ((position.floor() as usize).min(107), position.fract())
+    }
+}
+
+impl DashaSystem for KalachakraDasha {
+    fn kind(&self) -> DashaSystemKind {
+        DashaSystemKind::Kalachakra
+    }
+
+    fn maha_dashas(&self, moon_longitude: f64) -> (Vec<(DashaLord, f64)>, f64) {
+        let (pada, fraction) = Self::moon_pada(moon_longitude);
+        let signs = kalachakra_pada(pada);
+        let lifespan: f64 = signs.iter().map(|&sign| kalachakra_years(sign)).sum();
+
+        // Years of the pada already lived, and the sign they run out in
+        let mut passed = fraction * lifespan;
+        let mut first = signs.len() - 1;
+        for (index, &sign) in signs.iter().enumerate() {
+            if passed < kalachakra_years(sign) {
+                first = index;
+                break;
+            }
+            passed -= kalachakra_years(sign);
+        }
+        let elapsed = (passed / kalachakra_years(signs[first])).min(1.0);
+
+        let periods = signs[first..]
+            .iter()
+            .copied()
+            .chain((1..4).flat_map(|next| kalachakra_pada(pada + next)))
+            .map(|sign| (DashaLord::Sign(sign), kalachakra_years(sign)))
+            .collect();
+        (periods, elapsed)
+    }
+
+    fn sub_periods(&self, moon_longitude: f64, lord: DashaLord) -> Vec<(DashaLord, f64)> {
+        let signs = kalachakra_pada(Self::moon_pada(moon_longitude).0);
+        let lifespan: f64 = signs.iter().map(|&sign| kalachakra_years(sign)).sum();
+        let first = match lord {
+            DashaLord::Sign(sign) => sign,
+            _ => signs[0],
+        };
+        rotated_shares(&signs, first, kalachakra_years, lifespan, DashaLord::Sign)
+    }
+}
+
+// ---------------------------
+// ## Periods
+// ---------------------------
+
+/// Mahadashas from birth until [`DASHA_LIFESPAN_YEARS`] are covered. The first is the
+/// whole period running at birth, so it begins before `birth` by the part already passed
+/// and its sub-periods fall where the published tables put them.
+#[cfg(not(feature = "model-only"))]
+pub(crate) fn maha_periods(
+    system: &dyn DashaSystem,
+    moon_longitude: f64,
+    birth: DateTime<Utc>,
+    year_days: f64,
+) -> Vec<DashaPeriod> {
+    let (sequence, elapsed) = system.maha_dashas(moon_longitude);
+    lay_out_periods(&sequence, elapsed, birth, year_days)
+}
+
+/// Periods of `sequence`, repeated as needed, from birth until [`DASHA_LIFESPAN_YEARS`]
+/// are covered, the first already `elapsed` (a fraction of it) at birth.
+pub(crate) fn lay_out_periods(
+    sequence: &[(DashaLord, f64)],
+    elapsed: f64,
+    birth: DateTime<Utc>,
+    year_days: f64,
+) -> Vec<DashaPeriod> {
+    let year_seconds = year_days * 86400.0;
+    let duration = |years: f64| ChronoDuration::seconds((years * year_seconds) as i64);
+    let mut periods: Vec<DashaPeriod> = Vec::new();
+    let mut total_years = 0.0;
+    for &(lord, years) in sequence.iter().cycle() {
+        if total_years >= DASHA_LIFESPAN_YEARS || years <= 0.0 {
+            break;
+        }
+        let (start, end) = match periods.last() {
+            None => {
+                let balance = years * (1.0 - elapsed);
+                total_years += balance;
+                (birth - duration(years * elapsed), birth + duration(balance))
+            }
+            Some(previous) => {
+                total_years += years;
+                (previous.end, previous.end + duration(years))
+            }
+        };
+        periods.push(DashaPeriod { lord, start, end });
+    }
+    periods
+}
+
+/// Splits `period` into sub-periods by `fractions` of it, in order.
+pub(crate) fn split_by_fractions(
+    period: &DashaPeriod,
+    fractions: Vec<(DashaLord, f64)>,
+) -> Vec<DashaPeriod> {
+    let duration = (period.end - period.start).num_seconds() as f64;
+    let mut start = period.start;
+    fractions
+        .into_iter()
+        .map(|(lord, fraction)| {
+            let end = start + ChronoDuration::seconds((duration * fraction) as i64);
+            let sub = DashaPeriod { lord, start, end };
+            start = end;
+            sub
+        })
+        .collect()
+}
+
+/// Splits `period` by the fractions `system` gives for its lord.
+pub(crate) fn split_period(
+    system: &dyn DashaSystem,
+    moon_longitude: f64,
+    period: &DashaPeriod,
+) -> Vec<DashaPeriod> {
+    split_by_fractions(period, system.sub_periods(moon_longitude, period.lord))
+}
+
+impl DashaInfo {
+    /// The sub-periods of `period`, one of this chart's periods at any level, under the
+    /// system the dashas were calculated with.
+    pub fn sub_periods(&self, period: &DashaPeriod) -> Vec<DashaPeriod> {
+        split_period(self.system.system(), self.moon_longitude, period)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The maha, antar and pratyantar periods running at `as_of` under `system`.
+    pub fn calculate_dasha_with(
+        &self,
+        birth_info: &BirthInfo,
+        system: &dyn DashaSystem,
+        as_of: DateTime<Utc>,
+    ) -> Result<DashaInfo, AstrologyError> {
+        let moon_longitude = self.sidereal_longitude(
+            CelestialBody::Moon,
+            date_to_julian_day(birth_info.date_time),
+        )?;
+        let maha_dashas = maha_periods(
+            system,
+            moon_longitude,
+            birth_info.date_time,
+            self.conventions.dasha_year_days,
+        );
+
+        let running = |periods: &[DashaPeriod]| {
+            periods
+                .iter()
+                .find(|period| as_of >= period.start && as_of < period.end)
+                .unwrap_or(&periods[0])
+                .clone()
+        };
+        let maha = running(&maha_dashas);
+        let antar = running(&split_period(system, moon_longitude, &maha));
+        let pratyantar = running(&split_period(system, moon_longitude, &antar));
+
+        let chart = self.calculate_chart(birth_info, HouseSystem::default())?;
+        let lord_conditions = self.calculate_lord_conditions(&chart);
+        let lord_condition = |lord: DashaLord| {
+            lord_conditions
+                .get(&lord.lord())
+                .cloned()
+                .ok_or(AstrologyError::MissingBody(lord.lord()))
+        };
+
+        Ok(DashaInfo {
+            maha_dasha_lord: lord_condition(maha.lord)?,
+            antar_dasha_lord: lord_condition(antar.lord)?,
+            pratyantar_dasha_lord: lord_condition(pratyantar.lord)?,
+            maha_dasha: maha.lord,
+            maha_dasha_start: maha.start,
+            maha_dasha_end: maha.end,
+            antar_dasha: antar.lord,
+            antar_dasha_start: antar.start,
+            antar_dasha_end: antar.end,
+            pratyantar_dasha: pratyantar.lord,
+            pratyantar_dasha_start: pratyantar.start,
+            pratyantar_dasha_end: pratyantar.end,
+            dasha_year_days: self.conventions.dasha_year_days,
+            system: system.kind(),
+            moon_longitude,
+            maha_dashas,
+            as_of,
+            lord_conditions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Length of a period in days. With 360-day years and 30-day months the published
+    /// tables' years, months and days come out exactly.
+    fn days(period: &DashaPeriod) -> i64 {
+        (period.end - period.start).num_days()
+    }
+
+    fn ymd(years: i64, months: i64, days: i64) -> i64 {
+        years * 360 + months * 30 + days
+    }
+
+    fn period(lord: Dasha, years: f64) -> DashaPeriod {
+        let start = DateTime::parse_from_rfc3339("2000-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        DashaPeriod {
+            lord: lord.into(),
+            start,
+            end: start + ChronoDuration::days((years * 360.0) as i64),
+        }
+    }
+
+    fn antardashas(maha: &DashaPeriod) -> Vec<(DashaLord, i64)> {
+        split_period(&VimshottariDasha, 0.0, maha)
+            .iter()
+            .map(|antar| (antar.lord, days(antar)))
+            .collect()
+    }
+
+    #[test]
+    fn antardashas_start_from_the_maha_lord() {
+        for dasha in Dasha::sequence() {
+            let antars = split_period(&VimshottariDasha, 0.0, &period(dasha, dasha.years()));
+            assert_eq!(antars[0].lord, DashaLord::from(dasha));
+            let lords: Vec<DashaLord> = antars.iter().map(|antar| antar.lord).collect();
+            let sequence = Dasha::sequence();
+            let first = sequence.iter().position(|&d| d == dasha).unwrap();
+            for (i, lord) in lords.iter().enumerate() {
+                assert_eq!(*lord, DashaLord::from(sequence[(first + i) % 9]));
+            }
+        }
+    }
+
+    #[test]
+    fn ketu_antardashas_match_the_published_table() {
+        use Dasha::*;
+        let expected = [
+            (Ketu, ymd(0, 4, 27)),
+            (Venus, ymd(1, 2, 0)),
+            (Sun, ymd(0, 4, 6)),
+            (Moon, ymd(0, 7, 0)),
+            (Mars, ymd(0, 4, 27)),
+            (Rahu, ymd(1, 0, 18)),
+            (Jupiter, ymd(0, 11, 6)),
+            (Saturn, ymd(1, 1, 9)),
+            (Mercury, ymd(0, 11, 27)),
+        ]
+        .map(|(lord, days)| (DashaLord::from(lord), days));
+        assert_eq!(antardashas(&period(Ketu, 7.0)), expected);
+    }
+
+    #[test]
+    fn saturn_antardashas_match_the_published_table() {
+        use Dasha::*;
+        let expected = [
+            (Saturn, ymd(3, 0, 3)),
+            (Mercury, ymd(2, 8, 9)),
+            (Ketu, ymd(1, 1, 9)),
+            (Venus, ymd(3, 2, 0)),
+            (Sun, ymd(0, 11, 12)),
+            (Moon, ymd(1, 7, 0)),
+            (Mars, ymd(1, 1, 9)),
+            (Rahu, ymd(2, 10, 6)),
+            (Jupiter, ymd(2, 6, 12)),
+        ]
+        .map(|(lord, days)| (DashaLord::from(lord), days));
+        assert_eq!(antardashas(&period(Saturn, 19.0)), expected);
+    }
+
+    #[test]
+    fn pratyantardashas_start_from_the_antar_lord() {
+        // Venus-Venus runs 3 years 4 months; its Venus-Venus-Venus pratyantar 6 months 20
+        // days, followed by the Sun's 2 months
+        let maha = period(Dasha::Venus, 20.0);
+        let antar = &split_period(&VimshottariDasha, 0.0, &maha)[0];
+        assert_eq!(days(antar), ymd(3, 4, 0));
+        let pratyantars = split_period(&VimshottariDasha, 0.0, antar);
+        assert_eq!(pratyantars[0].lord, DashaLord::from(Dasha::Venus));
+        assert_eq!(days(&pratyantars[0]), ymd(0, 6, 20));
+        assert_eq!(pratyantars[1].lord, DashaLord::from(Dasha::Sun));
+        assert_eq!(days(&pratyantars[1]), ymd(0, 2, 0));
+    }
+
+    #[test]
+    #[cfg(not(feature = "model-only"))]
+    fn birth_mahadasha_begins_before_birth() {
+        // The Moon halfway through Bharani, a Venus nakshatra: ten of Venus's twenty years
+        // have passed at birth
+        let birth = period(Dasha::Venus, 0.0).start;
+        let mahas = maha_periods(&VimshottariDasha, 20.0, birth, 360.0);
+        assert_eq!(mahas[0].lord, DashaLord::from(Dasha::Venus));
+        assert_eq!((birth - mahas[0].start).num_days(), ymd(10, 0, 0));
+        assert_eq!((mahas[0].end - birth).num_days(), ymd(10, 0, 0));
+        assert_eq!(mahas[1].lord, DashaLord::from(Dasha::Sun));
+        assert_eq!(mahas[1].start, mahas[0].end);
+
+        // Venus-Mars begins six years into the period, and Venus-Venus ended before birth
+        let antars = split_period(&VimshottariDasha, 20.0, &mahas[0]);
+        assert!(antars[0].end < birth);
+        assert_eq!((antars[3].start - mahas[0].start).num_days(), ymd(6, 0, 0));
+    }
+
+    #[test]
+    #[cfg(not(feature = "model-only"))]
+    fn every_period_lord_has_its_natal_condition() {
+        let ephemeris = SwissEph::new().unwrap();
+        let birth = BirthInfo::parse("1991-06-18 07:10 +05:30 @ 11.2588,75.7804").unwrap();
+        let dashas = ephemeris
+            .calculate_dasha_with(&birth, &VimshottariDasha, birth.date_time)
+            .unwrap();
+        for maha in &dashas.maha_dashas {
+            for period in dashas.sub_periods(maha).iter().chain([maha]) {
+                let condition = dashas.lord_condition(period).unwrap();
+                assert_eq!(condition.planet, period.lord.lord());
+                assert!(condition.aspects.iter().all(|aspect| {
+                    aspect.planet1 == condition.planet || aspect.planet2 == condition.planet
+                }));
+            }
+        }
+        assert_eq!(
+            dashas.lord_condition(&dashas.maha_dashas[0]),
+            Some(&dashas.maha_dasha_lord)
+        );
+    }
+}
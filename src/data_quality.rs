@@ -0,0 +1,212 @@
+use super::*;
+
+/// What a [`Warning`] is about.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum WarningKind {
+    /// The ascendant is so near the end of a sign that a birth time a few minutes off
+    /// would change the lagna.
+    AscendantNearSignBoundary,
+    /// The UTC offset is far from the mean solar time at the longitude, as when an offset
+    /// or a longitude has the wrong sign.
+    TimezoneMismatch,
+    /// The date is outside the years the ephemeris covers accurately.
+    OutsideEphemerisRange,
+    /// Latitude and longitude are both zero, which usually means no place was given.
+    NullIsland,
+    /// Something the chart itself could not do as asked, such as drawing houses in the
+    /// system chosen.
+    Chart,
+}
+
+/// A sign that a chart's inputs may be wrong, or that the chart may be less reliable than
+/// it looks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(kind: WarningKind, message: impl Into<String>) -> Self {
+        Warning {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Degrees from a sign boundary within which the ascendant is flagged, about four minutes
+/// of birth time.
+pub const ASCENDANT_BOUNDARY_ORB: f64 = 1.0;
+
+/// Hours between the UTC offset and the mean solar time at the longitude beyond which they
+/// are flagged. Wide zones such as China's and summer time stay within it.
+pub const TIMEZONE_MISMATCH_HOURS: f64 = 3.0;
+
+/// Degrees the ascendant moves in a minute on average, a full turn in a sidereal day.
+const ASCENDANT_MOTION: f64 = 360.9856 / 1440.0;
+
+impl BirthInfo {
+    /// Warnings about the place of birth: coordinates at (0, 0), and a UTC offset more
+    /// than [`TIMEZONE_MISMATCH_HOURS`] from the longitude's mean solar time.
+    pub fn location_warnings(&self) -> Vec<Warning> {
+        let location = &self.location;
+        let mut warnings = Vec::new();
+        if location.latitude == 0.0 && location.longitude == 0.0 {
+            warnings.push(Warning::new(
+                WarningKind::NullIsland,
+                "Latitude and longitude are both 0°, in the Gulf of Guinea; the place of \
+                 birth was probably never set",
+            ));
+            // Any offset is as likely as another there
+            return warnings;
+        }
+
+        let solar = location.longitude / 15.0;
+        let apart = (location.timezone as f64 - solar + 12.0).rem_euclid(24.0) - 12.0;
+        if apart.abs() > TIMEZONE_MISMATCH_HOURS {
+            warnings.push(Warning::new(
+                WarningKind::TimezoneMismatch,
+                format!(
+                    "UTC offset {:+} hours is {:.1} hours from mean solar time at longitude \
+                     {:.4}°; check the signs of both",
+                    location.timezone,
+                    apart.abs(),
+                    location.longitude
+                ),
+            ));
+        }
+        warnings
+    }
+}
+
+impl ChartInfo {
+    /// A warning when the ascendant is within [`ASCENDANT_BOUNDARY_ORB`] of the sign before
+    /// or after it.
+    pub fn ascendant_warning(&self) -> Option<Warning> {
+        let ascendant = Longitude::new(self.ascendant.longitude());
+        let degree = ascendant.degree_in_sign();
+        let (distance, other) = if degree < 30.0 - degree {
+            (degree, Longitude::new(ascendant.degrees() - 30.0).sign())
+        } else {
+            (
+                30.0 - degree,
+                Longitude::new(ascendant.degrees() + 30.0).sign(),
+            )
+        };
+        if distance >= ASCENDANT_BOUNDARY_ORB {
+            return None;
+        }
+        Some(Warning::new(
+            WarningKind::AscendantNearSignBoundary,
+            format!(
+                "The ascendant is {:.2}° from {:?}; a birth time about {:.0} minutes off \
+                 would make it the lagna",
+                distance,
+                other,
+                (distance / ASCENDANT_MOTION).ceil()
+            ),
+        ))
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Everything suspicious about a birth and the chart cast for it: the place, a date the
+    /// engine's ephemeris does not cover, an ascendant on a sign boundary and the chart's
+    /// own warnings.
+    pub fn birth_warnings(&self, birth_info: &BirthInfo, chart: &ChartInfo) -> Vec<Warning> {
+        let mut warnings = birth_info.location_warnings();
+        if let Some(gap) = self.files.coverage_gap(birth_info.date_time.year()) {
+            warnings.push(Warning::new(WarningKind::OutsideEphemerisRange, gap));
+        }
+        warnings.extend(chart.ascendant_warning());
+        warnings.extend(
+            chart
+                .warnings
+                .iter()
+                .map(|warning| Warning::new(WarningKind::Chart, warning.clone())),
+        );
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn birth(latitude: f64, longitude: f64, timezone: f32) -> BirthInfo {
+        BirthInfo {
+            date_time: DateTime::from_timestamp(977_400_000, 0).unwrap(),
+            location: Location::new(latitude, longitude, timezone),
+            person: None,
+        }
+    }
+
+    fn kinds(warnings: &[Warning]) -> Vec<WarningKind> {
+        warnings.iter().map(|warning| warning.kind).collect()
+    }
+
+    #[test]
+    fn suspicious_places_are_flagged() {
+        assert_eq!(
+            kinds(&birth(0.0, 0.0, 5.5).location_warnings()),
+            vec![WarningKind::NullIsland]
+        );
+        assert!(birth(28.61, 77.21, 5.5).location_warnings().is_empty());
+        // Kashgar keeps Beijing time, three hours ahead of the Sun
+        assert!(birth(39.47, 75.99, 8.0).location_warnings().is_empty());
+        assert_eq!(
+            kinds(&birth(28.61, 77.21, -5.5).location_warnings()),
+            vec![WarningKind::TimezoneMismatch]
+        );
+        // Samoa, west of the date line, is 13 hours ahead
+        assert!(birth(-13.83, -171.76, 13.0).location_warnings().is_empty());
+    }
+
+    #[test]
+    fn an_ascendant_on_the_cusp_is_flagged() {
+        let chart = |sign, degree| ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign,
+                degree,
+            },
+            houses: Vec::new(),
+            planets: Vec::new(),
+            warnings: Vec::new(),
+        };
+        assert!(chart(ZodiacSign::Leo, 15.0).ascendant_warning().is_none());
+        let late = chart(ZodiacSign::Pisces, 29.8).ascendant_warning().unwrap();
+        assert_eq!(late.kind, WarningKind::AscendantNearSignBoundary);
+        assert!(late.message.contains("Aries"), "{}", late);
+        let early = chart(ZodiacSign::Aries, 0.5).ascendant_warning().unwrap();
+        assert!(early.message.contains("Pisces"), "{}", early);
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn dates_past_the_bundled_files_are_flagged() {
+        let ephemeris = SwissEph::new().unwrap();
+        let mut future = birth(28.61, 77.21, 5.5);
+        future.date_time = Utc.with_ymd_and_hms(2450, 6, 1, 12, 0, 0).unwrap();
+        let chart = ephemeris
+            .calculate_chart(&future, HouseSystem::WholeSign)
+            .unwrap();
+        assert!(kinds(&ephemeris.birth_warnings(&future, &chart))
+            .contains(&WarningKind::OutsideEphemerisRange));
+
+        let report = crate::examples::natal_report().unwrap();
+        assert!(!kinds(&report.warnings).contains(&WarningKind::OutsideEphemerisRange));
+    }
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+/// Planets in the order their horas follow one another, which the choghadiyas of the day
+/// follow too.
+const HORA_ORDER: [CelestialBody; 7] = [
+    CelestialBody::Sun,
+    CelestialBody::Venus,
+    CelestialBody::Mercury,
+    CelestialBody::Moon,
+    CelestialBody::Saturn,
+    CelestialBody::Jupiter,
+    CelestialBody::Mars,
+];
+
+/// How favourable a stretch of the day is for new undertakings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum PeriodQuality {
+    Auspicious,
+    Neutral,
+    Inauspicious,
+}
+
+/// The seven kinds of choghadiya, an eighth of the day or of the night.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Choghadiya {
+    Udveg,
+    Char,
+    Labh,
+    Amrit,
+    Kaal,
+    Shubh,
+    Rog,
+}
+
+impl Choghadiya {
+    /// In the order of [`HORA_ORDER`], each ruled by the planet at the same place.
+    #[cfg(not(feature = "model-only"))]
+    const ALL: [Choghadiya; 7] = [
+        Choghadiya::Udveg,
+        Choghadiya::Char,
+        Choghadiya::Labh,
+        Choghadiya::Amrit,
+        Choghadiya::Kaal,
+        Choghadiya::Shubh,
+        Choghadiya::Rog,
+    ];
+
+    pub fn lord(&self) -> CelestialBody {
+        HORA_ORDER[*self as usize]
+    }
+
+    /// Amrit, Shubh and Labh are auspicious, Char neutral and the rest inauspicious.
+    pub fn quality(&self) -> PeriodQuality {
+        match self {
+            Choghadiya::Amrit | Choghadiya::Shubh | Choghadiya::Labh => PeriodQuality::Auspicious,
+            Choghadiya::Char => PeriodQuality::Neutral,
+            Choghadiya::Udveg | Choghadiya::Kaal | Choghadiya::Rog => PeriodQuality::Inauspicious,
+        }
+    }
+}
+
+impl CelestialBody {
+    /// Horas of the natural benefics, Jupiter, Venus, Mercury and the Moon, are
+    /// auspicious; those of the Sun, Mars and Saturn are not.
+    pub fn hora_quality(&self) -> PeriodQuality {
+        match self {
+            CelestialBody::Jupiter
+            | CelestialBody::Venus
+            | CelestialBody::Mercury
+            | CelestialBody::Moon => PeriodQuality::Auspicious,
+            _ => PeriodQuality::Inauspicious,
+        }
+    }
+}
+
+/// The Vedic day from one sunrise to the next, divided into choghadiyas and horas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DayPeriods {
+    pub vara: Vara,
+    pub sunrise: DateTime<Utc>,
+    pub sunset: DateTime<Utc>,
+    pub next_sunrise: DateTime<Utc>,
+    /// Eight by day and eight by night. The day's run starts with the one ruled by the
+    /// weekday lord and each night's with the fifth from it.
+    pub choghadiyas: Vec<PanchangaLimb<Choghadiya>>,
+    /// The planets ruling the twelve horas of the day and the twelve of the night, the
+    /// first ruled by the weekday lord.
+    pub horas: Vec<PanchangaLimb<CelestialBody>>,
+}
+
+/// `from` to `to` cut into `count` equal parts, the `n`th given `value(n)`.
+#[cfg(not(feature = "model-only"))]
+fn divide<T>(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    count: i32,
+    value: impl Fn(usize) -> T,
+) -> impl Iterator<Item = PanchangaLimb<T>> {
+    let part = (to - from) / count;
+    (0..count).map(move |index| PanchangaLimb {
+        value: value(index as usize),
+        start: from + part * index,
+        end: if index == count - 1 {
+            to
+        } else {
+            from + part * (index + 1)
+        },
+    })
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Choghadiyas and horas of the Vedic day beginning at sunrise on the local `date`.
+    /// Fails where the Sun does not rise or set that day.
+    pub fn calculate_day_periods(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<DayPeriods, AstrologyError> {
+        let vara = Vara::from_weekday(date.weekday());
+        let sunrise = self.sunrise(date, location)?;
+        let sunset = self.sunset(date, location)?;
+        let next_sunrise = self.sunrise(date.succ_opt().unwrap_or(date), location)?;
+
+        let first = HORA_ORDER
+            .iter()
+            .position(|&lord| lord == vara.lord())
+            .unwrap_or(0);
+        let choghadiyas = divide(sunrise, sunset, 8, |index| {
+            Choghadiya::ALL[(first + index) % 7]
+        })
+        .chain(divide(sunset, next_sunrise, 8, |index| {
+            Choghadiya::ALL[(first + 5 * (index + 1)) % 7]
+        }))
+        .collect();
+        let horas = divide(sunrise, sunset, 12, |index| HORA_ORDER[(first + index) % 7])
+            .chain(divide(sunset, next_sunrise, 12, |index| {
+                HORA_ORDER[(first + 12 + index) % 7]
+            }))
+            .collect();
+
+        Ok(DayPeriods {
+            vara,
+            sunrise,
+            sunset,
+            next_sunrise,
+            choghadiyas,
+            horas,
+        })
+    }
+}
+
+/// Daily windows avoided for new undertakings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum InauspiciousPeriod {
+    RahuKalam,
+    Yamaganda,
+    GulikaKalam,
+    /// One of the day's thirty muhurtas ruled out by the weekday; some weekdays have two.
+    Durmuhurtham,
+}
+
+/// Eighth of the daytime, from 1, holding Rahu Kalam, Yamaganda and Gulika Kalam on each
+/// weekday, Sunday first.
+#[cfg(not(feature = "model-only"))]
+const KALAM_EIGHTHS: [(InauspiciousPeriod, [i32; 7]); 3] = [
+    (InauspiciousPeriod::RahuKalam, [8, 2, 7, 5, 6, 4, 3]),
+    (InauspiciousPeriod::Yamaganda, [5, 4, 3, 2, 1, 7, 6]),
+    (InauspiciousPeriod::GulikaKalam, [7, 6, 5, 4, 3, 2, 1]),
+];
+
+/// Durmuhurthas of each weekday, Sunday first, counting the fifteen muhurtas of the day
+/// from 1 and those of the night on from 16.
+#[cfg(not(feature = "model-only"))]
+const DURMUHURTHAS: [&[i32]; 7] = [&[14], &[9, 12], &[4, 22], &[8], &[6, 12], &[4, 9], &[1, 2]];
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Rahu Kalam, Yamaganda, Gulika Kalam and the durmuhurthas of the Vedic day beginning
+    /// at sunrise on the local `date`, in time order. The kalams are eighths of the
+    /// daytime and the muhurtas fifteenths of the day or of the night.
+    pub fn inauspicious_periods(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<Vec<PanchangaLimb<InauspiciousPeriod>>, AstrologyError> {
+        let weekday = date.weekday().num_days_from_sunday() as usize;
+        let sunrise = self.sunrise(date, location)?;
+        let sunset = self.sunset(date, location)?;
+        let next_sunrise = self.sunrise(date.succ_opt().unwrap_or(date), location)?;
+
+        let eighth = (sunset - sunrise) / 8;
+        let mut periods: Vec<PanchangaLimb<InauspiciousPeriod>> = KALAM_EIGHTHS
+            .iter()
+            .map(|&(period, eighths)| {
+                let start = sunrise + eighth * (eighths[weekday] - 1);
+                PanchangaLimb {
+                    value: period,
+                    start,
+                    end: start + eighth,
+                }
+            })
+            .collect();
+
+        let day_muhurta = (sunset - sunrise) / 15;
+        let night_muhurta = (next_sunrise - sunset) / 15;
+        periods.extend(DURMUHURTHAS[weekday].iter().map(|&muhurta| {
+            let start = if muhurta <= 15 {
+                sunrise + day_muhurta * (muhurta - 1)
+            } else {
+                sunset + night_muhurta * (muhurta - 16)
+            };
+            PanchangaLimb {
+                value: InauspiciousPeriod::Durmuhurtham,
+                start,
+                end: start
+                    + if muhurta <= 15 {
+                        day_muhurta
+                    } else {
+                        night_muhurta
+                    },
+            }
+        }));
+
+        periods.sort_by_key(|period| period.start);
+        Ok(periods)
+    }
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+impl CompoundRelationship {
+    /// Virupas a planet takes in the sign of a lord it regards this way, as in the
+    /// saptavargaja bala of Shadbala.
+    pub fn dignity_points(&self) -> f64 {
+        match self {
+            CompoundRelationship::GreatFriend => 22.5,
+            CompoundRelationship::Friend => 15.0,
+            CompoundRelationship::Neutral => 7.5,
+            CompoundRelationship::Enemy => 3.75,
+            CompoundRelationship::GreatEnemy => 1.875,
+        }
+    }
+}
+
+impl DignityInfo {
+    /// Dignity of `planet` by sign and degree. With the `chart` it sits in, the lord of its
+    /// sign is weighed by five-fold relationship; without it, by natural relationship
+    /// alone.
+    pub fn of(
+        planet: &PlanetPosition,
+        chart: Option<&ChartInfo>,
+        friendships: &FriendshipMatrix,
+    ) -> Self {
+        let body = planet.planet;
+        let degree = planet.longitude.degree_in_sign();
+        let lord = planet.sign.lord();
+
+        let moolatrikona = body.moolatrikona_sign() == Some(planet.sign)
+            && body
+                .moolatrikona_range()
+                .is_some_and(|(start, end)| start <= degree && degree < end);
+        let own_sign = !matches!(body, CelestialBody::Rahu | CelestialBody::Ketu) && lord == body;
+        let deep_exaltation_distance = body
+            .exaltation_degree()
+            .map(|exaltation| planet.longitude.separation(Longitude::new(exaltation)));
+
+        let sign_lord_relationship = (lord != body).then(|| {
+            chart
+                .and_then(|chart| friendships.compound_relationship(chart, body, lord))
+                .unwrap_or_else(|| match friendships.relationship(body, lord) {
+                    Relationship::Friend => CompoundRelationship::Friend,
+                    Relationship::Neutral => CompoundRelationship::Neutral,
+                    Relationship::Enemy => CompoundRelationship::Enemy,
+                })
+        });
+
+        let uchcha = deep_exaltation_distance.map_or(0.0, |distance| (180.0 - distance) / 3.0);
+        let placement = if moolatrikona {
+            45.0
+        } else if own_sign {
+            30.0
+        } else {
+            sign_lord_relationship.map_or(0.0, |relationship| relationship.dignity_points())
+        };
+
+        DignityInfo {
+            moolatrikona,
+            own_sign,
+            exalted: body.exaltation_sign() == Some(planet.sign),
+            debilitated: body.debilitation_sign() == Some(planet.sign),
+            deep_exaltation_distance,
+            sign_lord_relationship,
+            score: uchcha + placement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn dignity_follows_degree_and_compound_friendship() {
+        let friendships = FriendshipMatrix::natural();
+
+        // The Sun's moolatrikona ends at 20° Leo; past it the Sun is merely in its own sign
+        let sun = DignityInfo::of(&planet(CelestialBody::Sun, 125.0), None, &friendships);
+        assert!(sun.moolatrikona && sun.own_sign);
+        let sun = DignityInfo::of(&planet(CelestialBody::Sun, 145.0), None, &friendships);
+        assert!(!sun.moolatrikona && sun.own_sign);
+        assert_eq!(sun.sign_lord_relationship, None);
+
+        // Deeply exalted, the Moon scores the full uchcha bala
+        let moon = DignityInfo::of(&planet(CelestialBody::Moon, 33.0), None, &friendships);
+        assert!(moon.exalted && !moon.moolatrikona);
+        assert_eq!(moon.deep_exaltation_distance, Some(0.0));
+        assert_eq!(moon.score, 60.0 + 7.5);
+
+        // Jupiter in Taurus regards Venus as a natural enemy; with Venus in the sign after,
+        // a temporal friend, the two become neutral
+        let jupiter = planet(CelestialBody::Jupiter, 40.0);
+        let venus = planet(CelestialBody::Venus, 70.0);
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 0.0,
+            },
+            houses: Vec::new(),
+            planets: vec![jupiter.clone(), venus],
+            warnings: Vec::new(),
+        };
+        assert_eq!(
+            DignityInfo::of(&jupiter, None, &friendships).sign_lord_relationship,
+            Some(CompoundRelationship::Enemy)
+        );
+        assert_eq!(
+            DignityInfo::of(&jupiter, Some(&chart), &friendships).sign_lord_relationship,
+            Some(CompoundRelationship::Neutral)
+        );
+        // Venus, naturally neutral to Jupiter, finds it in the 12th and so a friend
+        assert_eq!(
+            friendships.compound_relationship(&chart, CelestialBody::Venus, CelestialBody::Jupiter),
+            Some(CompoundRelationship::Friend)
+        );
+    }
+}
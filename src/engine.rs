@@ -0,0 +1,8 @@
+//! The Swiss Ephemeris engine and the time conversions built on it. Every calculation
+//! returns types from [`crate::model`].
+
+pub use crate::{
+    calculate_ayanamsa, date_to_julian_day, julian_day_to_date, reset_timing_stats, timing_stats,
+    ChartCache, SwissEph, SwissEphBuilder, TimedWork, TimingStats, WorkTiming,
+    CHART_CACHE_CAPACITY,
+};
@@ -1,42 +1,20 @@
-use chrono::{DateTime, Datelike, Duration as ChronoDuration, TimeZone, Timelike, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::error::Error;
-use std::ffi::{CStr, CString};
-use std::io::Cursor;
-use std::os::raw::{c_char, c_double, c_int};
-use std::sync::Once;
-use tempfile::NamedTempFile;
-use super::*;
-
+//! The Swiss Ephemeris bindings and the engine built on them.
 
+use super::*;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum CalculationFlag {
-    Speed = SEFLG_SPEED as isize,
-    NoGravitationalDeflection = 512,
-    NoAnnualAberration = 1024,
-    Topocentric = 32768,
-    Equatorial = 2048,
-    XYZ = 8192,
-    Radians = 16384,
-    Barycentric = 4,
-    Heliocentric = 8,
-}
+// ---------------------------
+// ## FFI Bindings for Swiss Ephemeris
+// ---------------------------
 
-// FFI Bindings for Swiss Ephemeris
+#[cfg(not(feature = "model-only"))]
 mod bindings {
     use super::*;
 
     extern "C" {
         // Initialize and close Swiss Ephemeris
         pub fn swe_set_ephe_path(path: *const c_char) -> c_int;
-        pub fn swe_set_sid_mode(
-            sid_mode: c_int,
-            t0: c_double,
-            ayan_t0: c_double,
-        ) -> c_int;
-        pub fn swe_close();
+        pub fn swe_set_sid_mode(sid_mode: c_int, t0: c_double, ayan_t0: c_double) -> c_int;
+        pub fn swe_set_topo(geolon: c_double, geolat: c_double, geoalt: c_double);
 
         // Calculate planetary positions
         pub fn swe_calc_ut(
@@ -47,6 +25,30 @@ mod bindings {
             serr: *mut c_char,
         ) -> c_int;
 
+        // Fixed stars, looked up by name in sefstars.txt
+        pub fn swe_fixstar_ut(
+            star: *mut c_char,
+            tjd_ut: c_double,
+            iflag: c_int,
+            xx: *mut c_double,
+            serr: *mut c_char,
+        ) -> c_int;
+        pub fn swe_fixstar_mag(star: *mut c_char, mag: *mut c_double, serr: *mut c_char) -> c_int;
+
+        // Rising, setting and meridian transits
+        pub fn swe_rise_trans(
+            tjd_ut: c_double,
+            ipl: c_int,
+            starname: *mut c_char,
+            epheflag: c_int,
+            rsmi: c_int,
+            geopos: *mut c_double,
+            atpress: c_double,
+            attemp: c_double,
+            tret: *mut c_double,
+            serr: *mut c_char,
+        ) -> c_int;
+
         // House calculations
         pub fn swe_houses_ex(
             tjd_ut: c_double,
@@ -58,20 +60,17 @@ mod bindings {
             ascmc: *mut c_double,
         ) -> c_int;
 
-        pub fn swe_house_pos(
-            armc: c_double,
-            geolat: c_double,
-            eps: c_double,
-            hsys: c_int,
-            at: *mut c_double,
-            serr: *mut c_char,
-        ) -> c_double;
-
         // Get planet name
         pub fn swe_get_planet_name(ipl: c_int, name: *mut c_char);
 
         // Ayanamsa
         pub fn swe_get_ayanamsa_ut(tjd_ut: c_double) -> c_double;
+        pub fn swe_get_ayanamsa_ex_ut(
+            tjd_ut: c_double,
+            iflag: c_int,
+            daya: *mut c_double,
+            serr: *mut c_char,
+        ) -> c_int;
 
         // Convert UTC to Julian Day
         pub fn swe_utc_to_jd(
@@ -101,277 +100,192 @@ mod bindings {
 }
 
 // Import bindings
-use bindings::*;
+#[cfg(not(feature = "model-only"))]
+pub(crate) use bindings::*;
+
+// ---------------------------
+// ## Constants for Swiss Ephemeris
+// ---------------------------
 
-// Constants for Swiss Ephemeris
 pub const SE_GREG_CAL: c_int = 1;
-pub const SE_SIDM_LAHIRI: c_int = 1;
-pub const SE_SIDM_FAGAN_BRADLEY: c_int = 2;
 
 // Flags for calculations
 pub const SEFLG_SPEED: c_int = 256;
-pub const SEFLG_SIDEREAL: c_int = 1 << 7;
-pub const SEFLG_SWIEPH: c_int = 1 << 0;
-
-// House system codes
-pub const SE_HS_PLACIDUS: c_int = 0;
-pub const SE_HS_KRISHNAMURTI: c_int = 10;
-pub const SE_HS_BUCHAREST: c_int = 11;
-pub const SE_HS_EQUATORIAL: c_int = 12;
-pub const SE_HS_MERCURY: c_int = 13;
-pub const SE_HS_CAMPANUS: c_int = 14;
-pub const SE_HS_MORIN: c_int = 15;
-pub const SE_HS_PORPHYRUS: c_int = 16;
-pub const SE_HS_VEHRENBERG: c_int = 17;
-pub const SE_HS_ALCABITUS: c_int = 18;
-pub const SE_HS_TOPHRAS: c_int = 19;
-pub const SE_HS_NAVAMSA: c_int = 20;
-pub const SE_HS_HORA: c_int = 21;
-
-// SwissEph Structure
-pub struct SwissEph {
-    _temp_file: NamedTempFile,
+pub const SEFLG_SIDEREAL: c_int = 64 * 1024;
+pub const SEFLG_SWIEPH: c_int = 2;
+pub const SEFLG_TOPOCTR: c_int = 32 * 1024;
+
+// Rise and set events
+pub const SE_CALC_RISE: c_int = 1;
+pub const SE_CALC_SET: c_int = 2;
+
+/// Swiss Ephemeris keeps its state per thread only where it is built with thread-local
+/// storage. On Apple platforms and Windows every thread shares one state, so calls must not
+/// overlap.
+#[cfg(not(feature = "model-only"))]
+pub(crate) const THREAD_LOCAL_EPHEMERIS: bool = !cfg!(any(target_vendor = "apple", windows));
+
+/// A value last handed to a Swiss Ephemeris setter, so that a setter which flushes the
+/// library's caches is only called again for a different value. It is kept where the
+/// library keeps its own: per thread with thread-local storage, once for the process
+/// without.
+#[cfg(not(feature = "model-only"))]
+pub(crate) struct LibrarySetting<T: 'static> {
+    pub(crate) per_thread: &'static std::thread::LocalKey<std::cell::RefCell<T>>,
+    pub(crate) per_process: std::sync::Mutex<T>,
 }
 
-static EPHE_FILE: &[u8] = include_bytes!("../ephe/sepl_18.se1"); // Ensure the ephemeris file is in ../ephe/
-static INIT: Once = Once::new();
+#[cfg(not(feature = "model-only"))]
+impl<T> LibrarySetting<T> {
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        if THREAD_LOCAL_EPHEMERIS {
+            self.per_thread.with_borrow_mut(f)
+        } else {
+            // The value is replaced whole, so a poisoned lock holds a complete one
+            f(&mut self
+                .per_process
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()))
+        }
+    }
+}
 
-impl SwissEph {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let mut temp_file = NamedTempFile::new()?;
-        std::io::copy(&mut Cursor::new(EPHE_FILE), &mut temp_file)?;
-
-        // Set ephemeris path once
-        INIT.call_once(|| {
-            let file_path = temp_file.path().to_str().unwrap();
-            let c_path = CString::new(file_path).unwrap();
-            unsafe {
-                swe_set_ephe_path(c_path.as_ptr());
-            }
-            eprintln!("Ephemeris file path set to: {}", file_path);
-        });
+// ---------------------------
+// ## SwissEph Structure
+// ---------------------------
 
-        Ok(SwissEph {
-            _temp_file: temp_file,
-        })
+#[cfg(not(feature = "model-only"))]
+pub struct SwissEph {
+    pub(crate) friendships: FriendshipMatrix,
+    pub(crate) conventions: Conventions,
+    pub(crate) files: EphemerisFiles,
+    pub(crate) position_cache: PositionCache,
+}
+
+/// Bundled ephemeris files: planets, the main asteroids with Chiron, and the fixed stars.
+/// Swiss Ephemeris looks them up by these names inside its ephemeris directory.
+#[cfg(not(feature = "model-only"))]
+static EPHE_FILES: [(&str, &[u8]); 3] = [
+    ("sepl_18.se1", include_bytes!("../ephe/sepl_18.se1")),
+    ("seas_18.se1", include_bytes!("../ephe/seas_18.se1")),
+    ("sefstars.txt", include_bytes!("../ephe/sefstars.txt")),
+];
+#[cfg(not(feature = "model-only"))]
+static EPHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// An engine on the bundled ephemeris files with the default conventions. See
+    /// [`SwissEph::builder`] for anything else.
+    pub fn new() -> Result<Self, AstrologyError> {
+        Self::builder().build()
     }
 
+    /// House of `planet_longitude`, which must be in the zodiac of `coord_system`.
     pub fn get_house(
         &self,
+        coord_system: CoordinateSystem,
         julian_day: JulianDay,
         planet_longitude: f64,
         latitude: f64,
         longitude: f64,
-        house_system: ChartType,
-    ) -> Result<House, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS, // Placidus
-            ChartType::Navamsa => SE_HS_NAVAMSA, // Navamsa
-            ChartType::Hora => SE_HS_HORA, // Hora
-            // Add other house systems as needed
-        };
-
-        let mut cusps: [c_double; 13] = [0.0; 13];
-        let mut ascmc: [c_double; 10] = [0.0; 10];
-
-        let hsys_code = hsys;
-
-        let flag = 0; // Additional flags can be set here
+        house_system: HouseSystem,
+    ) -> Result<House, AstrologyError> {
+        let houses =
+            self.calculate_houses(coord_system, julian_day, latitude, longitude, house_system)?;
+        Ok(house_from_cusps(&houses.cusps, planet_longitude))
+    }
 
-        let calc_result = unsafe {
-            swe_houses_ex(
+    pub fn calculate_ayanamsa(&self, julian_day: JulianDay) -> f64 {
+        self.set_sidereal_mode();
+        let mut ayanamsa: c_double = 0.0;
+        let mut error: [c_char; 256] = [0; 256];
+        unsafe {
+            swe_get_ayanamsa_ex_ut(
                 julian_day,
-                flag,
-                latitude,
-                longitude,
-                hsys_code,
-                cusps.as_mut_ptr(),
-                ascmc.as_mut_ptr(),
-            )
-        };
-
-        if calc_result < 0 {
-            return Err(CalculationError {
-                code: calc_result,
-                message: "Error calculating houses".to_string(),
-            });
-        }
-
-        let armc = ascmc[0];
-        let eps = ascmc[1];
-
-        let mut serr: [c_char; 256] = [0; 256];
-        let house_position = unsafe {
-            swe_house_pos(
-                armc,
-                latitude,
-                eps,
-                hsys_code,
-                &planet_longitude as *const f64 as *mut f64,
-                serr.as_mut_ptr(),
-            )
-        };
-
-        if house_position < 0.0 {
-            let error_message = unsafe { CStr::from_ptr(serr.as_ptr()) }
-                .to_string_lossy()
-                .into_owned();
-            return Err(CalculationError {
-                code: -1,
-                message: error_message,
-            });
+                self.ephemeris_flags(0),
+                &mut ayanamsa,
+                error.as_mut_ptr(),
+            );
         }
-
-        let house_number = house_position.floor() as usize;
-        Ok(match house_number {
-            1 => House::First,
-            2 => House::Second,
-            3 => House::Third,
-            4 => House::Fourth,
-            5 => House::Fifth,
-            6 => House::Sixth,
-            7 => House::Seventh,
-            8 => House::Eighth,
-            9 => House::Ninth,
-            10 => House::Tenth,
-            11 => House::Eleventh,
-            12 => House::Twelfth,
-            _ => House::First,
-        })
-    }
-
-    pub fn calculate_navamsa(&self, longitude: f64) -> f64 {
-        let normalized_longitude = longitude.rem_euclid(360.0);
-        let navamsa_longitude = (normalized_longitude / 3.0).rem_euclid(360.0);
-        navamsa_longitude
+        ayanamsa
     }
 
     pub fn calculate_nakshatra(&self, longitude: f64) -> NakshatraInfo {
         NakshatraInfo::from_longitude(longitude)
     }
 
+    pub fn calculate_nakshatras(&self, chart_info: &ChartInfo) -> Vec<NakshatraInfo> {
+        chart_info
+            .planets
+            .iter()
+            .map(|planet| self.calculate_nakshatra(planet.longitude.degrees()))
+            .collect()
+    }
+
     pub fn get_nakshatra_lord(&self, nakshatra: Nakshatra) -> CelestialBody {
         NakshatraInfo::get_nakshatra_lord(nakshatra)
     }
-
-    pub fn calculate_dasha(&self, birth_info: &BirthInfo) -> Result<DashaInfo, CalculationError> {
-        let julian_day = date_to_julian_day(birth_info.date_time);
-        let result = self.calculate(
+    /// The Moon's sign, nakshatra and Vimshottari balance at `date_time`, without needing a
+    /// birthplace or houses. Enough for nakshatra matching and daily forecasts.
+    pub fn moon_only_profile(
+        &self,
+        date_time: DateTime<Utc>,
+    ) -> Result<MoonProfile, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let longitude = match self.calculate(
             CoordinateSystem::Sidereal,
             julian_day,
             CelestialBody::Moon,
-            &[CalculationFlag::Speed],
-        )?;
-        let moon_longitude = match result {
-            AstronomicalResult::CelestialBody(info) => info.longitude,
+            &[],
+            None,
+        )? {
+            AstronomicalResult::CelestialBody(info) => info.longitude.rem_euclid(360.0),
             _ => {
-                return Err(CalculationError {
+                return Err(AstrologyError::Ephemeris {
                     code: -1,
                     message: "Failed to calculate Moon position".to_string(),
                 })
             }
         };
 
-        let nakshatra_info = self.calculate_nakshatra(moon_longitude);
-        let starting_dasha = match nakshatra_info.lord {
-            CelestialBody::Sun => Dasha::Sun,
-            CelestialBody::Moon => Dasha::Moon,
-            CelestialBody::Mars => Dasha::Mars,
-            CelestialBody::Mercury => Dasha::Mercury,
-            CelestialBody::Jupiter => Dasha::Jupiter,
-            CelestialBody::Venus => Dasha::Venus,
-            CelestialBody::Saturn => Dasha::Saturn,
-            CelestialBody::Rahu => Dasha::Rahu,
-            CelestialBody::Ketu => Dasha::Ketu,
-        };
-
-        let dasha_years = [
-            (Dasha::Ketu, 7.0),
-            (Dasha::Venus, 20.0),
-            (Dasha::Sun, 6.0),
-            (Dasha::Moon, 10.0),
-            (Dasha::Mars, 7.0),
-            (Dasha::Rahu, 18.0),
-            (Dasha::Jupiter, 16.0),
-            (Dasha::Saturn, 19.0),
-            (Dasha::Mercury, 17.0),
-        ];
-
-        let position_in_nakshatra = moon_longitude % 13.333333333333334;
-        let nakshatra_fraction = position_in_nakshatra / 13.333333333333334;
-
-        let total_dasha_years = dasha_years
-            .iter()
-            .find(|&&(dasha, _)| dasha == starting_dasha)
-            .map(|&(_, years)| years)
-            .unwrap_or(0.0);
-
-        let dasha_balance_years = total_dasha_years * (1.0 - nakshatra_fraction);
-
-        let mut maha_dasha_periods = Vec::new();
-        let mut index = dasha_years
-            .iter()
-            .position(|&(dasha, _)| dasha == starting_dasha)
-            .unwrap_or(0);
-
-        let mut maha_dasha_start = birth_info.date_time;
-
-        let (current_dasha, _) = dasha_years[index];
-        let maha_dasha_end = maha_dasha_start
-            + ChronoDuration::seconds((dasha_balance_years * 365.25 * 86400.0) as i64);
-        maha_dasha_periods.push((current_dasha, maha_dasha_start, maha_dasha_end));
-        maha_dasha_start = maha_dasha_end;
-        index = (index + 1) % dasha_years.len();
-
-        let mut total_years = dasha_balance_years;
-
-        while total_years < 120.0 {
-            let (current_dasha, years) = dasha_years[index];
-            let maha_dasha_end =
-                maha_dasha_start + ChronoDuration::seconds((years * 365.25 * 86400.0) as i64);
-            maha_dasha_periods.push((current_dasha, maha_dasha_start, maha_dasha_end));
-            maha_dasha_start = maha_dasha_end;
-
-            total_years += years;
-            index = (index + 1) % dasha_years.len();
-        }
-
-        let now = Utc::now();
-        let current_maha_dasha = maha_dasha_periods
-            .iter()
-            .find(|&&(dasha, start, end)| now >= start && now < end)
-            .unwrap_or(&maha_dasha_periods[0]);
-
-        let (maha_dasha, maha_dasha_start, maha_dasha_end) = *current_maha_dasha;
-
-        // Antar Dasha Calculation
-        let antar_dasha = starting_dasha;
-        let antar_dasha_start = maha_dasha_start;
-        let antar_dasha_end = maha_dasha_end;
-
-        // Pratyantar Dasha Calculation
-        let pratyantar_dasha = starting_dasha;
-        let pratyantar_dasha_start = antar_dasha_start;
-        let pratyantar_dasha_end = antar_dasha_end;
-
-        Ok(DashaInfo {
-            maha_dasha,
-            maha_dasha_start,
-            maha_dasha_end,
-            antar_dasha,
-            antar_dasha_start,
-            antar_dasha_end,
-            pratyantar_dasha,
-            pratyantar_dasha_start,
-            pratyantar_dasha_end,
+        let nakshatra = self.calculate_nakshatra(longitude);
+        let dasha = Dasha::from_lord(nakshatra.lord).ok_or_else(|| {
+            AstrologyError::Calculation(format!("{:?} does not rule a nakshatra", nakshatra.lord))
+        })?;
+        let nakshatra_fraction = (longitude % 13.333333333333334) / 13.333333333333334;
+        let dasha_balance_years = dasha.years() * (1.0 - nakshatra_fraction);
+        let year_seconds = self.conventions.dasha_year_days * 86400.0;
+        let dasha_end =
+            date_time + ChronoDuration::seconds((dasha_balance_years * year_seconds) as i64);
+
+        Ok(MoonProfile {
+            date_time,
+            longitude,
+            rasi: ZodiacSign::from_longitude(longitude),
+            nakshatra,
+            dasha,
+            dasha_balance_years,
+            dasha_end,
         })
     }
 
+    /// The Vimshottari periods running at `as_of`.
+    pub fn calculate_dasha(
+        &self,
+        birth_info: &BirthInfo,
+        as_of: DateTime<Utc>,
+    ) -> Result<DashaInfo, AstrologyError> {
+        self.calculate_dasha_with(birth_info, &VimshottariDasha, as_of)
+    }
+
+    /// Dignity, motion, combustion, war and nature of every planet in the chart.
     pub fn calculate_planetary_states(
         &self,
         chart_info: &ChartInfo,
-    ) -> Result<HashMap<CelestialBody, PlanetaryState>, CalculationError> {
+    ) -> Result<HashMap<CelestialBody, PlanetStateSet>, AstrologyError> {
         let mut states = HashMap::new();
+        let wars = chart_info.planetary_wars();
 
         let exaltation_points = [
             (CelestialBody::Sun, ZodiacSign::Aries, 10.0),
@@ -400,19 +314,40 @@ impl SwissEph {
         let own_signs = [
             (CelestialBody::Sun, vec![ZodiacSign::Leo]),
             (CelestialBody::Moon, vec![ZodiacSign::Cancer]),
-            (CelestialBody::Mars, vec![ZodiacSign::Aries, ZodiacSign::Scorpio]),
-            (CelestialBody::Mercury, vec![ZodiacSign::Gemini, ZodiacSign::Virgo]),
-            (CelestialBody::Jupiter, vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces]),
-            (CelestialBody::Venus, vec![ZodiacSign::Taurus, ZodiacSign::Libra]),
-            (CelestialBody::Saturn, vec![ZodiacSign::Capricorn, ZodiacSign::Aquarius]),
-            (CelestialBody::Rahu, vec![ZodiacSign::Gemini, ZodiacSign::Virgo]),
-            (CelestialBody::Ketu, vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces]),
+            (
+                CelestialBody::Mars,
+                vec![ZodiacSign::Aries, ZodiacSign::Scorpio],
+            ),
+            (
+                CelestialBody::Mercury,
+                vec![ZodiacSign::Gemini, ZodiacSign::Virgo],
+            ),
+            (
+                CelestialBody::Jupiter,
+                vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces],
+            ),
+            (
+                CelestialBody::Venus,
+                vec![ZodiacSign::Taurus, ZodiacSign::Libra],
+            ),
+            (
+                CelestialBody::Saturn,
+                vec![ZodiacSign::Capricorn, ZodiacSign::Aquarius],
+            ),
+            (
+                CelestialBody::Rahu,
+                vec![ZodiacSign::Gemini, ZodiacSign::Virgo],
+            ),
+            (
+                CelestialBody::Ketu,
+                vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces],
+            ),
         ];
 
         for planet_position in &chart_info.planets {
             let planet = planet_position.planet;
             let sign = planet_position.sign;
-            let longitude = planet_position.longitude % 30.0;
+            let longitude = planet_position.longitude.degree_in_sign();
 
             let exalted = exaltation_points
                 .iter()
@@ -441,58 +376,70 @@ impl SwissEph {
                 .find(|&&(p, ref signs)| p == planet && signs.contains(&sign))
                 .map(|_| PlanetaryState::OwnSign);
 
-            let friendly = match planet {
-                CelestialBody::Jupiter
-                | CelestialBody::Venus
-                | CelestialBody::Mercury
-                | CelestialBody::Moon
-                | CelestialBody::Sun => true,
-                CelestialBody::Saturn
-                | CelestialBody::Mars
-                | CelestialBody::Rahu
-                | CelestialBody::Ketu => false,
-            };
+            let relationship = self
+                .friendships
+                .compound_relationship(chart_info, planet, sign.lord())
+                .filter(|_| sign.lord() != planet)
+                .map(PlanetaryState::from);
 
-            let state = if let Some(ex_state) = exalted {
-                ex_state
-            } else if let Some(deb_state) = debilitated {
-                deb_state
-            } else if let Some(own_state) = own_sign {
-                own_state
+            let dignity = exalted.or(debilitated).or(own_sign).or(relationship);
+            let motion = if planet_position.retrograde {
+                PlanetaryState::Retrograde
             } else {
-                if friendly {
-                    PlanetaryState::Benefic
-                } else {
-                    PlanetaryState::Malefic
-                }
+                PlanetaryState::Direct
             };
-
-            let final_state = if planet_position.retrograde {
-                PlanetaryState::Retrograde
+            let nature = if chart_info.is_benefic(planet) {
+                PlanetaryState::Benefic
             } else {
-                state
+                PlanetaryState::Malefic
             };
 
-            states.insert(planet, final_state);
+            states.insert(
+                planet,
+                PlanetStateSet {
+                    dignity,
+                    motion,
+                    combust: chart_info.is_combust(planet, &self.conventions.combustion_orbs),
+                    war: wars.iter().find_map(|war| war.side(planet)),
+                    nature,
+                },
+            );
         }
 
         Ok(states)
     }
 
+    /// Dignities of the chart's planets, each scored with its five-fold relationship to
+    /// the lord of its sign.
+    pub fn calculate_dignities(&self, chart: &ChartInfo) -> HashMap<CelestialBody, DignityInfo> {
+        chart
+            .planets
+            .iter()
+            .map(|planet| {
+                let dignity = DignityInfo::of(planet, Some(chart), &self.friendships);
+                (planet.planet, dignity)
+            })
+            .collect()
+    }
+
+    /// Dignity of a planet alone, scored with its natural relationship to the lord of its
+    /// sign.
+    pub fn calculate_dignity(&self, planet: &PlanetPosition) -> DignityInfo {
+        DignityInfo::of(planet, None, &self.friendships)
+    }
+
+    /// Calculates a body's position. Passing an `observer` computes the position as seen
+    /// from that point on the Earth's surface rather than from the Earth's centre.
     pub fn calculate(
         &self,
         coord_system: CoordinateSystem,
         julian_day: JulianDay,
         body: CelestialBody,
         flags: &[CalculationFlag],
-    ) -> Result<AstronomicalResult, CalculationError> {
-        match coord_system {
-            CoordinateSystem::Sidereal => unsafe {
-                swe_set_sid_mode(SE_SIDM_LAHIRI, 0.0, 0.0);
-            },
-            CoordinateSystem::Tropical => unsafe {
-                swe_set_sid_mode(SE_SIDM_FAGAN_BRADLEY, 0.0, 0.0);
-            },
+        observer: Option<&Observer>,
+    ) -> Result<AstronomicalResult, AstrologyError> {
+        if coord_system == CoordinateSystem::Sidereal {
+            self.set_sidereal_mode();
         }
 
         let mut iflag: c_int = if coord_system == CoordinateSystem::Sidereal {
@@ -504,10 +451,30 @@ impl SwissEph {
             iflag |= *flag as c_int;
         }
 
-        let result = match body {
+        match observer {
+            Some(observer) => {
+                unsafe {
+                    swe_set_topo(observer.longitude, observer.latitude, observer.altitude);
+                }
+                iflag |= SEFLG_TOPOCTR;
+            }
+            None if iflag & SEFLG_TOPOCTR != 0 => {
+                return Err(AstrologyError::InvalidInput(
+                    "Topocentric calculation requires an observer".to_string(),
+                ));
+            }
+            None => {}
+        }
+
+        match body {
             CelestialBody::Ketu => {
-                let rahu_result =
-                    self.calculate(coord_system, julian_day, CelestialBody::Rahu, flags)?;
+                let rahu_result = self.calculate(
+                    coord_system,
+                    julian_day,
+                    CelestialBody::Rahu,
+                    flags,
+                    observer,
+                )?;
                 let (
                     longitude,
                     latitude,
@@ -525,7 +492,7 @@ impl SwissEph {
                         info.speed_distance,
                     ),
                     _ => {
-                        return Err(CalculationError {
+                        return Err(AstrologyError::Ephemeris {
                             code: -1,
                             message: "Failed to calculate Ketu".to_string(),
                         })
@@ -540,13 +507,37 @@ impl SwissEph {
                     speed_distance,
                 }))
             }
-            _ => {
+            _ => self
+                .calc_ut(julian_day, body, iflag)
+                .map(AstronomicalResult::CelestialBody),
+        }
+    }
+
+    /// Calls `swe_calc_ut` with flags already assembled, or takes the position from the
+    /// engine's cache. The sidereal mode must be set by the caller; Ketu is not handled.
+    pub(crate) fn calc_ut(
+        &self,
+        julian_day: JulianDay,
+        body: CelestialBody,
+        iflag: c_int,
+    ) -> Result<CelestialCoordinates, AstrologyError> {
+        let body_code = match body {
+            CelestialBody::Rahu => self.conventions.node_type.body_code(),
+            _ => body as c_int,
+        };
+        let iflag = self.ephemeris_flags(iflag);
+        self.position_cache
+            .get_or_calculate(julian_day, body_code, iflag, || {
+                let _timed = Timed::start(
+                    TimedWork::EphemerisCall,
+                    tracing::trace_span!("swe_calc_ut", julian_day, body = ?body),
+                );
                 let mut results: [c_double; 6] = [0.0; 6];
                 let mut error: [c_char; 256] = [0; 256];
                 let calc_result = unsafe {
                     swe_calc_ut(
                         julian_day,
-                        body as c_int,
+                        body_code,
                         iflag,
                         results.as_mut_ptr(),
                         error.as_mut_ptr(),
@@ -556,23 +547,23 @@ impl SwissEph {
                     let error_message = unsafe { CStr::from_ptr(error.as_ptr()) }
                         .to_string_lossy()
                         .into_owned();
-                    return Err(CalculationError {
+                    return Err(AstrologyError::Ephemeris {
                         code: calc_result,
                         message: error_message,
                     });
                 }
-                Ok(AstronomicalResult::CelestialBody(CelestialCoordinates {
+                self.check_files_used(julian_day, body, calc_result, unsafe {
+                    CStr::from_ptr(error.as_ptr())
+                })?;
+                Ok(CelestialCoordinates {
                     longitude: results[0],
                     latitude: results[1],
                     distance: results[2],
                     speed_longitude: results[3],
                     speed_latitude: results[4],
                     speed_distance: results[5],
-                }))
-            }
-        };
-
-        result
+                })
+            })
     }
 
     pub fn get_body_name(&self, body: CelestialBody) -> String {
@@ -596,19 +587,12 @@ impl SwissEph {
         julian_day: JulianDay,
         latitude: f64,
         longitude: f64,
-        house_system: ChartType,
-    ) -> Result<Vec<HousePosition>, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS,
-            ChartType::Navamsa => SE_HS_NAVAMSA,
-            ChartType::Hora => SE_HS_HORA,
-            // Add other house systems as needed
-        };
+        house_system: HouseSystem,
+    ) -> Result<Houses, AstrologyError> {
+        let hsys = house_system.code();
 
         if coord_system == CoordinateSystem::Sidereal {
-            unsafe {
-                swe_set_sid_mode(SE_SIDM_LAHIRI, 0.0, 0.0);
-            }
+            self.set_sidereal_mode();
         }
 
         let flag = if coord_system == CoordinateSystem::Sidereal {
@@ -620,10 +604,14 @@ impl SwissEph {
         let mut cusps: [c_double; 13] = [0.0; 13];
         let mut ascmc: [c_double; 10] = [0.0; 10];
 
+        let _timed = Timed::start(
+            TimedWork::HouseCall,
+            tracing::trace_span!("swe_houses_ex", julian_day, latitude, longitude),
+        );
         let calc_result = unsafe {
             swe_houses_ex(
                 julian_day,
-                flag,
+                self.ephemeris_flags(flag),
                 latitude,
                 longitude,
                 hsys,
@@ -633,37 +621,84 @@ impl SwissEph {
         };
 
         if calc_result < 0 {
-            return Err(CalculationError {
+            return Err(AstrologyError::Ephemeris {
                 code: calc_result,
                 message: "Error calculating houses".to_string(),
             });
         }
 
-        let house_positions: Vec<HousePosition> = (1..=12)
-            .map(|i| HousePosition {
+        let house_positions: Vec<HouseCusp> = (1..=12)
+            .map(|i| HouseCusp {
                 house: House::from_index(i).unwrap(),
                 sign: Self::get_zodiac_sign(cusps[i]),
                 degree: cusps[i] % 30.0,
             })
             .collect();
 
-        Ok(house_positions)
+        Ok(Houses {
+            cusps: house_positions,
+            angles: Angles {
+                ascendant: AnglePoint::from_longitude(ascmc[0]),
+                midheaven: AnglePoint::from_longitude(ascmc[1]),
+                vertex: AnglePoint::from_longitude(ascmc[3]),
+                east_point: AnglePoint::from_longitude(ascmc[4]),
+                armc: ascmc[2],
+            },
+        })
     }
 
-    pub fn calculate_ascendant(
+    /// Houses in `house_system`, or where it cannot be drawn, in Porphyry and failing that
+    /// in whole signs. Placidus and Koch divide the time the ascendant degree takes to
+    /// culminate, and above the polar circles some degrees never rise. Returns the system
+    /// used and, if it is not the one asked for, a warning saying so.
+    pub fn calculate_houses_with_fallback(
         &self,
         coord_system: CoordinateSystem,
         julian_day: JulianDay,
         latitude: f64,
         longitude: f64,
-        house_system: ChartType,
-    ) -> Result<HousePosition, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS,
-            ChartType::Navamsa => SE_HS_NAVAMSA,
-            ChartType::Hora => SE_HS_HORA,
-            // Add other house systems as needed
+        house_system: HouseSystem,
+    ) -> Result<(Houses, HouseSystem, Option<String>), AstrologyError> {
+        let error = match self.calculate_houses(
+            coord_system,
+            julian_day,
+            latitude,
+            longitude,
+            house_system,
+        ) {
+            Ok(houses) => return Ok((houses, house_system, None)),
+            Err(error) => error,
         };
+        for fallback in [HouseSystem::Porphyry, HouseSystem::WholeSign] {
+            if fallback == house_system {
+                continue;
+            }
+            if let Ok(houses) =
+                self.calculate_houses(coord_system, julian_day, latitude, longitude, fallback)
+            {
+                let warning = format!(
+                    "{:?} houses cannot be drawn at latitude {:.2}°; {:?} houses are used instead",
+                    house_system, latitude, fallback
+                );
+                return Ok((houses, fallback, Some(warning)));
+            }
+        }
+        Err(error)
+    }
+
+    pub fn calculate_ascendant(
+        &self,
+        coord_system: CoordinateSystem,
+        julian_day: JulianDay,
+        latitude: f64,
+        longitude: f64,
+        house_system: HouseSystem,
+    ) -> Result<HouseCusp, AstrologyError> {
+        let hsys = house_system.code();
+
+        if coord_system == CoordinateSystem::Sidereal {
+            self.set_sidereal_mode();
+        }
 
         let flag = if coord_system == CoordinateSystem::Sidereal {
             SEFLG_SIDEREAL
@@ -674,10 +709,14 @@ impl SwissEph {
         let mut cusps: [c_double; 13] = [0.0; 13];
         let mut ascmc: [c_double; 10] = [0.0; 10];
 
+        let _timed = Timed::start(
+            TimedWork::HouseCall,
+            tracing::trace_span!("swe_houses_ex", julian_day, latitude, longitude),
+        );
         let calc_result = unsafe {
             swe_houses_ex(
                 julian_day,
-                flag,
+                self.ephemeris_flags(flag),
                 latitude,
                 longitude,
                 hsys,
@@ -687,7 +726,7 @@ impl SwissEph {
         };
 
         if calc_result < 0 {
-            return Err(CalculationError {
+            return Err(AstrologyError::Ephemeris {
                 code: calc_result,
                 message: "Error calculating ascendant".to_string(),
             });
@@ -695,7 +734,7 @@ impl SwissEph {
 
         let ascendant_degree = ascmc[0];
         let sign = Self::get_zodiac_sign(ascendant_degree);
-        Ok(HousePosition {
+        Ok(HouseCusp {
             house: House::First,
             sign,
             degree: ascendant_degree % 30.0,
@@ -711,8 +750,20 @@ impl SwissEph {
         coord_system: CoordinateSystem,
         julian_day: JulianDay,
         chart_type: ChartType,
+        house_system: HouseSystem,
         birth_info: &BirthInfo,
-    ) -> Result<Vec<PlanetPosition>, CalculationError> {
+        observer: Option<&Observer>,
+    ) -> Result<Vec<PlanetPosition>, AstrologyError> {
+        let cusps = self
+            .calculate_houses(
+                coord_system,
+                julian_day,
+                birth_info.location.latitude,
+                birth_info.location.longitude,
+                house_system,
+            )?
+            .cusps;
+
         let planets = vec![
             CelestialBody::Sun,
             CelestialBody::Moon,
@@ -725,13 +776,41 @@ impl SwissEph {
             CelestialBody::Ketu,
         ];
 
+        self.calculate_body_positions(
+            &planets,
+            coord_system,
+            julian_day,
+            chart_type,
+            &cusps,
+            observer,
+        )
+    }
+
+    /// Positions of `bodies`, placed in houses by `cusps`, which must be in the zodiac of
+    /// `coord_system`.
+    pub fn calculate_body_positions(
+        &self,
+        bodies: &[CelestialBody],
+        coord_system: CoordinateSystem,
+        julian_day: JulianDay,
+        chart_type: ChartType,
+        cusps: &[HouseCusp],
+        observer: Option<&Observer>,
+    ) -> Result<Vec<PlanetPosition>, AstrologyError> {
         let mut positions = Vec::new();
 
-        for planet in planets {
-            let result =
-                self.calculate(coord_system, julian_day, planet, &[CalculationFlag::Speed])?;
+        for &planet in bodies {
+            let result = self.calculate(
+                coord_system,
+                julian_day,
+                planet,
+                &[CalculationFlag::Speed],
+                observer,
+            )?;
             let (longitude, latitude, speed) = match result {
-                AstronomicalResult::CelestialBody(info) => (info.longitude, info.latitude, info.speed_longitude),
+                AstronomicalResult::CelestialBody(info) => {
+                    (info.longitude, info.latitude, info.speed_longitude)
+                }
                 _ => continue,
             };
 
@@ -739,17 +818,14 @@ impl SwissEph {
                 ChartType::Rasi => longitude,
                 ChartType::Navamsa => self.calculate_navamsa(longitude),
                 ChartType::Hora => (longitude * 2.0) % 360.0, // Example for Hora
+                ChartType::Saptamsa => self.calculate_saptamsa(longitude),
+                ChartType::Dashamsa => self.calculate_dashamsa(longitude),
+                ChartType::Dwadasamsa => self.calculate_dwadasamsa(longitude),
                 // Add more chart types as needed
             };
 
             let sign = Self::get_zodiac_sign(adjusted_longitude);
-            let house = self.get_house(
-                julian_day,
-                adjusted_longitude,
-                birth_info.location.latitude,
-                birth_info.location.longitude,
-                chart_type,
-            )?;
+            let house = house_from_cusps(cusps, adjusted_longitude);
 
             let nakshatra = self.calculate_nakshatra(adjusted_longitude);
 
@@ -757,7 +833,7 @@ impl SwissEph {
 
             positions.push(PlanetPosition {
                 planet,
-                longitude: adjusted_longitude,
+                longitude: Longitude::new(adjusted_longitude),
                 latitude,
                 speed,
                 sign,
@@ -770,806 +846,121 @@ impl SwissEph {
         Ok(positions)
     }
 
-    pub fn calculate_yogas(&self, chart: &ChartInfo) -> Vec<YogaInfo> {
-        let mut yogas = Vec::new();
+    // ---------------------------
+    // ## Compatibility Calculations
+    // ---------------------------
 
-        let get_planet = |body: CelestialBody| -> Option<&PlanetPosition> {
-            chart.planets.iter().find(|p| p.planet == body)
+    /// Ashtakoota match of two births, taking the groom and bride from the genders of
+    /// their [`Person`]s. Where those do not tell, `first` is taken as the groom. In the
+    /// result, `mangal_dosha1` is the groom's.
+    pub fn calculate_marriage_match(
+        &self,
+        first: &BirthInfo,
+        second: &BirthInfo,
+    ) -> Result<CompatibilityInfo, AstrologyError> {
+        let (gender1, gender2) = (first.gender(), second.gender());
+        let swap = (gender1 == Gender::Female && gender2 != Gender::Female)
+            || (gender2 == Gender::Male && gender1 != Gender::Male);
+        let (groom, bride) = if swap {
+            (second, first)
+        } else {
+            (first, second)
         };
 
-        // Raj Yoga: Lord of 9th and 10th house conjunction
-        if let (Some(ninth_lord), Some(tenth_lord)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Saturn),
-        ) {
-            if (ninth_lord.longitude - tenth_lord.longitude).abs() < 10.0 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Raj,
-                    strength: 1.0,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Saturn],
-                });
-            }
-        }
-
-        // Gajakesari Yoga: Jupiter in a Kendra from Moon
-        if let (Some(jupiter), Some(moon)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Moon),
-        ) {
-            let house_diff = (jupiter.house as i32 - moon.house as i32).abs() % 12;
-            if house_diff == 4 || house_diff == 7 || house_diff == 10 || house_diff == 1 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Gajakesari,
-                    strength: 0.85,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Moon],
-                });
-            }
-        }
+        let house_system = self.conventions.house_system;
+        self.calculate_compatibility(
+            &self.calculate_chart(groom, house_system)?,
+            &self.calculate_chart(bride, house_system)?,
+        )
+    }
 
-        // Budhaditya Yoga: Sun and Mercury in same house
-        if let (Some(sun), Some(mercury)) = (
-            get_planet(CelestialBody::Sun),
-            get_planet(CelestialBody::Mercury),
-        ) {
-            if sun.house == mercury.house {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Budhaditya,
-                    strength: 0.9,
-                    involved_planets: vec![CelestialBody::Sun, CelestialBody::Mercury],
-                });
-            }
-        }
+    /// Ashtakoota match from the Moons of the two charts, with `chart1` as the groom and
+    /// `chart2` as the bride, and Mangal Dosha for each. Fails if either chart lacks the
+    /// Moon.
+    pub fn calculate_compatibility(
+        &self,
+        chart1: &ChartInfo,
+        chart2: &ChartInfo,
+    ) -> Result<CompatibilityInfo, AstrologyError> {
+        let moon = |chart: &ChartInfo| {
+            chart
+                .planet(CelestialBody::Moon)
+                .map(|moon| moon.longitude.degrees())
+                .ok_or(AstrologyError::MissingBody(CelestialBody::Moon))
+        };
+        let weights = &self.conventions.kuta_weights;
+        let kutas = Ashtakoota::from_moons(moon(chart1)?, moon(chart2)?, &self.friendships)
+            .weighted(weights);
+        let total = kutas.total();
+
+        Ok(CompatibilityInfo {
+            kuta_points: total.score.round() as u32,
+            compatibility_score: total.score / weights.total() * 100.0,
+            kutas,
+            mangal_dosha1: MangalDosha::from_chart(chart1),
+            mangal_dosha2: MangalDosha::from_chart(chart2),
+        })
+    }
 
-        // Hamsa Yoga: Jupiter in Kendra from Moon
-        if let (Some(jupiter), Some(moon)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Moon),
-        ) {
-            let house_diff = (jupiter.house as i32 - moon.house as i32).abs() % 12;
-            if house_diff == 4 || house_diff == 7 || house_diff == 10 || house_diff == 1 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Hamsa,
-                    strength: 0.8,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Moon],
-                });
-            }
-        }
+    pub fn calculate_special_lagnas(
+        &self,
+        chart: &ChartInfo,
+    ) -> Result<HashMap<SpecialLagna, Longitude>, AstrologyError> {
+        let mut special_lagnas = HashMap::new();
+
+        let ascendant_longitude = chart.ascendant.longitude();
+        let longitude = |body: CelestialBody| {
+            chart
+                .planet(body)
+                .map(|planet| planet.longitude.degrees())
+                .ok_or(AstrologyError::MissingBody(body))
+        };
+        let sun_longitude = longitude(CelestialBody::Sun)?;
+        let moon_longitude = longitude(CelestialBody::Moon)?;
 
-        // Malavya Yoga: Venus in a Kendra house
-        if let Some(venus) = get_planet(CelestialBody::Venus) {
-            if matches!(
-                venus.house,
-                House::First | House::Fourth | House::Seventh | House::Tenth
-            ) {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Malavya,
-                    strength: 0.75,
-                    involved_planets: vec![CelestialBody::Venus],
-                });
-            }
-        }
+        // Calculate Hora Lagna
+        let hora_lagna = Longitude::new(ascendant_longitude + (sun_longitude - moon_longitude));
+        special_lagnas.insert(SpecialLagna::Hora, hora_lagna);
 
-        yogas
-    }
+        // Calculate Ghati Lagna
+        let ghati_lagna =
+            Longitude::new(ascendant_longitude + (moon_longitude - sun_longitude) * 5.0);
+        special_lagnas.insert(SpecialLagna::Ghati, ghati_lagna);
 
-    pub fn calculate_aspects(&self, chart: &ChartInfo) -> Vec<AspectInfo> {
-        let mut aspects = Vec::new();
+        // Calculate Varnada Lagna
+        let varnada_lagna =
+            Longitude::new(ascendant_longitude + (sun_longitude - moon_longitude) * 3.0);
+        special_lagnas.insert(SpecialLagna::Varnada, varnada_lagna);
 
-        for (i, planet1) in chart.planets.iter().enumerate() {
-            for planet2 in chart.planets.iter().skip(i + 1) {
-                let angle = (planet2.longitude - planet1.longitude).abs() % 360.0;
-                let aspect = match angle {
-                    a if (0.0..=10.0).contains(&a) || (350.0..=360.0).contains(&a) => {
-                        Some(Aspect::Conjunction)
-                    }
-                    a if (170.0..=190.0).contains(&a) => Some(Aspect::Opposition),
-                    a if (115.0..=125.0).contains(&a) => Some(Aspect::Trine),
-                    a if (85.0..=95.0).contains(&a) => Some(Aspect::Square),
-                    a if (55.0..=65.0).contains(&a) => Some(Aspect::Sextile),
-                    _ => None,
-                };
+        // Calculate Sree Lagna
+        let sree_lagna = Longitude::new(ascendant_longitude + moon_longitude);
+        special_lagnas.insert(SpecialLagna::Sree, sree_lagna);
 
-                if let Some(asp) = aspect {
-                    aspects.push(AspectInfo {
-                        aspect: asp,
-                        planet1: planet1.planet,
-                        planet2: planet2.planet,
-                        orb: angle % 30.0,
-                    });
-                }
-            }
-        }
+        // Calculate Pranapada Lagna
+        let pranapada_lagna =
+            Longitude::new(ascendant_longitude + (sun_longitude - moon_longitude) * 7.0);
+        special_lagnas.insert(SpecialLagna::Pranapada, pranapada_lagna);
 
-        aspects
+        Ok(special_lagnas)
     }
 
-    pub fn calculate_strengths(&self, chart: &ChartInfo) -> HashMap<CelestialBody, StrengthInfo> {
-        let mut strengths = HashMap::new();
+    /// Remedies for the chart's weak planets: those retrograde or combust in the chart.
+    pub fn suggest_remedial_measures(&self, chart: &ChartInfo) -> Vec<RemedialMeasure> {
+        let mut remedies = Vec::new();
 
         for planet in &chart.planets {
-            let shad_bala = self.calculate_shadbala(planet, chart);
-            let ashtaka_varga = self.calculate_ashtakavarga(planet, chart);
-
-            strengths.insert(
-                planet.planet,
-                StrengthInfo {
-                    shad_bala,
-                    ashtaka_varga,
-                },
-            );
+            if self.is_planet_weak(chart, planet) {
+                if let Some(remedy) = self.get_remedy_for_planet(planet.planet) {
+                    remedies.push(remedy);
+                }
+            }
         }
 
-        strengths
-    }
-
-    fn calculate_shadbala(&self, planet: &PlanetPosition, chart: &ChartInfo) -> f64 {
-        let mut strength = 0.0;
-
-        // Sthana Bala (Positional Strength)
-        strength += match planet.house {
-            House::First | House::Fourth | House::Seventh | House::Tenth => 60.0,
-            House::Second | House::Fifth | House::Eighth | House::Eleventh => 30.0,
-            _ => 15.0,
-        };
-
-        // Dig Bala (Directional Strength)
-        strength += match (planet.planet, planet.house) {
-            (CelestialBody::Sun, House::Tenth) | (CelestialBody::Mars, House::Tenth) => 60.0,
-            (CelestialBody::Jupiter, House::First) | (CelestialBody::Mercury, House::First) => 60.0,
-            (CelestialBody::Moon, House::Fourth) | (CelestialBody::Venus, House::Fourth) => 60.0,
-            (CelestialBody::Saturn, House::Seventh) => 60.0,
-            _ => 30.0,
-        };
-
-        // Kala Bala (Temporal Strength)
-        strength += if planet.retrograde {
-            60.0
-        } else {
-            30.0
-        };
-
-        // Chesta Bala (Motional Strength)
-        strength += if planet.speed.abs() > 1.0 { 60.0 } else { 30.0 };
-
-        // Naisargika Bala (Natural Strength)
-        strength += match planet.planet {
-            CelestialBody::Saturn => 60.0,
-            CelestialBody::Jupiter => 50.0,
-            CelestialBody::Mars => 40.0,
-            CelestialBody::Sun => 30.0,
-            CelestialBody::Venus => 20.0,
-            CelestialBody::Mercury => 10.0,
-            CelestialBody::Moon => 0.0,
-            _ => 0.0,
-        };
-
-        // Drik Bala (Aspectual Strength)
-        strength += self
-            .calculate_aspects(chart)
-            .iter()
-            .filter(|a| a.planet1 == planet.planet || a.planet2 == planet.planet)
-            .count() as f64
-            * 10.0;
-
-        strength
-    }
-
-    fn calculate_ashtakavarga(&self, planet: &PlanetPosition, chart: &ChartInfo) -> u32 {
-        let mut points = 0;
-
-        for house in 1..=12 {
-            for other_planet in &chart.planets {
-                if other_planet.planet == planet.planet {
-                    continue;
-                }
-
-                let angle_diff = ((other_planet.longitude - planet.longitude).abs() % 360.0).floor();
-                let house_diff = ((angle_diff / 30.0).floor() as usize) % 12;
-
-                points += match (planet.planet, other_planet.planet, house_diff) {
-                    // Example rules for Ashtakavarga; needs detailed implementation
-                    (CelestialBody::Sun, _, 0..=11) => 1,
-                    (CelestialBody::Moon, _, 0..=11) => 1,
-                    (CelestialBody::Mars, _, 0..=11) => 1,
-                    (CelestialBody::Mercury, _, 0..=11) => 1,
-                    (CelestialBody::Jupiter, _, 0..=11) => 1,
-                    (CelestialBody::Venus, _, 0..=11) => 1,
-                    (CelestialBody::Saturn, _, 0..=11) => 1,
-                    (CelestialBody::Rahu, _, 0..=11) => 1,
-                    (CelestialBody::Ketu, _, 0..=11) => 1,
-                    _ => 0,
-                };
-            }
-        }
-
-        points
-    }
-
-    pub fn calculate_dignities(&self, chart: &ChartInfo) -> HashMap<CelestialBody, DignityInfo> {
-        let mut dignities = HashMap::new();
-
-        for planet in &chart.planets {
-            let dignity = DignityInfo {
-                moolatrikona: self.is_moolatrikona(planet),
-                own_sign: self.is_own_sign(planet),
-                exalted: self.is_exalted(planet),
-                debilitated: self.is_debilitated(planet),
-            };
-            dignities.insert(planet.planet, dignity);
-        }
-
-        dignities
-    }
-
-    fn is_moolatrikona(&self, planet: &PlanetPosition) -> bool {
-        match (planet.planet, planet.sign) {
-            (CelestialBody::Sun, ZodiacSign::Leo) => true,
-            (CelestialBody::Moon, ZodiacSign::Taurus) => true,
-            (CelestialBody::Mars, ZodiacSign::Aries) => true,
-            (CelestialBody::Mercury, ZodiacSign::Virgo) => true,
-            (CelestialBody::Jupiter, ZodiacSign::Sagittarius) => true,
-            (CelestialBody::Venus, ZodiacSign::Libra) => true,
-            (CelestialBody::Saturn, ZodiacSign::Aquarius) => true,
-            _ => false,
-        }
-    }
-
-    fn is_own_sign(&self, planet: &PlanetPosition) -> bool {
-        match (planet.planet, planet.sign) {
-            (CelestialBody::Sun, ZodiacSign::Leo) => true,
-            (CelestialBody::Moon, ZodiacSign::Cancer) => true,
-            (CelestialBody::Mars, ZodiacSign::Aries) | (CelestialBody::Mars, ZodiacSign::Scorpio) => true,
-            (CelestialBody::Mercury, ZodiacSign::Gemini) | (CelestialBody::Mercury, ZodiacSign::Virgo) => true,
-            (CelestialBody::Jupiter, ZodiacSign::Sagittarius) | (CelestialBody::Jupiter, ZodiacSign::Pisces) => true,
-            (CelestialBody::Venus, ZodiacSign::Taurus) | (CelestialBody::Venus, ZodiacSign::Libra) => true,
-            (CelestialBody::Saturn, ZodiacSign::Capricorn) | (CelestialBody::Saturn, ZodiacSign::Aquarius) => true,
-            _ => false,
-        }
-    }
-
-    fn is_exalted(&self, planet: &PlanetPosition) -> bool {
-        match (planet.planet, planet.sign) {
-            (CelestialBody::Sun, ZodiacSign::Aries) => true,
-            (CelestialBody::Moon, ZodiacSign::Taurus) => true,
-            (CelestialBody::Mars, ZodiacSign::Capricorn) => true,
-            (CelestialBody::Mercury, ZodiacSign::Virgo) => true,
-            (CelestialBody::Jupiter, ZodiacSign::Cancer) => true,
-            (CelestialBody::Venus, ZodiacSign::Pisces) => true,
-            (CelestialBody::Saturn, ZodiacSign::Libra) => true,
-            _ => false,
-        }
-    }
-
-    fn is_debilitated(&self, planet: &PlanetPosition) -> bool {
-        match (planet.planet, planet.sign) {
-            (CelestialBody::Sun, ZodiacSign::Libra) => true,
-            (CelestialBody::Moon, ZodiacSign::Scorpio) => true,
-            (CelestialBody::Mars, ZodiacSign::Cancer) => true,
-            (CelestialBody::Mercury, ZodiacSign::Pisces) => true,
-            (CelestialBody::Jupiter, ZodiacSign::Capricorn) => true,
-            (CelestialBody::Venus, ZodiacSign::Virgo) => true,
-            (CelestialBody::Saturn, ZodiacSign::Aries) => true,
-            _ => false,
-        }
-    }
-
-    pub fn calculate_bhavas(&self, chart: &ChartInfo) -> Vec<BhavaInfo> {
-        let mut bhavas = Vec::new();
-
-        for (i, house) in chart.houses.iter().enumerate() {
-            let bhava = House::from_index(i + 1).unwrap();
-            let lord = self.get_house_lord(house.sign);
-            let planets = chart
-                .planets
-                .iter()
-                .filter(|p| p.house == bhava)
-                .map(|p| p.planet)
-                .collect();
-
-            bhavas.push(BhavaInfo {
-                bhava,
-                sign: house.sign,
-                degree: house.degree,
-                lord,
-                planets,
-            });
-        }
-
-        bhavas
-    }
-
-    fn get_house_lord(&self, sign: ZodiacSign) -> CelestialBody {
-        match sign {
-            ZodiacSign::Aries | ZodiacSign::Scorpio => CelestialBody::Mars,
-            ZodiacSign::Taurus | ZodiacSign::Libra => CelestialBody::Venus,
-            ZodiacSign::Gemini | ZodiacSign::Virgo => CelestialBody::Mercury,
-            ZodiacSign::Cancer => CelestialBody::Moon,
-            ZodiacSign::Leo => CelestialBody::Sun,
-            ZodiacSign::Sagittarius | ZodiacSign::Pisces => CelestialBody::Jupiter,
-            ZodiacSign::Capricorn | ZodiacSign::Aquarius => CelestialBody::Saturn,
-        }
-    }
-
-    pub fn calculate_transits(
-        &self,
-        birth_info: &BirthInfo,
-        period: ChronoDuration,
-    ) -> Vec<TransitInfo> {
-        let mut transits = Vec::new();
-        let start_jd = date_to_julian_day(birth_info.date_time);
-        let end_jd = start_jd + period.num_days() as f64;
-
-        let planets = vec![
-            CelestialBody::Sun,
-            CelestialBody::Moon,
-            CelestialBody::Mars,
-            CelestialBody::Mercury,
-            CelestialBody::Jupiter,
-            CelestialBody::Venus,
-            CelestialBody::Saturn,
-        ];
-
-        for planet in planets {
-            let mut current_jd = start_jd;
-            let mut current_sign = self.get_zodiac_sign_for_planet(planet, current_jd);
-
-            while current_jd < end_jd {
-                current_jd += 1.0;
-                let new_sign = self.get_zodiac_sign_for_planet(planet, current_jd);
-                if new_sign != current_sign {
-                    transits.push(TransitInfo {
-                        planet,
-                        from_sign: current_sign,
-                        to_sign: new_sign,
-                        date: julian_day_to_date(current_jd),
-                    });
-                    current_sign = new_sign;
-                }
-            }
-        }
-
-        transits
-    }
-
-    fn get_zodiac_sign_for_planet(&self, planet: CelestialBody, jd: JulianDay) -> ZodiacSign {
-        let result = self.calculate(CoordinateSystem::Tropical, jd, planet, &[]);
-        if let Ok(AstronomicalResult::CelestialBody(info)) = result {
-            Self::get_zodiac_sign(info.longitude)
-        } else {
-            ZodiacSign::Aries // Default to Aries if calculation fails
-        }
-    }
-
-    pub fn calculate_varshaphal(
-        &self,
-        birth_info: &BirthInfo,
-        year: i32,
-    ) -> Option<VarshaphalInfo> {
-        let birth_jd = date_to_julian_day(birth_info.date_time);
-        let solar_return_jd = self.find_solar_return(birth_jd, year)?;
-
-        let ascendant = self
-            .calculate_ascendant(
-                CoordinateSystem::Tropical,
-                solar_return_jd,
-                birth_info.location.latitude,
-                birth_info.location.longitude,
-                ChartType::Rasi,
-            )
-            .ok()?;
-
-        let planets = self
-            .calculate_planet_positions(
-                CoordinateSystem::Tropical,
-                solar_return_jd,
-                ChartType::Rasi,
-                birth_info,
-            )
-            .ok()?;
-
-        Some(VarshaphalInfo {
-            year,
-            ascendant: ascendant.sign,
-            planets,
-        })
-    }
-
-    fn find_solar_return(&self, birth_jd: JulianDay, target_year: i32) -> Option<JulianDay> {
-        let birth_sun_long = self
-            .calculate(
-                CoordinateSystem::Tropical,
-                birth_jd,
-                CelestialBody::Sun,
-                &[],
-            )
-            .ok()
-            .and_then(|r| match r {
-                AstronomicalResult::CelestialBody(info) => Some(info.longitude),
-                _ => None,
-            })?;
-
-        // Approximate the solar return by adding target_year years to birth_jd
-        let mut low = birth_jd + (target_year as f64) * 365.25;
-        let mut high = low + 366.0; // Maximum one solar year
-
-        let mut solar_return_jd = None;
-
-        while high - low > 0.0001 {
-            let mid = (low + high) / 2.0;
-            let mid_sun_long = self
-                .calculate(CoordinateSystem::Tropical, mid, CelestialBody::Sun, &[])
-                .ok()
-                .and_then(|r| match r {
-                    AstronomicalResult::CelestialBody(info) => Some(info.longitude),
-                    _ => None,
-                })?;
-
-            let diff = (mid_sun_long - birth_sun_long).abs();
-            if diff < 0.001 {
-                solar_return_jd = Some(mid);
-                break;
-            } else if mid_sun_long < birth_sun_long {
-                low = mid;
-            } else {
-                high = mid;
-            }
-        }
-
-        solar_return_jd
-    }
-
-    pub fn calculate_compatibility(
-        &self,
-        chart1: &ChartInfo,
-        chart2: &ChartInfo,
-    ) -> CompatibilityInfo {
-        let kuta_points = self.calculate_kuta_points(chart1, chart2);
-        let compatibility_score = self.calculate_compatibility_score(chart1, chart2);
-
-        CompatibilityInfo {
-            kuta_points,
-            compatibility_score,
-        }
-    }
-
-    fn calculate_kuta_points(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let mut points = 0;
-
-        // Varna Kuta (1 point)
-        if self.check_varna_compatibility(chart1.ascendant, chart2.ascendant) {
-            points += 1;
-        }
-
-        // Vasya Kuta (2 points)
-        if self.check_vasya_compatibility(chart1.ascendant, chart2.ascendant) {
-            points += 2;
-        }
-
-        // Tara Kuta (3 points)
-        points += self.calculate_tara_kuta(chart1, chart2);
-
-        // Yoni Kuta (4 points)
-        points += self.calculate_yoni_kuta(chart1, chart2);
-
-        // Graha Maitri (5 points)
-        points += self.calculate_graha_maitri(chart1, chart2);
-
-        // Gana Kuta (6 points)
-        if self.check_gana_compatibility(chart1.ascendant, chart2.ascendant) {
-            points += 6;
-        }
-
-        // Bhakut Kuta (7 points)
-        if self.check_bhakut_compatibility(chart1.ascendant, chart2.ascendant) {
-            points += 7;
-        }
-
-        // Nadi Kuta (8 points)
-        if self.check_nadi_compatibility(chart1.ascendant, chart2.ascendant) {
-            points += 8;
-        }
-
-        points
-    }
-
-    fn calculate_compatibility_score(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> f64 {
-        let kuta_points = self.calculate_kuta_points(chart1, chart2) as f64;
-        let max_points = 36.0; // Maximum possible Kuta points
-
-        (kuta_points / max_points) * 100.0
-    }
-
-    fn check_varna_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let varna1 = self.get_varna(sign1);
-        let varna2 = self.get_varna(sign2);
-        varna1 >= varna2
-    }
-
-    fn get_varna(&self, sign: ZodiacSign) -> u32 {
-        match sign {
-            ZodiacSign::Leo | ZodiacSign::Aries | ZodiacSign::Sagittarius => 4, // Brahmin
-            ZodiacSign::Cancer | ZodiacSign::Scorpio | ZodiacSign::Pisces => 3, // Kshatriya
-            ZodiacSign::Gemini | ZodiacSign::Libra | ZodiacSign::Aquarius => 2, // Vaishya
-            ZodiacSign::Taurus | ZodiacSign::Virgo | ZodiacSign::Capricorn => 1, // Shudra
-        }
-    }
-
-    fn check_vasya_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let vasya_groups = vec![
-            vec![ZodiacSign::Leo, ZodiacSign::Aries],
-            vec![ZodiacSign::Cancer, ZodiacSign::Scorpio],
-            vec![ZodiacSign::Gemini, ZodiacSign::Libra, ZodiacSign::Aquarius],
-            vec![ZodiacSign::Taurus, ZodiacSign::Capricorn],
-            vec![ZodiacSign::Virgo, ZodiacSign::Pisces],
-            vec![ZodiacSign::Sagittarius],
-        ];
-
-        vasya_groups
-            .iter()
-            .any(|group| group.contains(&sign1) && group.contains(&sign2))
-    }
-
-    fn calculate_tara_kuta(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let moon1 = chart1
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-        let moon2 = chart2
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-
-        let nakshatra1 = moon1.nakshatra.nakshatra as u32;
-        let nakshatra2 = moon2.nakshatra.nakshatra as u32;
-
-        let tara = ((nakshatra2 - nakshatra1 + 27) % 27) / 3;
-
-        match tara {
-            1 | 3 | 5 | 7 => 3,
-            0 | 2 | 4 | 6 | 8 => 0,
-            _ => 0,
-        }
-    }
-
-    fn calculate_yoni_kuta(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let moon1 = chart1
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-        let moon2 = chart2
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-
-        let yoni1 = self.get_yoni(moon1.nakshatra.nakshatra);
-        let yoni2 = self.get_yoni(moon2.nakshatra.nakshatra);
-
-        if yoni1 == yoni2 {
-            4
-        } else if self.are_yonis_compatible(yoni1, yoni2) {
-            2
-        } else {
-            0
-        }
-    }
-
-    fn get_yoni(&self, nakshatra: Nakshatra) -> &'static str {
-        match nakshatra {
-            Nakshatra::Ashwini | Nakshatra::Shatabhisha => "Horse",
-            Nakshatra::Bharani | Nakshatra::Revati => "Elephant",
-            Nakshatra::Krittika | Nakshatra::Punarvasu => "Goat",
-            Nakshatra::Rohini | Nakshatra::UttaraPhalguni => "Snake",
-            Nakshatra::Mrigashira | Nakshatra::Chitra => "Dog",
-            Nakshatra::Ardra | Nakshatra::Shravana => "Cat",
-            Nakshatra::Pushya | Nakshatra::UttaraAshadha => "Ram",
-            Nakshatra::Ashlesha | Nakshatra::Jyeshtha => "Mongoose",
-            Nakshatra::Magha | Nakshatra::PurvaPhalguni => "Rat",
-            Nakshatra::Hasta | Nakshatra::Anuradha => "Buffalo",
-            Nakshatra::Swati | Nakshatra::Dhanishta => "Tiger",
-            Nakshatra::Vishakha | Nakshatra::PurvaAshadha => "Deer",
-            Nakshatra::Moola | Nakshatra::PurvaBhadrapada => "Monkey",
-            Nakshatra::UttaraBhadrapada => "Lion",
-        }
-    }
-
-    fn are_yonis_compatible(&self, yoni1: &str, yoni2: &str) -> bool {
-        let compatible_pairs = vec![
-            ("Horse", "Horse"),
-            ("Elephant", "Elephant"),
-            ("Goat", "Goat"),
-            ("Snake", "Snake"),
-            ("Dog", "Dog"),
-            ("Cat", "Cat"),
-            ("Ram", "Ram"),
-            ("Mongoose", "Mongoose"),
-            ("Rat", "Rat"),
-            ("Buffalo", "Buffalo"),
-            ("Tiger", "Deer"),
-            ("Deer", "Tiger"),
-            ("Monkey", "Monkey"),
-            ("Lion", "Lion"),
-        ];
-
-        compatible_pairs.contains(&(yoni1, yoni2)) || compatible_pairs.contains(&(yoni2, yoni1))
-    }
-
-    fn calculate_graha_maitri(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let lord1 = self.get_house_lord(chart1.ascendant);
-        let lord2 = self.get_house_lord(chart2.ascendant);
-
-        if self.are_planets_friends(lord1, lord2) {
-            5
-        } else if self.are_planets_neutral(lord1, lord2) {
-            3
-        } else {
-            0
-        }
-    }
-
-    fn are_planets_friends(&self, planet1: CelestialBody, planet2: CelestialBody) -> bool {
-        let friendships = [
-            (
-                CelestialBody::Sun,
-                vec![
-                    CelestialBody::Moon,
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                ],
-            ),
-            (
-                CelestialBody::Moon,
-                vec![CelestialBody::Sun, CelestialBody::Mercury],
-            ),
-            (
-                CelestialBody::Mars,
-                vec![
-                    CelestialBody::Sun,
-                    CelestialBody::Moon,
-                    CelestialBody::Jupiter,
-                ],
-            ),
-            (
-                CelestialBody::Mercury,
-                vec![CelestialBody::Sun, CelestialBody::Venus],
-            ),
-            (
-                CelestialBody::Jupiter,
-                vec![
-                    CelestialBody::Sun,
-                    CelestialBody::Moon,
-                    CelestialBody::Mars,
-                ],
-            ),
-            (
-                CelestialBody::Venus,
-                vec![CelestialBody::Mercury, CelestialBody::Saturn],
-            ),
-            (
-                CelestialBody::Saturn,
-                vec![CelestialBody::Mercury, CelestialBody::Venus],
-            ),
-        ];
-
-        friendships.iter().any(|&(p, ref friends)| {
-            (p == planet1 && friends.contains(&planet2)) || (p == planet2 && friends.contains(&planet1))
-        })
-    }
-
-    fn are_planets_neutral(&self, planet1: CelestialBody, planet2: CelestialBody) -> bool {
-        let neutral_relations = [
-            (CelestialBody::Sun, vec![CelestialBody::Mercury]),
-            (
-                CelestialBody::Moon,
-                vec![
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Mars,
-                vec![
-                    CelestialBody::Mercury,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Mercury,
-                vec![
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Jupiter,
-                vec![
-                    CelestialBody::Mercury,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Venus,
-                vec![CelestialBody::Mars, CelestialBody::Jupiter],
-            ),
-            (
-                CelestialBody::Saturn,
-                vec![CelestialBody::Mars, CelestialBody::Jupiter],
-            ),
-        ];
-
-        neutral_relations.iter().any(|&(p, ref neutrals)| {
-            (p == planet1 && neutrals.contains(&planet2)) || (p == planet2 && neutrals.contains(&planet1))
-        })
-    }
-
-    fn check_gana_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let gana1 = self.get_gana(sign1);
-        let gana2 = self.get_gana(sign2);
-
-        match (gana1, gana2) {
-            ("Deva", "Deva") | ("Manushya", "Manushya") | ("Rakshasa", "Rakshasa") => true,
-            ("Deva", "Manushya") | ("Manushya", "Deva") => true,
-            _ => false,
-        }
-    }
-
-    fn get_gana(&self, sign: ZodiacSign) -> &'static str {
-        match sign {
-            ZodiacSign::Aries | ZodiacSign::Leo | ZodiacSign::Sagittarius => "Deva",
-            ZodiacSign::Taurus | ZodiacSign::Virgo | ZodiacSign::Capricorn => "Manushya",
-            ZodiacSign::Gemini | ZodiacSign::Libra | ZodiacSign::Aquarius => "Deva",
-            ZodiacSign::Cancer | ZodiacSign::Scorpio | ZodiacSign::Pisces => "Rakshasa",
-        }
-    }
-
-    fn check_bhakut_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let diff = (sign2 as i32 - sign1 as i32 + 12) % 12;
-        matches!(diff, 1 | 2 | 3 | 4 | 5 | 7 | 9 | 11)
-    }
-
-    fn check_nadi_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let nadi1 = self.get_nadi(sign1);
-        let nadi2 = self.get_nadi(sign2);
-        nadi1 != nadi2
-    }
-
-    fn get_nadi(&self, sign: ZodiacSign) -> &'static str {
-        match sign {
-            ZodiacSign::Aries | ZodiacSign::Cancer | ZodiacSign::Libra | ZodiacSign::Capricorn => {
-                "Aadi"
-            }
-            ZodiacSign::Taurus | ZodiacSign::Leo | ZodiacSign::Scorpio | ZodiacSign::Aquarius => {
-                "Madhya"
-            }
-            ZodiacSign::Gemini | ZodiacSign::Virgo | ZodiacSign::Sagittarius | ZodiacSign::Pisces => {
-                "Antya"
-            }
-        }
-    }
-
-    pub fn suggest_remedial_measures(&self, chart: &ChartInfo) -> Vec<RemedialMeasure> {
-        let mut remedies = Vec::new();
-
-        for planet in &chart.planets {
-            if self.is_planet_weak(planet) {
-                let remedy = self.get_remedy_for_planet(planet.planet);
-                remedies.push(remedy);
-            }
-        }
-
-        // Add general remedies
-        remedies.push(RemedialMeasure {
-            description: "Practice meditation daily for spiritual growth".to_string(),
-            gemstone: None,
-        });
+        // Add general remedies
+        remedies.push(RemedialMeasure {
+            description: "Practice meditation daily for spiritual growth".to_string(),
+            gemstone: None,
+        });
 
         remedies.push(RemedialMeasure {
             description: "Perform charity on Saturdays to mitigate malefic influences".to_string(),
@@ -1579,49 +970,13 @@ impl SwissEph {
         remedies
     }
 
-    fn is_planet_weak(&self, planet: &PlanetPosition) -> bool {
-        planet.retrograde || self.is_debilitated(planet) || self.is_combust(planet)
+    fn is_planet_weak(&self, chart: &ChartInfo, planet: &PlanetPosition) -> bool {
+        planet.retrograde || chart.is_combust(planet.planet, &self.conventions.combustion_orbs)
     }
 
-    fn is_combust(&self, planet: &PlanetPosition) -> bool {
-        if planet.planet == CelestialBody::Sun {
-            return false;
-        }
-
-        let sun_position = self
-            .calculate(
-                CoordinateSystem::Tropical,
-                date_to_julian_day(Utc::now()),
-                CelestialBody::Sun,
-                &[],
-            )
-            .unwrap_or(AstronomicalResult::CelestialBody(CelestialCoordinates {
-                longitude: 0.0,
-                latitude: 0.0,
-                distance: 0.0,
-                speed_longitude: 0.0,
-                speed_latitude: 0.0,
-                speed_distance: 0.0,
-            }));
-
-        if let AstronomicalResult::CelestialBody(sun_info) = sun_position {
-            let angle_diff = (planet.longitude - sun_info.longitude).abs();
-            match planet.planet {
-                CelestialBody::Moon => angle_diff <= 12.0,
-                CelestialBody::Mars => angle_diff <= 17.0,
-                CelestialBody::Mercury => angle_diff <= 14.0,
-                CelestialBody::Jupiter => angle_diff <= 11.0,
-                CelestialBody::Venus => angle_diff <= 10.0,
-                CelestialBody::Saturn => angle_diff <= 15.0,
-                _ => false,
-            }
-        } else {
-            false
-        }
-    }
-
-    fn get_remedy_for_planet(&self, planet: CelestialBody) -> RemedialMeasure {
-        match planet {
+    /// Traditional remedy for a weak graha; the outer planets and asteroids have none.
+    fn get_remedy_for_planet(&self, planet: CelestialBody) -> Option<RemedialMeasure> {
+        let remedy = match planet {
             CelestialBody::Sun => RemedialMeasure {
                 description: "Offer water to the Sun every morning".to_string(),
                 gemstone: Some("Ruby".to_string()),
@@ -1658,120 +1013,49 @@ impl SwissEph {
                 description: "Perform fire rituals on Tuesdays".to_string(),
                 gemstone: Some("Cat's Eye".to_string()),
             },
-        }
-    }
-
-    pub fn generate_interpretation(&self, report: &Report) -> String {
-        let mut interpretation = String::new();
-
-        interpretation.push_str(&format!(
-            "Birth Chart Interpretation for {}\n\n",
-            report.birth_info.date_time.format("%Y-%m-%d %H:%M:%S")
-        ));
-
-        interpretation.push_str("Planetary Positions:\n");
-        for planet in &report.charts[0].planets {
-            interpretation.push_str(&format!(
-                "{}: {}° in {:?} (House {:?})\n",
-                self.get_body_name(planet.planet),
-                planet.longitude,
-                planet.nakshatra.nakshatra,
-                planet.house
-            ));
-        }
-
-        interpretation.push_str("\nAscendant: ");
-        interpretation.push_str(&format!("{}\n", report.charts[0].ascendant));
-
-        interpretation.push_str("\nYogas:\n");
-        for yoga in &report.yogas {
-            interpretation.push_str(&format!(
-                "{:?} Yoga (Strength: {:.2})\n",
-                yoga.yoga, yoga.strength
-            ));
-        }
-
-        interpretation.push_str("\nDasha Periods:\n");
-        interpretation.push_str(&format!(
-            "Maha Dasha: {:?} ({} to {})\n",
-            report.dashas.maha_dasha,
-            report.dashas.maha_dasha_start.format("%Y-%m-%d"),
-            report.dashas.maha_dasha_end.format("%Y-%m-%d")
-        ));
-        interpretation.push_str(&format!(
-            "Antar Dasha: {:?} ({} to {})\n",
-            report.dashas.antar_dasha,
-            report.dashas.antar_dasha_start.format("%Y-%m-%d"),
-            report.dashas.antar_dasha_end.format("%Y-%m-%d")
-        ));
-        interpretation.push_str(&format!(
-            "Pratyantar Dasha: {:?} ({} to {})\n",
-            report.dashas.pratyantar_dasha,
-            report.dashas.pratyantar_dasha_start.format("%Y-%m-%d"),
-            report.dashas.pratyantar_dasha_end.format("%Y-%m-%d")
-        ));
-
-        interpretation.push_str("\nPlanetary Strengths:\n");
-        for (planet, strength) in &report.strengths {
-            interpretation.push_str(&format!(
-                "{}: Shadbala = {:.2}, Ashtakavarga = {}\n",
-                self.get_body_name(*planet),
-                strength.shad_bala,
-                strength.ashtaka_varga
-            ));
-        }
-
-        interpretation.push_str("\nRemedial Measures:\n");
-        for remedy in &report.remedial_measures {
-            interpretation.push_str(&format!("- {}\n", remedy.description));
-            if let Some(gemstone) = &remedy.gemstone {
-                interpretation.push_str(&format!("  Recommended Gemstone: {}\n", gemstone));
-            }
-        }
-
-        interpretation
+            _ => return None,
+        };
+        Some(remedy)
     }
 
-   pub fn calculate_divisional_charts(&self, chart: &ChartInfo) -> Vec<DivisionalChart> {
-        let mut divisional_charts = Vec::new();
-
-        // D1 chart (Rashi chart)
-        divisional_charts.push(self.calculate_D1(chart));
-
-        // D2 chart (Hora chart)
-        divisional_charts.push(self.calculate_D2(chart));
-
-        // Add more divisional charts as needed (D3, D4, D9, etc.)
-
-        divisional_charts
+    pub fn calculate_divisional_charts(&self, chart: &ChartInfo) -> Vec<DivisionalChart> {
+        vec![
+            // Rashi, hora, saptamsa, navamsa, dashamsa and dwadasamsa
+            self.calculate_d1(chart),
+            self.calculate_d2(chart),
+            self.calculate_d7(chart),
+            self.calculate_d9(chart),
+            self.calculate_d10(chart),
+            self.calculate_d12(chart),
+        ]
     }
 
-    fn calculate_D1(&self, chart: &ChartInfo) -> DivisionalChart {
+    fn calculate_d1(&self, chart: &ChartInfo) -> DivisionalChart {
         DivisionalChart {
             chart_type: ChartType::Rasi,
-            ascendant: chart.ascendant,
-            houses: chart
-                .houses
-                .iter()
-                .map(|house| house.sign)
-                .collect::<Vec<ZodiacSign>>()
-                .try_into()
-                .unwrap(),
+            ascendant: chart.ascendant.sign,
+            // Signs of the cusps, or whole signs from the lagna where a cusp is missing
+            houses: std::array::from_fn(|i| {
+                chart.houses.get(i).map_or(
+                    ZodiacSign::from_longitude((chart.ascendant.sign as usize + i) as f64 * 30.0),
+                    |house| house.sign,
+                )
+            }),
             planets: chart.planets.clone(),
         }
     }
 
-    fn calculate_D2(&self, chart: &ChartInfo) -> DivisionalChart {
+    fn calculate_d2(&self, chart: &ChartInfo) -> DivisionalChart {
         let mut d2_planets = Vec::new();
 
         for planet in &chart.planets {
-            let d2_longitude = (planet.longitude * 2.0) % 360.0;
+            let d2_longitude = (planet.longitude.degrees() * 2.0) % 360.0;
             let d2_sign = ZodiacSign::from_longitude(d2_longitude);
             let d2_house = House::from_index(((d2_longitude / 30.0).floor() as usize) + 1).unwrap();
 
             d2_planets.push(PlanetPosition {
                 planet: planet.planet,
-                longitude: d2_longitude,
+                longitude: Longitude::new(d2_longitude),
                 latitude: planet.latitude,
                 speed: planet.speed,
                 sign: d2_sign,
@@ -1783,25 +1067,128 @@ impl SwissEph {
 
         DivisionalChart {
             chart_type: ChartType::Hora,
-            ascendant: ZodiacSign::from_longitude((chart.ascendant as u8 as f64 * 2.0) % 360.0),
+            ascendant: ZodiacSign::from_longitude((chart.ascendant.degree * 2.0).rem_euclid(360.0)),
             houses: [ZodiacSign::Aries; 12], // Placeholder, actual calculation needed
             planets: d2_planets,
         }
     }
-}
 
-impl Drop for SwissEph {
-    fn drop(&mut self) {
-        unsafe {
-            swe_close();
+    pub fn calculate_chart(
+        &self,
+        birth_info: &BirthInfo,
+        house_system: HouseSystem,
+    ) -> Result<ChartInfo, AstrologyError> {
+        self.calculate_chart_with_options(
+            birth_info,
+            &ChartOptions {
+                house_system,
+                ..ChartOptions::default()
+            },
+        )
+    }
+
+    pub fn calculate_chart_with_options(
+        &self,
+        birth_info: &BirthInfo,
+        options: &ChartOptions,
+    ) -> Result<ChartInfo, AstrologyError> {
+        birth_info.location.validate()?;
+        let julian_day = date_to_julian_day(birth_info.date_time);
+        let (houses, house_system, warning) = self.calculate_houses_with_fallback(
+            CoordinateSystem::Sidereal,
+            julian_day,
+            birth_info.location.latitude,
+            birth_info.location.longitude,
+            options.house_system,
+        )?;
+        let mut planets = self.calculate_planet_positions(
+            CoordinateSystem::Sidereal,
+            julian_day,
+            ChartType::Rasi,
+            house_system,
+            birth_info,
+            None,
+        )?;
+        let optional_bodies = options.optional_bodies();
+        if !optional_bodies.is_empty() {
+            planets.extend(self.calculate_body_positions(
+                &optional_bodies,
+                CoordinateSystem::Sidereal,
+                julian_day,
+                ChartType::Rasi,
+                &houses.cusps,
+                None,
+            )?);
         }
+
+        // The first cusp is only the ascendant degree in quadrant systems; whole-sign and
+        // Sripati houses start elsewhere.
+        let ascendant = HouseCusp {
+            house: House::First,
+            sign: houses.angles.ascendant.sign,
+            degree: houses.angles.ascendant.degree,
+        };
+
+        Ok(ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system,
+            ascendant,
+            houses: houses.cusps,
+            planets,
+            warnings: warning.into_iter().collect(),
+        })
     }
 }
 
+// ---------------------------
+// ## Astronomical Result Enum
+// ---------------------------
+
+pub enum AstronomicalResult {
+    CelestialBody(CelestialCoordinates),
+    HousePosition(Longitude),
+}
 
+// ---------------------------
+// ## Utility Functions
+// ---------------------------
+
+/// Directory holding the bundled ephemeris files, writing them out first if no engine has yet.
+#[cfg(not(feature = "model-only"))]
+pub(crate) fn bundled_ephemeris_dir() -> std::io::Result<&'static Path> {
+    if let Some(dir) = EPHE_DIR.get() {
+        return Ok(dir);
+    }
+    let dir = extract_ephemeris_files()?;
+    Ok(EPHE_DIR.get_or_init(|| {
+        tracing::debug!(path = %dir.display(), "ephemeris files ready");
+        dir
+    }))
+}
 
+/// Writes the bundled ephemeris files to a per-version directory under the system temp
+/// directory, reusing files left there by an earlier run. Under Emscripten the temporary
+/// directory is in its in-memory filesystem, so in a browser the files never reach a disk.
+#[cfg(not(feature = "model-only"))]
+fn extract_ephemeris_files() -> std::io::Result<PathBuf> {
+    let dir = std::env::temp_dir().join(concat!("aztro_core-", env!("CARGO_PKG_VERSION")));
+    std::fs::create_dir_all(&dir)?;
+    for (name, bytes) in EPHE_FILES {
+        let path = dir.join(name);
+        let up_to_date =
+            std::fs::metadata(&path).is_ok_and(|meta| meta.len() == bytes.len() as u64);
+        if !up_to_date {
+            // Write beside the target and rename, so a concurrent reader never sees a
+            // partial file.
+            let partial = dir.join(format!("{}.{}", name, std::process::id()));
+            std::fs::write(&partial, bytes)?;
+            std::fs::rename(&partial, &path)?;
+        }
+    }
+    Ok(dir)
+}
 
-// Utility Functions
+#[cfg(not(feature = "model-only"))]
 pub fn date_to_julian_day(date_time: DateTime<Utc>) -> JulianDay {
     let year = date_time.year();
     let month = date_time.month();
@@ -1810,8 +1197,9 @@ pub fn date_to_julian_day(date_time: DateTime<Utc>) -> JulianDay {
     let minute = date_time.minute();
     let second = date_time.second() as f64 + (date_time.nanosecond() as f64 / 1_000_000_000.0);
 
-    let mut tjd_ut: c_double = 0.0;
     let mut dret: [c_double; 2] = [0.0; 2];
+    // chrono counts every date in the Gregorian calendar, back past 1582; dates written
+    // in the Julian calendar are converted on the way in, by `CalendarSystem`
     let gregflag = SE_GREG_CAL;
     unsafe {
         swe_utc_to_jd(
@@ -1825,11 +1213,12 @@ pub fn date_to_julian_day(date_time: DateTime<Utc>) -> JulianDay {
             &mut dret[0],
             &mut dret[1],
         );
-        tjd_ut = dret[1]; // Use UT
     }
-    tjd_ut
+    // The second day is in UT, the first in TT
+    dret[1]
 }
 
+#[cfg(not(feature = "model-only"))]
 pub fn julian_day_to_date(jd: JulianDay) -> DateTime<Utc> {
     let mut year: c_int = 0;
     let mut month: c_int = 0;
@@ -1853,14 +1242,44 @@ pub fn julian_day_to_date(jd: JulianDay) -> DateTime<Utc> {
         );
     }
 
-    Utc.ymd(year as i32, month as u32, day as u32).and_hms_micro(
-        hour as u32,
-        minute as u32,
-        second.floor() as u32,
-        ((second.fract() * 1_000_000.0) as u32),
-    )
+    // The seconds go on as a duration, so that a leap second's 60 rolls into the next minute
+    let start_of_minute = Utc
+        .with_ymd_and_hms(
+            year,
+            month as u32,
+            day as u32,
+            hour as u32,
+            minute as u32,
+            0,
+        )
+        .single()
+        .expect("Swiss Ephemeris gives a valid UTC date");
+    start_of_minute + ChronoDuration::microseconds((second * 1_000_000.0) as i64)
+}
+
+/// Value of `ayanamsa` at `julian_day`. Moshier's ephemeris is enough for the nutation and
+/// aberration of the ayanamsas fixed by a star and needs no files, so this works before
+/// any engine is built.
+#[cfg(not(feature = "model-only"))]
+pub fn ayanamsa_at(ayanamsa: Ayanamsa, julian_day: JulianDay) -> AyanamsaInfo {
+    set_sidereal_mode(ayanamsa);
+    let mut ayanamsa_value: c_double = 0.0;
+    let mut error: [c_char; 256] = [0; 256];
+    unsafe {
+        swe_get_ayanamsa_ex_ut(
+            julian_day,
+            SEFLG_MOSEPH,
+            &mut ayanamsa_value,
+            error.as_mut_ptr(),
+        );
+    }
+    AyanamsaInfo {
+        ayanamsa_name: format!("{:?}", ayanamsa),
+        ayanamsa_value,
+    }
 }
 
+#[cfg(not(feature = "model-only"))]
 pub fn calculate_ayanamsa(julian_day: JulianDay) -> AyanamsaInfo {
     let ayanamsa_value = unsafe { swe_get_ayanamsa_ut(julian_day) };
     let ayanamsa_name = "Lahiri".to_string(); // Assuming Lahiri ayanamsa
@@ -1869,3 +1288,31 @@ pub fn calculate_ayanamsa(julian_day: JulianDay) -> AyanamsaInfo {
         ayanamsa_value,
     }
 }
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidereal_longitudes_are_tropical_ones_less_the_ayanamsa() {
+        let ephemeris = SwissEph::new().unwrap();
+        let julian_day = 2451545.0;
+        let sun = |coord_system| match ephemeris
+            .calculate(coord_system, julian_day, CelestialBody::Sun, &[], None)
+            .unwrap()
+        {
+            AstronomicalResult::CelestialBody(coordinates) => coordinates.longitude,
+            AstronomicalResult::HousePosition(_) => unreachable!(),
+        };
+        let tropical = sun(CoordinateSystem::Tropical);
+        let sidereal = sun(CoordinateSystem::Sidereal);
+
+        // Lahiri stood a little under 24° at J2000. The mean ayanamsa leaves out nutation,
+        // a few thousandths of a degree.
+        let ayanamsa = ephemeris.calculate_ayanamsa(julian_day);
+        assert!((23.8..23.9).contains(&ayanamsa), "{}", ayanamsa);
+        let difference = (tropical - sidereal).rem_euclid(360.0);
+        assert!((difference - ayanamsa).abs() < 0.01, "{}", difference);
+    }
+}
@@ -0,0 +1,319 @@
+use super::*;
+
+/// Where planetary positions come from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EphemerisMode {
+    /// Swiss Ephemeris files: the bundled ones, written to a temporary directory on first
+    /// use, or a full install given with [`SwissEphBuilder::ephe_path`]. Accurate to a
+    /// fraction of an arc second.
+    #[default]
+    SwissEph,
+    /// Moshier's analytical theory, built into the library. Needs no data files, so it
+    /// works on read-only filesystems, at a cost of about one arc second for the planets
+    /// and a few for the Moon.
+    Moshier,
+}
+
+#[cfg(not(feature = "model-only"))]
+pub const SEFLG_JPLEPH: c_int = 1;
+#[cfg(not(feature = "model-only"))]
+pub const SEFLG_MOSEPH: c_int = 4;
+
+/// The files an engine reads, set on the calling thread before each calculation.
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum EphemerisFiles {
+    /// Moshier mode reads nothing.
+    None,
+    Bundled(&'static Path),
+    /// A directory of the caller's. Dates its files do not cover are an error rather than
+    /// a silent fall back to Moshier.
+    External(PathBuf),
+}
+
+#[cfg(not(feature = "model-only"))]
+impl EphemerisFiles {
+    fn dir(&self) -> Option<&Path> {
+        match self {
+            EphemerisFiles::None => None,
+            EphemerisFiles::Bundled(dir) => Some(dir),
+            EphemerisFiles::External(dir) => Some(dir),
+        }
+    }
+
+    /// Why planet positions in `year` may be less accurate than usual: a date past the
+    /// files, where Swiss Ephemeris falls back to Moshier, or past Moshier's own range.
+    /// None when the year is covered.
+    pub(crate) fn coverage_gap(&self, year: i32) -> Option<String> {
+        match self.dir() {
+            None if !(MOSHIER_YEARS.0..=MOSHIER_YEARS.1).contains(&year) => Some(format!(
+                "{} is outside the years {} to {} of Moshier's theory",
+                year, MOSHIER_YEARS.0, MOSHIER_YEARS.1
+            )),
+            None => None,
+            Some(dir) => {
+                let covered = coverage(dir, "sepl")
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&year));
+                (!covered).then(|| {
+                    format!(
+                        "No planet file covers {}, which the files cover {}; positions come \
+                         from Moshier's theory",
+                        year,
+                        describe_coverage(dir, "sepl")
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Years Moshier's theory holds for, numbered astronomically.
+#[cfg(not(feature = "model-only"))]
+const MOSHIER_YEARS: (i32, i32) = (-3000, 3000);
+
+/// Directory last handed to Swiss Ephemeris. Changing it closes every open file, so it is
+/// only set when an engine needs a different one.
+#[cfg(not(feature = "model-only"))]
+static EPHE_PATH: LibrarySetting<Option<PathBuf>> = LibrarySetting {
+    per_thread: &EPHE_PATH_ON_THREAD,
+    per_process: std::sync::Mutex::new(None),
+};
+
+#[cfg(not(feature = "model-only"))]
+thread_local! {
+    static EPHE_PATH_ON_THREAD: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Years covered by an ephemeris file whose name starts with `prefix`. `sepl_18.se1` holds
+/// the planets from 1800 to 2399 and `semom06.se1` the Moon over the 600 years before 1 AD,
+/// numbered astronomically (1 BC is year 0).
+#[cfg(not(feature = "model-only"))]
+fn file_years(name: &str, prefix: &str) -> Option<(i32, i32)> {
+    let stem = name.strip_suffix(".se1")?.strip_prefix(prefix)?;
+    let (sign, century) = match stem.split_at_checked(1)? {
+        ("_", century) => (1, century),
+        ("m", century) => (-1, century),
+        _ => return None,
+    };
+    let start = sign * century.parse::<i32>().ok()? * 100;
+    Some((start, start + 599))
+}
+
+/// Year ranges covered by the files in `dir` starting with `prefix`, merged where they
+/// touch.
+#[cfg(not(feature = "model-only"))]
+fn coverage(dir: &Path, prefix: &str) -> Vec<(i32, i32)> {
+    let mut ranges: Vec<(i32, i32)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| file_years(&entry.file_name().to_string_lossy(), prefix))
+        .collect();
+    ranges.sort();
+
+    let mut merged: Vec<(i32, i32)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(not(feature = "model-only"))]
+fn describe_coverage(dir: &Path, prefix: &str) -> String {
+    let ranges = coverage(dir, prefix);
+    if ranges.is_empty() {
+        return "no years".to_string();
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| format!("{} to {}", start, end))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(not(feature = "model-only"))]
+impl EphemerisMode {
+    /// The Swiss Ephemeris flag that selects this source.
+    pub(crate) fn flag(&self) -> c_int {
+        match self {
+            EphemerisMode::SwissEph => SEFLG_SWIEPH,
+            EphemerisMode::Moshier => SEFLG_MOSEPH,
+        }
+    }
+}
+
+/// Builds a [`SwissEph`] with settings other than the defaults.
+///
+/// ```no_run
+/// use aztro_core::*;
+///
+/// let ephemeris = SwissEph::builder()
+///     .ephemeris_mode(EphemerisMode::Moshier)
+///     .tradition(Tradition::Kerala)
+///     .build()?;
+/// # Ok::<(), aztro_core::AstrologyError>(())
+/// ```
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Clone, Default)]
+pub struct SwissEphBuilder {
+    mode: EphemerisMode,
+    ephe_path: Option<PathBuf>,
+    conventions: Conventions,
+    friendships: FriendshipMatrix,
+    position_cache: PositionCacheSettings,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEphBuilder {
+    pub fn ephemeris_mode(mut self, mode: EphemerisMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Reads Swiss Ephemeris files from `path` instead of the bundled ones. Swiss
+    /// Ephemeris picks the file for each date itself, so a directory holding the planet
+    /// (`sepl*`), Moon (`semo*`) and asteroid (`seas*`) files of several centuries covers
+    /// all of them. Every geocentric position needs the Moon file of its date as well as
+    /// the planet file. Ignored in [`EphemerisMode::Moshier`].
+    pub fn ephe_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ephe_path = Some(path.into());
+        self
+    }
+
+    pub fn tradition(self, tradition: Tradition) -> Self {
+        self.conventions(tradition.conventions())
+    }
+
+    pub fn conventions(mut self, conventions: Conventions) -> Self {
+        self.conventions = conventions;
+        self
+    }
+
+    pub fn friendships(mut self, friendships: FriendshipMatrix) -> Self {
+        self.friendships = friendships;
+        self
+    }
+
+    /// Caches positions as set out in `settings` rather than the default 32768 positions a
+    /// millionth of a day apart. A capacity of zero turns the cache off; a precision that
+    /// is not a positive number fails the build.
+    pub fn position_cache(mut self, settings: PositionCacheSettings) -> Self {
+        self.position_cache = settings;
+        self
+    }
+
+    /// Fails when the bundled files cannot be written out, when an `ephe_path` lacks
+    /// planet or Moon files, or when the position cache precision is not positive.
+    pub fn build(self) -> Result<SwissEph, AstrologyError> {
+        let precision = self.position_cache.precision;
+        if !(precision > 0.0 && precision.is_finite()) {
+            return Err(AstrologyError::InvalidInput(format!(
+                "Position cache precision must be a positive number of days, not {}",
+                precision
+            )));
+        }
+        let files = match (self.mode, self.ephe_path) {
+            (EphemerisMode::Moshier, _) => EphemerisFiles::None,
+            (EphemerisMode::SwissEph, None) => EphemerisFiles::Bundled(
+                bundled_ephemeris_dir()
+                    .map_err(|error| AstrologyError::EphemerisFiles(error.to_string()))?,
+            ),
+            (EphemerisMode::SwissEph, Some(dir)) => {
+                for (prefix, kind) in [("sepl", "planet"), ("semo", "Moon")] {
+                    if coverage(&dir, prefix).is_empty() {
+                        return Err(AstrologyError::EphemerisFiles(format!(
+                            "No Swiss Ephemeris {} files ({}*.se1) in {}",
+                            kind,
+                            prefix,
+                            dir.display()
+                        )));
+                    }
+                }
+                EphemerisFiles::External(dir)
+            }
+        };
+        Ok(SwissEph {
+            friendships: self.friendships,
+            conventions: self.conventions,
+            files,
+            position_cache: PositionCache::new(self.position_cache),
+        })
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    pub fn builder() -> SwissEphBuilder {
+        SwissEphBuilder::default()
+    }
+
+    /// An engine reading Swiss Ephemeris files from `path`, for dates beyond the bundled
+    /// 1800 to 2399. See [`SwissEphBuilder::ephe_path`].
+    pub fn with_ephe_path(path: impl Into<PathBuf>) -> Result<Self, AstrologyError> {
+        Self::builder().ephe_path(path).build()
+    }
+
+    pub fn ephemeris_mode(&self) -> EphemerisMode {
+        match self.files {
+            EphemerisFiles::None => EphemerisMode::Moshier,
+            _ => EphemerisMode::SwissEph,
+        }
+    }
+
+    /// `iflag` with its ephemeris bits replaced by the engine's own source. Swiss
+    /// Ephemeris prefers its files over Moshier when both bits are set.
+    ///
+    /// Also points Swiss Ephemeris at the engine's files, so it is called right before
+    /// each call into the library.
+    pub(crate) fn ephemeris_flags(&self, iflag: c_int) -> c_int {
+        if let Some(dir) = self.files.dir() {
+            EPHE_PATH.with(|current| {
+                if current.as_deref() != Some(dir) {
+                    // A path with a NUL byte cannot be passed on; the library then falls
+                    // back to its default directory.
+                    let c_path =
+                        CString::new(dir.to_string_lossy().into_owned()).unwrap_or_default();
+                    unsafe {
+                        swe_set_ephe_path(c_path.as_ptr());
+                    }
+                    *current = Some(dir.to_path_buf());
+                }
+            });
+        }
+        iflag & !(SEFLG_JPLEPH | SEFLG_SWIEPH | SEFLG_MOSEPH) | self.ephemeris_mode().flag()
+    }
+
+    /// Checks what `swe_calc_ut` reported for `body`. With a directory of the caller's, a
+    /// date its files do not cover is an error instead of a quiet Moshier position. A
+    /// missing planet file clears the returned Swiss Ephemeris flag; a missing Moon file
+    /// only leaves a warning.
+    pub(crate) fn check_files_used(
+        &self,
+        julian_day: JulianDay,
+        body: CelestialBody,
+        retflag: c_int,
+        warning: &CStr,
+    ) -> Result<(), AstrologyError> {
+        let EphemerisFiles::External(dir) = &self.files else {
+            return Ok(());
+        };
+        if retflag & SEFLG_SWIEPH != 0 && !warning.to_string_lossy().contains("Moshier") {
+            return Ok(());
+        }
+
+        Err(AstrologyError::DateOutOfRange(format!(
+            "No ephemeris file in {} covers {:?} at {}; its planet files cover {} and its \
+                 Moon files {}",
+            dir.display(),
+            body,
+            julian_day_to_date(julian_day).format("%Y-%m-%d"),
+            describe_coverage(dir, "sepl"),
+            describe_coverage(dir, "semo")
+        )))
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+/// One body's place in an ephemeris row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EphemerisEntry {
+    pub body: CelestialBody,
+    /// Sidereal longitude.
+    pub longitude: f64,
+    pub latitude: f64,
+    /// Degrees per day in longitude; negative while retrograde.
+    pub speed: f64,
+    pub sign: ZodiacSign,
+    pub nakshatra: NakshatraInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EphemerisRow {
+    pub date_time: DateTime<Utc>,
+    /// One entry per requested body, in the order requested.
+    pub entries: Vec<EphemerisEntry>,
+}
+
+/// Rows of an ephemeris computed one step at a time, from [`SwissEph::ephemeris_table`].
+#[cfg(not(feature = "model-only"))]
+pub struct EphemerisTable<'a> {
+    ephemeris: &'a SwissEph,
+    bodies: Vec<CelestialBody>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: ChronoDuration,
+    index: i32,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl EphemerisTable<'_> {
+    fn row(&self, date_time: DateTime<Utc>) -> Result<EphemerisRow, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let iflag = SEFLG_SIDEREAL | SEFLG_SPEED;
+        // Another engine may have switched the mode since the last row.
+        self.ephemeris.set_sidereal_mode();
+
+        let mut rahu = None;
+        let mut entries = Vec::with_capacity(self.bodies.len());
+        for &body in &self.bodies {
+            let coordinates = match body {
+                CelestialBody::Ketu | CelestialBody::Rahu => {
+                    let rahu = match rahu {
+                        Some(rahu) => rahu,
+                        None => *rahu.insert(self.ephemeris.calc_ut(
+                            julian_day,
+                            CelestialBody::Rahu,
+                            iflag,
+                        )?),
+                    };
+                    if body == CelestialBody::Ketu {
+                        CelestialCoordinates {
+                            longitude: (rahu.longitude + 180.0) % 360.0,
+                            latitude: -rahu.latitude,
+                            speed_latitude: -rahu.speed_latitude,
+                            ..rahu
+                        }
+                    } else {
+                        rahu
+                    }
+                }
+                _ => self.ephemeris.calc_ut(julian_day, body, iflag)?,
+            };
+
+            entries.push(EphemerisEntry {
+                body,
+                longitude: coordinates.longitude,
+                latitude: coordinates.latitude,
+                speed: coordinates.speed_longitude,
+                sign: ZodiacSign::from_longitude(coordinates.longitude),
+                nakshatra: NakshatraInfo::from_longitude(coordinates.longitude),
+            });
+        }
+
+        Ok(EphemerisRow { date_time, entries })
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl Iterator for EphemerisTable<'_> {
+    type Item = Result<EphemerisRow, AstrologyError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step <= ChronoDuration::zero() {
+            return None;
+        }
+        let date_time = self.start + self.step * self.index;
+        if date_time > self.end {
+            return None;
+        }
+        self.index += 1;
+        Some(self.row(date_time))
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Sidereal positions of `bodies` from `start` to `end` inclusive, every `step`. Rows
+    /// are computed as the iterator is advanced, so long tables need not be held in memory.
+    /// A step that is not positive gives no rows.
+    pub fn ephemeris_table(
+        &self,
+        bodies: &[CelestialBody],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: ChronoDuration,
+    ) -> EphemerisTable<'_> {
+        EphemerisTable {
+            ephemeris: self,
+            bodies: bodies.to_vec(),
+            start,
+            end,
+            step,
+            index: 0,
+        }
+    }
+}
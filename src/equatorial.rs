@@ -0,0 +1,180 @@
+use super::*;
+
+/// Declination beyond which a planet is out of bounds: 23°27', the greatest the Sun reaches.
+pub const OUT_OF_BOUNDS_DECLINATION: f64 = 23.45;
+
+/// Degrees two declinations may differ by and still be parallel.
+pub const DECLINATION_ORB: f64 = 1.0;
+
+/// Where a planet stands on the celestial equator's grid rather than the ecliptic's. The
+/// same in both zodiacs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EquatorialPosition {
+    pub planet: CelestialBody,
+    /// Degrees east along the equator from the March equinox, 0-360.
+    pub right_ascension: f64,
+    /// Degrees north of the equator, negative to the south.
+    pub declination: f64,
+    /// Change in declination in degrees a day.
+    pub declination_speed: f64,
+    /// Declination beyond [`OUT_OF_BOUNDS_DECLINATION`], north or south.
+    pub out_of_bounds: bool,
+}
+
+impl EquatorialPosition {
+    pub fn new(
+        planet: CelestialBody,
+        right_ascension: f64,
+        declination: f64,
+        declination_speed: f64,
+    ) -> Self {
+        EquatorialPosition {
+            planet,
+            right_ascension: right_ascension.rem_euclid(360.0),
+            declination,
+            declination_speed,
+            out_of_bounds: declination.abs() > OUT_OF_BOUNDS_DECLINATION,
+        }
+    }
+}
+
+/// An aspect of declination.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DeclinationAspect {
+    /// The same declination on the same side of the equator, read like a conjunction.
+    Parallel,
+    /// The same declination on opposite sides, read like an opposition.
+    ContraParallel,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DeclinationAspectInfo {
+    pub aspect: DeclinationAspect,
+    pub planet1: CelestialBody,
+    pub planet2: CelestialBody,
+    /// Degrees from exact.
+    pub orb: f64,
+}
+
+/// Parallels and contra-parallels between every pair of `positions` within `orb`, in the
+/// order the pairs are listed.
+pub fn declination_aspects(
+    positions: &[EquatorialPosition],
+    orb: f64,
+) -> Vec<DeclinationAspectInfo> {
+    let mut aspects = Vec::new();
+    for (i, first) in positions.iter().enumerate() {
+        for second in positions.iter().skip(i + 1) {
+            let parallel = (first.declination - second.declination).abs();
+            let contra_parallel = (first.declination + second.declination).abs();
+            let (aspect, distance) = if parallel <= contra_parallel {
+                (DeclinationAspect::Parallel, parallel)
+            } else {
+                (DeclinationAspect::ContraParallel, contra_parallel)
+            };
+            if distance <= orb {
+                aspects.push(DeclinationAspectInfo {
+                    aspect,
+                    planet1: first.planet,
+                    planet2: second.planet,
+                    orb: distance,
+                });
+            }
+        }
+    }
+    aspects
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Right ascension and declination of each of `bodies` at `date_time`.
+    pub fn calculate_equatorial(
+        &self,
+        date_time: DateTime<Utc>,
+        bodies: &[CelestialBody],
+    ) -> Result<Vec<EquatorialPosition>, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let flags = [CalculationFlag::Speed, CalculationFlag::Equatorial];
+        bodies
+            .iter()
+            .map(|&body| {
+                // Right ascension counts from the equinox whatever the zodiac
+                match self.calculate(CoordinateSystem::Tropical, julian_day, body, &flags, None)? {
+                    AstronomicalResult::CelestialBody(coordinates) => Ok(EquatorialPosition::new(
+                        body,
+                        coordinates.longitude,
+                        coordinates.latitude,
+                        coordinates.speed_latitude,
+                    )),
+                    _ => Err(AstrologyError::Ephemeris {
+                        code: -1,
+                        message: format!("Failed to calculate {:?} on the equator", body),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Equatorial positions of the chart's planets at the birth, in the chart's order.
+    pub fn calculate_chart_equatorial(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+    ) -> Result<Vec<EquatorialPosition>, AstrologyError> {
+        let bodies: Vec<CelestialBody> = chart.planets.iter().map(|planet| planet.planet).collect();
+        self.calculate_equatorial(birth_info.date_time, &bodies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declinations_are_parallel_or_contra_parallel() {
+        let positions = [
+            EquatorialPosition::new(CelestialBody::Sun, 80.0, 23.2, 0.0),
+            EquatorialPosition::new(CelestialBody::Moon, 250.0, -23.5, 0.1),
+            EquatorialPosition::new(CelestialBody::Venus, 100.0, 22.6, 0.0),
+            EquatorialPosition::new(CelestialBody::Mars, 10.0, 4.0, 0.0),
+        ];
+        assert!(!positions[0].out_of_bounds);
+        assert!(positions[1].out_of_bounds);
+
+        let aspects = declination_aspects(&positions, DECLINATION_ORB);
+        assert_eq!(aspects.len(), 3);
+        assert_eq!(aspects[0].aspect, DeclinationAspect::ContraParallel);
+        assert_eq!(
+            (aspects[0].planet1, aspects[0].planet2),
+            (CelestialBody::Sun, CelestialBody::Moon)
+        );
+        assert_eq!(aspects[1].aspect, DeclinationAspect::Parallel);
+        assert!((aspects[1].orb - 0.6).abs() < 1e-9);
+        assert_eq!(aspects[2].planet1, CelestialBody::Moon);
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn the_sun_is_on_the_equator_at_the_equinox() {
+        let ephemeris = SwissEph::new().unwrap();
+        // The March equinox of 2024 fell at 03:06 UT on the 20th, the June solstice at
+        // 20:51 UT on the 20th
+        let equinox = DateTime::from_timestamp(1_710_903_960, 0).unwrap();
+        let solstice = DateTime::from_timestamp(1_718_916_660, 0).unwrap();
+        let sun = &ephemeris
+            .calculate_equatorial(equinox, &[CelestialBody::Sun])
+            .unwrap()[0];
+        assert!(sun.declination.abs() < 0.01, "{}", sun.declination);
+        assert!(sun.declination_speed > 0.0);
+        let bodies = [CelestialBody::Sun, CelestialBody::Rahu, CelestialBody::Ketu];
+        let positions = ephemeris.calculate_equatorial(solstice, &bodies).unwrap();
+        assert!((positions[0].right_ascension - 90.0).abs() < 0.01);
+        assert!((positions[0].declination - 23.44).abs() < 0.01);
+        assert!(!positions[0].out_of_bounds);
+        // Ketu stands opposite Rahu on the sky
+        assert!((positions[1].declination + positions[2].declination).abs() < 1e-9);
+    }
+}
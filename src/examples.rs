@@ -0,0 +1,91 @@
+//! Worked scenarios that run the whole pipeline on fixed birth data. Each function is what
+//! an application would write for one screen, and its doc test checks the result, so the
+//! set doubles as an end-to-end test of reports, dashas, panchanga and matching.
+
+use crate::*;
+
+/// Born 18 June 1991, 07:10 IST, in Kozhikode.
+pub fn native() -> Result<BirthInfo, BirthInfoError> {
+    Ok(Location::kozhikode()
+        .born_at(1991, 6, 18, 7, 10, 0)?
+        .for_person("Native", Gender::Male))
+}
+
+/// Born 4 February 1993, 21:25 IST, in Thrissur.
+pub fn partner() -> Result<BirthInfo, BirthInfoError> {
+    Ok(Location::thrissur()
+        .born_at(1993, 2, 4, 21, 25, 0)?
+        .for_person("Partner", Gender::Female))
+}
+
+/// The full natal report for [`native`]: charts, dashas, yogas, strengths and the rest.
+///
+/// ```
+/// use aztro_core::{examples, CelestialBody, Nakshatra, ZodiacSign};
+///
+/// let report = examples::natal_report()?;
+/// let chart = &report.charts[0];
+/// let moon = chart
+///     .planets
+///     .iter()
+///     .find(|p| p.planet == CelestialBody::Moon)
+///     .unwrap();
+/// assert_eq!(chart.ascendant.sign, ZodiacSign::Gemini);
+/// assert_eq!(moon.nakshatra.nakshatra, Nakshatra::PurvaPhalguni);
+/// assert_eq!(report.dashas.maha_dasha_lord.planet, report.dashas.maha_dasha.lord());
+/// # Ok::<(), aztro_core::AstrologyError>(())
+/// ```
+pub fn natal_report() -> Result<Report, AstrologyError> {
+    Report::calculate("Native", native()?, Gender::Male)
+}
+
+/// Ashtakoota points between [`native`] and [`partner`], and the quick Tara, Yoni and Gana
+/// check from their birth stars alone.
+///
+/// ```
+/// use aztro_core::examples;
+///
+/// let (compatibility, quick) = examples::matchmaking()?;
+/// assert_eq!(compatibility.kuta_points, 29);
+/// assert_eq!(compatibility.kutas.nadi.score, 8.0);
+/// assert!(!compatibility.mangal_dosha1.is_present());
+/// assert_eq!(quick.total().score, 8.5);
+/// assert_eq!(quick.total().max, 13.0);
+/// # Ok::<(), aztro_core::AstrologyError>(())
+/// ```
+pub fn matchmaking() -> Result<(CompatibilityInfo, QuickMatch), AstrologyError> {
+    let ephemeris = SwissEph::new()?;
+    let (native, partner) = (native()?, partner()?);
+    let compatibility = ephemeris.calculate_marriage_match(&partner, &native)?;
+    let quick = quick_match(
+        ephemeris
+            .moon_only_profile(native.date_time)?
+            .nakshatra
+            .nakshatra,
+        ephemeris
+            .moon_only_profile(partner.date_time)?
+            .nakshatra
+            .nakshatra,
+    );
+    Ok((compatibility, quick))
+}
+
+/// Panchanga at 06:30 IST on 16 October 2024 in Kozhikode.
+///
+/// ```
+/// use aztro_core::{examples, Nakshatra, Paksha, Tithi, Vara};
+///
+/// let panchanga = examples::daily_panchanga()?;
+/// assert_eq!(panchanga.vara.value, Vara::Budhavara);
+/// assert_eq!(panchanga.paksha, Paksha::Shukla);
+/// assert_eq!(panchanga.tithi.value, Tithi::Chaturdashi);
+/// assert_eq!(panchanga.nakshatra.value, Nakshatra::UttaraBhadrapada);
+/// assert!(panchanga.tithi.start < panchanga.tithi.end);
+/// # Ok::<(), aztro_core::AstrologyError>(())
+/// ```
+pub fn daily_panchanga() -> Result<Panchanga, AstrologyError> {
+    let ephemeris = SwissEph::new()?;
+    let location = Location::kozhikode();
+    let morning = location.clone().born_at(2024, 10, 16, 6, 30, 0)?;
+    ephemeris.calculate_panchanga(morning.date_time, &location)
+}
@@ -0,0 +1,305 @@
+use super::*;
+
+/// The fixed stars bundled with the crate: the junction star (yogatara) of each nakshatra,
+/// by which the texts fix its place, and the royal stars Sirius and Fomalhaut.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum FixedStar {
+    Sheratan,
+    Bharani,
+    Alcyone,
+    Aldebaran,
+    Meissa,
+    Betelgeuse,
+    Sirius,
+    Pollux,
+    AsellusAustralis,
+    Ashlesha,
+    Regulus,
+    Zosma,
+    Denebola,
+    Algorab,
+    Spica,
+    Arcturus,
+    Zubenelgenubi,
+    Dschubba,
+    Antares,
+    Shaula,
+    KausMedia,
+    Nunki,
+    Altair,
+    Rotanev,
+    Hydor,
+    Fomalhaut,
+    Markab,
+    Algenib,
+    Revati,
+}
+
+impl FixedStar {
+    /// Every bundled star, in order of right ascension from Aries.
+    pub fn all() -> [FixedStar; 29] {
+        use FixedStar::*;
+        [
+            Sheratan,
+            Bharani,
+            Alcyone,
+            Aldebaran,
+            Meissa,
+            Betelgeuse,
+            Sirius,
+            Pollux,
+            AsellusAustralis,
+            Ashlesha,
+            Regulus,
+            Zosma,
+            Denebola,
+            Algorab,
+            Spica,
+            Arcturus,
+            Zubenelgenubi,
+            Dschubba,
+            Antares,
+            Shaula,
+            KausMedia,
+            Nunki,
+            Altair,
+            Rotanev,
+            Hydor,
+            Fomalhaut,
+            Markab,
+            Algenib,
+            Revati,
+        ]
+    }
+
+    /// Bayer or Flamsteed designation, by which Swiss Ephemeris star files index the star,
+    /// so a full catalogue given with [`SwissEphBuilder::ephe_path`] finds it too.
+    pub fn designation(&self) -> &'static str {
+        match self {
+            FixedStar::Sheratan => "beAri",
+            FixedStar::Bharani => "41Ari",
+            FixedStar::Alcyone => "etTau",
+            FixedStar::Aldebaran => "alTau",
+            FixedStar::Meissa => "laOri",
+            FixedStar::Betelgeuse => "alOri",
+            FixedStar::Sirius => "alCMa",
+            FixedStar::Pollux => "beGem",
+            FixedStar::AsellusAustralis => "deCnc",
+            FixedStar::Ashlesha => "epHya",
+            FixedStar::Regulus => "alLeo",
+            FixedStar::Zosma => "deLeo",
+            FixedStar::Denebola => "beLeo",
+            FixedStar::Algorab => "deCrv",
+            FixedStar::Spica => "alVir",
+            FixedStar::Arcturus => "alBoo",
+            FixedStar::Zubenelgenubi => "al-2Lib",
+            FixedStar::Dschubba => "deSco",
+            FixedStar::Antares => "alSco",
+            FixedStar::Shaula => "laSco",
+            FixedStar::KausMedia => "deSgr",
+            FixedStar::Nunki => "siSgr",
+            FixedStar::Altair => "alAql",
+            FixedStar::Rotanev => "beDel",
+            FixedStar::Hydor => "laAqr",
+            FixedStar::Fomalhaut => "alPsA",
+            FixedStar::Markab => "alPeg",
+            FixedStar::Algenib => "gaPeg",
+            FixedStar::Revati => "zePsc",
+        }
+    }
+
+    /// The junction star of `nakshatra`.
+    pub fn yogatara(nakshatra: Nakshatra) -> FixedStar {
+        match nakshatra {
+            Nakshatra::Ashwini => FixedStar::Sheratan,
+            Nakshatra::Bharani => FixedStar::Bharani,
+            Nakshatra::Krittika => FixedStar::Alcyone,
+            Nakshatra::Rohini => FixedStar::Aldebaran,
+            Nakshatra::Mrigashira => FixedStar::Meissa,
+            Nakshatra::Ardra => FixedStar::Betelgeuse,
+            Nakshatra::Punarvasu => FixedStar::Pollux,
+            Nakshatra::Pushya => FixedStar::AsellusAustralis,
+            Nakshatra::Ashlesha => FixedStar::Ashlesha,
+            Nakshatra::Magha => FixedStar::Regulus,
+            Nakshatra::PurvaPhalguni => FixedStar::Zosma,
+            Nakshatra::UttaraPhalguni => FixedStar::Denebola,
+            Nakshatra::Hasta => FixedStar::Algorab,
+            Nakshatra::Chitra => FixedStar::Spica,
+            Nakshatra::Swati => FixedStar::Arcturus,
+            Nakshatra::Vishakha => FixedStar::Zubenelgenubi,
+            Nakshatra::Anuradha => FixedStar::Dschubba,
+            Nakshatra::Jyeshtha => FixedStar::Antares,
+            Nakshatra::Moola => FixedStar::Shaula,
+            Nakshatra::PurvaAshadha => FixedStar::KausMedia,
+            Nakshatra::UttaraAshadha => FixedStar::Nunki,
+            Nakshatra::Shravana => FixedStar::Altair,
+            Nakshatra::Dhanishta => FixedStar::Rotanev,
+            Nakshatra::Shatabhisha => FixedStar::Hydor,
+            Nakshatra::PurvaBhadrapada => FixedStar::Markab,
+            Nakshatra::UttaraBhadrapada => FixedStar::Algenib,
+            Nakshatra::Revati => FixedStar::Revati,
+        }
+    }
+
+    /// The nakshatra whose junction star this is, if any.
+    pub fn nakshatra(&self) -> Option<Nakshatra> {
+        (0..27)
+            .map(|index| Nakshatra::from_longitude(index as f64 * 360.0 / 27.0 + 1.0))
+            .find(|&nakshatra| FixedStar::yogatara(nakshatra) == *self)
+    }
+}
+
+/// Where a fixed star stands on a date, in the sidereal zodiac of the engine's ayanamsa.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FixedStarPosition {
+    pub star: FixedStar,
+    pub longitude: Longitude,
+    /// Ecliptic latitude in degrees. Stars far from the ecliptic, such as Arcturus, are
+    /// conjunct a planet in longitude only.
+    pub latitude: f64,
+    /// Visual magnitude; brighter stars have lower numbers.
+    pub magnitude: f64,
+}
+
+/// A natal planet within the orb of a fixed star.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FixedStarConjunction {
+    pub star: FixedStar,
+    pub planet: CelestialBody,
+    /// Shortest arc in longitude between the two, between 0 and the orb.
+    pub distance: f64,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Position of `star` at `julian_day`, with precession and the star's proper motion
+    /// since the catalogue epoch. Reads `sefstars.txt` from the ephemeris directory, so it
+    /// fails in Moshier mode and with an `ephe_path` lacking the file.
+    pub fn fixed_star_position(
+        &self,
+        star: FixedStar,
+        julian_day: JulianDay,
+    ) -> Result<FixedStarPosition, AstrologyError> {
+        if self.ephemeris_mode() == EphemerisMode::Moshier {
+            return Err(AstrologyError::EphemerisFiles(
+                "fixed stars need sefstars.txt, which Moshier mode does not read".to_string(),
+            ));
+        }
+        self.set_sidereal_mode();
+        let iflag = self.ephemeris_flags(SEFLG_SIDEREAL);
+
+        // Swiss Ephemeris writes the star's full name back into the buffer, under which it
+        // may not find the star again: stars its own ayanamsas use are built in under the
+        // nakshatra's name. So each call gets the designation afresh.
+        let name = || {
+            let mut name = [0 as c_char; 256];
+            for (slot, byte) in name
+                .iter_mut()
+                .zip(format!(",{}", star.designation()).bytes())
+            {
+                *slot = byte as c_char;
+            }
+            name
+        };
+        let mut results: [c_double; 6] = [0.0; 6];
+        let mut magnitude: c_double = 0.0;
+        let mut error: [c_char; 256] = [0; 256];
+        let mut code = unsafe {
+            swe_fixstar_ut(
+                name().as_mut_ptr(),
+                julian_day,
+                iflag,
+                results.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+        if code >= 0 {
+            code =
+                unsafe { swe_fixstar_mag(name().as_mut_ptr(), &mut magnitude, error.as_mut_ptr()) };
+        }
+        if code < 0 {
+            return Err(AstrologyError::Ephemeris {
+                code,
+                message: unsafe { CStr::from_ptr(error.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned(),
+            });
+        }
+        Ok(FixedStarPosition {
+            star,
+            longitude: Longitude::new(results[0]),
+            latitude: results[1],
+            magnitude,
+        })
+    }
+
+    /// Positions of every bundled star at `julian_day`.
+    pub fn fixed_star_positions(
+        &self,
+        julian_day: JulianDay,
+    ) -> Result<Vec<FixedStarPosition>, AstrologyError> {
+        FixedStar::all()
+            .into_iter()
+            .map(|star| self.fixed_star_position(star, julian_day))
+            .collect()
+    }
+
+    /// Every planet of `chart` within `orb` degrees of longitude of a bundled star as it
+    /// stood at the birth, closest first. Orbs of a degree or two are usual; the stars
+    /// move about a degree in 72 years, so the birth date matters.
+    pub fn fixed_star_conjunctions(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+        orb: f64,
+    ) -> Result<Vec<FixedStarConjunction>, AstrologyError> {
+        let stars = self.fixed_star_positions(date_to_julian_day(birth_info.date_time))?;
+        let mut conjunctions: Vec<FixedStarConjunction> = stars
+            .iter()
+            .flat_map(|star| {
+                chart.planets.iter().map(|planet| FixedStarConjunction {
+                    star: star.star,
+                    planet: planet.planet,
+                    distance: star.longitude.separation(planet.longitude),
+                })
+            })
+            .filter(|conjunction| conjunction.distance <= orb)
+            .collect();
+        conjunctions.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(conjunctions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_nakshatra_has_its_own_yogatara() {
+        for star in FixedStar::all() {
+            if let Some(nakshatra) = star.nakshatra() {
+                assert_eq!(FixedStar::yogatara(nakshatra), star);
+            }
+        }
+        let yogataras = FixedStar::all()
+            .iter()
+            .filter(|star| star.nakshatra().is_some())
+            .count();
+        assert_eq!(yogataras, 27);
+    }
+
+    #[test]
+    #[cfg(not(feature = "model-only"))]
+    fn spica_stands_near_the_start_of_libra() {
+        // Lahiri's ayanamsa is defined by Spica at 180°
+        let ephemeris = SwissEph::new().unwrap();
+        let spica = ephemeris
+            .fixed_star_position(FixedStar::Spica, 2_451_545.0)
+            .unwrap();
+        assert!(spica.longitude.separation(Longitude::new(180.0)) < 0.05);
+        assert!((spica.magnitude - 0.97).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,280 @@
+use super::*;
+
+/// Relationship of one planet towards another, natural (naisargika) or, in a chart,
+/// temporal (tatkalika).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Relationship {
+    Friend,
+    Neutral,
+    Enemy,
+}
+
+/// Five-fold (panchadha) relationship of one planet towards another in a chart: the
+/// natural relationship moved one step towards friendship if the other planet is a
+/// temporal friend, and one step away if not.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum CompoundRelationship {
+    /// Adhi-mitra: a natural friend that is also a temporal friend.
+    GreatFriend,
+    Friend,
+    Neutral,
+    Enemy,
+    /// Adhi-shatru: a natural enemy that is also a temporal enemy.
+    GreatEnemy,
+}
+
+impl CompoundRelationship {
+    /// Combines a natural relationship with a temporal one, each counting one step
+    /// towards friendship or enmity.
+    pub fn combine(natural: Relationship, temporal: Relationship) -> Self {
+        let steps = |relationship| match relationship {
+            Relationship::Friend => 1,
+            Relationship::Neutral => 0,
+            Relationship::Enemy => -1,
+        };
+        match steps(natural) + steps(temporal) {
+            2 => CompoundRelationship::GreatFriend,
+            1 => CompoundRelationship::Friend,
+            0 => CompoundRelationship::Neutral,
+            -1 => CompoundRelationship::Enemy,
+            _ => CompoundRelationship::GreatEnemy,
+        }
+    }
+}
+
+impl From<CompoundRelationship> for PlanetaryState {
+    fn from(relationship: CompoundRelationship) -> Self {
+        match relationship {
+            CompoundRelationship::GreatFriend => PlanetaryState::GreatFriend,
+            CompoundRelationship::Friend => PlanetaryState::Friend,
+            CompoundRelationship::Neutral => PlanetaryState::Neutral,
+            CompoundRelationship::Enemy => PlanetaryState::Enemy,
+            CompoundRelationship::GreatEnemy => PlanetaryState::GreatEnemy,
+        }
+    }
+}
+
+/// Temporal (tatkalika) relationship of a planet in sign `from` towards one in sign `to`:
+/// planets in the 2nd, 3rd, 4th, 10th, 11th and 12th signs from it are its friends for the
+/// chart, and the rest, its own sign included, its enemies.
+pub fn temporal_relationship(from: ZodiacSign, to: ZodiacSign) -> Relationship {
+    match (to as usize + 12 - from as usize) % 12 + 1 {
+        2..=4 | 10..=12 => Relationship::Friend,
+        _ => Relationship::Enemy,
+    }
+}
+
+/// How each planet regards every other. Relationships are directional: Mercury counts the
+/// Moon a friend while the Moon counts Mercury an enemy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FriendshipMatrix {
+    /// `relations[a][b]` is how `a` regards `b`.
+    pub relations: HashMap<CelestialBody, HashMap<CelestialBody, Relationship>>,
+}
+
+impl FriendshipMatrix {
+    /// The classical table from Brihat Parashara Hora Shastra for the seven planets.
+    ///
+    /// Parashara gives no relationships for the nodes. Here both nodes befriend Venus and
+    /// Saturn, Rahu also Mercury and Ketu also Mars, and both are hostile to the Sun and
+    /// Moon, with Rahu hostile to Mars as well; the seven planets return whatever regard a
+    /// node holds for them. Schools that differ can replace these entries with
+    /// [`FriendshipMatrix::with_override`].
+    pub fn natural() -> Self {
+        use CelestialBody::*;
+
+        let table: [(CelestialBody, &[CelestialBody], &[CelestialBody]); 9] = [
+            (Sun, &[Moon, Mars, Jupiter], &[Venus, Saturn]),
+            (Moon, &[Sun, Mercury], &[]),
+            (Mars, &[Sun, Moon, Jupiter], &[Mercury]),
+            (Mercury, &[Sun, Venus], &[Moon]),
+            (Jupiter, &[Sun, Moon, Mars], &[Mercury, Venus]),
+            (Venus, &[Mercury, Saturn], &[Sun, Moon]),
+            (Saturn, &[Mercury, Venus], &[Sun, Moon, Mars]),
+            (Rahu, &[Mercury, Venus, Saturn], &[Sun, Moon, Mars]),
+            (Ketu, &[Mars, Venus, Saturn], &[Sun, Moon]),
+        ];
+
+        let mut matrix = FriendshipMatrix {
+            relations: HashMap::new(),
+        };
+        for (planet, friends, enemies) in table {
+            for other in CelestialBody::iter().filter(|&other| other != planet) {
+                let relationship = if friends.contains(&other) {
+                    Relationship::Friend
+                } else if enemies.contains(&other) {
+                    Relationship::Enemy
+                } else {
+                    Relationship::Neutral
+                };
+                matrix.set(planet, other, relationship);
+            }
+        }
+
+        for node in [Rahu, Ketu] {
+            for planet in CelestialBody::iter().filter(|&p| !matches!(p, Rahu | Ketu)) {
+                matrix.set(planet, node, matrix.relationship(node, planet));
+            }
+        }
+
+        matrix
+    }
+
+    /// How `planet` regards `other`. A planet is neutral towards itself.
+    pub fn relationship(&self, planet: CelestialBody, other: CelestialBody) -> Relationship {
+        self.relations
+            .get(&planet)
+            .and_then(|row| row.get(&other))
+            .copied()
+            .unwrap_or(Relationship::Neutral)
+    }
+
+    /// Five-fold relationship of `planet` towards `other` where both sit in `chart`, or
+    /// `None` if either is missing from it.
+    pub fn compound_relationship(
+        &self,
+        chart: &ChartInfo,
+        planet: CelestialBody,
+        other: CelestialBody,
+    ) -> Option<CompoundRelationship> {
+        let from = chart.planet(planet)?.sign;
+        let to = chart.planet(other)?.sign;
+        Some(CompoundRelationship::combine(
+            self.relationship(planet, other),
+            temporal_relationship(from, to),
+        ))
+    }
+
+    /// The temporal relationships between the planets of `chart`, by the signs they
+    /// occupy. Planets missing from the chart have no entries.
+    pub fn temporal(chart: &ChartInfo) -> Self {
+        let mut matrix = FriendshipMatrix {
+            relations: HashMap::new(),
+        };
+        for planet in &chart.planets {
+            for other in chart
+                .planets
+                .iter()
+                .filter(|other| other.planet != planet.planet)
+            {
+                let relationship = temporal_relationship(planet.sign, other.sign);
+                matrix.set(planet.planet, other.planet, relationship);
+            }
+        }
+        matrix
+    }
+
+    /// Replaces how `planet` regards `other`; the reverse direction is left as it was.
+    pub fn with_override(
+        mut self,
+        planet: CelestialBody,
+        other: CelestialBody,
+        relationship: Relationship,
+    ) -> Self {
+        self.set(planet, other, relationship);
+        self
+    }
+
+    pub fn set(&mut self, planet: CelestialBody, other: CelestialBody, relationship: Relationship) {
+        self.relations
+            .entry(planet)
+            .or_default()
+            .insert(other, relationship);
+    }
+
+    pub fn friends_of(&self, planet: CelestialBody) -> Vec<CelestialBody> {
+        self.with_relationship(planet, Relationship::Friend)
+    }
+
+    pub fn enemies_of(&self, planet: CelestialBody) -> Vec<CelestialBody> {
+        self.with_relationship(planet, Relationship::Enemy)
+    }
+
+    fn with_relationship(
+        &self,
+        planet: CelestialBody,
+        relationship: Relationship,
+    ) -> Vec<CelestialBody> {
+        CelestialBody::iter()
+            .filter(|&other| other != planet && self.relationship(planet, other) == relationship)
+            .collect()
+    }
+}
+
+impl Default for FriendshipMatrix {
+    fn default() -> Self {
+        FriendshipMatrix::natural()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Uses `friendships` instead of the natural table wherever planetary relationships
+    /// are scored, such as Graha Maitri.
+    pub fn with_friendships(mut self, friendships: FriendshipMatrix) -> Self {
+        self.friendships = friendships;
+        self
+    }
+
+    pub fn friendships(&self) -> &FriendshipMatrix {
+        &self.friendships
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, sign: ZodiacSign) -> PlanetPosition {
+        let longitude = sign as usize as f64 * 30.0 + 15.0;
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign,
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn temporal_friends_sit_either_side() {
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 0.0,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                planet(CelestialBody::Mars, ZodiacSign::Aries),
+                planet(CelestialBody::Sun, ZodiacSign::Cancer),
+                planet(CelestialBody::Saturn, ZodiacSign::Leo),
+            ],
+            warnings: Vec::new(),
+        };
+
+        let temporal = FriendshipMatrix::temporal(&chart);
+        use CelestialBody::*;
+        assert_eq!(temporal.relationship(Mars, Sun), Relationship::Friend);
+        assert_eq!(temporal.relationship(Sun, Mars), Relationship::Friend);
+        assert_eq!(temporal.relationship(Mars, Saturn), Relationship::Enemy);
+        assert_eq!(temporal.relationship(Sun, Saturn), Relationship::Friend);
+        assert!(!temporal.relations[&Mars].contains_key(&Moon));
+
+        // The Sun, Mars's natural friend, sits 4th from it and becomes a great friend;
+        // Saturn, naturally neutral, sits 5th and becomes an enemy
+        let natural = FriendshipMatrix::natural();
+        let compound = |other| natural.compound_relationship(&chart, Mars, other);
+        assert_eq!(compound(Sun), Some(CompoundRelationship::GreatFriend));
+        assert_eq!(compound(Saturn), Some(CompoundRelationship::Enemy));
+        assert_eq!(compound(Moon), None);
+    }
+}
@@ -0,0 +1,319 @@
+use super::*;
+
+// ---------------------------
+// ## Combustion
+// ---------------------------
+
+/// A planet's combustion orb when moving forward and when retrograde.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CombustionOrb {
+    pub direct: f64,
+    pub retrograde: f64,
+}
+
+impl CombustionOrb {
+    const fn both(orb: f64) -> Self {
+        CombustionOrb {
+            direct: orb,
+            retrograde: orb,
+        }
+    }
+}
+
+/// Distances from the Sun within which each planet is combust. The Sun, the nodes and the
+/// bodies beyond Saturn never are.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CombustionOrbs {
+    /// The Moon is never retrograde.
+    pub moon: f64,
+    pub mars: CombustionOrb,
+    pub mercury: CombustionOrb,
+    pub jupiter: CombustionOrb,
+    pub venus: CombustionOrb,
+    pub saturn: CombustionOrb,
+}
+
+impl CombustionOrbs {
+    /// Orb of `body` in the given motion, or `None` for bodies that never combust.
+    pub fn orb(&self, body: CelestialBody, retrograde: bool) -> Option<f64> {
+        let orb = match body {
+            CelestialBody::Moon => return Some(self.moon),
+            CelestialBody::Mars => self.mars,
+            CelestialBody::Mercury => self.mercury,
+            CelestialBody::Jupiter => self.jupiter,
+            CelestialBody::Venus => self.venus,
+            CelestialBody::Saturn => self.saturn,
+            _ => return None,
+        };
+        Some(if retrograde {
+            orb.retrograde
+        } else {
+            orb.direct
+        })
+    }
+}
+
+impl Default for CombustionOrbs {
+    /// The Surya Siddhanta's orbs, with Mercury and Venus combust only closer in while
+    /// retrograde.
+    fn default() -> Self {
+        CombustionOrbs {
+            moon: 12.0,
+            mars: CombustionOrb::both(17.0),
+            mercury: CombustionOrb {
+                direct: 14.0,
+                retrograde: 12.0,
+            },
+            jupiter: CombustionOrb::both(11.0),
+            venus: CombustionOrb {
+                direct: 10.0,
+                retrograde: 8.0,
+            },
+            saturn: CombustionOrb::both(15.0),
+        }
+    }
+}
+
+// ---------------------------
+// ## Planetary War
+// ---------------------------
+
+/// Two of Mars, Mercury, Jupiter, Venus and Saturn closer than this are at war.
+pub const PLANETARY_WAR_ORB: f64 = 1.0;
+
+/// Graha yuddha: two star planets within [`PLANETARY_WAR_ORB`] of each other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PlanetaryWar {
+    /// The planet further north in ecliptic latitude.
+    pub winner: CelestialBody,
+    pub loser: CelestialBody,
+    /// Degrees of longitude between the two.
+    pub separation: f64,
+}
+
+/// How a planetary war went for one of the two planets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct WarSide {
+    pub opponent: CelestialBody,
+    pub won: bool,
+}
+
+impl PlanetaryWar {
+    /// The war as `body` saw it, or `None` if it was not one of the two.
+    pub fn side(&self, body: CelestialBody) -> Option<WarSide> {
+        if body == self.winner {
+            Some(WarSide {
+                opponent: self.loser,
+                won: true,
+            })
+        } else if body == self.loser {
+            Some(WarSide {
+                opponent: self.winner,
+                won: false,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl ChartInfo {
+    /// Whether `body` is within its orb of the chart's own Sun, the retrograde orb if it is
+    /// retrograde in the chart.
+    pub fn is_combust(&self, body: CelestialBody, orbs: &CombustionOrbs) -> bool {
+        let (Some(position), Some(sun)) = (self.planet(body), self.planet(CelestialBody::Sun))
+        else {
+            return false;
+        };
+        orbs.orb(body, position.retrograde)
+            .is_some_and(|orb| position.longitude.separation(sun.longitude) <= orb)
+    }
+
+    /// Every planetary war in the chart.
+    pub fn planetary_wars(&self) -> Vec<PlanetaryWar> {
+        let warriors: Vec<&PlanetPosition> = [
+            CelestialBody::Mars,
+            CelestialBody::Mercury,
+            CelestialBody::Jupiter,
+            CelestialBody::Venus,
+            CelestialBody::Saturn,
+        ]
+        .into_iter()
+        .filter_map(|body| self.planet(body))
+        .collect();
+
+        let mut wars = Vec::new();
+        for (index, first) in warriors.iter().enumerate() {
+            for second in &warriors[index + 1..] {
+                let separation = first.longitude.separation(second.longitude);
+                if separation > PLANETARY_WAR_ORB {
+                    continue;
+                }
+                let (winner, loser) = if first.latitude >= second.latitude {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                wars.push(PlanetaryWar {
+                    winner: winner.planet,
+                    loser: loser.planet,
+                    separation,
+                });
+            }
+        }
+        wars
+    }
+
+    /// Whether `body` acts as a benefic in this chart. Jupiter and Venus always do and the
+    /// Sun, Mars, Saturn and the nodes never. The Moon is benefic while waxing and Mercury
+    /// unless a malefic shares its sign. Of the other bodies Neptune and the asteroids
+    /// count as benefics.
+    pub fn is_benefic(&self, body: CelestialBody) -> bool {
+        match body {
+            CelestialBody::Jupiter
+            | CelestialBody::Venus
+            | CelestialBody::Neptune
+            | CelestialBody::Ceres
+            | CelestialBody::Pallas
+            | CelestialBody::Juno
+            | CelestialBody::Vesta => true,
+            CelestialBody::Sun
+            | CelestialBody::Mars
+            | CelestialBody::Saturn
+            | CelestialBody::Rahu
+            | CelestialBody::Ketu
+            | CelestialBody::Uranus
+            | CelestialBody::Pluto
+            | CelestialBody::Chiron => false,
+            CelestialBody::Moon => self.is_moon_waxing(),
+            CelestialBody::Mercury => {
+                let Some(mercury) = self.planet(CelestialBody::Mercury) else {
+                    return true;
+                };
+                !self.planets.iter().any(|other| {
+                    other.sign == mercury.sign
+                        && matches!(
+                            other.planet,
+                            CelestialBody::Sun
+                                | CelestialBody::Mars
+                                | CelestialBody::Saturn
+                                | CelestialBody::Rahu
+                                | CelestialBody::Ketu
+                        )
+                })
+            }
+        }
+    }
+
+    /// The Moon is less than 180° ahead of the Sun.
+    fn is_moon_waxing(&self) -> bool {
+        match (
+            self.planet(CelestialBody::Moon),
+            self.planet(CelestialBody::Sun),
+        ) {
+            (Some(moon), Some(sun)) => {
+                (moon.longitude.degrees() - sun.longitude.degrees()).rem_euclid(360.0) < 180.0
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(planet: CelestialBody, longitude: f64, latitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet,
+            longitude: Longitude::new(longitude),
+            latitude,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    fn chart(planets: Vec<PlanetPosition>) -> ChartInfo {
+        ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 0.0,
+            },
+            houses: Vec::new(),
+            planets,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn retrograde_venus_burns_only_closer_in() {
+        let mut venus = position(CelestialBody::Venus, 109.0, 0.0);
+        let sun = position(CelestialBody::Sun, 100.0, 0.0);
+        let orbs = CombustionOrbs::default();
+
+        assert!(chart(vec![sun.clone(), venus.clone()]).is_combust(CelestialBody::Venus, &orbs));
+        venus.retrograde = true;
+        assert!(!chart(vec![sun, venus]).is_combust(CelestialBody::Venus, &orbs));
+    }
+
+    #[test]
+    fn combustion_is_measured_across_0_aries() {
+        let chart = chart(vec![
+            position(CelestialBody::Sun, 355.0, 0.0),
+            position(CelestialBody::Saturn, 8.0, 0.0),
+        ]);
+        assert!(chart.is_combust(CelestialBody::Saturn, &CombustionOrbs::default()));
+        assert!(!chart.is_combust(CelestialBody::Sun, &CombustionOrbs::default()));
+    }
+
+    #[test]
+    fn the_northern_planet_wins_a_war() {
+        let chart = chart(vec![
+            position(CelestialBody::Mars, 200.2, -1.0),
+            position(CelestialBody::Venus, 200.8, 1.5),
+            position(CelestialBody::Jupiter, 210.0, 0.0),
+        ]);
+        let wars = chart.planetary_wars();
+        assert_eq!(wars.len(), 1);
+        assert_eq!(wars[0].winner, CelestialBody::Venus);
+        assert_eq!(wars[0].loser, CelestialBody::Mars);
+        assert_eq!(
+            wars[0].side(CelestialBody::Mars),
+            Some(WarSide {
+                opponent: CelestialBody::Venus,
+                won: false,
+            })
+        );
+        assert_eq!(wars[0].side(CelestialBody::Jupiter), None);
+    }
+
+    #[test]
+    fn moon_and_mercury_take_their_nature_from_the_chart() {
+        let waxing = chart(vec![
+            position(CelestialBody::Sun, 10.0, 0.0),
+            position(CelestialBody::Moon, 100.0, 0.0),
+            position(CelestialBody::Mercury, 40.0, 0.0),
+            position(CelestialBody::Saturn, 50.0, 0.0),
+        ]);
+        assert!(waxing.is_benefic(CelestialBody::Moon));
+        assert!(!waxing.is_benefic(CelestialBody::Mercury));
+
+        let waning = chart(vec![
+            position(CelestialBody::Sun, 10.0, 0.0),
+            position(CelestialBody::Moon, 300.0, 0.0),
+            position(CelestialBody::Mercury, 70.0, 0.0),
+        ]);
+        assert!(!waning.is_benefic(CelestialBody::Moon));
+        assert!(waning.is_benefic(CelestialBody::Mercury));
+    }
+}
@@ -0,0 +1,295 @@
+use super::*;
+
+impl BirthInfo {
+    /// Births in an AAF file, the exchange format of Astrodienst's AstroDataBank, one for
+    /// each `#A93` record and the `#B93` record after it. Other lines are skipped.
+    ///
+    /// ```text
+    /// #A93:Einstein,Albert,m,14.3.1879,11:30,Ulm,D
+    /// #B93:*,48n24,10e00,0hE40,0
+    /// ```
+    ///
+    /// A record that cannot be read gives an error naming its line, and the rest are still
+    /// read.
+    pub fn from_aaf(text: &str) -> Vec<Result<BirthInfo, AstrologyError>> {
+        let mut births = Vec::new();
+        let mut pending: Option<(usize, &str)> = None;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(fields) = line.strip_prefix("#A93:") {
+                if let Some((line, _)) = pending.replace((index + 1, fields)) {
+                    births.push(Err(import_error(line, "#A93 record without #B93")));
+                }
+            } else if let Some(fields) = line.strip_prefix("#B93:") {
+                let birth = match pending.take() {
+                    Some((line, person)) => {
+                        aaf_birth(person, fields).map_err(|error| import_error(line, error))
+                    }
+                    None => Err(import_error(index + 1, "#B93 record without #A93")),
+                };
+                births.push(birth);
+            }
+        }
+        if let Some((line, _)) = pending {
+            births.push(Err(import_error(line, "#A93 record without #B93")));
+        }
+        births
+    }
+
+    /// Births in a CSV file with a header row, such as Quick*Chart and spreadsheets export.
+    /// Columns are found by their headers, in any case and order: `date`, `time`,
+    /// `latitude` (or `lat`) and `longitude` (`lon`, `long`) are needed, and `name`,
+    /// `gender` (`sex`) and `timezone` (`tz`, `offset`, `zone`) are read if present. The
+    /// time zone is an offset in hours, a UTC offset such as `+05:30` or an IANA name, and
+    /// UTC without one. Coordinates may be decimal degrees or as AAF writes them, `48n24`.
+    ///
+    /// A row that cannot be read gives an error naming its line, and the rest are still
+    /// read.
+    pub fn from_csv(text: &str) -> Vec<Result<BirthInfo, AstrologyError>> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            return Vec::new();
+        };
+        let header: Vec<String> = csv_fields(header)
+            .iter()
+            .map(|name| name.trim().to_ascii_lowercase())
+            .collect();
+        let column = |names: &[&str]| header.iter().position(|name| names.contains(&&**name));
+        let columns = CsvColumns {
+            name: column(&["name"]),
+            gender: column(&["gender", "sex"]),
+            date: column(&["date"]),
+            time: column(&["time"]),
+            timezone: column(&["timezone", "tz", "offset", "zone"]),
+            latitude: column(&["latitude", "lat"]),
+            longitude: column(&["longitude", "lon", "long"]),
+        };
+
+        lines
+            .map(|(index, line)| {
+                columns
+                    .birth(&csv_fields(line))
+                    .map_err(|error| import_error(index + 1, error))
+            })
+            .collect()
+    }
+}
+
+fn import_error(line: usize, error: impl fmt::Display) -> AstrologyError {
+    AstrologyError::InvalidInput(format!("line {}: {}", line, error))
+}
+
+/// Name, first name, sex, date, time, place and country from an `#A93` record, and the
+/// Julian day, latitude, longitude, time zone and daylight saving from its `#B93`.
+fn aaf_birth(person: &str, place: &str) -> Result<BirthInfo, BirthInfoError> {
+    let person: Vec<&str> = person.split(',').map(str::trim).collect();
+    let place: Vec<&str> = place.split(',').map(str::trim).collect();
+    let date = parse_date(field(&person, 3).ok_or(BirthInfoError::MissingDate)?)?;
+    let time = parse_time(field(&person, 4).ok_or(BirthInfoError::MissingTime)?, None)?;
+    let latitude = parse_degrees(field(&place, 1).ok_or(BirthInfoError::MissingLocation)?)?;
+    let longitude = parse_degrees(field(&place, 2).ok_or(BirthInfoError::MissingLocation)?)?;
+    let mut offset = parse_aaf_zone(field(&place, 3).unwrap_or("0hE"))?;
+    if let Some(daylight) = field(&place, 4) {
+        offset += daylight
+            .parse::<f32>()
+            .map_err(|_| BirthInfoError::InvalidOffset(daylight.to_string()))?;
+    }
+
+    let mut birth = BirthInfo::new()
+        .date(date.0, date.1, date.2)
+        .time(time.hour(), time.minute(), time.second())
+        .tz_offset(offset)
+        .location(Location::new(latitude, longitude, offset))
+        .build()?;
+    let name = [field(&person, 1), field(&person, 0)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    birth.person = person_of(&name, field(&person, 2));
+    Ok(birth)
+}
+
+/// Field `index` of an AAF record, `None` if it is missing, empty or `*` for unknown.
+fn field<'a>(fields: &[&'a str], index: usize) -> Option<&'a str> {
+    fields
+        .get(index)
+        .copied()
+        .filter(|field| !field.is_empty() && *field != "*")
+}
+
+/// Columns of a CSV file holding each part of a birth.
+struct CsvColumns {
+    name: Option<usize>,
+    gender: Option<usize>,
+    date: Option<usize>,
+    time: Option<usize>,
+    timezone: Option<usize>,
+    latitude: Option<usize>,
+    longitude: Option<usize>,
+}
+
+impl CsvColumns {
+    fn birth(&self, fields: &[String]) -> Result<BirthInfo, BirthInfoError> {
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column))
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+        };
+
+        let date = parse_date(field(self.date).ok_or(BirthInfoError::MissingDate)?)?;
+        let time = field(self.time).ok_or(BirthInfoError::MissingTime)?;
+        let (time, meridiem) = match time.to_ascii_lowercase() {
+            lower if lower.ends_with("am") || lower.ends_with("pm") => (
+                time[..time.len() - 2].trim().to_string(),
+                Some(lower[lower.len() - 2..].to_string()),
+            ),
+            _ => (time.to_string(), None),
+        };
+        let time = parse_time(&time, meridiem.as_deref())?;
+        let latitude = parse_degrees(field(self.latitude).ok_or(BirthInfoError::MissingLocation)?)?;
+        let longitude =
+            parse_degrees(field(self.longitude).ok_or(BirthInfoError::MissingLocation)?)?;
+
+        let builder = BirthInfo::new()
+            .date(date.0, date.1, date.2)
+            .time(time.hour(), time.minute(), time.second())
+            .location(Location::new(latitude, longitude, 0.0));
+        let builder = match field(self.timezone) {
+            None => builder.tz_offset(0.0),
+            Some(zone) if zone.contains('/') => builder.tz_name(zone),
+            Some(zone) => match zone.parse::<f32>() {
+                Ok(hours) => builder.tz_offset(hours),
+                Err(_) => builder.tz_offset(parse_utc_offset(zone)? as f32 / 3600.0),
+            },
+        };
+        let mut birth = builder.build()?;
+        birth.person = person_of(field(self.name).unwrap_or(""), field(self.gender));
+        Ok(birth)
+    }
+}
+
+/// The person a record names, if it names one or gives a sex.
+fn person_of(name: &str, sex: Option<&str>) -> Option<Person> {
+    let gender = match sex.map(|sex| sex.to_ascii_lowercase()) {
+        Some(sex) if sex.starts_with('m') => Gender::Male,
+        Some(sex) if sex.starts_with('f') => Gender::Female,
+        _ => Gender::Unspecified,
+    };
+    (!name.is_empty() || gender != Gender::Unspecified).then(|| Person {
+        name: name.to_string(),
+        gender,
+    })
+}
+
+/// Decimal degrees, or degrees and minutes around a hemisphere letter as AAF writes them:
+/// `48n24` is 48°24′ north and `74w0` 74° west. Seconds may follow the minutes after a `:`.
+fn parse_degrees(value: &str) -> Result<f64, BirthInfoError> {
+    if let Ok(degrees) = value.parse::<f64>() {
+        return Ok(degrees);
+    }
+    let invalid = || BirthInfoError::InvalidCoordinates(format!("'{}' is not a coordinate", value));
+    let split = value
+        .find(|c: char| "nsewNSEW".contains(c))
+        .ok_or_else(invalid)?;
+    let (degrees, rest) = value.split_at(split);
+    let sign = if "swSW".contains(&rest[..1]) {
+        -1.0
+    } else {
+        1.0
+    };
+    let mut parts = rest[1..].split(':');
+    let number = |part: Option<&str>| match part.filter(|part| !part.is_empty()) {
+        Some(part) => part.parse::<f64>().map_err(|_| invalid()),
+        None => Ok(0.0),
+    };
+    let degrees = number(Some(degrees))?;
+    let minutes = number(parts.next())?;
+    let seconds = number(parts.next())?;
+    Ok(sign * (degrees + minutes / 60.0 + seconds / 3600.0))
+}
+
+/// Hours ahead of UTC from an AAF zone such as `1hE`, `5hW`, `5h30E` or `0hE40`, the
+/// letter telling east or west and the minutes on either side of it.
+fn parse_aaf_zone(zone: &str) -> Result<f32, BirthInfoError> {
+    let invalid = || BirthInfoError::InvalidOffset(zone.to_string());
+    let sign = if zone.contains(['W', 'w']) { -1.0 } else { 1.0 };
+    let digits = zone.replace(['E', 'e', 'W', 'w'], "");
+    let (hours, minutes) = digits.split_once(['h', 'H']).ok_or_else(invalid)?;
+    let hours: f32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: f32 = match minutes {
+        "" => 0.0,
+        minutes => minutes.parse().map_err(|_| invalid())?,
+    };
+    Ok(sign * (hours + minutes / 60.0))
+}
+
+/// The fields of a CSV line. Quoted fields may hold commas, and `""` within them stands
+/// for a quote.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aaf_records_become_births() {
+        let births = BirthInfo::from_aaf(
+            "#A93:Einstein,Albert,m,14.3.1879,11:30,Ulm,D\n\
+             #B93:*,48n24,10e00,0hE40,0\n\
+             #A93:Nobody,*,*,1.1.2000,12:00,Nowhere,X\n\
+             #A93:Lincoln,Abraham,m,12.2.1809,6:54,Hodgenville KY,USA\n\
+             #B93:*,37n34,85w44,5hW,0\n",
+        );
+        assert_eq!(births.len(), 3);
+
+        let einstein = births[0].as_ref().unwrap();
+        assert_eq!(einstein.date_time.to_rfc3339(), "1879-03-14T10:50:00+00:00");
+        assert!((einstein.location.latitude - 48.4).abs() < 1e-9);
+        assert_eq!(einstein.person.as_ref().unwrap().name, "Albert Einstein");
+        assert_eq!(einstein.gender(), Gender::Male);
+
+        assert!(births[1].is_err());
+        let lincoln = births[2].as_ref().unwrap();
+        assert_eq!(lincoln.date_time.to_rfc3339(), "1809-02-12T11:54:00+00:00");
+        assert!(lincoln.location.longitude < -85.0);
+    }
+
+    #[test]
+    fn csv_columns_are_found_by_header() {
+        let births = BirthInfo::from_csv(
+            "Name,Sex,Date,Time,Lat,Lon,Zone\n\
+             \"Mohan, Aghil\",M,18/06/1991,7:10 AM,11.2588,75.7804,+05:30\n\
+             Someone,F,2000-01-01,25:00,0,0,0\n",
+        );
+        assert_eq!(births.len(), 2);
+        let first = births[0].as_ref().unwrap();
+        assert_eq!(first.date_time.to_rfc3339(), "1991-06-18T01:40:00+00:00");
+        assert_eq!(first.person.as_ref().unwrap().name, "Mohan, Aghil");
+        assert!(
+            matches!(&births[1], Err(AstrologyError::InvalidInput(message)) if message.starts_with("line 3"))
+        );
+    }
+}
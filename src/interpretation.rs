@@ -0,0 +1,485 @@
+use super::*;
+
+/// One part of an interpretation, each read from the report on its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum InterpretationTopic {
+    Ascendant,
+    PlanetsInSigns,
+    PlanetsInHouses,
+    /// Where the lord of each house of the rasi chart sits.
+    HouseLords,
+    Yogas,
+    /// The running maha, antar and pratyantar dashas.
+    Dashas,
+    /// Lords of the saptamsa (D7).
+    Children,
+    /// Lords of the dwadasamsa (D12).
+    Parents,
+    /// Lords of the dashamsa (D10).
+    Career,
+}
+
+impl InterpretationTopic {
+    /// Every topic, in the order the sections come out.
+    pub fn all() -> [InterpretationTopic; 9] {
+        use InterpretationTopic::*;
+        [
+            Ascendant,
+            PlanetsInSigns,
+            PlanetsInHouses,
+            HouseLords,
+            Yogas,
+            Dashas,
+            Children,
+            Parents,
+            Career,
+        ]
+    }
+}
+
+/// What a fragment of text is written for. Content packs supply a template for as many
+/// keys as they have something to say about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum InterpretationKey {
+    Ascendant(ZodiacSign),
+    PlanetInSign(CelestialBody, ZodiacSign),
+    PlanetInHouse(CelestialBody, House),
+    /// A yoga, by its name.
+    Yoga(String),
+    MahaDasha(DashaLord),
+    AntarDasha(DashaLord),
+    PratyantarDasha(DashaLord),
+    /// The lord of `lord_of` placed in `house`, in the rasi chart or a divisional one.
+    LordInHouse {
+        chart_type: ChartType,
+        lord_of: House,
+        house: House,
+    },
+}
+
+impl InterpretationKey {
+    /// The section the key's fragment belongs in.
+    pub fn topic(&self) -> InterpretationTopic {
+        match self {
+            InterpretationKey::Ascendant(_) => InterpretationTopic::Ascendant,
+            InterpretationKey::PlanetInSign(..) => InterpretationTopic::PlanetsInSigns,
+            InterpretationKey::PlanetInHouse(..) => InterpretationTopic::PlanetsInHouses,
+            InterpretationKey::Yoga(_) => InterpretationTopic::Yogas,
+            InterpretationKey::MahaDasha(_)
+            | InterpretationKey::AntarDasha(_)
+            | InterpretationKey::PratyantarDasha(_) => InterpretationTopic::Dashas,
+            InterpretationKey::LordInHouse { chart_type, .. } => match chart_type {
+                ChartType::Saptamsa => InterpretationTopic::Children,
+                ChartType::Dwadasamsa => InterpretationTopic::Parents,
+                ChartType::Dashamsa => InterpretationTopic::Career,
+                _ => InterpretationTopic::HouseLords,
+            },
+        }
+    }
+}
+
+/// Texts for an interpretation, such as a content pack in one language. Templates name
+/// the facts they use in braces, `{planet}` or `{house}`; see [`Report::interpret`] for
+/// the names each key fills in.
+pub trait InterpretationPack: Send + Sync {
+    /// Heading of the section for `topic`.
+    fn title(&self, topic: InterpretationTopic) -> String;
+
+    /// Template for `key`, or `None` to say nothing about it.
+    fn template(&self, key: &InterpretationKey) -> Option<String>;
+}
+
+/// An [`InterpretationPack`] held in tables: a template for particular keys, and for
+/// everything else a template for each topic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplatePack {
+    titles: HashMap<InterpretationTopic, String>,
+    defaults: HashMap<InterpretationTopic, String>,
+    templates: HashMap<InterpretationKey, String>,
+}
+
+impl TemplatePack {
+    /// Plain English statements of the facts of each topic, to build a pack on.
+    pub fn english() -> Self {
+        use InterpretationTopic::*;
+        let topics = [
+            (
+                Ascendant,
+                "Ascendant",
+                "The lagna rises at {longitude}, ruled by {lord}.",
+            ),
+            (
+                PlanetsInSigns,
+                "Planets in signs",
+                "{planet} is at {longitude}, in {nakshatra} pada {pada}.",
+            ),
+            (
+                PlanetsInHouses,
+                "Planets in houses",
+                "{planet} is in the {house} house.",
+            ),
+            (
+                HouseLords,
+                "House lords",
+                "The lord of the {lord_of} house, {lord}, is in the {house} house.",
+            ),
+            (Yogas, "Yogas", "{yoga}, with strength {strength}."),
+            (
+                Dashas,
+                "Dashas",
+                "The {level} dasha of {lord} runs from {start} to {end}.",
+            ),
+            (
+                Children,
+                "Children (D7)",
+                "In the {chart}, the lord of the {lord_of} house, {lord}, is in the {house} house.",
+            ),
+            (
+                Parents,
+                "Parents (D12)",
+                "In the {chart}, the lord of the {lord_of} house, {lord}, is in the {house} house.",
+            ),
+            (
+                Career,
+                "Career (D10)",
+                "In the {chart}, the lord of the {lord_of} house, {lord}, is in the {house} house.",
+            ),
+        ];
+        topics
+            .into_iter()
+            .fold(TemplatePack::default(), |pack, (topic, title, template)| {
+                pack.with_title(topic, title).with_default(topic, template)
+            })
+    }
+
+    pub fn with_title(mut self, topic: InterpretationTopic, title: impl Into<String>) -> Self {
+        self.titles.insert(topic, title.into());
+        self
+    }
+
+    /// Template for the keys of `topic` that have none of their own.
+    pub fn with_default(mut self, topic: InterpretationTopic, template: impl Into<String>) -> Self {
+        self.defaults.insert(topic, template.into());
+        self
+    }
+
+    pub fn with_template(mut self, key: InterpretationKey, template: impl Into<String>) -> Self {
+        self.templates.insert(key, template.into());
+        self
+    }
+}
+
+impl InterpretationPack for TemplatePack {
+    fn title(&self, topic: InterpretationTopic) -> String {
+        self.titles
+            .get(&topic)
+            .cloned()
+            .unwrap_or_else(|| format!("{:?}", topic))
+    }
+
+    fn template(&self, key: &InterpretationKey) -> Option<String> {
+        self.templates
+            .get(key)
+            .or_else(|| self.defaults.get(&key.topic()))
+            .cloned()
+    }
+}
+
+/// A piece of an interpretation with the key its template was chosen by.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct InterpretationFragment {
+    pub key: InterpretationKey,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct InterpretationSection {
+    pub topic: InterpretationTopic,
+    pub title: String,
+    pub fragments: Vec<InterpretationFragment>,
+}
+
+/// The title, then each fragment on a line of its own.
+impl fmt::Display for InterpretationSection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.title)?;
+        for fragment in &self.fragments {
+            writeln!(f, "- {}", fragment.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// `template` with each `{name}` of `facts` replaced by its value.
+fn fill(template: &str, facts: &[(&str, String)]) -> String {
+    facts
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+fn house_name(house: House) -> String {
+    ordinal(house as u8)
+}
+
+/// Where the lords of `houses` sit in a chart with `signs` on the cusps of its houses, as
+/// keys with their facts.
+fn lords_in_houses(
+    chart_type: ChartType,
+    signs: [ZodiacSign; 12],
+    planets: &[PlanetPosition],
+    houses: &[House],
+) -> Vec<(InterpretationKey, Vec<(&'static str, String)>)> {
+    houses
+        .iter()
+        .filter_map(|&lord_of| {
+            let lord = signs[lord_of as usize - 1].lord();
+            let house = planets.iter().find(|planet| planet.planet == lord)?.house;
+            Some((
+                InterpretationKey::LordInHouse {
+                    chart_type,
+                    lord_of,
+                    house,
+                },
+                vec![
+                    ("chart", format!("{:?}", chart_type)),
+                    ("lord_of", house_name(lord_of)),
+                    ("lord", format!("{:?}", lord)),
+                    ("house", house_name(house)),
+                ],
+            ))
+        })
+        .collect()
+}
+
+impl Report {
+    /// The report read out in `pack`'s words, a section for each topic the pack has
+    /// anything to say about.
+    ///
+    /// Templates may use `{sign}`, `{longitude}` and `{lord}` for the ascendant;
+    /// `{planet}`, `{sign}`, `{longitude}`, `{nakshatra}`, `{pada}` and `{house}` for
+    /// planets; `{yoga}`, `{planets}` and `{strength}` for yogas; `{level}`, `{lord}`,
+    /// `{start}` and `{end}` for dashas; and `{chart}`, `{lord_of}`, `{lord}` and `{house}`
+    /// for house lords.
+    pub fn interpret(&self, pack: &dyn InterpretationPack) -> Vec<InterpretationSection> {
+        let mut facts: Vec<(InterpretationKey, Vec<(&str, String)>)> = Vec::new();
+
+        if let Some(chart) = self.charts.first() {
+            let lagna = chart.ascendant.sign;
+            facts.push((
+                InterpretationKey::Ascendant(lagna),
+                vec![
+                    ("sign", lagna.to_string()),
+                    (
+                        "longitude",
+                        Longitude::new(chart.ascendant.longitude()).to_string(),
+                    ),
+                    ("lord", format!("{:?}", lagna.lord())),
+                ],
+            ));
+            for planet in &chart.planets {
+                let planet_facts = vec![
+                    ("planet", format!("{:?}", planet.planet)),
+                    ("sign", planet.sign.to_string()),
+                    ("longitude", planet.longitude.to_string()),
+                    ("nakshatra", format!("{:?}", planet.nakshatra.nakshatra)),
+                    ("pada", planet.nakshatra.pada.to_string()),
+                    ("house", house_name(planet.house)),
+                ];
+                facts.push((
+                    InterpretationKey::PlanetInSign(planet.planet, planet.sign),
+                    planet_facts.clone(),
+                ));
+                facts.push((
+                    InterpretationKey::PlanetInHouse(planet.planet, planet.house),
+                    planet_facts,
+                ));
+            }
+            // Whole signs from the lagna when the chart carries no cusps
+            let signs = std::array::from_fn(|i| match chart.houses.get(i) {
+                Some(cusp) => cusp.sign,
+                None => ZodiacSign::from_longitude((lagna as usize + i) as f64 * 30.0),
+            });
+            let all_houses: Vec<House> = House::all().collect();
+            facts.extend(lords_in_houses(
+                ChartType::Rasi,
+                signs,
+                &chart.planets,
+                &all_houses,
+            ));
+        }
+
+        for yoga in self.yogas.iter().filter(|yoga| !yoga.cancelled) {
+            let planets: Vec<String> = yoga
+                .involved_planets
+                .iter()
+                .map(|planet| format!("{:?}", planet))
+                .collect();
+            facts.push((
+                InterpretationKey::Yoga(yoga.yoga.name.clone()),
+                vec![
+                    ("yoga", yoga.yoga.name.clone()),
+                    ("planets", planets.join(", ")),
+                    ("strength", format!("{:.2}", yoga.strength)),
+                ],
+            ));
+        }
+
+        let dashas = &self.dashas;
+        for (key, level, lord, start, end) in [
+            (
+                InterpretationKey::MahaDasha(dashas.maha_dasha),
+                "maha",
+                dashas.maha_dasha,
+                dashas.maha_dasha_start,
+                dashas.maha_dasha_end,
+            ),
+            (
+                InterpretationKey::AntarDasha(dashas.antar_dasha),
+                "antar",
+                dashas.antar_dasha,
+                dashas.antar_dasha_start,
+                dashas.antar_dasha_end,
+            ),
+            (
+                InterpretationKey::PratyantarDasha(dashas.pratyantar_dasha),
+                "pratyantar",
+                dashas.pratyantar_dasha,
+                dashas.pratyantar_dasha_start,
+                dashas.pratyantar_dasha_end,
+            ),
+        ] {
+            facts.push((
+                key,
+                vec![
+                    ("level", level.to_string()),
+                    ("lord", lord.to_string()),
+                    ("start", start.format("%Y-%m-%d").to_string()),
+                    ("end", end.format("%Y-%m-%d").to_string()),
+                ],
+            ));
+        }
+
+        // The lagna and 5th of the D7, the 4th and 9th of the D12 for mother and father,
+        // and the lagna and 10th of the D10
+        for (chart_type, houses) in [
+            (ChartType::Saptamsa, [House::First, House::Fifth]),
+            (ChartType::Dwadasamsa, [House::Fourth, House::Ninth]),
+            (ChartType::Dashamsa, [House::First, House::Tenth]),
+        ] {
+            if let Some(chart) = self
+                .divisional_charts
+                .iter()
+                .find(|chart| chart.chart_type == chart_type)
+            {
+                facts.extend(lords_in_houses(
+                    chart_type,
+                    chart.houses,
+                    &chart.planets,
+                    &houses,
+                ));
+            }
+        }
+
+        InterpretationTopic::all()
+            .into_iter()
+            .filter_map(|topic| {
+                let fragments: Vec<InterpretationFragment> = facts
+                    .iter()
+                    .filter(|(key, _)| key.topic() == topic)
+                    .filter_map(|(key, key_facts)| {
+                        let template = pack.template(key)?;
+                        Some(InterpretationFragment {
+                            key: key.clone(),
+                            text: fill(&template, key_facts),
+                        })
+                    })
+                    .collect();
+                (!fragments.is_empty()).then(|| InterpretationSection {
+                    topic,
+                    title: pack.title(topic),
+                    fragments,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn particular_templates_override_the_topic_default() {
+        let pack = TemplatePack::english().with_template(
+            InterpretationKey::PlanetInHouse(CelestialBody::Sun, House::Tenth),
+            "{planet} in the {house} brings recognition.",
+        );
+        let key = InterpretationKey::PlanetInHouse(CelestialBody::Sun, House::Tenth);
+        let facts = [
+            ("planet", "Sun".to_string()),
+            ("house", house_name(House::Tenth)),
+        ];
+        assert_eq!(
+            fill(&pack.template(&key).unwrap(), &facts),
+            "Sun in the 10th brings recognition."
+        );
+
+        let elsewhere = InterpretationKey::PlanetInHouse(CelestialBody::Sun, House::Ninth);
+        assert_eq!(
+            pack.template(&elsewhere).as_deref(),
+            Some("{planet} is in the {house} house.")
+        );
+        assert_eq!(TemplatePack::default().template(&elsewhere), None);
+        assert_eq!(
+            InterpretationKey::LordInHouse {
+                chart_type: ChartType::Dashamsa,
+                lord_of: House::Tenth,
+                house: House::First,
+            }
+            .topic(),
+            InterpretationTopic::Career
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "model-only"))]
+    fn reports_read_out_in_sections() {
+        let report = crate::examples::natal_report().unwrap();
+        let sections = report.interpret(&TemplatePack::english());
+        let topics: Vec<InterpretationTopic> =
+            sections.iter().map(|section| section.topic).collect();
+        assert_eq!(topics[..4], InterpretationTopic::all()[..4]);
+        let house_lords = &sections[3];
+        assert_eq!(house_lords.fragments.len(), 12);
+        assert!(sections
+            .iter()
+            .flat_map(|section| &section.fragments)
+            .all(|fragment| !fragment.text.contains('{')));
+
+        // A pack that only speaks of the Moon
+        let moon_only = TemplatePack::default()
+            .with_default(InterpretationTopic::PlanetsInSigns, "{planet} in {sign}");
+        struct MoonOnly(TemplatePack);
+        impl InterpretationPack for MoonOnly {
+            fn title(&self, topic: InterpretationTopic) -> String {
+                self.0.title(topic)
+            }
+            fn template(&self, key: &InterpretationKey) -> Option<String> {
+                match key {
+                    InterpretationKey::PlanetInSign(CelestialBody::Moon, _) => self.0.template(key),
+                    _ => None,
+                }
+            }
+        }
+        let sections = report.interpret(&MoonOnly(moon_only));
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].fragments.len(), 1);
+        assert!(sections[0].fragments[0].text.starts_with("Moon in "));
+    }
+}
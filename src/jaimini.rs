@@ -0,0 +1,444 @@
+use super::*;
+
+/// The chara (movable) karakas, ranked by how far each planet has advanced in its sign.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum CharaKaraka {
+    Atmakaraka,
+    Amatyakaraka,
+    Bhratrikaraka,
+    Matrikaraka,
+    /// Only in the eight-karaka scheme.
+    Pitrikaraka,
+    Putrakaraka,
+    Gnatikaraka,
+    Darakaraka,
+}
+
+/// Which planets take part in the chara karakas.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum KarakaScheme {
+    /// Sun to Saturn, as in the Jaimini Sutras.
+    Seven,
+    /// Sun to Saturn and Rahu, as in Parashara. Rahu moves backwards, so its degrees are
+    /// counted from the end of its sign.
+    #[default]
+    Eight,
+}
+
+impl KarakaScheme {
+    pub fn karakas(&self) -> Vec<CharaKaraka> {
+        use CharaKaraka::*;
+        match self {
+            KarakaScheme::Seven => vec![
+                Atmakaraka,
+                Amatyakaraka,
+                Bhratrikaraka,
+                Matrikaraka,
+                Putrakaraka,
+                Gnatikaraka,
+                Darakaraka,
+            ],
+            KarakaScheme::Eight => vec![
+                Atmakaraka,
+                Amatyakaraka,
+                Bhratrikaraka,
+                Matrikaraka,
+                Pitrikaraka,
+                Putrakaraka,
+                Gnatikaraka,
+                Darakaraka,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CharaKarakaInfo {
+    pub karaka: CharaKaraka,
+    pub planet: CelestialBody,
+    /// Degrees the planet has advanced in its sign, the figure the karakas are ranked by.
+    pub degree: f64,
+}
+
+/// The Jaimini reading of a birth chart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct JaiminiReport {
+    /// From the Atmakaraka down to the Darakaraka.
+    pub karakas: Vec<CharaKarakaInfo>,
+    /// Navamsa sign of the Atmakaraka.
+    pub karakamsa: ZodiacSign,
+    /// Arudha of the first house.
+    pub arudha_lagna: ZodiacSign,
+    /// Arudha of every house, the lagna's included.
+    pub bhava_arudhas: HashMap<House, ZodiacSign>,
+    /// Chara dasha from birth: a first round of all twelve signs from the lagna, then a
+    /// second round of the years the first left short of twelve.
+    pub chara_dasha: Vec<DashaPeriod>,
+    /// The chara dasha runs through the zodiac in order; otherwise backwards.
+    pub chara_dasha_direct: bool,
+}
+
+impl JaiminiReport {
+    pub fn atmakaraka(&self) -> Option<CelestialBody> {
+        self.karakas.first().map(|karaka| karaka.planet)
+    }
+
+    /// The twelve antardashas of a chara dasha period, a twelfth each. They start from the
+    /// sign after the period's own in the direction of the dasha and end with its own.
+    pub fn chara_antardashas(&self, period: &DashaPeriod) -> Vec<DashaPeriod> {
+        let DashaLord::Sign(sign) = period.lord else {
+            return Vec::new();
+        };
+        let twelfth = (period.end - period.start) / 12;
+        (1..=12)
+            .map(|step| {
+                let offset = if self.chara_dasha_direct {
+                    step
+                } else {
+                    12 - step
+                };
+                let start = period.start + twelfth * (step as i32 - 1);
+                DashaPeriod {
+                    lord: DashaLord::Sign(sign_at(sign as usize + offset)),
+                    start,
+                    end: if step == 12 {
+                        period.end
+                    } else {
+                        start + twelfth
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+fn sign_at(index: usize) -> ZodiacSign {
+    ZodiacSign::from_longitude((index % 12) as f64 * 30.0)
+}
+
+/// Signs counted from `from` to `to`, both included, going forward.
+fn count_forward(from: ZodiacSign, to: ZodiacSign) -> usize {
+    (to as usize + 12 - from as usize) % 12 + 1
+}
+
+/// Signs whose chara dasha years are counted forward to the lord, and whose place as 9th
+/// from the lagna runs the dasha forward.
+fn is_savya(sign: ZodiacSign) -> bool {
+    matches!(
+        sign,
+        ZodiacSign::Aries
+            | ZodiacSign::Taurus
+            | ZodiacSign::Gemini
+            | ZodiacSign::Libra
+            | ZodiacSign::Scorpio
+            | ZodiacSign::Sagittarius
+    )
+}
+
+impl ChartInfo {
+    pub(crate) fn planet(&self, body: CelestialBody) -> Option<&PlanetPosition> {
+        self.planets.iter().find(|planet| planet.planet == body)
+    }
+
+    /// The chara karakas under `scheme`, from the Atmakaraka down. Planets missing from the
+    /// chart are left out, along with the karakas they would have taken.
+    pub fn chara_karakas(&self, scheme: KarakaScheme) -> Vec<CharaKarakaInfo> {
+        let mut bodies = vec![
+            CelestialBody::Sun,
+            CelestialBody::Moon,
+            CelestialBody::Mars,
+            CelestialBody::Mercury,
+            CelestialBody::Jupiter,
+            CelestialBody::Venus,
+            CelestialBody::Saturn,
+        ];
+        if scheme == KarakaScheme::Eight {
+            bodies.push(CelestialBody::Rahu);
+        }
+        let mut ranked: Vec<(CelestialBody, f64)> = bodies
+            .into_iter()
+            .filter_map(|body| {
+                let degree = self.planet(body)?.longitude.degree_in_sign();
+                Some(match body {
+                    CelestialBody::Rahu => (body, 30.0 - degree),
+                    _ => (body, degree),
+                })
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        scheme
+            .karakas()
+            .into_iter()
+            .zip(ranked)
+            .map(|(karaka, (planet, degree))| CharaKarakaInfo {
+                karaka,
+                planet,
+                degree,
+            })
+            .collect()
+    }
+
+    /// Lord of `sign` for Jaimini purposes. Scorpio and Aquarius have two, Mars and Ketu
+    /// and Saturn and Rahu; the stronger is taken. A lord sitting in the sign itself gives
+    /// way to the other, then the one with more planets beside it wins, then the exalted
+    /// one, then the one further on in its sign.
+    pub fn jaimini_lord(&self, sign: ZodiacSign) -> CelestialBody {
+        let co_lord = match sign {
+            ZodiacSign::Scorpio => CelestialBody::Ketu,
+            ZodiacSign::Aquarius => CelestialBody::Rahu,
+            _ => return sign.lord(),
+        };
+        let lord = sign.lord();
+        let (Some(first), Some(second)) = (self.planet(lord), self.planet(co_lord)) else {
+            return lord;
+        };
+
+        match (first.sign == sign, second.sign == sign) {
+            (true, false) => return co_lord,
+            (false, true) => return lord,
+            _ => {}
+        }
+        let companions = |position: &PlanetPosition| {
+            self.planets
+                .iter()
+                .filter(|planet| planet.planet != position.planet && planet.sign == position.sign)
+                .count()
+        };
+        let exalted =
+            |position: &PlanetPosition| position.planet.exaltation_sign() == Some(position.sign);
+        let strength = |position: &PlanetPosition| {
+            (
+                companions(position),
+                exalted(position),
+                position.longitude.degree_in_sign(),
+            )
+        };
+        let (first_strength, second_strength) = (strength(first), strength(second));
+        let first_wins = match first_strength.0.cmp(&second_strength.0) {
+            std::cmp::Ordering::Equal => match (first_strength.1, second_strength.1) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => first_strength.2 >= second_strength.2,
+            },
+            ordering => ordering.is_gt(),
+        };
+        if first_wins {
+            lord
+        } else {
+            co_lord
+        }
+    }
+
+    /// The stronger of two signs, as Jaimini compares the signs a dasha may start from: the
+    /// one holding more of the nine grahas, then one holding an exalted graha, then a dual
+    /// sign over a fixed one and a fixed over a movable, then the one whose lord is further
+    /// on in its sign. `first` wins a tie.
+    pub fn stronger_sign(&self, first: ZodiacSign, second: ZodiacSign) -> ZodiacSign {
+        let strength = |sign: ZodiacSign| {
+            let grahas: Vec<&PlanetPosition> = self
+                .planets
+                .iter()
+                .filter(|planet| {
+                    planet.sign == sign
+                        && !CelestialBody::outer_planets().contains(&planet.planet)
+                        && !CelestialBody::asteroids().contains(&planet.planet)
+                })
+                .collect();
+            let exalted = grahas
+                .iter()
+                .any(|planet| planet.planet.exaltation_sign() == Some(sign));
+            // Movable, fixed and dual signs take turns from Aries
+            let modality = sign as usize % 3;
+            let lord_degree = self
+                .planet(self.jaimini_lord(sign))
+                .map_or(0.0, |lord| lord.longitude.degree_in_sign());
+            (grahas.len(), exalted, modality, lord_degree)
+        };
+        let (a, b) = (strength(first), strength(second));
+        let first_wins = match (a.0, a.1, a.2).cmp(&(b.0, b.1, b.2)) {
+            std::cmp::Ordering::Equal => a.3 >= b.3,
+            ordering => ordering.is_gt(),
+        };
+        if first_wins {
+            first
+        } else {
+            second
+        }
+    }
+
+    /// Arudha (pada) of `house`, counting whole signs from the lagna: as far beyond the
+    /// house lord as the lord is from the house. An arudha falling in the house itself or
+    /// the 7th from it moves to the 10th from there.
+    pub fn arudha(&self, house: House) -> ZodiacSign {
+        let sign = sign_at(self.ascendant.sign as usize + house as usize - 1);
+        let lord_sign = self
+            .planet(self.jaimini_lord(sign))
+            .map_or(sign, |lord| lord.sign);
+        let distance = count_forward(sign, lord_sign);
+        let arudha = sign_at(lord_sign as usize + distance - 1);
+        match count_forward(sign, arudha) {
+            1 | 7 => sign_at(arudha as usize + 9),
+            _ => arudha,
+        }
+    }
+
+    /// Arudhas A1 to A12, the padas of every house. A1 is the arudha lagna and A12 the
+    /// upapada.
+    pub fn arudhas(&self) -> HashMap<House, ZodiacSign> {
+        House::all()
+            .map(|house| (house, self.arudha(house)))
+            .collect()
+    }
+
+    /// Upapada lagna, the arudha of the 12th house, read for marriage and the spouse.
+    pub fn upapada(&self) -> ZodiacSign {
+        self.arudha(House::Twelfth)
+    }
+
+    /// Length of `sign`'s chara dasha in years: the signs from it to its lord, less one,
+    /// counted forward from savya signs and backward from the rest. A lord in the sign
+    /// itself gives twelve. An exalted lord adds a year and a debilitated one takes a year
+    /// away.
+    pub fn chara_dasha_years(&self, sign: ZodiacSign) -> u32 {
+        let Some(lord) = self.planet(self.jaimini_lord(sign)) else {
+            return 12;
+        };
+        let count = if is_savya(sign) {
+            count_forward(sign, lord.sign)
+        } else {
+            count_forward(lord.sign, sign)
+        };
+        let years = match count - 1 {
+            0 => 12,
+            years => years as u32,
+        };
+        if lord.planet.exaltation_sign() == Some(lord.sign) {
+            years + 1
+        } else if lord.planet.debilitation_sign() == Some(lord.sign) {
+            years - 1
+        } else {
+            years
+        }
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Chara karakas, karakamsa, arudhas and chara dasha of a birth, on the engine's house
+    /// system. Arudhas count whole signs from the lagna whatever the house system.
+    pub fn calculate_jaimini(
+        &self,
+        birth_info: &BirthInfo,
+        scheme: KarakaScheme,
+    ) -> Result<JaiminiReport, AstrologyError> {
+        let chart = self.calculate_chart(birth_info, self.conventions.house_system)?;
+
+        let karakas = chart.chara_karakas(scheme);
+        let atmakaraka = karakas
+            .first()
+            .and_then(|karaka| chart.planet(karaka.planet))
+            .ok_or_else(|| {
+                AstrologyError::InvalidInput(
+                    "No planets in the chart to rank as karakas".to_string(),
+                )
+            })?;
+        let karakamsa =
+            ZodiacSign::from_longitude(navamsa_longitude(atmakaraka.longitude.degrees()));
+
+        let bhava_arudhas = chart.arudhas();
+
+        let lagna = chart.ascendant.sign;
+        let chara_dasha_direct = is_savya(sign_at(lagna as usize + 8));
+        let signs: Vec<ZodiacSign> = (0..12)
+            .map(|step| {
+                let offset = if chara_dasha_direct { step } else { 12 - step };
+                sign_at(lagna as usize + offset)
+            })
+            .collect();
+        let first_round: Vec<(ZodiacSign, u32)> = signs
+            .iter()
+            .map(|&sign| (sign, chart.chara_dasha_years(sign)))
+            .collect();
+        let second_round = first_round
+            .iter()
+            .map(|&(sign, years)| (sign, 12u32.saturating_sub(years)));
+
+        let year_seconds = self.conventions.dasha_year_days * 86400.0;
+        let mut start = birth_info.date_time;
+        let chara_dasha = first_round
+            .iter()
+            .copied()
+            .chain(second_round)
+            .filter(|&(_, years)| years > 0)
+            .map(|(sign, years)| {
+                let end = start + ChronoDuration::seconds((years as f64 * year_seconds) as i64);
+                let period = DashaPeriod {
+                    lord: DashaLord::Sign(sign),
+                    start,
+                    end,
+                };
+                start = end;
+                period
+            })
+            .collect();
+
+        Ok(JaiminiReport {
+            arudha_lagna: bhava_arudhas[&House::First],
+            karakas,
+            karakamsa,
+            bhava_arudhas,
+            chara_dasha,
+            chara_dasha_direct,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn arudhas_skip_the_house_and_its_seventh() {
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 10.0,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                planet(CelestialBody::Mars, 100.0),
+                planet(CelestialBody::Jupiter, 40.0),
+            ],
+            warnings: Vec::new(),
+        };
+        // Mars is 4th from the lagna and the 4th from Mars is Libra, the 7th, so the
+        // arudha lagna moves on to the 10th from Libra
+        let arudhas = chart.arudhas();
+        assert_eq!(arudhas.len(), 12);
+        assert_eq!(arudhas[&House::First], ZodiacSign::Cancer);
+        // Jupiter, lord of Pisces, is 3rd from it in Taurus
+        assert_eq!(chart.upapada(), ZodiacSign::Cancer);
+        assert_eq!(arudhas[&House::Twelfth], chart.upapada());
+    }
+}
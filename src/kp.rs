@@ -0,0 +1,317 @@
+use super::*;
+
+const KP_NAKSHATRA_SPAN: f64 = 360.0 / 27.0;
+/// Degrees by which a longitude may miss a sub boundary through rounding alone.
+const BOUNDARY_TOLERANCE: f64 = 1e-9;
+
+/// The chain of rulers Krishnamurti Paddhati reads for a longitude. Each nakshatra is cut
+/// into nine subs in proportion to the Vimshottari years, starting from the nakshatra's own
+/// lord, and each sub is cut again the same way into sub-subs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KpLords {
+    pub sign_lord: CelestialBody,
+    pub star_lord: CelestialBody,
+    pub sub_lord: CelestialBody,
+    pub sub_sub_lord: CelestialBody,
+}
+
+impl KpLords {
+    pub fn from_longitude(longitude: f64) -> KpLords {
+        let longitude = longitude.rem_euclid(360.0);
+        let star_lord = NakshatraInfo::from_longitude(longitude).lord;
+        let star = Dasha::from_lord(star_lord).unwrap_or(Dasha::Ketu);
+
+        let (sub, offset, span) =
+            vimshottari_division(star, KP_NAKSHATRA_SPAN, longitude % KP_NAKSHATRA_SPAN);
+        let (sub_sub, _, _) = vimshottari_division(sub, span, offset);
+
+        KpLords {
+            sign_lord: ZodiacSign::from_longitude(longitude).lord(),
+            star_lord,
+            sub_lord: sub.lord(),
+            sub_sub_lord: sub_sub.lord(),
+        }
+    }
+}
+
+/// Splits `span` degrees into nine parts in Vimshottari proportion starting from `first`,
+/// and returns the part holding `offset`, the offset within that part and its width. An
+/// offset within rounding error of a boundary is in the part that starts there.
+fn vimshottari_division(first: Dasha, span: f64, offset: f64) -> (Dasha, f64, f64) {
+    let sequence = Dasha::sequence();
+    let start = sequence.iter().position(|&d| d == first).unwrap_or(0);
+    let mut remaining = offset;
+    let mut part = (first, 0.0);
+    for i in 0..sequence.len() {
+        let dasha = sequence[(start + i) % sequence.len()];
+        let width = span * dasha.years() / 120.0;
+        part = (dasha, width);
+        if remaining < width - BOUNDARY_TOLERANCE {
+            break;
+        }
+        remaining -= width;
+    }
+    (part.0, remaining.clamp(0.0, part.1), part.1)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KpCusp {
+    pub house: House,
+    pub longitude: f64,
+    pub lords: KpLords,
+}
+
+impl KpCusp {
+    pub fn from_cusp(cusp: &HouseCusp) -> KpCusp {
+        KpCusp {
+            house: cusp.house,
+            longitude: cusp.longitude(),
+            lords: KpLords::from_longitude(cusp.longitude()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KpPlanet {
+    pub planet: CelestialBody,
+    pub longitude: f64,
+    pub house: House,
+    pub lords: KpLords,
+}
+
+impl KpPlanet {
+    pub fn from_position(planet: &PlanetPosition) -> KpPlanet {
+        KpPlanet {
+            planet: planet.planet,
+            longitude: planet.longitude.degrees(),
+            house: planet.house,
+            lords: KpLords::from_longitude(planet.longitude.degrees()),
+        }
+    }
+}
+
+/// A chart read the KP way: Placidus cusps and planets on the KP ayanamsa, each with its
+/// lords, and the significators of every house.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KpChart {
+    pub cusps: Vec<KpCusp>,
+    pub planets: Vec<KpPlanet>,
+    /// Planets signifying each house, strongest first.
+    pub significators: HashMap<House, Vec<CelestialBody>>,
+}
+
+impl KpChart {
+    pub fn from_cusps_and_planets(cusps: Vec<KpCusp>, planets: Vec<KpPlanet>) -> KpChart {
+        let significators = cusps
+            .iter()
+            .map(|cusp| (cusp.house, house_significators(cusp, &planets)))
+            .collect();
+        KpChart {
+            cusps,
+            planets,
+            significators,
+        }
+    }
+
+    pub fn cusp(&self, house: House) -> Option<&KpCusp> {
+        self.cusps.iter().find(|cusp| cusp.house == house)
+    }
+
+    pub fn significators_of(&self, house: House) -> &[CelestialBody] {
+        self.significators
+            .get(&house)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+/// The four levels of signification, strongest first: planets in the star of an occupant,
+/// the occupants, planets in the star of the cusp's sign lord, and that lord itself.
+fn house_significators(cusp: &KpCusp, planets: &[KpPlanet]) -> Vec<CelestialBody> {
+    let occupants: Vec<CelestialBody> = planets
+        .iter()
+        .filter(|planet| planet.house == cusp.house)
+        .map(|planet| planet.planet)
+        .collect();
+    let owner = cusp.lords.sign_lord;
+
+    let in_star_of = |lords: &[CelestialBody]| {
+        planets
+            .iter()
+            .filter(|planet| lords.contains(&planet.lords.star_lord))
+            .map(|planet| planet.planet)
+            .collect::<Vec<_>>()
+    };
+
+    let mut significators = Vec::new();
+    for planet in in_star_of(&occupants)
+        .into_iter()
+        .chain(occupants.iter().copied())
+        .chain(in_star_of(&[owner]))
+        .chain(std::iter::once(owner))
+    {
+        if !significators.contains(&planet) {
+            significators.push(planet);
+        }
+    }
+    significators
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Casts the KP chart for `birth_info`. KP always uses its own ayanamsa and Placidus
+    /// cusps, whatever conventions this engine was given; the node type is kept.
+    pub fn calculate_kp_chart(&self, birth_info: &BirthInfo) -> Result<KpChart, AstrologyError> {
+        let chart = self
+            .kp_engine()
+            .calculate_chart(birth_info, HouseSystem::Krishnamurti)?;
+
+        let cusps = chart.houses.iter().map(KpCusp::from_cusp).collect();
+        let planets = chart.planets.iter().map(KpPlanet::from_position).collect();
+
+        Ok(KpChart::from_cusps_and_planets(cusps, planets))
+    }
+
+    /// This engine switched to the KP ayanamsa and Placidus cusps.
+    pub(crate) fn kp_engine(&self) -> SwissEph {
+        SwissEph {
+            friendships: self.friendships.clone(),
+            conventions: Conventions {
+                ayanamsa: Ayanamsa::Krishnamurti,
+                house_system: HouseSystem::Krishnamurti,
+                ..self.conventions
+            },
+            files: self.files.clone(),
+            position_cache: PositionCache::new(self.position_cache.settings()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CelestialBody::*;
+
+    fn dms(degrees: u32, minutes: u32, seconds: f64) -> f64 {
+        degrees as f64 + minutes as f64 / 60.0 + seconds / 3600.0
+    }
+
+    #[test]
+    fn subs_change_where_the_kp_table_has_them() {
+        // Ashwini, Bharani and the start of Krittika in the standard table of 249 subs,
+        // each boundary with the sub before and after it
+        let boundaries = [
+            (dms(0, 46, 40.0), Ketu, Venus),
+            (dms(3, 0, 0.0), Venus, Sun),
+            (dms(3, 40, 0.0), Sun, Moon),
+            (dms(4, 46, 40.0), Moon, Mars),
+            (dms(5, 33, 20.0), Mars, Rahu),
+            (dms(7, 33, 20.0), Rahu, Jupiter),
+            (dms(9, 20, 0.0), Jupiter, Saturn),
+            (dms(11, 26, 40.0), Saturn, Mercury),
+            (dms(13, 20, 0.0), Mercury, Venus),
+            (dms(15, 33, 20.0), Venus, Sun),
+            (dms(21, 53, 20.0), Jupiter, Saturn),
+            (dms(25, 53, 20.0), Mercury, Ketu),
+            (dms(26, 40, 0.0), Ketu, Sun),
+            (dms(29, 13, 20.0), Mars, Rahu),
+            (dms(31, 13, 20.0), Rahu, Jupiter),
+        ];
+        for (boundary, before, after) in boundaries {
+            assert_eq!(
+                KpLords::from_longitude(boundary - 1e-6).sub_lord,
+                before,
+                "{}",
+                boundary
+            );
+            assert_eq!(
+                KpLords::from_longitude(boundary + 1e-6).sub_lord,
+                after,
+                "{}",
+                boundary
+            );
+        }
+
+        // The Rahu sub of Krittika runs on from Aries into Taurus, which is why the table
+        // has 249 subs and not 243
+        let aries = KpLords::from_longitude(29.9);
+        let taurus = KpLords::from_longitude(30.1);
+        assert_eq!(
+            (aries.sign_lord, aries.star_lord, aries.sub_lord),
+            (Mars, Sun, Rahu)
+        );
+        assert_eq!(
+            (taurus.sign_lord, taurus.star_lord, taurus.sub_lord),
+            (Venus, Sun, Rahu)
+        );
+        // Revati ends in Saturn's sub, and Ashwini starts again from Ketu at 0° Aries
+        assert_eq!(KpLords::from_longitude(359.99).sub_lord, Saturn);
+        assert_eq!(KpLords::from_longitude(-0.01).sub_lord, Saturn);
+        assert_eq!(KpLords::from_longitude(0.0).sub_lord, Ketu);
+        // 60° falls on the boundary of the Saturn and Mercury subs of Mrigashira
+        assert_eq!(KpLords::from_longitude(60.0).sub_lord, Mercury);
+    }
+
+    #[test]
+    fn sub_subs_divide_each_sub_in_the_same_proportions() {
+        // The Ketu sub of Ashwini, 46'40" wide, split from Ketu: 2'43.3", then Venus 7'46.7"
+        let ketu_sub = [
+            (dms(0, 2, 43.33), Ketu, Venus),
+            (dms(0, 10, 30.0), Venus, Sun),
+            (dms(0, 46, 40.0), Mercury, Venus),
+        ];
+        // The Venus sub of Ashwini, 2°13'20" wide from 0°46'40", split from Venus: 22'13.3"
+        let venus_sub = [
+            (dms(1, 8, 53.33), Venus, Sun),
+            (dms(1, 15, 33.33), Sun, Moon),
+        ];
+        for (boundary, before, after) in ketu_sub.into_iter().chain(venus_sub) {
+            let (below, above) = (
+                KpLords::from_longitude(boundary - 1e-5),
+                KpLords::from_longitude(boundary + 1e-5),
+            );
+            assert_eq!(below.sub_sub_lord, before, "{}", boundary);
+            assert_eq!(above.sub_sub_lord, after, "{}", boundary);
+        }
+        assert_eq!(KpLords::from_longitude(dms(1, 0, 0.0)).sub_lord, Venus);
+    }
+
+    #[test]
+    fn significators_come_strongest_first() {
+        let planet = |planet, longitude, house| KpPlanet {
+            planet,
+            longitude,
+            house,
+            lords: KpLords::from_longitude(longitude),
+        };
+        let cusp = KpCusp {
+            house: House::First,
+            longitude: 5.0,
+            lords: KpLords::from_longitude(5.0),
+        };
+        let chart = KpChart::from_cusps_and_planets(
+            vec![cusp],
+            vec![
+                // Mars, lord of the cusp's sign, in Mula, Ketu's star
+                planet(Mars, 240.0, House::Ninth),
+                // In Chitra, a star of Mars
+                planet(Saturn, 180.0, House::Seventh),
+                // The occupant, in Bharani
+                planet(Sun, 20.0, House::First),
+                // In Uttara Phalguni, a star of the Sun
+                planet(Jupiter, 150.0, House::Fifth),
+                planet(Venus, 320.0, House::Eleventh),
+            ],
+        );
+
+        assert_eq!(
+            chart.significators_of(House::First),
+            [Jupiter, Sun, Saturn, Mars]
+        );
+        assert!(chart.significators_of(House::Second).is_empty());
+    }
+}
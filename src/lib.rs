@@ -1,22 +1,157 @@
 // src/main.rs
 
-use chrono::{DateTime, Datelike, Duration as ChronoDuration, FixedOffset, Local, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Duration as ChronoDuration, FixedOffset, NaiveDate, NaiveTime, TimeZone,
+    Timelike, Utc,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::error::Error;
-use std::ffi::{CStr, CString};
 use std::fmt;
-use std::io::Cursor;
-use std::os::raw::{c_char, c_double, c_int};
-use std::sync::Once;
-use std::time::Duration;
-use tempfile::NamedTempFile;
+use std::os::raw::c_int;
+
+#[cfg(not(feature = "model-only"))]
+use chrono::Datelike;
+#[cfg(not(feature = "model-only"))]
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_double},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+pub mod core;
+pub mod model;
+#[cfg(all(feature = "capi", not(feature = "model-only")))]
+pub mod capi;
+#[cfg(not(feature = "model-only"))]
+pub mod engine;
+#[cfg(not(feature = "model-only"))]
+pub mod examples;
+#[cfg(all(any(test, feature = "verify"), not(feature = "model-only")))]
+pub mod verify;
+
+mod ashtakavarga;
+mod aspects;
+mod avastha;
+mod bala;
+#[cfg(not(feature = "model-only"))]
+mod batch;
+mod bhava_chalit;
+mod calendar;
+#[cfg(not(feature = "model-only"))]
+mod chart_cache;
+mod chart_file;
+mod chart_svg;
+mod cities;
+mod compatibility;
+mod conditional_dasha;
+mod dasha;
+mod dasha_systems;
+mod data_quality;
+mod day_periods;
+mod dignity;
+mod ephemeris;
+mod ephemeris_mode;
+mod ephemeris_table;
+mod equatorial;
+mod fixed_stars;
+mod friendship;
+mod graha;
+mod import;
+mod interpretation;
+mod jaimini;
+mod kp;
+mod locale;
+mod longitude;
+mod lunisolar;
+mod midpoints;
+mod motion;
+mod muhurta;
+mod mundane;
+mod nakshatra;
+mod overlay;
+mod panchanga;
+mod position_cache;
+mod prashna;
+mod progressions;
+mod proximity;
+mod rectification;
+mod returns;
+#[cfg(not(feature = "model-only"))]
+mod rise_set;
+mod sade_sati;
+mod sankranti;
+mod synastry;
+mod tarabala;
+#[cfg(not(feature = "model-only"))]
+mod timing;
+mod tradition;
+mod transits;
+mod upagrahas;
+mod vargas;
+mod varshaphal;
+mod yogas;
+
+pub use ashtakavarga::*;
+pub use aspects::*;
+pub use avastha::*;
+pub use bala::*;
+pub use bhava_chalit::*;
+pub use calendar::*;
+#[cfg(not(feature = "model-only"))]
+pub use chart_cache::*;
+pub use chart_file::*;
+pub use compatibility::*;
+pub use conditional_dasha::*;
+pub use dasha::*;
+pub use dasha_systems::*;
+pub use data_quality::*;
+pub use day_periods::*;
+pub use ephemeris::*;
+pub use ephemeris_mode::*;
+pub use ephemeris_table::*;
+pub use equatorial::*;
+pub use fixed_stars::*;
+pub use friendship::*;
+pub use graha::*;
+pub use interpretation::*;
+pub use jaimini::*;
+pub use kp::*;
+pub use locale::*;
+pub use longitude::*;
+pub use lunisolar::*;
+pub use midpoints::*;
+pub use motion::*;
+pub use muhurta::*;
+pub use mundane::*;
+pub use nakshatra::*;
+pub use overlay::*;
+pub use panchanga::*;
+pub use position_cache::*;
+pub use prashna::*;
+pub use progressions::*;
+pub use proximity::*;
+pub use rectification::*;
+pub use returns::*;
+pub use sade_sati::*;
+pub use sankranti::*;
+pub use synastry::*;
+pub use tarabala::*;
+#[cfg(not(feature = "model-only"))]
+pub use timing::*;
+pub use tradition::*;
+pub use transits::*;
+pub use upagrahas::*;
+pub use vargas::*;
+pub use varshaphal::*;
+pub use yogas::*;
 
 // ---------------------------
 // ## Enumerations
 // ---------------------------
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum CalculationFlag {
     Speed = SEFLG_SPEED as isize,
     NoGravitationalDeflection = 512,
@@ -29,13 +164,15 @@ pub enum CalculationFlag {
     Heliocentric = 8,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum CoordinateSystem {
     Tropical,
     Sidereal,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 #[repr(i32)]
 pub enum CelestialBody {
     Sun = 0,
@@ -45,11 +182,39 @@ pub enum CelestialBody {
     Mars = 4,
     Jupiter = 5,
     Saturn = 6,
+    Uranus = 7,
+    Neptune = 8,
+    Pluto = 9,
     Rahu = 11,
+    Chiron = 15,
+    Ceres = 17,
+    Pallas = 18,
+    Juno = 19,
+    Vesta = 20,
     Ketu = 999,
 }
 
 impl CelestialBody {
+    pub fn outer_planets() -> [CelestialBody; 3] {
+        [
+            CelestialBody::Uranus,
+            CelestialBody::Neptune,
+            CelestialBody::Pluto,
+        ]
+    }
+
+    /// Chiron and the four major asteroids.
+    pub fn asteroids() -> [CelestialBody; 5] {
+        [
+            CelestialBody::Chiron,
+            CelestialBody::Ceres,
+            CelestialBody::Pallas,
+            CelestialBody::Juno,
+            CelestialBody::Vesta,
+        ]
+    }
+
+    /// The nine grahas.
     fn iter() -> impl Iterator<Item = CelestialBody> {
         [
             CelestialBody::Sun,
@@ -65,9 +230,88 @@ impl CelestialBody {
         .iter()
         .copied()
     }
+
+    /// Distance from the Sun within which the planet is combust while moving forward, or
+    /// `None` for bodies that never combust. See [`CombustionOrbs`] for retrograde orbs.
+    pub fn combustion_orb(&self) -> Option<f64> {
+        CombustionOrbs::default().orb(*self, false)
+    }
+
+    /// Sign of exaltation for the seven planets.
+    pub fn exaltation_sign(&self) -> Option<ZodiacSign> {
+        match self {
+            CelestialBody::Sun => Some(ZodiacSign::Aries),
+            CelestialBody::Moon => Some(ZodiacSign::Taurus),
+            CelestialBody::Mars => Some(ZodiacSign::Capricorn),
+            CelestialBody::Mercury => Some(ZodiacSign::Virgo),
+            CelestialBody::Jupiter => Some(ZodiacSign::Cancer),
+            CelestialBody::Venus => Some(ZodiacSign::Pisces),
+            CelestialBody::Saturn => Some(ZodiacSign::Libra),
+            _ => None,
+        }
+    }
+
+    /// Sidereal longitude of deepest exaltation for the seven planets.
+    pub fn exaltation_degree(&self) -> Option<f64> {
+        let degree = match self {
+            CelestialBody::Sun => 10.0,
+            CelestialBody::Moon => 3.0,
+            CelestialBody::Mars => 28.0,
+            CelestialBody::Mercury => 15.0,
+            CelestialBody::Jupiter => 5.0,
+            CelestialBody::Venus => 27.0,
+            CelestialBody::Saturn => 20.0,
+            _ => return None,
+        };
+        self.exaltation_sign()
+            .map(|sign| sign as usize as f64 * 30.0 + degree)
+    }
+
+    /// Sign of debilitation, opposite the sign of exaltation.
+    pub fn debilitation_sign(&self) -> Option<ZodiacSign> {
+        self.exaltation_sign()
+            .map(|sign| ZodiacSign::from_longitude((sign as usize + 6) as f64 * 30.0))
+    }
+
+    /// Sidereal longitude of deepest debilitation, opposite the point of deepest exaltation.
+    pub fn debilitation_degree(&self) -> Option<f64> {
+        self.exaltation_degree()
+            .map(|degree| (degree + 180.0).rem_euclid(360.0))
+    }
+
+    /// Degrees within the moolatrikona sign that make up the moolatrikona, after Brihat
+    /// Parashara Hora Shastra: Sun 0-20° Leo, Moon 4-30° Taurus, Mars 0-12° Aries, Mercury
+    /// 16-20° Virgo, Jupiter 0-10° Sagittarius, Venus 0-15° Libra and Saturn 0-20°
+    /// Aquarius. The rest of the sign is exaltation or own sign.
+    pub fn moolatrikona_range(&self) -> Option<(f64, f64)> {
+        match self {
+            CelestialBody::Sun => Some((0.0, 20.0)),
+            CelestialBody::Moon => Some((4.0, 30.0)),
+            CelestialBody::Mars => Some((0.0, 12.0)),
+            CelestialBody::Mercury => Some((16.0, 20.0)),
+            CelestialBody::Jupiter => Some((0.0, 10.0)),
+            CelestialBody::Venus => Some((0.0, 15.0)),
+            CelestialBody::Saturn => Some((0.0, 20.0)),
+            _ => None,
+        }
+    }
+
+    pub fn moolatrikona_sign(&self) -> Option<ZodiacSign> {
+        match self {
+            CelestialBody::Sun => Some(ZodiacSign::Leo),
+            CelestialBody::Moon => Some(ZodiacSign::Taurus),
+            CelestialBody::Mars => Some(ZodiacSign::Aries),
+            CelestialBody::Mercury => Some(ZodiacSign::Virgo),
+            CelestialBody::Jupiter => Some(ZodiacSign::Sagittarius),
+            CelestialBody::Venus => Some(ZodiacSign::Libra),
+            CelestialBody::Saturn => Some(ZodiacSign::Aquarius),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum House {
     First = 1,
     Second,
@@ -103,11 +347,12 @@ impl House {
     }
 
     pub fn all() -> impl Iterator<Item = House> {
-        (1..=12).map(House::from_index).flatten()
+        (1..=12).filter_map(House::from_index)
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum ZodiacSign {
     Aries = 0,
     Taurus,
@@ -125,9 +370,7 @@ pub enum ZodiacSign {
 
 impl ZodiacSign {
     pub fn from_longitude(longitude: f64) -> Self {
-        let normalized_longitude = longitude.rem_euclid(360.0);
-        let sign_index = (normalized_longitude / 30.0).floor() as usize;
-        match sign_index {
+        match crate::core::sign_index(longitude) {
             0 => ZodiacSign::Aries,
             1 => ZodiacSign::Taurus,
             2 => ZodiacSign::Gemini,
@@ -143,6 +386,19 @@ impl ZodiacSign {
             _ => ZodiacSign::Aries, // Fallback
         }
     }
+
+    /// The planet ruling the sign.
+    pub fn lord(&self) -> CelestialBody {
+        match self {
+            ZodiacSign::Aries | ZodiacSign::Scorpio => CelestialBody::Mars,
+            ZodiacSign::Taurus | ZodiacSign::Libra => CelestialBody::Venus,
+            ZodiacSign::Gemini | ZodiacSign::Virgo => CelestialBody::Mercury,
+            ZodiacSign::Cancer => CelestialBody::Moon,
+            ZodiacSign::Leo => CelestialBody::Sun,
+            ZodiacSign::Sagittarius | ZodiacSign::Pisces => CelestialBody::Jupiter,
+            ZodiacSign::Capricorn | ZodiacSign::Aquarius => CelestialBody::Saturn,
+        }
+    }
 }
 
 impl fmt::Display for ZodiacSign {
@@ -165,7 +421,8 @@ impl fmt::Display for ZodiacSign {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Nakshatra {
     Ashwini,
     Bharani,
@@ -198,9 +455,7 @@ pub enum Nakshatra {
 
 impl Nakshatra {
     pub fn from_longitude(longitude: f64) -> Nakshatra {
-        let normalized_longitude = longitude.rem_euclid(360.0);
-        let nakshatra_index = (normalized_longitude / 13.333333333333334).floor() as usize;
-        match nakshatra_index {
+        match crate::core::nakshatra_index(longitude) {
             0 => Nakshatra::Ashwini,
             1 => Nakshatra::Bharani,
             2 => Nakshatra::Krittika,
@@ -233,7 +488,8 @@ impl Nakshatra {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Dasha {
     Ketu,
     Venus,
@@ -246,7 +502,8 @@ pub enum Dasha {
     Mercury,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum PlanetaryState {
     Exalted,
     DeepExaltation,
@@ -272,14 +529,94 @@ impl fmt::Display for PlanetaryState {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The conditions of a planet in a chart, each judged on its own so that, say, a
+/// retrograde planet keeps its exaltation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PlanetStateSet {
+    /// Exalted, debilitated (either deeply) or in its own sign, if any of these, and
+    /// otherwise its five-fold relationship to the lord of its sign.
+    pub dignity: Option<PlanetaryState>,
+    /// [`PlanetaryState::Retrograde`] or [`PlanetaryState::Direct`].
+    pub motion: PlanetaryState,
+    pub combust: bool,
+    /// The planet's side of a planetary war, if it is in one.
+    pub war: Option<WarSide>,
+    /// [`PlanetaryState::Benefic`] or [`PlanetaryState::Malefic`].
+    pub nature: PlanetaryState,
+}
+
+impl PlanetStateSet {
+    pub fn is_retrograde(&self) -> bool {
+        self.motion == PlanetaryState::Retrograde
+    }
+}
+
+impl fmt::Display for PlanetStateSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut states: Vec<String> = self.dignity.iter().map(|state| state.to_string()).collect();
+        states.push(self.motion.to_string());
+        if self.combust {
+            states.push(PlanetaryState::Combust.to_string());
+        }
+        if let Some(war) = &self.war {
+            let outcome = if war.won { "Won" } else { "Lost" };
+            states.push(format!("{} war with {:?}", outcome, war.opponent));
+        }
+        states.push(self.nature.to_string());
+        write!(f, "{}", states.join(", "))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum ChartType {
     Rasi,
     Navamsa,
     Hora,
+    Saptamsa,
+    Dashamsa,
+    Dwadasamsa,
+}
+
+/// How the sky is divided into the twelve houses. Independent of `ChartType`: any chart
+/// can be cast in any house system.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum HouseSystem {
+    #[default]
+    Placidus,
+    Koch,
+    /// Each sign is one house, counted from the sign of the ascendant.
+    WholeSign,
+    /// Twelve 30° houses starting at the ascendant degree.
+    Equal,
+    /// Porphyry cusps with each bhava starting midway between two of them.
+    Sripati,
+    Campanus,
+    Porphyry,
+    /// Krishnamurti Paddhati, which reads Placidus cusps.
+    Krishnamurti,
+}
+
+impl HouseSystem {
+    /// The `hsys` character Swiss Ephemeris expects for this system.
+    pub fn code(&self) -> c_int {
+        let code = match self {
+            HouseSystem::Placidus | HouseSystem::Krishnamurti => b'P',
+            HouseSystem::Koch => b'K',
+            HouseSystem::WholeSign => b'W',
+            HouseSystem::Equal => b'E',
+            HouseSystem::Sripati => b'S',
+            HouseSystem::Campanus => b'C',
+            HouseSystem::Porphyry => b'O',
+        };
+        code as c_int
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum SpecialLagna {
     Bhava,
     Hora,
@@ -289,31 +626,70 @@ pub enum SpecialLagna {
     Pranapada,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Upagraha {
     Dhuma,
     Vyatipata,
     Parivesha,
     Indrachaapa,
     Upaketu,
+    Kala,
+    Mrityu,
+    ArthaPrahara,
+    YamaGhantaka,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum SensitivePoint {
     Gulika,
     Mandi,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Aspect {
     Conjunction,
     Opposition,
     Trine,
     Square,
     Sextile,
+    /// 150°, a minor aspect, only found when asked for.
+    Quincunx,
+    /// 30°, a minor aspect, only found when asked for.
+    SemiSextile,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// One of the angles of a chart, as held in [`Angles`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Angle {
+    Ascendant,
+    Midheaven,
+    Vertex,
+    EastPoint,
+}
+
+/// What a planet aspects.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AspectTarget {
+    Planet(CelestialBody),
+    Angle(Angle),
+    Cusp(House),
+}
+
+/// Whether an aspect is growing closer to exact or moving away from it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AspectPhase {
+    Applying,
+    Separating,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Trait {
     Health,
     Wealth,
@@ -347,7 +723,8 @@ pub enum Trait {
     Generosity,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum On {
     Oneself,
     Spouse,
@@ -378,7 +755,8 @@ pub enum On {
 // ## Structures
 // ---------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct CelestialCoordinates {
     pub longitude: f64,
     pub latitude: f64,
@@ -388,22 +766,80 @@ pub struct CelestialCoordinates {
     pub speed_distance: f64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct HouseCusp {
     pub house: House,
     pub sign: ZodiacSign,
     pub degree: f64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct CalculationError {
-    pub code: i32,
-    pub message: String,
+impl HouseCusp {
+    /// Longitude of the cusp in the zodiac, from its sign and degree within the sign.
+    pub fn longitude(&self) -> f64 {
+        self.sign as usize as f64 * 30.0 + self.degree
+    }
+}
+
+/// A point fixed by the horizon and the meridian rather than by a body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AnglePoint {
+    pub longitude: Longitude,
+    pub sign: ZodiacSign,
+    /// Degrees into the sign.
+    pub degree: f64,
+    pub nakshatra: NakshatraInfo,
+}
+
+impl AnglePoint {
+    pub fn from_longitude(longitude: f64) -> AnglePoint {
+        let longitude = Longitude::new(longitude);
+        AnglePoint {
+            longitude,
+            sign: ZodiacSign::from_longitude(longitude.degrees()),
+            degree: longitude.degree_in_sign(),
+            nakshatra: NakshatraInfo::from_longitude(longitude.degrees()),
+        }
+    }
+}
+
+/// The angles of a chart, in the zodiac its houses were calculated in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Angles {
+    pub ascendant: AnglePoint,
+    /// Where the ecliptic crosses the meridian, the cusp of the 10th in quadrant systems.
+    pub midheaven: AnglePoint,
+    /// Where the ecliptic crosses the prime vertical in the west.
+    pub vertex: AnglePoint,
+    /// The equatorial ascendant: the ecliptic degree whose right ascension is a quarter of
+    /// the circle past the midheaven's.
+    pub east_point: AnglePoint,
+    /// Right ascension of the midheaven in degrees. It is the same in both zodiacs.
+    pub armc: f64,
+}
+
+impl Angles {
+    /// Local sidereal time in hours, the ARMC turned into time.
+    pub fn local_sidereal_time(&self) -> f64 {
+        self.armc / 15.0
+    }
+}
+
+/// The cusps and angles of one house calculation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Houses {
+    /// From the 1st house to the 12th.
+    pub cusps: Vec<HouseCusp>,
+    pub angles: Angles,
 }
 
 pub type JulianDay = f64;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct NakshatraInfo {
     pub nakshatra: Nakshatra,
     pub pada: u8,
@@ -413,10 +849,9 @@ pub struct NakshatraInfo {
 
 impl NakshatraInfo {
     pub fn from_longitude(longitude: f64) -> NakshatraInfo {
-        let normalized_longitude = longitude.rem_euclid(360.0);
+        let normalized_longitude = crate::core::normalize(longitude);
         let nakshatra = Nakshatra::from_longitude(normalized_longitude);
-        let pada_length = 13.333333333333334 / 4.0;
-        let pada = ((normalized_longitude % 13.333333333333334) / pada_length).floor() as u8 + 1;
+        let pada = crate::core::pada(normalized_longitude);
         let lord = NakshatraInfo::get_nakshatra_lord(nakshatra);
         NakshatraInfo {
             nakshatra,
@@ -433,104 +868,184 @@ impl NakshatraInfo {
             Nakshatra::Krittika => CelestialBody::Sun,
             Nakshatra::Rohini => CelestialBody::Moon,
             Nakshatra::Mrigashira => CelestialBody::Mars,
-            Nakshatra::Ardra => CelestialBody::Saturn,
-            Nakshatra::Punarvasu => CelestialBody::Mercury,
-            Nakshatra::Pushya => CelestialBody::Venus,
-            Nakshatra::Ashlesha => CelestialBody::Sun,
-            Nakshatra::Magha => CelestialBody::Moon,
-            Nakshatra::PurvaPhalguni => CelestialBody::Mars,
-            Nakshatra::UttaraPhalguni => CelestialBody::Rahu,
-            Nakshatra::Hasta => CelestialBody::Jupiter,
-            Nakshatra::Chitra => CelestialBody::Saturn,
-            Nakshatra::Swati => CelestialBody::Mercury,
-            Nakshatra::Vishakha => CelestialBody::Venus,
-            Nakshatra::Anuradha => CelestialBody::Sun,
-            Nakshatra::Jyeshtha => CelestialBody::Moon,
-            Nakshatra::Moola => CelestialBody::Mars,
-            Nakshatra::PurvaAshadha => CelestialBody::Rahu,
-            Nakshatra::UttaraAshadha => CelestialBody::Jupiter,
-            Nakshatra::Shravana => CelestialBody::Saturn,
-            Nakshatra::Dhanishta => CelestialBody::Mercury,
-            Nakshatra::Shatabhisha => CelestialBody::Venus,
-            Nakshatra::PurvaBhadrapada => CelestialBody::Rahu,
-            Nakshatra::UttaraBhadrapada => CelestialBody::Jupiter,
-            Nakshatra::Revati => CelestialBody::Saturn,
+            Nakshatra::Ardra => CelestialBody::Rahu,
+            Nakshatra::Punarvasu => CelestialBody::Jupiter,
+            Nakshatra::Pushya => CelestialBody::Saturn,
+            Nakshatra::Ashlesha => CelestialBody::Mercury,
+            Nakshatra::Magha => CelestialBody::Ketu,
+            Nakshatra::PurvaPhalguni => CelestialBody::Venus,
+            Nakshatra::UttaraPhalguni => CelestialBody::Sun,
+            Nakshatra::Hasta => CelestialBody::Moon,
+            Nakshatra::Chitra => CelestialBody::Mars,
+            Nakshatra::Swati => CelestialBody::Rahu,
+            Nakshatra::Vishakha => CelestialBody::Jupiter,
+            Nakshatra::Anuradha => CelestialBody::Saturn,
+            Nakshatra::Jyeshtha => CelestialBody::Mercury,
+            Nakshatra::Moola => CelestialBody::Ketu,
+            Nakshatra::PurvaAshadha => CelestialBody::Venus,
+            Nakshatra::UttaraAshadha => CelestialBody::Sun,
+            Nakshatra::Shravana => CelestialBody::Moon,
+            Nakshatra::Dhanishta => CelestialBody::Mars,
+            Nakshatra::Shatabhisha => CelestialBody::Rahu,
+            Nakshatra::PurvaBhadrapada => CelestialBody::Jupiter,
+            Nakshatra::UttaraBhadrapada => CelestialBody::Saturn,
+            Nakshatra::Revati => CelestialBody::Mercury,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DashaInfo {
-    pub maha_dasha: Dasha,
+    pub maha_dasha: DashaLord,
     pub maha_dasha_start: DateTime<Utc>,
     pub maha_dasha_end: DateTime<Utc>,
-    pub antar_dasha: Dasha,
+    pub antar_dasha: DashaLord,
     pub antar_dasha_start: DateTime<Utc>,
     pub antar_dasha_end: DateTime<Utc>,
-    pub pratyantar_dasha: Dasha,
+    pub pratyantar_dasha: DashaLord,
     pub pratyantar_dasha_start: DateTime<Utc>,
     pub pratyantar_dasha_end: DateTime<Utc>,
+    pub maha_dasha_lord: LordCondition,
+    pub antar_dasha_lord: LordCondition,
+    pub pratyantar_dasha_lord: LordCondition,
+    /// Natal condition of every planet, keyed by planet, for the lord of any period in the
+    /// dasha tree. See [`DashaInfo::lord_condition`].
+    #[serde(default)]
+    pub lord_conditions: HashMap<CelestialBody, LordCondition>,
+    /// Days in a dasha year under the conventions the periods were calculated with.
+    pub dasha_year_days: f64,
+    #[serde(default)]
+    pub system: DashaSystemKind,
+    /// Sidereal longitude of the Moon at birth, from which the periods are counted.
+    #[serde(default)]
+    pub moon_longitude: f64,
+    /// Every mahadasha of the native's life. The first is the whole period running at
+    /// birth, so it begins before birth.
+    #[serde(default)]
+    pub maha_dashas: Vec<DashaPeriod>,
+    /// Moment the running periods above were picked for.
+    #[serde(default)]
+    pub as_of: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Yoga {
-    pub name: String,
-    pub condition: Condition,
-    pub effects: Effects,
-    pub strength: f64,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Condition {
-    pub description: String,
-    pub check: fn(chart: &ChartInfo) -> bool,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Effects {
-    pub description: String,
-    pub apply: fn(chart: &ChartInfo) -> Impact,
+/// The Moon alone at a moment, for when no birthplace is known. None of these values
+/// depend on the observer, so they hold wherever the birth took place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MoonProfile {
+    pub date_time: DateTime<Utc>,
+    /// Sidereal longitude of the Moon.
+    pub longitude: f64,
+    pub rasi: ZodiacSign,
+    pub nakshatra: NakshatraInfo,
+    /// Vimshottari mahadasha running at `date_time`.
+    pub dasha: Dasha,
+    /// Years of `dasha` still to run at `date_time`.
+    pub dasha_balance_years: f64,
+    pub dasha_end: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Impact {
     Positive(On, Trait, f64),
     Negative(On, Trait, f64),
     Neutral(On, Trait, f64),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct YogaInfo {
-    pub yoga: Yoga,
-    pub strength: f64,
-    pub involved_planets: Vec<CelestialBody>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct AyanamsaInfo {
     pub ayanamsa_name: String,
     pub ayanamsa_value: f64,
 }
 
+#[cfg(not(feature = "model-only"))]
 impl AyanamsaInfo {
     pub fn calculate(julian_day: JulianDay) -> Self {
         // Actual calculation using FFI bindings
         calculate_ayanamsa(julian_day)
     }
+
+    /// Every supported ayanamsa at `julian_day`, in the order of [`Ayanamsa::all`]. Charts
+    /// from software on different ayanamsas differ by the gaps between them.
+    pub fn compare_all(julian_day: JulianDay) -> Vec<Self> {
+        Ayanamsa::all()
+            .into_iter()
+            .map(|ayanamsa| ayanamsa_at(ayanamsa, julian_day))
+            .collect()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct ChartInfo {
     pub chart_type: ChartType,
+    /// The system the houses are drawn in, which near the poles may not be the one asked
+    /// for; see `warnings`.
+    pub house_system: HouseSystem,
     pub ascendant: HouseCusp,
     pub houses: Vec<HouseCusp>,
     pub planets: Vec<PlanetPosition>,
+    /// Anything the chart could not be cast as asked, such as houses drawn in another
+    /// system near the poles.
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl ChartInfo {
+    /// House of a longitude in the chart's zodiac, found from the chart's cusps.
+    pub fn house_of(&self, longitude: f64) -> House {
+        house_from_cusps(&self.houses, longitude)
+    }
+}
+
+/// How a chart is cast. The default is the traditional Vedic chart: the nine grahas with
+/// Placidus houses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChartOptions {
+    pub house_system: HouseSystem,
+    /// Adds Uranus, Neptune and Pluto.
+    pub include_outer_planets: bool,
+    /// Adds Chiron, Ceres, Pallas, Juno and Vesta.
+    pub include_asteroids: bool,
+}
+
+impl ChartOptions {
+    /// Bodies to calculate beyond the nine grahas.
+    pub fn optional_bodies(&self) -> Vec<CelestialBody> {
+        let mut bodies = Vec::new();
+        if self.include_outer_planets {
+            bodies.extend(CelestialBody::outer_planets());
+        }
+        if self.include_asteroids {
+            bodies.extend(CelestialBody::asteroids());
+        }
+        bodies
+    }
+}
+
+/// The cusp interval containing `longitude`. Cusps and longitude must share a zodiac.
+fn house_from_cusps(cusps: &[HouseCusp], longitude: f64) -> House {
+    cusps
+        .iter()
+        .enumerate()
+        .find(|&(i, cusp)| {
+            let start = cusp.longitude();
+            let end = cusps[(i + 1) % cusps.len()].longitude();
+            (longitude - start).rem_euclid(360.0) < (end - start).rem_euclid(360.0)
+        })
+        .map(|(_, cusp)| cusp.house)
+        .unwrap_or(House::First)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct PlanetPosition {
     pub planet: CelestialBody,
-    pub longitude: f64,
+    pub longitude: Longitude,
     pub latitude: f64,
     pub speed: f64,
     pub sign: ZodiacSign,
@@ -539,54 +1054,166 @@ pub struct PlanetPosition {
     pub retrograde: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum Gender {
     Male,
     Female,
+    Unspecified,
+}
+
+/// Whose birth a [`BirthInfo`] records.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Person {
+    pub name: String,
+    pub gender: Gender,
+}
+
+/// How a [`Report`] is calculated beyond the engine's conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ReportOptions {
+    /// The moment whose running dashas the report shows. Fixing it makes the report the
+    /// same however often, and whenever, it is calculated.
+    pub reference_time: DateTime<Utc>,
+}
+
+impl ReportOptions {
+    /// A report as of `reference_time`.
+    pub fn at(reference_time: DateTime<Utc>) -> Self {
+        ReportOptions { reference_time }
+    }
+
+    /// A report as of the present moment.
+    pub fn now() -> Self {
+        ReportOptions::at(Utc::now())
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Everything a [`Report`] was calculated with, so it can be checked or calculated again.
+/// Reports saved before these were recorded read back with the defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ReportSettings {
+    pub ayanamsa: Ayanamsa,
+    /// The system the rasi chart's houses were drawn in, after any fall back near the
+    /// poles.
+    pub house_system: HouseSystem,
+    pub node_type: NodeType,
+    pub dasha_year_days: f64,
+    pub reference_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Report {
     pub name: String,
     pub gender: Gender,
     pub birth_info: BirthInfo,
+    pub local_date_time: DateTime<FixedOffset>,
     pub ayanamsa: AyanamsaInfo,
     pub charts: Vec<ChartInfo>,
     pub dashas: DashaInfo,
     pub yogas: Vec<YogaInfo>,
     pub nakshatras: Vec<NakshatraInfo>,
-    pub planetary_states: HashMap<CelestialBody, PlanetaryState>,
+    pub planetary_states: HashMap<CelestialBody, PlanetStateSet>,
+    /// Baladi, Jagradadi and Deeptadi avasthas of the grahas.
+    #[serde(default)]
+    pub avasthas: HashMap<CelestialBody, AvasthaInfo>,
     pub divisional_charts: Vec<DivisionalChart>,
-    pub special_lagnas: HashMap<SpecialLagna, f64>,
-    pub upagrahas: HashMap<Upagraha, f64>,
-    pub sensitive_points: HashMap<SensitivePoint, f64>,
+    #[serde(default)]
+    pub varga_flags: HashMap<CelestialBody, VargaFlags>,
+    pub special_lagnas: HashMap<SpecialLagna, Longitude>,
+    /// Arudhas A1 to A12 of the rasi chart, counted in whole signs from the lagna: the
+    /// first is the arudha lagna and the twelfth the upapada.
+    #[serde(default)]
+    pub arudhas: HashMap<House, ZodiacSign>,
+    pub upagrahas: HashMap<Upagraha, UpagrahaInfo>,
+    pub sensitive_points: HashMap<SensitivePoint, Longitude>,
     pub strengths: HashMap<CelestialBody, StrengthInfo>,
+    /// Ishta and Kashta phala of the seven planets.
+    #[serde(default)]
+    pub ishta_kashta: HashMap<CelestialBody, IshtaKashta>,
+    /// Bhava bala of the rasi chart's bhavas, the 1st first.
+    #[serde(default)]
+    pub bhava_bala: Vec<BhavaBala>,
+    /// Right ascension and declination of the rasi chart's planets, in the chart's order.
+    #[serde(default)]
+    pub equatorial: Vec<EquatorialPosition>,
+    pub ashtakavarga: AshtakavargaReport,
     pub remedial_measures: Vec<RemedialMeasure>,
+    /// Anything suspicious about the birth data, such as an ascendant on a sign boundary.
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+    #[serde(default)]
+    pub settings: ReportSettings,
 }
 
  
 impl Report {
+    #[cfg(not(feature = "model-only"))]
     pub fn calculate(
         name: impl AsRef<str>,
         birth: impl Into<BirthInfo>,
         gender: impl Into<Gender>,
-    ) -> Result<Self, CalculationError> {
+    ) -> Result<Self, AstrologyError> {
+        let ephemeris = SwissEph::new()?;
+        Self::calculate_with(&ephemeris, name, birth, gender)
+    }
+
+    /// Like [`Report::calculate`], but on an engine the caller already has, so many
+    /// reports can share one.
+    #[cfg(not(feature = "model-only"))]
+    pub fn calculate_with(
+        ephemeris: &SwissEph,
+        name: impl AsRef<str>,
+        birth: impl Into<BirthInfo>,
+        gender: impl Into<Gender>,
+    ) -> Result<Self, AstrologyError> {
+        Self::calculate_with_options(ephemeris, name, birth, gender, ReportOptions::now())
+    }
+
+    /// Like [`Report::calculate_with`], with the running dashas those at `as_of` rather
+    /// than now, so the same inputs always give the same report.
+    #[cfg(not(feature = "model-only"))]
+    pub fn calculate_as_of(
+        ephemeris: &SwissEph,
+        name: impl AsRef<str>,
+        birth: impl Into<BirthInfo>,
+        gender: impl Into<Gender>,
+        as_of: DateTime<Utc>,
+    ) -> Result<Self, AstrologyError> {
+        Self::calculate_with_options(ephemeris, name, birth, gender, ReportOptions::at(as_of))
+    }
+
+    /// Like [`Report::calculate_with`], as set out in `options`. Nothing in the report
+    /// depends on the clock, so the same inputs, options and engine always give the same
+    /// report.
+    #[cfg(not(feature = "model-only"))]
+    pub fn calculate_with_options(
+        ephemeris: &SwissEph,
+        name: impl AsRef<str>,
+        birth: impl Into<BirthInfo>,
+        gender: impl Into<Gender>,
+        options: ReportOptions,
+    ) -> Result<Self, AstrologyError> {
         let name = name.as_ref();
-        let birth_info = birth.into();
+        let _timed = Timed::start(TimedWork::Report, tracing::debug_span!("report", name));
         let gender = gender.into();
-        let ephemeris = SwissEph::new().unwrap();
-        let birth_info = BirthInfo {
-            date_time: birth_info.date_time,
-            location: birth_info.location,
-        };
+        let birth_info = birth.into().for_person(name, gender);
         // Calculate the ayanamsa
-        let ayanamsa = AyanamsaInfo::calculate(date_to_julian_day(birth_info.date_time));
+        let ayanamsa = AyanamsaInfo {
+            ayanamsa_name: format!("{:?}", ephemeris.conventions().ayanamsa),
+            ayanamsa_value: ephemeris
+                .calculate_ayanamsa(date_to_julian_day(birth_info.date_time)),
+        };
 
         // Calculate the chart
-        let chart = ephemeris.calculate_chart(&birth_info)?;
+        let chart = ephemeris.calculate_chart(&birth_info, HouseSystem::default())?;
 
         // Calculate the dashas
-        let dashas = ephemeris.calculate_dasha(&birth_info)?;
+        let dashas = ephemeris.calculate_dasha(&birth_info, options.reference_time)?;
 
         // Calculate the yogas
         let yogas = ephemeris.calculate_yogas(&chart);
@@ -597,28 +1224,55 @@ impl Report {
         // Calculate the planetary states
         let planetary_states = ephemeris.calculate_planetary_states(&chart)?;
 
+        // Calculate the avasthas
+        let avasthas = ephemeris.calculate_avasthas(&chart);
+
         // Calculate the divisional charts
         let divisional_charts = ephemeris.calculate_divisional_charts(&chart);
 
+        // Calculate vargottama, pushkara and deep exaltation flags
+        let varga_flags = chart
+            .planets
+            .iter()
+            .map(|planet| (planet.planet, planet.varga_flags()))
+            .collect();
+
         // Calculate special lagnas
-        let special_lagnas = ephemeris.calculate_special_lagnas(&chart);
+        let special_lagnas = ephemeris.calculate_special_lagnas(&chart)?;
+
+        // Calculate arudhas
+        let arudhas = chart.arudhas();
 
         // Calculate upagrahas
-        // let upagrahas = ephemeris.calculate_upagrahas(&chart);
-        let upagrahas = HashMap::new();
+        let upagrahas = ephemeris.calculate_upagrahas(&birth_info, &chart)?;
 
         // Calculate sensitive points
-        // let sensitive_points = ephemeris.calculate_sensitive_points(&chart);
-        let sensitive_points = HashMap::new();
+        let sensitive_points = ephemeris.calculate_sensitive_points(&birth_info, &chart)?;
         // Calculate strengths
         // let strengths = ephemeris.calculate_strengths(&chart);
         let strengths = HashMap::new();
+        let ishta_kashta = chart.ishta_kashta(ayanamsa.ayanamsa_value);
+        let bhava_bala = chart.bhava_bala(ephemeris.friendships());
+        let equatorial = ephemeris.calculate_chart_equatorial(&birth_info, &chart)?;
+        // Calculate ashtakavarga
+        let ashtakavarga = ephemeris.calculate_ashtakavarga(&chart);
         // Calculate remedial measures
         let remedial_measures = ephemeris.suggest_remedial_measures(&chart);
+        // Check the birth data
+        let warnings = ephemeris.birth_warnings(&birth_info, &chart);
+        let conventions = ephemeris.conventions();
+        let settings = ReportSettings {
+            ayanamsa: conventions.ayanamsa,
+            house_system: chart.house_system,
+            node_type: conventions.node_type,
+            dasha_year_days: conventions.dasha_year_days,
+            reference_time: options.reference_time,
+        };
 
         Ok(Self {
             name: name.to_string(),
             gender,
+            local_date_time: birth_info.local_date_time(),
             birth_info: birth_info.clone(),
             ayanamsa,
             charts: vec![chart],
@@ -626,393 +1280,906 @@ impl Report {
             yogas,
             nakshatras,
             planetary_states,
+            avasthas,
             divisional_charts,
+            varga_flags,
             special_lagnas,
+            arudhas,
             upagrahas,
             sensitive_points,
             strengths,
+            ishta_kashta,
+            bhava_bala,
+            equatorial,
+            ashtakavarga,
             remedial_measures,
+            warnings,
+            settings,
         })
     }
 
- 
+    /// The bhavas from the strongest to the weakest by total bhava bala.
+    pub fn bhavas_by_strength(&self) -> Vec<&BhavaBala> {
+        let mut ranked: Vec<&BhavaBala> = self.bhava_bala.iter().collect();
+        ranked.sort_by(|a, b| b.total.total_cmp(&a.total));
+        ranked
+    }
+
+    /// Prints the report as laid out by its [`Display`](fmt::Display) implementation.
     pub fn pretty_print(&self) {
-        const RESET: &str   = "\x1b[0m";
-        const BOLD: &str    = "\x1b[1m";
-        const CYAN: &str    = "\x1b[36m";
-        const MAGENTA: &str = "\x1b[35m";
-        const YELLOW: &str  = "\x1b[33m";
-        const GREEN: &str   = "\x1b[32m";
-        const BLUE: &str    = "\x1b[34m";
-        const RED: &str     = "\x1b[31m";
-    
-        let c = |text: &str, color: &str| format!("{}{}{}", color, text, RESET);
-    
-        println!("╭───────────────────────── {} ─────────────────────────╮", c("ॐ", CYAN));
-        
-        // Basic Information
-        println!("│ Name: {:<40} Gender: {:<24} │", 
-            c(&self.name, MAGENTA), 
-            c(&format!("{:?}", self.gender), BLUE)
-        );
-        println!("│ Date: {:<40} Location: {:.2}°N, {:.2}°E{:<13} │", 
-            c(&self.birth_info.date_time.format("%Y-%m-%d %H:%M:%S").to_string(), YELLOW),
-            self.birth_info.location.latitude, 
-            self.birth_info.location.longitude,
-            ""
-        );
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Ascendant and Houses
-        let ascendant = &self.charts[0].ascendant;
-        println!("│ {:<25} │ {:<25} │ {:<25} │", 
-            c("House", CYAN), 
-            c("Sign", GREEN), 
-            c("Degree", YELLOW)
-        );
-        println!("├──────────────────────┼────────────────────┼────────────────────┤");
-        println!("│ {:<25} │ {:<25} │ {:<25.1} │", 
-            c("Ascendant", BLUE), 
-            c(&format!("{:?}", ascendant.sign), GREEN), 
-            ascendant.degree
-        );
-        for house in &self.charts[0].houses {
-            println!("│ {:<25} │ {:<25} │ {:<25.1} │", 
-                c(&format!("{:?}", house.house), BLUE),
-                c(&format!("{:?}", house.sign), GREEN),
-                house.degree
-            );
+        print!("{}", self);
+    }
+}
+
+/// A plain-text report in sections: birth details, the rasi chart's houses and planets,
+/// the running dashas, yogas and special lagnas. Longitudes are shown as degrees, minutes
+/// and seconds within the sign, and every section comes out in the same order each time.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = &self.birth_info.location;
+        let coordinate = |value: f64, positive: char, negative: char| {
+            format!(
+                "{:.4}°{}",
+                value.abs(),
+                if value < 0.0 { negative } else { positive }
+            )
+        };
+        writeln!(f, "{:<10} {}", "Name", self.name)?;
+        writeln!(f, "{:<10} {:?}", "Gender", self.gender)?;
+        writeln!(
+            f,
+            "{:<10} {}",
+            "Born",
+            self.local_date_time.format("%Y-%m-%d %H:%M:%S %:z")
+        )?;
+        writeln!(
+            f,
+            "{:<10} {}, {}",
+            "Place",
+            coordinate(location.latitude, 'N', 'S'),
+            coordinate(location.longitude, 'E', 'W')
+        )?;
+        writeln!(
+            f,
+            "{:<10} {} {:.4}°",
+            "Ayanamsa", self.ayanamsa.ayanamsa_name, self.ayanamsa.ayanamsa_value
+        )?;
+        writeln!(
+            f,
+            "{:<10} {}",
+            "As of",
+            self.settings.reference_time.format("%Y-%m-%d %H:%M:%S UTC")
+        )?;
+        for warning in &self.warnings {
+            writeln!(f, "{:<10} {}", "Warning", warning)?;
         }
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Planets
-        println!("│ {:<20} │ {:<20} │ {:<20} │ {:<20} │ {:<20} │ {:<20} │ {:<20} │", 
-            c("Planet", CYAN), c("Sign", GREEN), c("Degree", YELLOW), c("Retro", RED), 
-            c("House", BLUE), c("Nakshatra", MAGENTA), c("State", CYAN)
-        );
-        println!("├──────────────────┼──────────────────┼──────────────────┼──────────────────┼──────────────────┼──────────────────┼──────────────────┤");
-        for planet in &self.charts[0].planets {
-            let retrograde = if planet.retrograde { "R" } else { " " };
-            let state = self.planetary_states.get(&planet.planet).unwrap();
-            println!("│ {:<20} │ {:<20} │ {:<20.1} │ {:<20} │ {:<20} │ {:<20} │ {:<20} │",
-                c(&format!("{:?}", planet.planet), BLUE),
-                c(&format!("{:?}", planet.sign), GREEN),
-                planet.longitude % 30.0,
-                c(retrograde, RED),
-                c(&format!("{:?}", planet.house), YELLOW),
-                c(&format!("{:?}", planet.nakshatra.nakshatra), MAGENTA),
-                c(&state.to_string(), CYAN)
-            );
+
+        if let Some(chart) = self.charts.first() {
+            writeln!(f, "\nHouses")?;
+            writeln!(
+                f,
+                "{:<10} {}",
+                "Ascendant",
+                Longitude::new(chart.ascendant.longitude())
+            )?;
+            for cusp in &chart.houses {
+                writeln!(
+                    f,
+                    "{:<10} {}",
+                    format!("{:?}", cusp.house),
+                    Longitude::new(cusp.longitude())
+                )?;
+            }
+
+            writeln!(f, "\nPlanets")?;
+            writeln!(
+                f,
+                "{:<8} {:<22} {:<2} {:<9} {:<16} {:<4} State",
+                "Planet", "Longitude", "R", "House", "Nakshatra", "Pada"
+            )?;
+            for planet in &chart.planets {
+                writeln!(
+                    f,
+                    "{:<8} {:<22} {:<2} {:<9} {:<16} {:<4} {}",
+                    format!("{:?}", planet.planet),
+                    planet.longitude,
+                    if planet.retrograde { "R" } else { "" },
+                    format!("{:?}", planet.house),
+                    format!("{:?}", planet.nakshatra.nakshatra),
+                    planet.nakshatra.pada,
+                    self.planetary_states
+                        .get(&planet.planet)
+                        .map(PlanetStateSet::to_string)
+                        .unwrap_or_default()
+                )?;
+            }
         }
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Nakshatras
-        println!("│ {:<20} │ {:<20} │ {:<20} │ {:<25} │", 
-            c("Nakshatra", MAGENTA), c("Pada", YELLOW), c("Lord", BLUE), c("Degree", GREEN)
-        );
-        println!("├──────────────────┼──────────────────┼──────────────────┼─────────────────────┤");
-        for nakshatra in &self.nakshatras {
-            println!("│ {:<20} │ {:<20} │ {:<20} │ {:<25.2} │", 
-                c(&format!("{:?}", nakshatra.nakshatra), MAGENTA),
-                nakshatra.pada,
-                c(&format!("{:?}", nakshatra.lord), BLUE),
-                nakshatra.degree
-            );
+
+        writeln!(f, "\nDashas")?;
+        let dashas = &self.dashas;
+        for (level, dasha, start, end) in [
+            (
+                "Maha",
+                dashas.maha_dasha,
+                dashas.maha_dasha_start,
+                dashas.maha_dasha_end,
+            ),
+            (
+                "Antar",
+                dashas.antar_dasha,
+                dashas.antar_dasha_start,
+                dashas.antar_dasha_end,
+            ),
+            (
+                "Pratyantar",
+                dashas.pratyantar_dasha,
+                dashas.pratyantar_dasha_start,
+                dashas.pratyantar_dasha_end,
+            ),
+        ] {
+            writeln!(
+                f,
+                "{:<10} {:<8} {} to {}",
+                level,
+                dasha,
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            )?;
         }
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Dasha
-        println!("│ {:<20} │ {:<20} │ {:<35} │", 
-            c("Dasha", CYAN), c("Planet", BLUE), c("Period", YELLOW)
-        );
-        println!("├──────────────────┼──────────────────┼───────────────────────────────────┤");
-        println!("│ {:<20} │ {:<20} │ {:<35} │",
-            c("Maha", MAGENTA),
-            c(&format!("{:?}", self.dashas.maha_dasha), BLUE),
-            c(&format!("{} to {}", 
-                self.dashas.maha_dasha_start.format("%Y-%m-%d"),
-                self.dashas.maha_dasha_end.format("%Y-%m-%d")
-            ), YELLOW)
-        );
-        println!("│ {:<20} │ {:<20} │ {:<35} │",
-            c("Antar", MAGENTA),
-            c(&format!("{:?}", self.dashas.antar_dasha), BLUE),
-            c(&format!("{} to {}", 
-                self.dashas.antar_dasha_start.format("%Y-%m-%d"),
-                self.dashas.antar_dasha_end.format("%Y-%m-%d")
-            ), YELLOW)
-        );
-        println!("│ {:<20} │ {:<20} │ {:<35} │",
-            c("Pratyantar", MAGENTA),
-            c(&format!("{:?}", self.dashas.pratyantar_dasha), BLUE),
-            c(&format!("{} to {}", 
-                self.dashas.pratyantar_dasha_start.format("%Y-%m-%d"),
-                self.dashas.pratyantar_dasha_end.format("%Y-%m-%d")
-            ), YELLOW)
-        );
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Yogas
-        println!("│ {:<30} │ {:<20} │ {:<25} │", 
-            c("Yoga", GREEN), c("Strength", YELLOW), c("Planets", BLUE)
-        );
-        println!("├──────────────────────────────────┼──────────────────┼─────────────────────┤");
+
+        writeln!(f, "\nYogas")?;
+        if self.yogas.is_empty() {
+            writeln!(f, "None")?;
+        }
+        let width = self
+            .yogas
+            .iter()
+            .map(|yoga| yoga.yoga.name.chars().count())
+            .max()
+            .unwrap_or(0);
         for yoga in &self.yogas {
-            println!("│ {:<30} │ {:<20.2} │ {:<25} │", 
-                c(&yoga.yoga.name, GREEN),
+            let mut details: Vec<String> = yoga
+                .involved_planets
+                .iter()
+                .map(|planet| format!("{:?}", planet))
+                .collect();
+            if yoga.cancelled {
+                details.push("cancelled".to_string());
+            }
+            let line = format!(
+                "{:<width$} {:.2} {}",
+                yoga.yoga.name,
                 yoga.strength,
-                c(&format!("{:?}", yoga.involved_planets), BLUE)
+                details.join(", "),
+                width = width
             );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        writeln!(f, "\nSpecial lagnas")?;
+        for lagna in [
+            SpecialLagna::Bhava,
+            SpecialLagna::Hora,
+            SpecialLagna::Ghati,
+            SpecialLagna::Varnada,
+            SpecialLagna::Sree,
+            SpecialLagna::Pranapada,
+        ] {
+            if let Some(longitude) = self.special_lagnas.get(&lagna) {
+                writeln!(f, "{:<10} {}", format!("{:?}", lagna), longitude)?;
+            }
         }
-        
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        
-        // Special Lagnas
-        println!("│ {:<30} │ {:<45} │", 
-            c("Special Lagna", CYAN), c("Degree", YELLOW)
-        );
-        println!("├──────────────────────────────────┼─────────────────────────────────────┤");
-        for (lagna, degree) in &self.special_lagnas {
-            println!("│ {:<30} │ {:<45.2} │", 
-                c(&format!("{:?}", lagna), CYAN),
-                degree
-            );
+
+        if !self.arudhas.is_empty() {
+            writeln!(f, "\nArudhas")?;
+            for house in House::all() {
+                if let Some(sign) = self.arudhas.get(&house) {
+                    let name = match house {
+                        House::First => "AL".to_string(),
+                        House::Twelfth => "UL".to_string(),
+                        _ => format!("A{}", house as usize),
+                    };
+                    writeln!(f, "{:<10} {:?}", name, sign)?;
+                }
+            }
         }
-        
-        println!("╰─────────────────────────────────────────────────────────────────╯");
+        Ok(())
     }
-  
-  }
+}
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct BirthInfo {
     pub date_time: DateTime<Utc>,
     pub location: Location,
+    /// The native, when known. Charts cast for a moment rather than a person, such as
+    /// transits or returns, have none.
+    pub person: Option<Person>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Location {
-    pub latitude: f64,
-    pub longitude: f64,
-    pub timezone: f32,
-}
-
-impl Location {
-    pub fn new(latitude: f64, longitude: f64 , timezone: f32) -> Self {
-        Location {
-            latitude,
-            longitude,
-            timezone,
+impl BirthInfo {
+    /// The same birth, recorded as that of `name`.
+    pub fn for_person(self, name: impl Into<String>, gender: Gender) -> BirthInfo {
+        BirthInfo {
+            person: Some(Person {
+                name: name.into(),
+                gender,
+            }),
+            ..self
         }
     }
 
-    pub fn delhi() -> Self {
-        Location {
-            latitude: 28.6139,
-            longitude: 77.2090,
-            timezone:5.5
-        }
-    }
-    pub fn mumbai() -> Self {
-        Location {
-            latitude: 19.0760,
-            longitude: 72.8777,
-            timezone:5.5
-        }
+    /// Gender of the native, [`Gender::Unspecified`] when no person is recorded.
+    pub fn gender(&self) -> Gender {
+        self.person
+            .as_ref()
+            .map_or(Gender::Unspecified, |person| person.gender)
     }
-    pub fn bangalore() -> Self {
-        Location {
-            latitude: 12.9716,
-            longitude: 77.5946,
-            timezone:5.5
+
+    /// Parses a birth record such as `"1991-06-18 07:10 +05:30 @ 11.2588,75.7804"`.
+    ///
+    /// Accepted date forms are `YYYY-MM-DD`, `YYYY/MM/DD` and `DD/MM/YYYY` (with `-`, `/`
+    /// or `.` as separator). Times may be 24-hour or 12-hour with an `AM`/`PM` suffix, with
+    /// optional seconds. The UTC offset (`+05:30`, `+0530`, `+5`, `Z`, `UTC`, `GMT+5:30`) is
+    /// optional and defaults to UTC. Coordinates follow an `@` as `latitude,longitude`.
+    /// Dates before 15 October 1582 are read as Julian, as [`CalendarSystem::Automatic`].
+    pub fn parse(input: &str) -> Result<BirthInfo, BirthInfoError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(BirthInfoError::Empty);
         }
-    }
-    pub fn chennai() -> Self {
-        Location {
-            latitude: 13.0827,
-            longitude: 80.2707,
-            timezone:5.5
+
+        let (moment, coordinates) = match input.split_once('@') {
+            Some((moment, coordinates)) => (moment.trim(), coordinates.trim()),
+            None => return Err(BirthInfoError::MissingLocation),
+        };
+
+        let (latitude, longitude) = parse_coordinates(coordinates)?;
+
+        // Allow ISO-8601 style "1991-06-18T07:10" by treating the `T` as a separator.
+        let is_date_char = |c: char| c.is_ascii_digit() || "-/.".contains(c);
+        let moment = match moment.find('T') {
+            Some(index) if index > 0 && moment[..index].chars().all(is_date_char) => {
+                format!("{} {}", &moment[..index], &moment[index + 1..])
+            }
+            _ => moment.to_string(),
+        };
+
+        let mut tokens = moment.split_whitespace().map(str::to_string).collect::<Vec<String>>();
+        if tokens.is_empty() {
+            return Err(BirthInfoError::Empty);
+        }
+        let (year, month, day) = parse_date(&tokens.remove(0))?;
+        let date = CalendarSystem::default()
+            .to_gregorian(year, month, day)
+            .ok_or_else(|| {
+                BirthInfoError::InvalidDate(format!("{:04}-{:02}-{:02}", year, month, day))
+            })?;
+
+        if tokens.is_empty() {
+            return Err(BirthInfoError::MissingTime);
+        }
+        let mut time_token = tokens.remove(0);
+
+        // An offset may be glued onto the time ("07:10+05:30", "07:10Z").
+        let mut offset_token = None;
+        if let Some(index) = time_token.find(['+', '-', 'Z', 'z']) {
+            offset_token = Some(time_token[index..].to_string());
+            time_token.truncate(index);
+        }
+
+        // The meridiem may be glued on ("7:10PM") or a separate token ("7:10 PM").
+        let mut meridiem = None;
+        let lower = time_token.to_ascii_lowercase();
+        if lower.ends_with("am") || lower.ends_with("pm") {
+            meridiem = Some(lower[lower.len() - 2..].to_string());
+            time_token.truncate(time_token.len() - 2);
+        } else if let Some(next) = tokens.first() {
+            let next = next.to_ascii_lowercase().replace('.', "");
+            if next == "am" || next == "pm" {
+                meridiem = Some(next);
+                tokens.remove(0);
+            }
         }
-    }
-    pub fn kannur() -> Self {
-        Location {
-            latitude: 11.8740,
-            longitude: 75.3600,
-            timezone:5.5
+        let time = parse_time(&time_token, meridiem.as_deref())?;
+
+        if offset_token.is_none() && !tokens.is_empty() {
+            offset_token = Some(tokens.remove(0));
         }
-    }
-    pub fn kolkata() -> Self {
-        Location {
-            latitude: 22.5052,
-            longitude: 87.3616,
-            timezone:5.5
+        if !tokens.is_empty() {
+            return Err(BirthInfoError::UnexpectedInput(tokens.join(" ")));
         }
-    }
-    pub fn abu_dhabi() -> Self {
+
+        let offset_seconds = match offset_token {
+            Some(token) => parse_utc_offset(&token)?,
+            None => 0,
+        };
+        let offset = FixedOffset::east_opt(offset_seconds).ok_or_else(|| {
+            BirthInfoError::InvalidOffset(format!("{} seconds", offset_seconds))
+        })?;
+
+        let local = date.and_time(time);
+        let date_time = offset
+            .from_local_datetime(&local)
+            .single()
+            .ok_or_else(|| BirthInfoError::InvalidTime(local.to_string()))?
+            .with_timezone(&Utc);
+
+        Ok(BirthInfo {
+            date_time,
+            location: Location::new(latitude, longitude, offset_seconds as f32 / 3600.0),
+            person: None,
+        })
+    }
+}
+
+impl std::str::FromStr for BirthInfo {
+    type Err = BirthInfoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BirthInfo::parse(s)
+    }
+}
+
+impl BirthInfo {
+    /// Starts building a birth record from its local date and time.
+    ///
+    /// ```
+    /// use aztro_core::{BirthInfo, Location};
+    ///
+    /// let birth = BirthInfo::new()
+    ///     .date(1991, 6, 18)
+    ///     .time(7, 10, 0)
+    ///     .tz_name("Asia/Kolkata")
+    ///     .location(Location::kozhikode())
+    ///     .build()?;
+    /// assert_eq!(birth.date_time.to_rfc3339(), "1991-06-18T01:40:00+00:00");
+    /// assert_eq!(birth.location.timezone, 5.5);
+    /// # Ok::<(), aztro_core::BirthInfoError>(())
+    /// ```
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> BirthInfoBuilder {
+        BirthInfoBuilder::default()
+    }
+
+    /// The birth moment on the clock at the birth place, using the offset stored in
+    /// `location.timezone`.
+    pub fn local_date_time(&self) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt((self.location.timezone * 3600.0).round() as i32)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+        self.date_time.with_timezone(&offset)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum BirthTimeZone {
+    Offset(f32),
+    Named(String),
+}
+
+/// Builds a [`BirthInfo`] from local civil time, converting to UTC on `build`.
+///
+/// The zone can be a fixed offset in hours (`tz_offset`) or an IANA name such as
+/// `"Europe/London"` (`tz_name`). Named zones resolve the offset in force at the birth
+/// moment, so historical daylight saving and standard-time changes are honoured. Without
+/// either, the location's own `tz_name` is used, and failing that its `timezone`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BirthInfoBuilder {
+    date: Option<(i32, u32, u32)>,
+    time: Option<(u32, u32, u32)>,
+    zone: Option<BirthTimeZone>,
+    location: Option<Location>,
+    calendar: CalendarSystem,
+}
+
+impl BirthInfoBuilder {
+    pub fn date(mut self, year: i32, month: u32, day: u32) -> Self {
+        self.date = Some((year, month, day));
+        self
+    }
+
+    pub fn time(mut self, hour: u32, minute: u32, second: u32) -> Self {
+        self.time = Some((hour, minute, second));
+        self
+    }
+
+    pub fn tz_offset(mut self, hours: f32) -> Self {
+        self.zone = Some(BirthTimeZone::Offset(hours));
+        self
+    }
+
+    pub fn tz_name(mut self, name: impl Into<String>) -> Self {
+        self.zone = Some(BirthTimeZone::Named(name.into()));
+        self
+    }
+
+    pub fn location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// The calendar `date` is written in, by default Julian before 15 October 1582 and
+    /// Gregorian after.
+    pub fn calendar(mut self, calendar: CalendarSystem) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Converts the local birth time to UTC. The location's `timezone` is replaced with the
+    /// offset actually in force at birth.
+    ///
+    /// A local time skipped by a daylight-saving change is an error. A time that occurs
+    /// twice when clocks go back resolves to the earlier (daylight) instant.
+    pub fn build(self) -> Result<BirthInfo, BirthInfoError> {
+        let (year, month, day) = self.date.ok_or(BirthInfoError::MissingDate)?;
+        let (hour, minute, second) = self.time.ok_or(BirthInfoError::MissingTime)?;
+        let mut location = self.location.ok_or(BirthInfoError::MissingLocation)?;
+
+        let date = self.calendar.to_gregorian(year, month, day).ok_or_else(|| {
+            BirthInfoError::InvalidDate(format!("{:04}-{:02}-{:02}", year, month, day))
+        })?;
+        let time = NaiveTime::from_hms_opt(hour, minute, second).ok_or_else(|| {
+            BirthInfoError::InvalidTime(format!("{:02}:{:02}:{:02}", hour, minute, second))
+        })?;
+        let local = date.and_time(time);
+
+        let zone = self.zone.unwrap_or_else(|| match &location.tz_name {
+            Some(name) => BirthTimeZone::Named(name.clone()),
+            None => BirthTimeZone::Offset(location.timezone),
+        });
+        let date_time = match zone {
+            BirthTimeZone::Offset(hours) => {
+                let seconds = (hours * 3600.0).round() as i32;
+                let offset = FixedOffset::east_opt(seconds).ok_or_else(|| {
+                    BirthInfoError::InvalidOffset(format!("{} hours", hours))
+                })?;
+                offset
+                    .from_local_datetime(&local)
+                    .single()
+                    .ok_or_else(|| BirthInfoError::InvalidTime(local.to_string()))?
+                    .with_timezone(&Utc)
+            }
+            BirthTimeZone::Named(name) => {
+                let tz: chrono_tz::Tz = name
+                    .parse()
+                    .map_err(|_| BirthInfoError::UnknownTimeZone(name.clone()))?;
+                tz.from_local_datetime(&local)
+                    .earliest()
+                    .ok_or_else(|| {
+                        BirthInfoError::NonexistentLocalTime(format!("{} in {}", local, name))
+                    })?
+                    .with_timezone(&Utc)
+            }
+        };
+
+        let offset_seconds = (local - date_time.naive_utc()).num_seconds();
+        location.timezone = offset_seconds as f32 / 3600.0;
+
+        Ok(BirthInfo {
+            date_time,
+            location,
+            person: None,
+        })
+    }
+}
+
+/// Year, month and day of a date, the day unchecked against the month since the calendar
+/// the date is written in decides which days exist.
+fn parse_date(token: &str) -> Result<(i32, u32, u32), BirthInfoError> {
+    let parts: Vec<&str> = token.split(['-', '/', '.']).collect();
+    let is_number = |part: &&str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    if parts.len() != 3 || !parts.iter().all(is_number) {
+        return Err(BirthInfoError::InvalidDate(format!(
+            "'{}' is not in YYYY-MM-DD or DD/MM/YYYY form",
+            token
+        )));
+    }
+
+    let number = |part: &str| part.parse::<u32>().unwrap_or(0);
+    let (year, month, day) = if parts[0].len() == 4 {
+        (number(parts[0]) as i32, number(parts[1]), number(parts[2]))
+    } else if parts[2].len() == 4 {
+        (number(parts[2]) as i32, number(parts[1]), number(parts[0]))
+    } else {
+        return Err(BirthInfoError::InvalidDate(format!(
+            "'{}' has no four-digit year",
+            token
+        )));
+    };
+
+    if !(1..=12).contains(&month) {
+        return Err(BirthInfoError::InvalidDate(format!(
+            "month {} is out of range in '{}'",
+            month, token
+        )));
+    }
+
+    if !(1..=31).contains(&day) {
+        return Err(BirthInfoError::InvalidDate(format!(
+            "day {} is out of range in '{}'",
+            day, token
+        )));
+    }
+
+    Ok((year, month, day))
+}
+
+fn parse_time(token: &str, meridiem: Option<&str>) -> Result<NaiveTime, BirthInfoError> {
+    let parts: Vec<&str> = token.split(':').collect();
+    if !(2..=3).contains(&parts.len()) || parts.iter().any(|p| p.is_empty()) {
+        return Err(BirthInfoError::InvalidTime(format!(
+            "'{}' is not in HH:MM or HH:MM:SS form",
+            token
+        )));
+    }
+
+    let field = |part: &str, name: &str| {
+        part.parse::<u32>().map_err(|_| {
+            BirthInfoError::InvalidTime(format!(
+                "{} '{}' is not a number in '{}'",
+                name, part, token
+            ))
+        })
+    };
+    let mut hour = field(parts[0], "hour")?;
+    let minute = field(parts[1], "minute")?;
+    let second = match parts.get(2) {
+        Some(part) => field(part, "second")?,
+        None => 0,
+    };
+
+    if let Some(meridiem) = meridiem {
+        if !(1..=12).contains(&hour) {
+            return Err(BirthInfoError::InvalidTime(format!(
+                "hour {} is out of range for a 12-hour clock",
+                hour
+            )));
+        }
+        hour = match (meridiem, hour) {
+            ("am", 12) => 0,
+            ("pm", 12) => 12,
+            ("pm", h) => h + 12,
+            (_, h) => h,
+        };
+    }
+
+    NaiveTime::from_hms_opt(hour, minute, second).ok_or_else(|| {
+        BirthInfoError::InvalidTime(format!("'{}' is not a valid time of day", token))
+    })
+}
+
+fn parse_utc_offset(token: &str) -> Result<i32, BirthInfoError> {
+    let upper = token.trim().to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    if rest.is_empty() || rest == "Z" {
+        return Ok(0);
+    }
+
+    let invalid = || {
+        BirthInfoError::InvalidOffset(format!("'{}' is not a UTC offset like +05:30", token))
+    };
+    let sign = match rest.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return Err(invalid()),
+    };
+    let digits = &rest[1..];
+    if !digits.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return Err(invalid());
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() == 4 => (&digits[..2], &digits[2..]),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 14 || minutes >= 60 {
+        return Err(invalid());
+    }
+
+    Ok(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_coordinates(input: &str) -> Result<(f64, f64), BirthInfoError> {
+    let (latitude, longitude) = input.split_once(',').ok_or_else(|| {
+        BirthInfoError::InvalidCoordinates(format!(
+            "'{}' is not in 'latitude,longitude' form",
+            input
+        ))
+    })?;
+
+    let (latitude, longitude) = (latitude.trim(), longitude.trim());
+    let latitude: f64 = latitude.parse().map_err(|_| {
+        BirthInfoError::InvalidCoordinates(format!("latitude '{}' is not a number", latitude))
+    })?;
+    let longitude: f64 = longitude.parse().map_err(|_| {
+        BirthInfoError::InvalidCoordinates(format!("longitude '{}' is not a number", longitude))
+    })?;
+
+    check_coordinates(latitude, longitude)?;
+    Ok((latitude, longitude))
+}
+
+/// Rejects latitudes outside -90..90 and longitudes outside -180..180, NaN among them.
+fn check_coordinates(latitude: f64, longitude: f64) -> Result<(), BirthInfoError> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(BirthInfoError::InvalidCoordinates(format!(
+            "latitude {} is outside -90..90",
+            latitude
+        )));
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(BirthInfoError::InvalidCoordinates(format!(
+            "longitude {} is outside -180..180",
+            longitude
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Hours ahead of UTC.
+    pub timezone: f32,
+    /// IANA tz database zone, such as `"Asia/Kolkata"`. When set, birth times given in
+    /// local clock time take their offset from the zone's history rather than from
+    /// `timezone`.
+    #[serde(default)]
+    pub tz_name: Option<String>,
+    /// Height above sea level in metres.
+    #[serde(default)]
+    pub altitude: f64,
+}
+
+impl Location {
+    /// A place at sea level with a fixed UTC offset of `timezone` hours.
+    pub fn new(latitude: f64, longitude: f64 , timezone: f32) -> Self {
         Location {
-            latitude: 24.4667,
-            longitude: 54.3667,
-            timezone: 4.0,
+            latitude,
+            longitude,
+            timezone,
+            tz_name: None,
+            altitude: 0.0,
         }
     }
-    pub fn dubai() -> Self {
-        Location {
-            latitude: 25.276987,
-            longitude: 55.296234,
-            timezone: 4.0,
+
+    /// Like [`Location::new`], but fails on a latitude outside -90..90, a longitude outside
+    /// -180..180 or a UTC offset beyond ±14 hours.
+    pub fn try_new(latitude: f64, longitude: f64, timezone: f32) -> Result<Self, BirthInfoError> {
+        let location = Location::new(latitude, longitude, timezone);
+        location.validate()?;
+        Ok(location)
+    }
+
+    /// Checks the coordinates and offset are in range and the altitude is a number.
+    /// Charts are only calculated for valid locations.
+    pub fn validate(&self) -> Result<(), BirthInfoError> {
+        check_coordinates(self.latitude, self.longitude)?;
+        if !(-14.0..=14.0).contains(&self.timezone) {
+            return Err(BirthInfoError::InvalidOffset(format!(
+                "{} hours is beyond ±14",
+                self.timezone
+            )));
         }
+        if !self.altitude.is_finite() {
+            return Err(BirthInfoError::InvalidCoordinates(format!(
+                "altitude {} is not a number",
+                self.altitude
+            )));
+        }
+        Ok(())
+    }
+
+    /// The same place in tz database zone `name`, whose offsets are used for local birth
+    /// times from then on.
+    pub fn with_tz_name(mut self, name: impl Into<String>) -> Self {
+        self.tz_name = Some(name.into());
+        self
+    }
+
+    /// The same place at `metres` above sea level.
+    pub fn with_altitude(mut self, metres: f64) -> Self {
+        self.altitude = metres;
+        self
+    }
+
+    pub fn delhi() -> Self {
+        Location::preset("Delhi")
+    }
+    pub fn mumbai() -> Self {
+        Location::preset("Mumbai")
+    }
+    pub fn bangalore() -> Self {
+        Location::preset("Bangalore")
+    }
+    pub fn chennai() -> Self {
+        Location::preset("Chennai")
+    }
+    pub fn kannur() -> Self {
+        Location::preset("Kannur")
+    }
+    pub fn kolkata() -> Self {
+        Location::preset("Kolkata")
+    }
+    pub fn abu_dhabi() -> Self {
+        Location::preset("Abu Dhabi")
+    }
+    pub fn dubai() -> Self {
+        Location::preset("Dubai")
     }
     pub fn sharjah() -> Self {
-        Location {
-            latitude: 25.3550,
-            longitude: 55.4000,
-            timezone: 4.0,
-        }
+        Location::preset("Sharjah")
     }
     pub fn malappuram() -> Self {
-        Location {
-            latitude: 10.7900,
-            longitude: 76.0700,
-            timezone:5.5
-        }
+        Location::preset("Malappuram")
     }
     pub fn kochi() -> Self {
-        Location {
-            latitude: 9.9312,
-            longitude: 76.2673,
-            timezone:5.5
-        }
+        Location::preset("Kochi")
     }
     pub fn kollam() -> Self {
-        Location {
-            latitude: 8.8857,
-            longitude: 76.5881,
-            timezone:5.5
-        }
+        Location::preset("Kollam")
     }
     pub fn thrissur() -> Self {
-        Location {
-            latitude: 10.522,
-            longitude: 76.2100,
-            timezone:5.5
-        }
+        Location::preset("Thrissur")
     }
     pub fn kozhikode() -> Self {
-        Location {
-            latitude: 11.2588,
-            longitude: 75.7804,
-            timezone:5.5
-        }
+        Location::preset("Kozhikode")
     }
     pub fn wayanad() -> Self {
-        Location {
-            latitude: 11.6900,
-            longitude: 75.8900,
-            timezone:5.5
-        }
+        Location::preset("Wayanad")
     }
     pub fn munnar() -> Self {
-        Location {
-            latitude: 10.0000,
-            longitude: 77.0667,
-            timezone:5.5
-        }
+        Location::preset("Munnar")
     }
     pub fn idukki() -> Self {
-        Location {
-            latitude: 10.0000,
-            longitude: 77.0667,
-            timezone:5.5
-        }
+        Location::preset("Idukki")
     }
     pub fn kottayam() -> Self {
-        Location {
-            latitude: 10.0000,
-            longitude: 76.5000,
-            timezone:5.5
-        }
+        Location::preset("Kottayam")
     }
     pub fn alappuzha() -> Self {
-        Location {
-            latitude: 9.4900,
-            longitude: 76.3200,
-            timezone:5.5
-        }
+        Location::preset("Alappuzha")
+    }
+
+    /// A birth here at the given local clock time. With a `tz_name`, the offset is the one
+    /// the tz database has for the zone at that moment, daylight saving and historical
+    /// changes included, and `timezone` is updated to it; without, `timezone` is used as
+    /// is. [`Location::from_coordinates`] finds the zone for a bare latitude and longitude
+    /// where it is certain, and says so where it is not.
+    ///
+    /// A date or time that does not exist, such as 30 February or a clock time skipped
+    /// when daylight saving began, is an error.
+    pub fn born_at(
+        self,
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> Result<BirthInfo, BirthInfoError> {
+        BirthInfo::new()
+            .date(year, month, day)
+            .time(hour, minute, second)
+            .location(self)
+            .build()
     }
+}
 
-    pub fn born_at(self, year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> BirthInfo {
-        // Create DateTime<Utc> from input
-        let mut date_time = Utc.with_ymd_and_hms(year, month, day, hour, minute, second).unwrap();
-        let timezone_offset = FixedOffset::east_opt((self.timezone * 60.0 * 60.0) as i32).unwrap();
-        date_time =date_time - timezone_offset;
+/// Observer position on the Earth's surface for topocentric calculations.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Observer {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Height above sea level in metres.
+    pub altitude: f64,
+}
 
-        BirthInfo {
-            date_time,
-            location: self,
+impl Observer {
+    pub fn new(latitude: f64, longitude: f64, altitude: f64) -> Self {
+        Observer {
+            latitude,
+            longitude,
+            altitude,
         }
     }
-    
-
-
+}
 
+impl From<&Location> for Observer {
+    fn from(location: &Location) -> Self {
+        Observer::new(location.latitude, location.longitude, location.altitude)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct RemedialMeasure {
     pub description: String,
     pub gemstone: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct StrengthInfo {
     pub shad_bala: f64,
     pub ashtaka_varga: u32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DignityInfo {
+    /// Within the degrees of the moolatrikona sign given by
+    /// [`CelestialBody::moolatrikona_range`].
     pub moolatrikona: bool,
     pub own_sign: bool,
     pub exalted: bool,
     pub debilitated: bool,
+    /// Degrees from the point of deepest exaltation, for the seven planets.
+    #[serde(default)]
+    pub deep_exaltation_distance: Option<f64>,
+    /// How the planet regards the lord of its sign, five-fold where the chart is known and
+    /// natural otherwise; `None` in its own sign.
+    #[serde(default)]
+    pub sign_lord_relationship: Option<CompoundRelationship>,
+    /// Dignity in virupas, out of 105: uchcha bala, up to 60 at the point of deepest
+    /// exaltation and none at deepest debilitation, plus 45 for moolatrikona, 30 for own
+    /// sign and 22.5, 15, 7.5, 3.75 or 1.875 for the sign of a great friend, friend,
+    /// neutral, enemy or great enemy.
+    #[serde(default)]
+    pub score: f64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct BhavaInfo {
     pub bhava: House,
+    /// Sign and degree of the cusp where the bhava begins.
     pub sign: ZodiacSign,
     pub degree: f64,
     pub lord: CelestialBody,
     pub planets: Vec<CelestialBody>,
+    /// The bhava's cusp, where it begins.
+    pub start: Longitude,
+    /// Bhava madhya, midway between the bhava's cusp and the next.
+    pub madhya: Longitude,
+    /// Sandhi where the bhava ends, the next bhava's cusp.
+    pub end: Longitude,
+    /// Planets of the bhava within [`BHAVA_SANDHI_ORB`] of either end, which are weak.
+    pub sandhi_planets: Vec<CelestialBody>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TransitInfo {
     pub planet: CelestialBody,
     pub from_sign: ZodiacSign,
     pub to_sign: ZodiacSign,
     pub date: DateTime<Utc>,
+    /// The body moved backwards over the boundary, leaving `from_sign` for the one before.
+    pub retrograde: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct VarshaphalInfo {
-    pub year: i32,
-    pub ascendant: ZodiacSign,
-    pub planets: Vec<PlanetPosition>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct CompatibilityInfo {
     pub kuta_points: u32,
     pub compatibility_score: f64,
+    /// Each kuta scaled to the configured [`KutaWeights`].
+    pub kutas: Ashtakoota,
+    pub mangal_dosha1: MangalDosha,
+    pub mangal_dosha2: MangalDosha,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DivisionalChart {
     pub chart_type: ChartType,
     pub ascendant: ZodiacSign,
@@ -1020,1974 +2187,225 @@ pub struct DivisionalChart {
     pub planets: Vec<PlanetPosition>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct AspectInfo {
     pub aspect: Aspect,
     pub planet1: CelestialBody,
+    /// The planet aspected. For an angle or a cusp, the same as `planet1`.
     pub planet2: CelestialBody,
     pub orb: f64,
+    /// From the planets' speeds; None when they move together and the orb holds.
+    #[serde(default)]
+    pub phase: Option<AspectPhase>,
+    /// What `planet1` aspects: `planet2`, an angle or a house cusp.
+    pub target: AspectTarget,
 }
 
 // ---------------------------
 // ## Error Handling
 // ---------------------------
 
-#[derive(Debug)]
+/// Everything that can go wrong in the crate.
+#[derive(Debug, Clone, PartialEq, thiserror::Error, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum AstrologyError {
-    CalculationError(CalculationError),
-    EphemerisError(String),
+    /// A call into Swiss Ephemeris failed; `code` is what the library returned.
+    #[error("Swiss Ephemeris error {code}: {message}")]
+    Ephemeris { code: i32, message: String },
+    /// The ephemeris files could not be written out or found.
+    #[error("Ephemeris files: {0}")]
+    EphemerisFiles(String),
+    /// A saved chart could not be written or read.
+    #[error("Chart file: {0}")]
+    ChartFile(String),
+    /// A chart lacks a body the calculation needs.
+    #[error("{0:?} is missing from the chart")]
+    MissingBody(CelestialBody),
+    /// A date the calculation or the ephemeris files do not cover.
+    #[error("Date out of range: {0}")]
+    DateOutOfRange(String),
+    /// A body or point that does not rise or set where and when asked.
+    #[error("{0}")]
+    NoRiseOrSet(String),
+    #[error("Invalid input: {0}")]
     InvalidInput(String),
-    UnknownError(String),
+    #[error(transparent)]
+    BirthInfo(#[from] BirthInfoError),
+    /// A calculation with no result for its inputs.
+    #[error("Calculation error: {0}")]
+    Calculation(String),
 }
 
-impl fmt::Display for AstrologyError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            AstrologyError::CalculationError(err) => {
-                write!(f, "Calculation Error {}: {}", err.code, err.message)
-            }
-            AstrologyError::EphemerisError(msg) => write!(f, "Ephemeris Error: {}", msg),
-            AstrologyError::InvalidInput(msg) => write!(f, "Invalid Input: {}", msg),
-            AstrologyError::UnknownError(msg) => write!(f, "Unknown Error: {}", msg),
-        }
-    }
+#[derive(Debug, Clone, PartialEq, thiserror::Error, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BirthInfoError {
+    #[error("Birth info is empty")]
+    Empty,
+    #[error("Birth info has a date but no time")]
+    MissingTime,
+    #[error("Birth info has no location; expected '@ latitude,longitude'")]
+    MissingLocation,
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+    #[error("Invalid time: {0}")]
+    InvalidTime(String),
+    #[error("Invalid UTC offset: {0}")]
+    InvalidOffset(String),
+    #[error("Invalid coordinates: {0}")]
+    InvalidCoordinates(String),
+    #[error("Unexpected input: '{0}'")]
+    UnexpectedInput(String),
+    #[error("Birth info has no date")]
+    MissingDate,
+    #[error("Unknown time zone: '{0}'")]
+    UnknownTimeZone(String),
+    #[error("Local time skipped by a clock change: {0}")]
+    NonexistentLocalTime(String),
+    #[error("No time zone known for certain at {0}; name one with Location::with_tz_name")]
+    UnresolvedTimeZone(String),
 }
 
-impl Error for AstrologyError {}
-
-// ---------------------------
-// ## FFI Bindings for Swiss Ephemeris
-// ---------------------------
-
-mod bindings {
+#[cfg(test)]
+mod tests {
     use super::*;
 
-    extern "C" {
-        // Initialize and close Swiss Ephemeris
-        pub fn swe_set_ephe_path(path: *const c_char) -> c_int;
-        pub fn swe_set_sid_mode(sid_mode: c_int, t0: c_double, ayan_t0: c_double) -> c_int;
-        pub fn swe_close();
-
-        // Calculate planetary positions
-        pub fn swe_calc_ut(
-            tjd_ut: c_double,
-            ipl: c_int,
-            iflag: c_int,
-            xx: *mut c_double,
-            serr: *mut c_char,
-        ) -> c_int;
-
-        // House calculations
-        pub fn swe_houses_ex(
-            tjd_ut: c_double,
-            iflag: c_int,
-            geolat: c_double,
-            geolon: c_double,
-            hsys: c_int,
-            cusps: *mut c_double,
-            ascmc: *mut c_double,
-        ) -> c_int;
-
-        pub fn swe_house_pos(
-            armc: c_double,
-            geolat: c_double,
-            eps: c_double,
-            hsys: c_int,
-            at: *mut c_double,
-            serr: *mut c_char,
-        ) -> c_double;
-
-        // Get planet name
-        pub fn swe_get_planet_name(ipl: c_int, name: *mut c_char);
-
-        // Ayanamsa
-        pub fn swe_get_ayanamsa_ut(tjd_ut: c_double) -> c_double;
-
-        // Convert UTC to Julian Day
-        pub fn swe_utc_to_jd(
-            year: c_int,
-            month: c_int,
-            day: c_int,
-            hour: c_int,
-            minute: c_int,
-            sec: c_double,
-            gregflag: c_int,
-            jdet: *mut c_double,
-            jday: *mut c_double,
-        ) -> c_int;
-
-        // Convert Julian Day to UTC
-        pub fn swe_jdut1_to_utc(
-            tjd_ut: c_double,
-            gregflag: c_int,
-            year: *mut c_int,
-            month: *mut c_int,
-            day: *mut c_int,
-            hour: *mut c_int,
-            minute: *mut c_int,
-            sec: *mut c_double,
-        ) -> c_int;
-    }
-}
-
-// Import bindings
-use bindings::*;
-
-// ---------------------------
-// ## Constants for Swiss Ephemeris
-// ---------------------------
-
-pub const SE_GREG_CAL: c_int = 1;
-pub const SE_SIDM_LAHIRI: c_int = 1;
-pub const SE_SIDM_FAGAN_BRADLEY: c_int = 2;
-
-// Flags for calculations
-pub const SEFLG_SPEED: c_int = 256;
-pub const SEFLG_SIDEREAL: c_int = 1 << 7;
-pub const SEFLG_SWIEPH: c_int = 1 << 0;
-
-// House system codes
-pub const SE_HS_PLACIDUS: c_int = 0;
-pub const SE_HS_KRISHNAMURTI: c_int = 10;
-pub const SE_HS_BUCHAREST: c_int = 11;
-pub const SE_HS_EQUATORIAL: c_int = 12;
-pub const SE_HS_MERCURY: c_int = 13;
-pub const SE_HS_CAMPANUS: c_int = 14;
-pub const SE_HS_MORIN: c_int = 15;
-pub const SE_HS_PORPHYRUS: c_int = 16;
-pub const SE_HS_VEHRENBERG: c_int = 17;
-pub const SE_HS_ALCABITUS: c_int = 18;
-pub const SE_HS_TOPHRAS: c_int = 19;
-pub const SE_HS_NAVAMSA: c_int = 20;
-pub const SE_HS_HORA: c_int = 21;
-
-// ---------------------------
-// ## SwissEph Structure
-// ---------------------------
-
-pub struct SwissEph {
-    _temp_file: NamedTempFile,
-}
-
-static EPHE_FILE: &[u8] = include_bytes!("../ephe/sepl_18.se1"); // Ensure the ephemeris file is in ../ephe/
-static INIT: Once = Once::new();
-
-impl SwissEph {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let mut temp_file = NamedTempFile::new()?;
-        std::io::copy(&mut Cursor::new(EPHE_FILE), &mut temp_file)?;
-
-        // Set ephemeris path once
-        INIT.call_once(|| {
-            let file_path = temp_file.path().to_str().unwrap();
-            let c_path = CString::new(file_path).unwrap();
-            unsafe {
-                swe_set_ephe_path(c_path.as_ptr());
-            }
-            eprintln!("Ephemeris file path set to: {}", file_path);
-        });
-
-        Ok(SwissEph {
-            _temp_file: temp_file,
-        })
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
     }
 
-    pub fn get_house(
-        &self,
-        julian_day: JulianDay,
-        planet_longitude: f64,
-        latitude: f64,
-        longitude: f64,
-        house_system: ChartType,
-    ) -> Result<House, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS,   // Placidus
-            ChartType::Navamsa => SE_HS_NAVAMSA, // Navamsa
-            ChartType::Hora => SE_HS_HORA,       // Hora
-                                                  // Add other house systems as needed
-        };
-
-        let mut cusps: [c_double; 13] = [0.0; 13];
-        let mut ascmc: [c_double; 10] = [0.0; 10];
-
-        let hsys_code = hsys;
-
-        let flag = 0; // Additional flags can be set here
-
-        let calc_result = unsafe {
-            swe_houses_ex(
-                julian_day,
-                flag,
-                latitude,
-                longitude,
-                hsys_code,
-                cusps.as_mut_ptr(),
-                ascmc.as_mut_ptr(),
-            )
-        };
+    #[test]
+    fn birth_records_parse_in_every_listed_form() {
+        let expected = utc(1991, 6, 18, 1, 40);
+        for input in [
+            "1991-06-18 07:10 +05:30 @ 11.2588,75.7804",
+            "1991-06-18T07:10+05:30 @ 11.2588,75.7804",
+            "1991/06/18 07:10:00 +0530 @ 11.2588,75.7804",
+            "18/06/1991 7:10 AM UTC+5:30 @ 11.2588,75.7804",
+            "18.06.1991 7:10am +05:30 @ 11.2588,75.7804",
+        ] {
+            let birth = BirthInfo::parse(input).unwrap();
+            assert_eq!(birth.date_time, expected, "{}", input);
+            assert_eq!(birth.location.timezone, 5.5, "{}", input);
+            assert_eq!(birth.location.latitude, 11.2588);
+        }
+
+        let evening = "18-06-1991 7:10 PM Z @ 11.2588,75.7804"
+            .parse::<BirthInfo>()
+            .unwrap();
+        assert_eq!(evening.date_time, utc(1991, 6, 18, 19, 10));
+        let no_offset = BirthInfo::parse("1991-06-18 19:10 @ 11.2588,75.7804").unwrap();
+        assert_eq!(no_offset.date_time, evening.date_time);
+        assert_eq!(no_offset.location.timezone, 0.0);
+    }
+
+    #[test]
+    fn dates_times_and_offsets_parse_alone() {
+        assert_eq!(parse_date("1991-06-18"), Ok((1991, 6, 18)));
+        assert_eq!(parse_date("18/06/1991"), Ok((1991, 6, 18)));
+        assert!(matches!(
+            parse_date("91-06-18"),
+            Err(BirthInfoError::InvalidDate(_))
+        ));
+        assert!(matches!(
+            parse_date("1991-13-01"),
+            Err(BirthInfoError::InvalidDate(_))
+        ));
 
-        if calc_result < 0 {
-            return Err(CalculationError {
-                code: calc_result,
-                message: "Error calculating houses".to_string(),
-            });
+        let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        assert_eq!(parse_time("07:10", None), Ok(time(7, 10)));
+        assert_eq!(parse_time("12:05", Some("am")), Ok(time(0, 5)));
+        assert_eq!(parse_time("12:05", Some("pm")), Ok(time(12, 5)));
+        assert_eq!(parse_time("11:59", Some("pm")), Ok(time(23, 59)));
+        for (token, meridiem) in [
+            ("00:30", Some("am")),
+            ("13:00", Some("pm")),
+            ("24:00", None),
+        ] {
+            assert!(matches!(
+                parse_time(token, meridiem),
+                Err(BirthInfoError::InvalidTime(_))
+            ));
         }
 
-        let armc = ascmc[0];
-        let eps = ascmc[1];
-
-        let mut serr: [c_char; 256] = [0; 256];
-        let house_position = unsafe {
-            swe_house_pos(
-                armc,
-                latitude,
-                eps,
-                hsys_code,
-                &planet_longitude as *const f64 as *mut f64,
-                serr.as_mut_ptr(),
-            )
-        };
-
-        if house_position < 0.0 {
-            let error_message = unsafe { CStr::from_ptr(serr.as_ptr()) }
-                .to_string_lossy()
-                .into_owned();
-            return Err(CalculationError {
-                code: -1,
-                message: error_message,
-            });
+        assert_eq!(parse_utc_offset("+0530"), Ok(19_800));
+        assert_eq!(parse_utc_offset("+05:30"), Ok(19_800));
+        assert_eq!(parse_utc_offset("-3"), Ok(-10_800));
+        assert_eq!(parse_utc_offset("GMT-03:30"), Ok(-12_600));
+        assert_eq!(parse_utc_offset("UTC"), Ok(0));
+        assert_eq!(parse_utc_offset("z"), Ok(0));
+    }
+
+    #[test]
+    fn bad_input_is_an_error_not_a_panic() {
+        for offset in ["+15:00", "+05:60", "+a€", "+0５30", "+-5", "0530", "IST"] {
+            assert!(
+                matches!(
+                    parse_utc_offset(offset),
+                    Err(BirthInfoError::InvalidOffset(_))
+                ),
+                "{}",
+                offset
+            );
         }
 
-        let house_number = house_position.floor() as usize;
-        Ok(match house_number {
-            1 => House::First,
-            2 => House::Second,
-            3 => House::Third,
-            4 => House::Fourth,
-            5 => House::Fifth,
-            6 => House::Sixth,
-            7 => House::Seventh,
-            8 => House::Eighth,
-            9 => House::Ninth,
-            10 => House::Tenth,
-            11 => House::Eleventh,
-            12 => House::Twelfth,
-            _ => House::First,
-        })
-    }
-
-    pub fn calculate_ayanamsa(&self, julian_day: JulianDay) -> f64 {
-        unsafe { swe_get_ayanamsa_ut(julian_day) }
-    }
-
-    pub fn calculate_navamsa(&self, longitude: f64) -> f64 {
-        let normalized_longitude = longitude.rem_euclid(360.0);
-        let navamsa_longitude = (normalized_longitude / 3.0).rem_euclid(360.0);
-        navamsa_longitude
-    }
-
-    pub fn calculate_nakshatra(&self, longitude: f64) -> NakshatraInfo {
-        NakshatraInfo::from_longitude(longitude)
-    }
-
-    pub fn calculate_nakshatras(&self, chart_info: &ChartInfo) -> Vec<NakshatraInfo> {
-        chart_info
-            .planets
-            .iter()
-            .map(|planet| self.calculate_nakshatra(planet.longitude))
-            .collect()
-    }
-
-    pub fn get_nakshatra_lord(&self, nakshatra: Nakshatra) -> CelestialBody {
-        NakshatraInfo::get_nakshatra_lord(nakshatra)
+        let error = |input| BirthInfo::parse(input).unwrap_err();
+        assert_eq!(error("  "), BirthInfoError::Empty);
+        assert_eq!(error("1991-06-18 07:10"), BirthInfoError::MissingLocation);
+        assert_eq!(error("1991-06-18 @ 11.2,75.7"), BirthInfoError::MissingTime);
+        assert!(matches!(
+            error("1991-06-18 07:10 +a€ @ 11.2,75.7"),
+            BirthInfoError::InvalidOffset(_)
+        ));
+        assert!(matches!(
+            error("१९९१-06-18 07:10 @ 11.2,75.7"),
+            BirthInfoError::InvalidDate(_)
+        ));
+        assert!(matches!(
+            error("1991-06-18 0७:10 @ 11.2,75.7"),
+            BirthInfoError::InvalidTime(_)
+        ));
+        assert!(matches!(
+            error("1991-02-30 07:10 @ 11.2,75.7"),
+            BirthInfoError::InvalidDate(_)
+        ));
+        assert!(matches!(
+            error("1991-06-18 07:10 +05:30 @ 95,75.7"),
+            BirthInfoError::InvalidCoordinates(_)
+        ));
+        assert!(matches!(
+            error("1991-06-18 07:10 +05:30 IST @ 11.2,75.7"),
+            BirthInfoError::UnexpectedInput(_)
+        ));
     }
-    pub fn calculate_dasha(&self, birth_info: &BirthInfo) -> Result<DashaInfo, CalculationError> {
-        let julian_day = date_to_julian_day(birth_info.date_time);
-        let result = self.calculate(
-            CoordinateSystem::Sidereal,
-            julian_day,
-            CelestialBody::Moon,
-            &[CalculationFlag::Speed],
-        )?;
-        let moon_longitude = match result {
-            AstronomicalResult::CelestialBody(info) => info.longitude,
-            _ => {
-                return Err(CalculationError {
-                    code: -1,
-                    message: "Failed to calculate Moon position".to_string(),
-                })
-            }
-        };
-
-        let nakshatra_info = self.calculate_nakshatra(moon_longitude);
-        let starting_dasha = match nakshatra_info.lord {
-            CelestialBody::Sun => Dasha::Sun,
-            CelestialBody::Moon => Dasha::Moon,
-            CelestialBody::Mars => Dasha::Mars,
-            CelestialBody::Mercury => Dasha::Mercury,
-            CelestialBody::Jupiter => Dasha::Jupiter,
-            CelestialBody::Venus => Dasha::Venus,
-            CelestialBody::Saturn => Dasha::Saturn,
-            CelestialBody::Rahu => Dasha::Rahu,
-            CelestialBody::Ketu => Dasha::Ketu,
-        };
-
-        let dasha_sequence = [
-            Dasha::Ketu,
-            Dasha::Venus,
-            Dasha::Sun,
-            Dasha::Moon,
-            Dasha::Mars,
-            Dasha::Rahu,
-            Dasha::Jupiter,
-            Dasha::Saturn,
-            Dasha::Mercury,
-        ];
-
-        let dasha_years = [
-            (Dasha::Ketu, 7.0),
-            (Dasha::Venus, 20.0),
-            (Dasha::Sun, 6.0),
-            (Dasha::Moon, 10.0),
-            (Dasha::Mars, 7.0),
-            (Dasha::Rahu, 18.0),
-            (Dasha::Jupiter, 16.0),
-            (Dasha::Saturn, 19.0),
-            (Dasha::Mercury, 17.0),
-        ];
-
-        let position_in_nakshatra = moon_longitude % 13.333333333333334;
-        let nakshatra_fraction = position_in_nakshatra / 13.333333333333334;
-
-        let total_dasha_years = dasha_years
-            .iter()
-            .find(|&&(dasha, _)| dasha == starting_dasha)
-            .map(|&(_, years)| years)
-            .unwrap_or(0.0);
-
-        let dasha_balance_years = total_dasha_years * (1.0 - nakshatra_fraction);
-
-        let mut maha_dasha_periods = Vec::new();
-        let mut index = dasha_sequence
-            .iter()
-            .position(|&dasha| dasha == starting_dasha)
-            .unwrap_or(0);
-
-        let mut maha_dasha_start = birth_info.date_time;
-
-        let mut total_years = dasha_balance_years;
 
-        // First Dasha
-        {
-            let current_dasha = dasha_sequence[index];
-            let years = dasha_balance_years;
-            let maha_dasha_end =
-                maha_dasha_start + ChronoDuration::seconds((years * 365.25 * 86400.0) as i64);
-            maha_dasha_periods.push((current_dasha, maha_dasha_start, maha_dasha_end));
-            maha_dasha_start = maha_dasha_end;
-            index = (index + 1) % dasha_sequence.len();
-        }
-
-        while total_years < 120.0 {
-            let current_dasha = dasha_sequence[index];
-            let years = dasha_years
-                .iter()
-                .find(|&&(dasha, _)| dasha == current_dasha)
-                .map(|&(_, years)| years)
-                .unwrap_or(0.0);
-
-            let maha_dasha_end =
-                maha_dasha_start + ChronoDuration::seconds((years * 365.25 * 86400.0) as i64);
-            maha_dasha_periods.push((current_dasha, maha_dasha_start, maha_dasha_end));
-            maha_dasha_start = maha_dasha_end;
-
-            total_years += years;
-            index = (index + 1) % dasha_sequence.len();
-        }
-
-        let now = Utc::now();
-        let current_maha_dasha = maha_dasha_periods
-            .iter()
-            .find(|&&(_, start, end)| now >= start && now < end)
-            .unwrap_or(&maha_dasha_periods[0]);
-
-        let (maha_dasha, maha_dasha_start, maha_dasha_end) = *current_maha_dasha;
-
-        // Antar Dasha Calculation
-        let maha_dasha_duration = (maha_dasha_end - maha_dasha_start).num_seconds() as f64;
-
-        let mut antar_dasha_start = maha_dasha_start;
-        let mut antar_dasha_periods = Vec::new();
-
-        for &antar_dasha in &dasha_sequence {
-            let antar_dasha_years = dasha_years
-                .iter()
-                .find(|&&(dasha, _)| dasha == antar_dasha)
-                .map(|&(_, years)| years)
-                .unwrap_or(0.0);
-
-            let antar_dasha_duration = maha_dasha_duration * (antar_dasha_years / 120.0);
-
-            let antar_dasha_end =
-                antar_dasha_start + ChronoDuration::seconds(antar_dasha_duration as i64);
-
-            antar_dasha_periods.push((antar_dasha, antar_dasha_start, antar_dasha_end));
-
-            antar_dasha_start = antar_dasha_end;
-        }
-
-        let current_antar_dasha = antar_dasha_periods
-            .iter()
-            .find(|&&(_, start, end)| now >= start && now < end)
-            .unwrap_or(&antar_dasha_periods[0]);
-
-        let (antar_dasha, antar_dasha_start, antar_dasha_end) = *current_antar_dasha;
-
-        // Pratyantar Dasha Calculation
-        let antar_dasha_duration = (antar_dasha_end - antar_dasha_start).num_seconds() as f64;
-
-        let mut pratyantar_dasha_start = antar_dasha_start;
-        let mut pratyantar_dasha_periods = Vec::new();
-
-        for &pratyantar_dasha in &dasha_sequence {
-            let pratyantar_dasha_years = dasha_years
-                .iter()
-                .find(|&&(dasha, _)| dasha == pratyantar_dasha)
-                .map(|&(_, years)| years)
-                .unwrap_or(0.0);
-
-            let pratyantar_dasha_duration = antar_dasha_duration * (pratyantar_dasha_years / 120.0);
-
-            let pratyantar_dasha_end =
-                pratyantar_dasha_start + ChronoDuration::seconds(pratyantar_dasha_duration as i64);
-
-            pratyantar_dasha_periods.push((
-                pratyantar_dasha,
-                pratyantar_dasha_start,
-                pratyantar_dasha_end,
-            ));
+    #[test]
+    fn births_at_a_location_use_its_zone_and_fail_on_missing_times() {
+        // India kept war time, UTC+6:30, through 1945
+        let war_time = Location::kolkata().born_at(1943, 5, 1, 12, 0, 0).unwrap();
+        assert_eq!(war_time.date_time, utc(1943, 5, 1, 5, 30));
+        assert_eq!(war_time.location.timezone, 6.5);
+
+        let fixed = Location::new(11.2588, 75.7804, 5.5);
+        assert_eq!(
+            fixed
+                .clone()
+                .born_at(1991, 6, 18, 7, 10, 0)
+                .unwrap()
+                .date_time,
+            utc(1991, 6, 18, 1, 40)
+        );
+        assert!(matches!(
+            fixed.born_at(1991, 2, 30, 7, 10, 0),
+            Err(BirthInfoError::InvalidDate(_))
+        ));
 
-            pratyantar_dasha_start = pratyantar_dasha_end;
-        }
-
-        let current_pratyantar_dasha = pratyantar_dasha_periods
-            .iter()
-            .find(|&&(_, start, end)| now >= start && now < end)
-            .unwrap_or(&pratyantar_dasha_periods[0]);
-
-        let (pratyantar_dasha, pratyantar_dasha_start, pratyantar_dasha_end) =
-            *current_pratyantar_dasha;
-
-        Ok(DashaInfo {
-            maha_dasha,
-            maha_dasha_start,
-            maha_dasha_end,
-            antar_dasha,
-            antar_dasha_start,
-            antar_dasha_end,
-            pratyantar_dasha,
-            pratyantar_dasha_start,
-            pratyantar_dasha_end,
-        })
-    }
-
-    pub fn calculate_planetary_states(
-        &self,
-        chart_info: &ChartInfo,
-    ) -> Result<HashMap<CelestialBody, PlanetaryState>, CalculationError> {
-        let mut states = HashMap::new();
-
-        let exaltation_points = [
-            (CelestialBody::Sun, ZodiacSign::Aries, 10.0),
-            (CelestialBody::Moon, ZodiacSign::Taurus, 3.0),
-            (CelestialBody::Mars, ZodiacSign::Capricorn, 28.0),
-            (CelestialBody::Mercury, ZodiacSign::Virgo, 15.0),
-            (CelestialBody::Jupiter, ZodiacSign::Cancer, 5.0),
-            (CelestialBody::Venus, ZodiacSign::Pisces, 27.0),
-            (CelestialBody::Saturn, ZodiacSign::Libra, 20.0),
-            (CelestialBody::Rahu, ZodiacSign::Gemini, 20.0),
-            (CelestialBody::Ketu, ZodiacSign::Sagittarius, 20.0),
-        ];
-
-        let debilitation_points = [
-            (CelestialBody::Sun, ZodiacSign::Libra, 10.0),
-            (CelestialBody::Moon, ZodiacSign::Scorpio, 3.0),
-            (CelestialBody::Mars, ZodiacSign::Cancer, 28.0),
-            (CelestialBody::Mercury, ZodiacSign::Pisces, 15.0),
-            (CelestialBody::Jupiter, ZodiacSign::Capricorn, 5.0),
-            (CelestialBody::Venus, ZodiacSign::Virgo, 27.0),
-            (CelestialBody::Saturn, ZodiacSign::Aries, 20.0),
-            (CelestialBody::Rahu, ZodiacSign::Sagittarius, 20.0),
-            (CelestialBody::Ketu, ZodiacSign::Gemini, 20.0),
-        ];
-
-        let own_signs = [
-            (CelestialBody::Sun, vec![ZodiacSign::Leo]),
-            (CelestialBody::Moon, vec![ZodiacSign::Cancer]),
-            (
-                CelestialBody::Mars,
-                vec![ZodiacSign::Aries, ZodiacSign::Scorpio],
-            ),
-            (
-                CelestialBody::Mercury,
-                vec![ZodiacSign::Gemini, ZodiacSign::Virgo],
-            ),
-            (
-                CelestialBody::Jupiter,
-                vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces],
-            ),
-            (
-                CelestialBody::Venus,
-                vec![ZodiacSign::Taurus, ZodiacSign::Libra],
-            ),
-            (
-                CelestialBody::Saturn,
-                vec![ZodiacSign::Capricorn, ZodiacSign::Aquarius],
-            ),
-            (
-                CelestialBody::Rahu,
-                vec![ZodiacSign::Gemini, ZodiacSign::Virgo],
-            ),
-            (
-                CelestialBody::Ketu,
-                vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces],
-            ),
-        ];
-
-        for planet_position in &chart_info.planets {
-            let planet = planet_position.planet;
-            let sign = planet_position.sign;
-            let longitude = planet_position.longitude % 30.0;
-
-            let exalted = exaltation_points
-                .iter()
-                .find(|&&(p, s, _)| p == planet && s == sign)
-                .map(|&(_, _, deg)| {
-                    if (longitude - deg).abs() < 1.0 {
-                        PlanetaryState::DeepExaltation
-                    } else {
-                        PlanetaryState::Exalted
-                    }
-                });
-
-            let debilitated = debilitation_points
-                .iter()
-                .find(|&&(p, s, _)| p == planet && s == sign)
-                .map(|&(_, _, deg)| {
-                    if (longitude - deg).abs() < 1.0 {
-                        PlanetaryState::DeepDebilitation
-                    } else {
-                        PlanetaryState::Debilitated
-                    }
-                });
-
-            let own_sign = own_signs
-                .iter()
-                .find(|&&(p, ref signs)| p == planet && signs.contains(&sign))
-                .map(|_| PlanetaryState::OwnSign);
-
-            let friendly = match planet {
-                CelestialBody::Jupiter
-                | CelestialBody::Venus
-                | CelestialBody::Mercury
-                | CelestialBody::Moon
-                | CelestialBody::Sun => true,
-                CelestialBody::Saturn
-                | CelestialBody::Mars
-                | CelestialBody::Rahu
-                | CelestialBody::Ketu => false,
-            };
-
-            let state = if let Some(ex_state) = exalted {
-                ex_state
-            } else if let Some(deb_state) = debilitated {
-                deb_state
-            } else if let Some(own_state) = own_sign {
-                own_state
-            } else {
-                if friendly {
-                    PlanetaryState::Benefic
-                } else {
-                    PlanetaryState::Malefic
-                }
-            };
-
-            let final_state = if planet_position.retrograde {
-                PlanetaryState::Retrograde
-            } else {
-                state
-            };
-
-            states.insert(planet, final_state);
-        }
-
-        Ok(states)
-    }
-
-    pub fn calculate(
-        &self,
-        coord_system: CoordinateSystem,
-        julian_day: JulianDay,
-        body: CelestialBody,
-        flags: &[CalculationFlag],
-    ) -> Result<AstronomicalResult, CalculationError> {
-        match coord_system {
-            CoordinateSystem::Sidereal => unsafe {
-                swe_set_sid_mode(SE_SIDM_LAHIRI, 0.0, 0.0);
-            },
-            CoordinateSystem::Tropical => unsafe {
-                swe_set_sid_mode(SE_SIDM_FAGAN_BRADLEY, 0.0, 0.0);
-            },
-        }
-
-        let mut iflag: c_int = if coord_system == CoordinateSystem::Sidereal {
-            SEFLG_SIDEREAL
-        } else {
-            0
-        };
-        for flag in flags {
-            iflag |= *flag as c_int;
-        }
-
-        let result = match body {
-            CelestialBody::Ketu => {
-                let rahu_result =
-                    self.calculate(coord_system, julian_day, CelestialBody::Rahu, flags)?;
-                let (
-                    longitude,
-                    latitude,
-                    distance,
-                    speed_longitude,
-                    speed_latitude,
-                    speed_distance,
-                ) = match rahu_result {
-                    AstronomicalResult::CelestialBody(info) => (
-                        (info.longitude + 180.0) % 360.0,
-                        -info.latitude,
-                        info.distance,
-                        info.speed_longitude,
-                        -info.speed_latitude,
-                        info.speed_distance,
-                    ),
-                    _ => {
-                        return Err(CalculationError {
-                            code: -1,
-                            message: "Failed to calculate Ketu".to_string(),
-                        })
-                    }
-                };
-                Ok(AstronomicalResult::CelestialBody(CelestialCoordinates {
-                    longitude,
-                    latitude,
-                    distance,
-                    speed_longitude,
-                    speed_latitude,
-                    speed_distance,
-                }))
-            }
-            _ => {
-                let mut results: [c_double; 6] = [0.0; 6];
-                let mut error: [c_char; 256] = [0; 256];
-                let calc_result = unsafe {
-                    swe_calc_ut(
-                        julian_day,
-                        body as c_int,
-                        iflag,
-                        results.as_mut_ptr(),
-                        error.as_mut_ptr(),
-                    )
-                };
-                if calc_result < 0 {
-                    let error_message = unsafe { CStr::from_ptr(error.as_ptr()) }
-                        .to_string_lossy()
-                        .into_owned();
-                    return Err(CalculationError {
-                        code: calc_result,
-                        message: error_message,
-                    });
-                }
-                Ok(AstronomicalResult::CelestialBody(CelestialCoordinates {
-                    longitude: results[0],
-                    latitude: results[1],
-                    distance: results[2],
-                    speed_longitude: results[3],
-                    speed_latitude: results[4],
-                    speed_distance: results[5],
-                }))
-            }
-        };
-
-        result
-    }
-
-    pub fn get_body_name(&self, body: CelestialBody) -> String {
-        match body {
-            CelestialBody::Ketu => "Ketu".to_string(),
-            _ => {
-                let mut name: [c_char; 256] = [0; 256];
-                unsafe {
-                    swe_get_planet_name(body as c_int, name.as_mut_ptr());
-                }
-                unsafe { CStr::from_ptr(name.as_ptr()) }
-                    .to_string_lossy()
-                    .into_owned()
-            }
-        }
-    }
-
-    pub fn calculate_houses(
-        &self,
-        coord_system: CoordinateSystem,
-        julian_day: JulianDay,
-        latitude: f64,
-        longitude: f64,
-        house_system: ChartType,
-    ) -> Result<Vec<HouseCusp>, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS,
-            ChartType::Navamsa => SE_HS_NAVAMSA,
-            ChartType::Hora => SE_HS_HORA,
-            // Add other house systems as needed
-        };
-
-        if coord_system == CoordinateSystem::Sidereal {
-            unsafe {
-                swe_set_sid_mode(SE_SIDM_LAHIRI, 0.0, 0.0);
-            }
-        }
-
-        let flag = if coord_system == CoordinateSystem::Sidereal {
-            SEFLG_SIDEREAL
-        } else {
-            0
-        };
-
-        let mut cusps: [c_double; 13] = [0.0; 13];
-        let mut ascmc: [c_double; 10] = [0.0; 10];
-
-        let calc_result = unsafe {
-            swe_houses_ex(
-                julian_day,
-                flag,
-                latitude,
-                longitude,
-                hsys,
-                cusps.as_mut_ptr(),
-                ascmc.as_mut_ptr(),
-            )
-        };
-
-        if calc_result < 0 {
-            return Err(CalculationError {
-                code: calc_result,
-                message: "Error calculating houses".to_string(),
-            });
-        }
-
-        let house_positions: Vec<HouseCusp> = (1..=12)
-            .map(|i| HouseCusp {
-                house: House::from_index(i).unwrap(),
-                sign: Self::get_zodiac_sign(cusps[i]),
-                degree: cusps[i] % 30.0,
-            })
-            .collect();
-
-        Ok(house_positions)
-    }
-
-    pub fn calculate_ascendant(
-        &self,
-        coord_system: CoordinateSystem,
-        julian_day: JulianDay,
-        latitude: f64,
-        longitude: f64,
-        house_system: ChartType,
-    ) -> Result<HouseCusp, CalculationError> {
-        let hsys = match house_system {
-            ChartType::Rasi => SE_HS_PLACIDUS,
-            ChartType::Navamsa => SE_HS_NAVAMSA,
-            ChartType::Hora => SE_HS_HORA,
-            // Add other house systems as needed
-        };
-
-        let flag = if coord_system == CoordinateSystem::Sidereal {
-            SEFLG_SIDEREAL
-        } else {
-            0
-        };
-
-        let mut cusps: [c_double; 13] = [0.0; 13];
-        let mut ascmc: [c_double; 10] = [0.0; 10];
-
-        let calc_result = unsafe {
-            swe_houses_ex(
-                julian_day,
-                flag,
-                latitude,
-                longitude,
-                hsys,
-                cusps.as_mut_ptr(),
-                ascmc.as_mut_ptr(),
-            )
-        };
-
-        if calc_result < 0 {
-            return Err(CalculationError {
-                code: calc_result,
-                message: "Error calculating ascendant".to_string(),
-            });
-        }
-
-        let ascendant_degree = ascmc[0];
-        let sign = Self::get_zodiac_sign(ascendant_degree);
-        Ok(HouseCusp {
-            house: House::First,
-            sign,
-            degree: ascendant_degree % 30.0,
-        })
-    }
-
-    fn get_zodiac_sign(longitude: f64) -> ZodiacSign {
-        ZodiacSign::from_longitude(longitude)
-    }
-
-    pub fn calculate_planet_positions(
-        &self,
-        coord_system: CoordinateSystem,
-        julian_day: JulianDay,
-        chart_type: ChartType,
-        birth_info: &BirthInfo,
-    ) -> Result<Vec<PlanetPosition>, CalculationError> {
-        let planets = vec![
-            CelestialBody::Sun,
-            CelestialBody::Moon,
-            CelestialBody::Mars,
-            CelestialBody::Mercury,
-            CelestialBody::Jupiter,
-            CelestialBody::Venus,
-            CelestialBody::Saturn,
-            CelestialBody::Rahu,
-            CelestialBody::Ketu,
-        ];
-
-        let mut positions = Vec::new();
-
-        for planet in planets {
-            let result =
-                self.calculate(coord_system, julian_day, planet, &[CalculationFlag::Speed])?;
-            let (longitude, latitude, speed) = match result {
-                AstronomicalResult::CelestialBody(info) => {
-                    (info.longitude, info.latitude, info.speed_longitude)
-                }
-                _ => continue,
-            };
-
-            let adjusted_longitude = match chart_type {
-                ChartType::Rasi => longitude,
-                ChartType::Navamsa => self.calculate_navamsa(longitude),
-                ChartType::Hora => (longitude * 2.0) % 360.0, // Example for Hora
-                                                              // Add more chart types as needed
-            };
-
-            let sign = Self::get_zodiac_sign(adjusted_longitude);
-            let house = self.get_house(
-                julian_day,
-                adjusted_longitude,
-                birth_info.location.latitude,
-                birth_info.location.longitude,
-                chart_type,
-            )?;
-
-            let nakshatra = self.calculate_nakshatra(adjusted_longitude);
-
-            let retrograde = speed < 0.0;
-
-            positions.push(PlanetPosition {
-                planet,
-                longitude: adjusted_longitude,
-                latitude,
-                speed,
-                sign,
-                house,
-                nakshatra,
-                retrograde,
-            });
-        }
-
-        Ok(positions)
-    }
-
-    // ---------------------------
-    // ## Compatibility Calculations
-    // ---------------------------
-
-    pub fn calculate_compatibility(
-        &self,
-        chart1: &ChartInfo,
-        chart2: &ChartInfo,
-    ) -> CompatibilityInfo {
-        let kuta_points = self.calculate_kuta_points(chart1, chart2);
-        let compatibility_score = self.calculate_compatibility_score(chart1, chart2);
-
-        CompatibilityInfo {
-            kuta_points,
-            compatibility_score,
-        }
-    }
-
-    pub fn calculate_yogas(&self, chart: &ChartInfo) -> Vec<YogaInfo> {
-        let mut yogas = Vec::new();
-
-        let get_planet = |body: CelestialBody| -> Option<&PlanetPosition> {
-            chart.planets.iter().find(|p| p.planet == body)
-        };
-
-        // Example Yoga 1: Raj Yoga - Lord of 9th and 10th house conjunction
-        if let (Some(ninth_lord), Some(tenth_lord)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Saturn),
-        ) {
-            if (ninth_lord.longitude - tenth_lord.longitude).abs() < 10.0 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga {
-                        name: "Raj Yoga".to_string(),
-                        condition: Condition {
-                            description: "Conjunction of lords of 9th and 10th houses".to_string(),
-                            check: |chart| {
-                                let ninth_lord = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.house == House::Ninth)
-                                    .map(|p| p.planet);
-                                let tenth_lord = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.house == House::Tenth)
-                                    .map(|p| p.planet);
-                                match (ninth_lord, tenth_lord) {
-                                    (Some(n), Some(t)) => {
-                                        let p1 =
-                                            chart.planets.iter().find(|p| p.planet == n).unwrap();
-                                        let p2 =
-                                            chart.planets.iter().find(|p| p.planet == t).unwrap();
-                                        (p1.longitude - p2.longitude).abs() < 10.0
-                                    }
-                                    _ => false,
-                                }
-                            },
-                        },
-                        effects: Effects {
-                            description: "Enhances authority and career prospects.".to_string(),
-                            apply: |chart| Impact::Positive(On::Oneself, Trait::Career, 8.0),
-                        },
-                        strength: 1.0,
-                    },
-                    strength: 1.0,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Saturn],
-                });
-            }
-        }
-
-        // Example Yoga 2: Gajakesari Yoga - Jupiter in a Kendra from Moon
-        if let (Some(jupiter), Some(moon)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Moon),
-        ) {
-            let house_diff = (jupiter.house as i32 - moon.house as i32).abs() % 12;
-            if house_diff == 4 || house_diff == 7 || house_diff == 10 || house_diff == 1 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga {
-                        name: "Gajakesari Yoga".to_string(),
-                        condition: Condition {
-                            description: "Jupiter in Kendra from Moon".to_string(),
-                            check: |chart| {
-                                let j = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Jupiter)
-                                    .unwrap();
-                                let m = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Moon)
-                                    .unwrap();
-                                let house_diff = (j.house as i32 - m.house as i32).abs() % 12;
-                                house_diff == 4
-                                    || house_diff == 7
-                                    || house_diff == 10
-                                    || house_diff == 1
-                            },
-                        },
-                        effects: Effects {
-                            description: "Brings intelligence and prosperity.".to_string(),
-                            apply: |chart| Impact::Positive(On::Oneself, Trait::Wealth, 7.0),
-                        },
-                        strength: 0.85,
-                    },
-                    strength: 0.85,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Moon],
-                });
-            }
-        }
-
-        // Example Yoga 3: Budhaditya Yoga - Sun and Mercury in same house
-        if let (Some(sun), Some(mercury)) = (
-            get_planet(CelestialBody::Sun),
-            get_planet(CelestialBody::Mercury),
-        ) {
-            if sun.house == mercury.house {
-                yogas.push(YogaInfo {
-                    yoga: Yoga {
-                        name: "Budhaditya Yoga".to_string(),
-                        condition: Condition {
-                            description: "Sun and Mercury in the same house".to_string(),
-                            check: |chart| {
-                                let s = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Sun)
-                                    .unwrap();
-                                let m = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Mercury)
-                                    .unwrap();
-                                s.house == m.house
-                            },
-                        },
-                        effects: Effects {
-                            description: "Enhances communication and intelligence.".to_string(),
-                            apply: |chart| Impact::Positive(On::Oneself, Trait::Communication, 8.0),
-                        },
-                        strength: 0.9,
-                    },
-                    strength: 0.9,
-                    involved_planets: vec![CelestialBody::Sun, CelestialBody::Mercury],
-                });
-            }
-        }
-
-        // Example Yoga 4: Hamsa Yoga - Jupiter in Kendra from Moon
-        if let (Some(jupiter), Some(moon)) = (
-            get_planet(CelestialBody::Jupiter),
-            get_planet(CelestialBody::Moon),
-        ) {
-            let house_diff = (jupiter.house as i32 - moon.house as i32).abs() % 12;
-            if house_diff == 4 || house_diff == 7 || house_diff == 10 || house_diff == 1 {
-                yogas.push(YogaInfo {
-                    yoga: Yoga {
-                        name: "Hamsa Yoga".to_string(),
-                        condition: Condition {
-                            description: "Jupiter in Kendra from Moon".to_string(),
-                            check: |chart| {
-                                let j = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Jupiter)
-                                    .unwrap();
-                                let m = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Moon)
-                                    .unwrap();
-                                let house_diff = (j.house as i32 - m.house as i32).abs() % 12;
-                                house_diff == 4
-                                    || house_diff == 7
-                                    || house_diff == 10
-                                    || house_diff == 1
-                            },
-                        },
-                        effects: Effects {
-                            description: "Bestows wisdom and prosperity.".to_string(),
-                            apply: |chart| Impact::Positive(On::Oneself, Trait::Wealth, 8.0),
-                        },
-                        strength: 0.8,
-                    },
-                    strength: 0.8,
-                    involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Moon],
-                });
-            }
-        }
-
-        // Example Yoga 5: Malavya Yoga - Venus in a Kendra house
-        if let Some(venus) = get_planet(CelestialBody::Venus) {
-            if matches!(
-                venus.house,
-                House::First | House::Fourth | House::Seventh | House::Tenth
-            ) {
-                yogas.push(YogaInfo {
-                    yoga: Yoga {
-                        name: "Malavya Yoga".to_string(),
-                        condition: Condition {
-                            description: "Venus in a Kendra house".to_string(),
-                            check: |chart| {
-                                let v = chart
-                                    .planets
-                                    .iter()
-                                    .find(|p| p.planet == CelestialBody::Venus)
-                                    .unwrap();
-                                matches!(
-                                    v.house,
-                                    House::First | House::Fourth | House::Seventh | House::Tenth
-                                )
-                            },
-                        },
-                        effects: Effects {
-                            description: "Enhances love and artistic abilities.".to_string(),
-                            apply: |chart| Impact::Positive(On::Oneself, Trait::Relationship, 7.0),
-                        },
-                        strength: 0.75,
-                    },
-                    strength: 0.75,
-                    involved_planets: vec![CelestialBody::Venus],
-                });
-            }
-        }
-
-        yogas
-    }
-
-    pub fn calculate_special_lagnas(&self, chart: &ChartInfo) -> HashMap<SpecialLagna, f64> {
-        let mut special_lagnas = HashMap::new();
-
-        let ascendant_longitude = chart.ascendant.degree;
-        let sun_longitude = chart
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Sun)
-            .unwrap()
-            .longitude;
-        let moon_longitude = chart
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap()
-            .longitude;
-
-        // Calculate Hora Lagna
-        let hora_lagna = (ascendant_longitude + (sun_longitude - moon_longitude)) % 360.0;
-        special_lagnas.insert(SpecialLagna::Hora, hora_lagna);
-
-        // Calculate Ghati Lagna
-        let ghati_lagna = (ascendant_longitude + (moon_longitude - sun_longitude) * 5.0) % 360.0;
-        special_lagnas.insert(SpecialLagna::Ghati, ghati_lagna);
-
-        // Calculate Varnada Lagna
-        let varnada_lagna = (ascendant_longitude + (sun_longitude - moon_longitude) * 3.0) % 360.0;
-        special_lagnas.insert(SpecialLagna::Varnada, varnada_lagna);
-
-        // Calculate Sree Lagna
-        let sree_lagna = (ascendant_longitude + moon_longitude) % 360.0;
-        special_lagnas.insert(SpecialLagna::Sree, sree_lagna);
-
-        // Calculate Pranapada Lagna
-        let pranapada_lagna =
-            (ascendant_longitude + (sun_longitude - moon_longitude) * 7.0) % 360.0;
-        special_lagnas.insert(SpecialLagna::Pranapada, pranapada_lagna);
-
-        special_lagnas
-    }
-
-    fn calculate_kuta_points(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let mut points = 0;
-
-        // Varna Kuta (1 point)
-        if self.check_varna_compatibility(chart1.ascendant.sign, chart2.ascendant.sign) {
-            points += 1;
-        }
-
-        // Vasya Kuta (2 points)
-        if self.check_vasya_compatibility(chart1.ascendant.sign, chart2.ascendant.sign) {
-            points += 2;
-        }
-
-        // Tara Kuta (3 points)
-        points += self.calculate_tara_kuta(chart1, chart2);
-
-        // Yoni Kuta (4 points)
-        points += self.calculate_yoni_kuta(chart1, chart2);
-
-        // Graha Maitri (5 points)
-        points += self.calculate_graha_maitri(chart1, chart2);
-
-        // Gana Kuta (6 points)
-        if self.check_gana_compatibility(chart1.ascendant.sign, chart2.ascendant.sign) {
-            points += 6;
-        }
-
-        // Bhakut Kuta (7 points)
-        if self.check_bhakut_compatibility(chart1.ascendant.sign, chart2.ascendant.sign) {
-            points += 7;
-        }
-
-        // Nadi Kuta (8 points)
-        if self.check_nadi_compatibility(chart1.ascendant.sign, chart2.ascendant.sign) {
-            points += 8;
-        }
-
-        points
-    }
-
-    fn calculate_compatibility_score(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> f64 {
-        let kuta_points = self.calculate_kuta_points(chart1, chart2) as f64;
-        let max_points = 36.0; // Maximum possible Kuta points
-
-        (kuta_points / max_points) * 100.0
-    }
-
-    fn check_varna_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let varna1 = self.get_varna(sign1);
-        let varna2 = self.get_varna(sign2);
-        varna1 >= varna2
-    }
-
-    fn get_varna(&self, sign: ZodiacSign) -> u32 {
-        match sign {
-            ZodiacSign::Leo | ZodiacSign::Aries | ZodiacSign::Sagittarius => 4, // Brahmin
-            ZodiacSign::Cancer | ZodiacSign::Scorpio | ZodiacSign::Pisces => 3, // Kshatriya
-            ZodiacSign::Gemini | ZodiacSign::Libra | ZodiacSign::Aquarius => 2, // Vaishya
-            ZodiacSign::Taurus | ZodiacSign::Virgo | ZodiacSign::Capricorn => 1, // Shudra
-        }
-    }
-
-    fn check_vasya_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let vasya_groups = vec![
-            vec![ZodiacSign::Leo, ZodiacSign::Aries],
-            vec![ZodiacSign::Cancer, ZodiacSign::Scorpio],
-            vec![ZodiacSign::Gemini, ZodiacSign::Libra, ZodiacSign::Aquarius],
-            vec![ZodiacSign::Taurus, ZodiacSign::Capricorn],
-            vec![ZodiacSign::Virgo, ZodiacSign::Pisces],
-            vec![ZodiacSign::Sagittarius],
-        ];
-
-        vasya_groups
-            .iter()
-            .any(|group| group.contains(&sign1) && group.contains(&sign2))
-    }
-
-    fn calculate_tara_kuta(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let moon1 = chart1
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-        let moon2 = chart2
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-
-        let nakshatra1 = moon1.nakshatra.nakshatra as u32;
-        let nakshatra2 = moon2.nakshatra.nakshatra as u32;
-
-        let tara = ((nakshatra2 + 27) - nakshatra1) % 27 / 3;
-
-        match tara {
-            1 | 3 | 5 | 7 => 3,
-            0 | 2 | 4 | 6 | 8 => 0,
-            _ => 0,
-        }
-    }
-
-    fn calculate_yoni_kuta(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let moon1 = chart1
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-        let moon2 = chart2
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-            .unwrap();
-
-        let yoni1 = self.get_yoni(moon1.nakshatra.nakshatra);
-        let yoni2 = self.get_yoni(moon2.nakshatra.nakshatra);
-
-        if yoni1 == yoni2 {
-            4
-        } else if self.are_yonis_compatible(yoni1, yoni2) {
-            2
-        } else {
-            0
-        }
-    }
-
-    fn get_yoni(&self, nakshatra: Nakshatra) -> &'static str {
-        match nakshatra {
-            Nakshatra::Ashwini | Nakshatra::Shatabhisha => "Horse",
-            Nakshatra::Bharani | Nakshatra::Revati => "Elephant",
-            Nakshatra::Krittika | Nakshatra::Punarvasu => "Goat",
-            Nakshatra::Rohini | Nakshatra::UttaraPhalguni => "Snake",
-            Nakshatra::Mrigashira | Nakshatra::Chitra => "Dog",
-            Nakshatra::Ardra | Nakshatra::Shravana => "Cat",
-            Nakshatra::Pushya | Nakshatra::UttaraAshadha => "Ram",
-            Nakshatra::Ashlesha | Nakshatra::Jyeshtha => "Mongoose",
-            Nakshatra::Magha | Nakshatra::PurvaPhalguni => "Rat",
-            Nakshatra::Hasta | Nakshatra::Anuradha => "Buffalo",
-            Nakshatra::Swati | Nakshatra::Dhanishta => "Tiger",
-            Nakshatra::Vishakha | Nakshatra::PurvaAshadha => "Deer",
-            Nakshatra::Moola | Nakshatra::PurvaBhadrapada => "Monkey",
-            Nakshatra::UttaraBhadrapada => "Lion",
-        }
-    }
-
-    fn are_yonis_compatible(&self, yoni1: &str, yoni2: &str) -> bool {
-        let compatible_pairs = vec![
-            ("Horse", "Horse"),
-            ("Elephant", "Elephant"),
-            ("Goat", "Goat"),
-            ("Snake", "Snake"),
-            ("Dog", "Dog"),
-            ("Cat", "Cat"),
-            ("Ram", "Ram"),
-            ("Mongoose", "Mongoose"),
-            ("Rat", "Rat"),
-            ("Buffalo", "Buffalo"),
-            ("Tiger", "Deer"),
-            ("Deer", "Tiger"),
-            ("Monkey", "Monkey"),
-            ("Lion", "Lion"),
-        ];
-
-        compatible_pairs.contains(&(yoni1, yoni2)) || compatible_pairs.contains(&(yoni2, yoni1))
-    }
-
-    pub fn calculate_graha_maitri(&self, chart1: &ChartInfo, chart2: &ChartInfo) -> u32 {
-        let lord1 = self.get_house_lord(chart1.ascendant.house);
-        let lord2 = self.get_house_lord(chart2.ascendant.house);
-
-        if self.are_planets_friends(lord1, lord2) {
-            5
-        } else if self.are_planets_neutral(lord1, lord2) {
-            3
-        } else {
-            0
-        }
-    }
-
-    fn are_planets_friends(&self, planet1: CelestialBody, planet2: CelestialBody) -> bool {
-        let friendships = [
-            (
-                CelestialBody::Sun,
-                vec![
-                    CelestialBody::Moon,
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                ],
-            ),
-            (
-                CelestialBody::Moon,
-                vec![CelestialBody::Sun, CelestialBody::Mercury],
-            ),
-            (
-                CelestialBody::Mars,
-                vec![
-                    CelestialBody::Sun,
-                    CelestialBody::Moon,
-                    CelestialBody::Jupiter,
-                ],
-            ),
-            (
-                CelestialBody::Mercury,
-                vec![CelestialBody::Sun, CelestialBody::Venus],
-            ),
-            (
-                CelestialBody::Jupiter,
-                vec![CelestialBody::Sun, CelestialBody::Moon, CelestialBody::Mars],
-            ),
-            (
-                CelestialBody::Venus,
-                vec![CelestialBody::Mercury, CelestialBody::Saturn],
-            ),
-            (
-                CelestialBody::Saturn,
-                vec![CelestialBody::Mercury, CelestialBody::Venus],
-            ),
-        ];
-
-        friendships.iter().any(|&(p, ref friends)| {
-            (p == planet1 && friends.contains(&planet2))
-                || (p == planet2 && friends.contains(&planet1))
-        })
-    }
-
-    fn are_planets_neutral(&self, planet1: CelestialBody, planet2: CelestialBody) -> bool {
-        let neutral_relations = [
-            (CelestialBody::Sun, vec![CelestialBody::Mercury]),
-            (
-                CelestialBody::Moon,
-                vec![
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Mars,
-                vec![
-                    CelestialBody::Mercury,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Mercury,
-                vec![
-                    CelestialBody::Mars,
-                    CelestialBody::Jupiter,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Jupiter,
-                vec![
-                    CelestialBody::Mercury,
-                    CelestialBody::Venus,
-                    CelestialBody::Saturn,
-                ],
-            ),
-            (
-                CelestialBody::Venus,
-                vec![CelestialBody::Mars, CelestialBody::Jupiter],
-            ),
-            (
-                CelestialBody::Saturn,
-                vec![CelestialBody::Mars, CelestialBody::Jupiter],
-            ),
-        ];
-
-        neutral_relations.iter().any(|&(p, ref neutrals)| {
-            (p == planet1 && neutrals.contains(&planet2))
-                || (p == planet2 && neutrals.contains(&planet1))
-        })
-    }
-
-    fn check_gana_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let gana1 = self.get_gana(sign1);
-        let gana2 = self.get_gana(sign2);
-
-        match (gana1, gana2) {
-            ("Deva", "Deva") | ("Manushya", "Manushya") | ("Rakshasa", "Rakshasa") => true,
-            ("Deva", "Manushya") | ("Manushya", "Deva") => true,
-            _ => false,
-        }
-    }
-
-    fn get_gana(&self, sign: ZodiacSign) -> &'static str {
-        match sign {
-            ZodiacSign::Aries | ZodiacSign::Leo | ZodiacSign::Sagittarius => "Deva",
-            ZodiacSign::Cancer | ZodiacSign::Scorpio | ZodiacSign::Pisces => "Rakshasa",
-            ZodiacSign::Gemini | ZodiacSign::Libra | ZodiacSign::Aquarius => "Deva",
-            ZodiacSign::Taurus | ZodiacSign::Virgo | ZodiacSign::Capricorn => "Manushya",
-        }
-    }
-
-    fn check_bhakut_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let diff = (sign2 as i32 - sign1 as i32 + 12) % 12;
-        matches!(diff, 1 | 2 | 3 | 4 | 5 | 7 | 9 | 11)
-    }
-
-    fn check_nadi_compatibility(&self, sign1: ZodiacSign, sign2: ZodiacSign) -> bool {
-        let nadi1 = self.get_nadi(sign1);
-        let nadi2 = self.get_nadi(sign2);
-        nadi1 != nadi2
-    }
-
-    fn get_nadi(&self, sign: ZodiacSign) -> &'static str {
-        match sign {
-            ZodiacSign::Aries | ZodiacSign::Cancer | ZodiacSign::Libra | ZodiacSign::Capricorn => {
-                "Aadi"
-            }
-            ZodiacSign::Taurus
-            | ZodiacSign::Virgo
-            | ZodiacSign::Sagittarius
-            | ZodiacSign::Pisces => "Madhya",
-            ZodiacSign::Gemini | ZodiacSign::Libra | ZodiacSign::Aquarius => "Antya",
-            _ => "Unknown",
-        }
-    }
-
-    pub fn suggest_remedial_measures(&self, chart: &ChartInfo) -> Vec<RemedialMeasure> {
-        let mut remedies = Vec::new();
-
-        for planet in &chart.planets {
-            if self.is_planet_weak(planet) {
-                let remedy = self.get_remedy_for_planet(planet.planet);
-                remedies.push(remedy);
-            }
-        }
-
-        // Add general remedies
-        remedies.push(RemedialMeasure {
-            description: "Practice meditation daily for spiritual growth".to_string(),
-            gemstone: None,
-        });
-
-        remedies.push(RemedialMeasure {
-            description: "Perform charity on Saturdays to mitigate malefic influences".to_string(),
-            gemstone: None,
-        });
-
-        remedies
-    }
-
-    fn is_planet_weak(&self, planet: &PlanetPosition) -> bool {
-        planet.retrograde || self.is_combust(planet)
-    }
-
-    fn is_combust(&self, planet: &PlanetPosition) -> bool {
-        if planet.planet == CelestialBody::Sun {
-            return false;
-        }
-
-        let current_julian_day = date_to_julian_day(Utc::now());
-        let sun_position = self
-            .calculate(
-                CoordinateSystem::Tropical,
-                current_julian_day,
-                CelestialBody::Sun,
-                &[],
-            )
-            .unwrap_or(AstronomicalResult::CelestialBody(CelestialCoordinates {
-                longitude: 0.0,
-                latitude: 0.0,
-                distance: 0.0,
-                speed_longitude: 0.0,
-                speed_latitude: 0.0,
-                speed_distance: 0.0,
-            }));
-
-        if let AstronomicalResult::CelestialBody(sun_info) = sun_position {
-            let angle_diff = (planet.longitude - sun_info.longitude).abs();
-            match planet.planet {
-                CelestialBody::Moon => angle_diff <= 12.0,
-                CelestialBody::Mars => angle_diff <= 17.0,
-                CelestialBody::Mercury => angle_diff <= 14.0,
-                CelestialBody::Jupiter => angle_diff <= 11.0,
-                CelestialBody::Venus => angle_diff <= 10.0,
-                CelestialBody::Saturn => angle_diff <= 15.0,
-                _ => false,
-            }
-        } else {
-            false
-        }
-    }
-
-    fn get_remedy_for_planet(&self, planet: CelestialBody) -> RemedialMeasure {
-        match planet {
-            CelestialBody::Sun => RemedialMeasure {
-                description: "Offer water to the Sun every morning".to_string(),
-                gemstone: Some("Ruby".to_string()),
-            },
-            CelestialBody::Moon => RemedialMeasure {
-                description: "Wear white clothes on Mondays".to_string(),
-                gemstone: Some("Pearl".to_string()),
-            },
-            CelestialBody::Mars => RemedialMeasure {
-                description: "Recite Mars mantra on Tuesdays".to_string(),
-                gemstone: Some("Red Coral".to_string()),
-            },
-            CelestialBody::Mercury => RemedialMeasure {
-                description: "Feed green vegetables to cows on Wednesdays".to_string(),
-                gemstone: Some("Emerald".to_string()),
-            },
-            CelestialBody::Jupiter => RemedialMeasure {
-                description: "Donate yellow items on Thursdays".to_string(),
-                gemstone: Some("Yellow Sapphire".to_string()),
-            },
-            CelestialBody::Venus => RemedialMeasure {
-                description: "Offer white flowers to Venus on Fridays".to_string(),
-                gemstone: Some("Diamond".to_string()),
-            },
-            CelestialBody::Saturn => RemedialMeasure {
-                description: "Feed black sesame seeds to birds on Saturdays".to_string(),
-                gemstone: Some("Blue Sapphire".to_string()),
-            },
-            CelestialBody::Rahu => RemedialMeasure {
-                description: "Donate to orphanages on Saturdays".to_string(),
-                gemstone: Some("Hessonite".to_string()),
-            },
-            CelestialBody::Ketu => RemedialMeasure {
-                description: "Perform fire rituals on Tuesdays".to_string(),
-                gemstone: Some("Cat's Eye".to_string()),
-            },
-        }
-    }
-
-    pub fn generate_interpretation(&self, report: &Report) -> String {
-        let mut interpretation = String::new();
-
-        interpretation.push_str(&format!(
-            "Birth Chart Interpretation for {}\n\n",
-            report.birth_info.date_time.format("%Y-%m-%d %H:%M:%S")
+        // Clocks in London went from 01:00 to 02:00 on 31 March 2024
+        let london = Location::new(51.5, -0.12, 0.0).with_tz_name("Europe/London");
+        assert!(matches!(
+            london.born_at(2024, 3, 31, 1, 30, 0),
+            Err(BirthInfoError::NonexistentLocalTime(_))
         ));
-
-        interpretation.push_str("Planetary Positions:\n");
-        for planet in &report.charts[0].planets {
-            interpretation.push_str(&format!(
-                "{}: {}° in {:?} (House {:?})\n",
-                self.get_body_name(planet.planet),
-                planet.longitude,
-                planet.nakshatra.nakshatra,
-                planet.house
-            ));
-        }
-
-        interpretation.push_str("\nAscendant: ");
-        interpretation.push_str(&format!("{:?}\n", report.charts[0].ascendant));
-
-        interpretation.push_str("\nYogas:\n");
-        for yoga in &report.yogas {
-            interpretation.push_str(&format!(
-                "{} Yoga (Strength: {:.2})\n",
-                yoga.yoga.name, yoga.strength
-            ));
-        }
-
-        interpretation.push_str("\nDasha Periods:\n");
-        interpretation.push_str(&format!(
-            "Maha Dasha: {:?} ({} to {})\n",
-            report.dashas.maha_dasha,
-            report.dashas.maha_dasha_start.format("%Y-%m-%d"),
-            report.dashas.maha_dasha_end.format("%Y-%m-%d")
-        ));
-        interpretation.push_str(&format!(
-            "Antar Dasha: {:?} ({} to {})\n",
-            report.dashas.antar_dasha,
-            report.dashas.antar_dasha_start.format("%Y-%m-%d"),
-            report.dashas.antar_dasha_end.format("%Y-%m-%d")
-        ));
-        interpretation.push_str(&format!(
-            "Pratyantar Dasha: {:?} ({} to {})\n",
-            report.dashas.pratyantar_dasha,
-            report.dashas.pratyantar_dasha_start.format("%Y-%m-%d"),
-            report.dashas.pratyantar_dasha_end.format("%Y-%m-%d")
-        ));
-
-        interpretation.push_str("\nPlanetary Strengths:\n");
-        for (planet, strength) in &report.strengths {
-            interpretation.push_str(&format!(
-                "{}: Shadbala = {:.2}, Ashtakavarga = {}\n",
-                self.get_body_name(*planet),
-                strength.shad_bala,
-                strength.ashtaka_varga
-            ));
-        }
-
-        interpretation.push_str("\nRemedial Measures:\n");
-        for remedy in &report.remedial_measures {
-            interpretation.push_str(&format!("- {}\n", remedy.description));
-            if let Some(gemstone) = &remedy.gemstone {
-                interpretation.push_str(&format!("  Recommended Gemstone: {}\n", gemstone));
-            }
-        }
-
-        interpretation
-    }
-
-    pub fn calculate_divisional_charts(&self, chart: &ChartInfo) -> Vec<DivisionalChart> {
-        let mut divisional_charts = Vec::new();
-
-        // D1 chart (Rashi chart)
-        divisional_charts.push(self.calculate_D1(chart));
-
-        // D2 chart (Hora chart)
-        divisional_charts.push(self.calculate_D2(chart));
-
-        // Add more divisional charts as needed (D3, D4, D9, etc.)
-
-        divisional_charts
-    }
-
-    fn calculate_D1(&self, chart: &ChartInfo) -> DivisionalChart {
-        DivisionalChart {
-            chart_type: ChartType::Rasi,
-            ascendant: chart.ascendant.sign,
-            houses: chart
-                .houses
-                .iter()
-                .map(|house| house.sign)
-                .collect::<Vec<ZodiacSign>>()
-                .try_into()
-                .unwrap(),
-            planets: chart.planets.clone(),
-        }
-    }
-
-    fn calculate_D2(&self, chart: &ChartInfo) -> DivisionalChart {
-        let mut d2_planets = Vec::new();
-
-        for planet in &chart.planets {
-            let d2_longitude = (planet.longitude * 2.0) % 360.0;
-            let d2_sign = ZodiacSign::from_longitude(d2_longitude);
-            let d2_house = House::from_index(((d2_longitude / 30.0).floor() as usize) + 1).unwrap();
-
-            d2_planets.push(PlanetPosition {
-                planet: planet.planet,
-                longitude: d2_longitude,
-                latitude: planet.latitude,
-                speed: planet.speed,
-                sign: d2_sign,
-                house: d2_house,
-                retrograde: planet.retrograde,
-                nakshatra: NakshatraInfo::from_longitude(d2_longitude),
-            });
-        }
-
-        DivisionalChart {
-            chart_type: ChartType::Hora,
-            ascendant: ZodiacSign::from_longitude((chart.ascendant.degree * 2.0).rem_euclid(360.0)),
-            houses: [ZodiacSign::Aries; 12], // Placeholder, actual calculation needed
-            planets: d2_planets,
-        }
-    }
-
-    pub fn calculate_chart(&self, birth_info: &BirthInfo) -> Result<ChartInfo, CalculationError> {
-        let julian_day = date_to_julian_day(birth_info.date_time);
-        let ayanamsa = self.calculate_ayanamsa(julian_day);
-        let houses = self.calculate_houses(
-            CoordinateSystem::Sidereal,
-            julian_day,
-            birth_info.location.latitude,
-            birth_info.location.longitude,
-            ChartType::Rasi,
-        )?;
-        let planets = self.calculate_planet_positions(
-            CoordinateSystem::Sidereal,
-            julian_day,
-            ChartType::Rasi,
-            birth_info,
-        )?;
-
-        let ascendant = houses.first().cloned().ok_or(CalculationError {
-            code: -1,
-            message: "Failed to calculate ascendant".to_string(),
-        })?;
-
-        Ok(ChartInfo {
-            chart_type: ChartType::Rasi,
-            ascendant,
-            houses,
-            planets,
-        })
-    }
-
-    fn calculate_house(
-        &self,
-        julian_day: f64,
-        latitude: f64,
-        longitude: f64,
-        chart_type: ChartType,
-        planet_longitude: f64,
-    ) -> Result<House, CalculationError> {
-        let hsys = match chart_type {
-            ChartType::Rasi => SE_HS_PLACIDUS,
-            ChartType::Navamsa => SE_HS_NAVAMSA,
-            ChartType::Hora => SE_HS_HORA,
-        };
-
-        let mut cusps: [c_double; 13] = [0.0; 13];
-        let mut ascmc: [c_double; 10] = [0.0; 10];
-        let mut planet_longitude = planet_longitude;
-
-        unsafe {
-            swe_houses_ex(
-                julian_day,
-                0, // flags
-                latitude,
-                longitude,
-                hsys,
-                cusps.as_mut_ptr(),
-                ascmc.as_mut_ptr(),
-            );
-        }
-
-        let house_position = unsafe {
-            swe_house_pos(
-                ascmc[2],
-                latitude,
-                ascmc[0],
-                hsys,
-                &mut planet_longitude as *mut f64,
-                std::ptr::null_mut(),
-            )
-        };
-
-        let house_number = house_position.floor() as usize;
-        House::from_index(house_number).ok_or(CalculationError {
-            code: -1,
-            message: format!("Invalid house number: {}", house_number),
-        })
-    }
-
-    pub fn is_house_compatible(&self, house1: House, house2: House) -> bool {
-        let angle_diff = (house2 as i32 - house1 as i32 + 12) % 12;
-        matches!(angle_diff, 1 | 2 | 3 | 4 | 5 | 7 | 9 | 11)
-    }
-
-    pub fn get_house_lord(&self, house: House) -> CelestialBody {
-        match house {
-            House::First => CelestialBody::Moon,
-            House::Second => CelestialBody::Mars,
-            House::Third => CelestialBody::Mercury,
-            House::Fourth => CelestialBody::Jupiter,
-            House::Fifth => CelestialBody::Venus,
-            House::Sixth => CelestialBody::Saturn,
-            House::Seventh => CelestialBody::Rahu,
-            House::Eighth => CelestialBody::Ketu,
-            House::Ninth => CelestialBody::Sun,
-            House::Tenth => CelestialBody::Moon,
-            House::Eleventh => CelestialBody::Mars,
-            House::Twelfth => CelestialBody::Mercury,
-            _ => CelestialBody::Sun,
-        }
-    }
-
-    pub fn calculate_house_lord_strength(&self, house: House, planet: CelestialBody) -> f64 {
-        let angle_diff = (planet as i32 - house as i32 + 12) % 12;
-        let strength = match angle_diff {
-            1 => 100.0,
-            2 => 95.0,
-            3 => 90.0,
-            4 => 85.0,
-            5 => 80.0,
-            6 => 75.0,
-            7 => 70.0,
-            8 => 65.0,
-            9 => 60.0,
-            10 => 55.0,
-            11 => 50.0,
-            _ => 0.0,
-        };
-
-        strength
-    }
-
-    pub fn calculate_house_lord_compatibility(
-        &self,
-        chart1: &ChartInfo,
-        chart2: &ChartInfo,
-    ) -> u32 {
-        let mut score = 0;
-
-        for house in House::all() {
-            let lord1 = self.get_house_lord(house);
-            let lord2 = self.get_house_lord(house);
-
-            let strength1 = self.calculate_house_lord_strength(house, lord1);
-            let strength2 = self.calculate_house_lord_strength(house, lord2);
-
-            if self.is_house_compatible(house, house) {
-                score += (strength1 + strength2) as u32;
-            }
-        }
-
-        score
-    }
-
-    pub fn calculate_house_lord_compatibility_score(
-        &self,
-        chart1: &ChartInfo,
-        chart2: &ChartInfo,
-    ) -> u32 {
-        let mut score = 0;
-
-        for house in House::all() {
-            let lord1 = self.get_house_lord(house);
-            let lord2 = self.get_house_lord(house);
-
-            let strength1 = self.calculate_house_lord_strength(house, lord1);
-            let strength2 = self.calculate_house_lord_strength(house, lord2);
-
-            if self.is_house_compatible(house, house) {
-                score += (strength1 + strength2) as u32;
-            }
-        }
-
-        score
-    }
-}
-
-// ---------------------------
-// ## Astronomical Result Enum
-// ---------------------------
-
-pub enum AstronomicalResult {
-    CelestialBody(CelestialCoordinates),
-    HousePosition(f64),
-}
-
-// ---------------------------
-// ## Utility Functions
-// ---------------------------
-
-pub fn date_to_julian_day(date_time: DateTime<Utc>) -> JulianDay {
-    let year = date_time.year();
-    let month = date_time.month();
-    let day = date_time.day();
-    let hour = date_time.hour();
-    let minute = date_time.minute();
-    let second = date_time.second() as f64 + (date_time.nanosecond() as f64 / 1_000_000_000.0);
-
-    let mut tjd_ut: c_double = 0.0;
-    let mut dret: [c_double; 2] = [0.0; 2];
-    let gregflag = SE_GREG_CAL;
-    unsafe {
-        swe_utc_to_jd(
-            year,
-            month as c_int,
-            day as c_int,
-            hour as c_int,
-            minute as c_int,
-            second,
-            gregflag,
-            &mut dret[0],
-            &mut dret[1],
-        );
-        tjd_ut = dret[1]; // Use UT
-    }
-    tjd_ut
-}
-
-pub fn julian_day_to_date(jd: JulianDay) -> DateTime<Utc> {
-    let mut year: c_int = 0;
-    let mut month: c_int = 0;
-    let mut day: c_int = 0;
-    let mut hour: c_int = 0;
-    let mut minute: c_int = 0;
-    let mut second: c_double = 0.0;
-
-    let gregflag = 1; // Gregorian calendar
-
-    unsafe {
-        swe_jdut1_to_utc(
-            jd,
-            gregflag,
-            &mut year,
-            &mut month,
-            &mut day,
-            &mut hour,
-            &mut minute,
-            &mut second,
-        );
-    }
-
-    Utc.ymd(year as i32, month as u32, day as u32)
-        .and_hms_micro(
-            hour as u32,
-            minute as u32,
-            second.floor() as u32,
-            ((second.fract() * 1_000_000.0) as u32),
-        )
-}
-
-pub fn calculate_ayanamsa(julian_day: JulianDay) -> AyanamsaInfo {
-    let ayanamsa_value = unsafe { swe_get_ayanamsa_ut(julian_day) };
-    let ayanamsa_name = "Lahiri".to_string(); // Assuming Lahiri ayanamsa
-    AyanamsaInfo {
-        ayanamsa_name,
-        ayanamsa_value,
     }
 }
@@ -0,0 +1,209 @@
+use super::*;
+
+/// Language names are shown in. Sanskrit is written in IAST transliteration; the others in
+/// their own scripts. Kerala and Tamil Nadu use their own names for the nakshatras and for
+/// several signs rather than Sanskrit spelt in their script, and those are the ones given.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Locale {
+    #[default]
+    English,
+    Hindi,
+    Malayalam,
+    Tamil,
+    Sanskrit,
+}
+
+impl Locale {
+    /// Column of the name tables below, which leave English to the types themselves.
+    fn column(&self) -> Option<usize> {
+        match self {
+            Locale::English => None,
+            Locale::Hindi => Some(0),
+            Locale::Malayalam => Some(1),
+            Locale::Tamil => Some(2),
+            Locale::Sanskrit => Some(3),
+        }
+    }
+}
+
+/// A name that can be shown in any [`Locale`].
+pub trait Localized {
+    fn name_in(&self, locale: Locale) -> String;
+}
+
+/// Hindi, Malayalam, Tamil and Sanskrit names, Aries first.
+const SIGNS: [[&str; 4]; 12] = [
+    ["मेष", "മേടം", "மேஷம்", "Meṣa"],
+    ["वृषभ", "ഇടവം", "ரிஷபம்", "Vṛṣabha"],
+    ["मिथुन", "മിഥുനം", "மிதுனம்", "Mithuna"],
+    ["कर्क", "കർക്കടകം", "கடகம்", "Karka"],
+    ["सिंह", "ചിങ്ങം", "சிம்மம்", "Siṃha"],
+    ["कन्या", "കന്നി", "கன்னி", "Kanyā"],
+    ["तुला", "തുലാം", "துலாம்", "Tulā"],
+    ["वृश्चिक", "വൃശ്ചികം", "விருச்சிகம்", "Vṛścika"],
+    ["धनु", "ധനു", "தனுசு", "Dhanus"],
+    ["मकर", "മകരം", "மகரம்", "Makara"],
+    ["कुम्भ", "കുംഭം", "கும்பம்", "Kumbha"],
+    ["मीन", "മീനം", "மீனம்", "Mīna"],
+];
+
+/// Hindi, Malayalam, Tamil and Sanskrit names, Ashwini first.
+const NAKSHATRAS: [[&str; 4]; 27] = [
+    ["अश्विनी", "അശ്വതി", "அசுவினி", "Aśvinī"],
+    ["भरणी", "ഭരണി", "பரணி", "Bharaṇī"],
+    ["कृत्तिका", "കാർത്തിക", "கார்த்திகை", "Kṛttikā"],
+    ["रोहिणी", "രോഹിണി", "ரோகிணி", "Rohiṇī"],
+    ["मृगशिरा", "മകയിരം", "மிருகசீரிடம்", "Mṛgaśirā"],
+    ["आर्द्रा", "തിരുവാതിര", "திருவாதிரை", "Ārdrā"],
+    ["पुनर्वसु", "പുണർതം", "புனர்பூசம்", "Punarvasu"],
+    ["पुष्य", "പൂയം", "பூசம்", "Puṣya"],
+    ["आश्लेषा", "ആയില്യം", "ஆயில்யம்", "Āśleṣā"],
+    ["मघा", "മകം", "மகம்", "Maghā"],
+    ["पूर्व फाल्गुनी", "പൂരം", "பூரம்", "Pūrvaphalgunī"],
+    ["उत्तर फाल्गुनी", "ഉത്രം", "உத்திரம்", "Uttaraphalgunī"],
+    ["हस्त", "അത്തം", "அஸ்தம்", "Hasta"],
+    ["चित्रा", "ചിത്തിര", "சித்திரை", "Citrā"],
+    ["स्वाति", "ചോതി", "சுவாதி", "Svātī"],
+    ["विशाखा", "വിശാഖം", "விசாகம்", "Viśākhā"],
+    ["अनुराधा", "അനിഴം", "அனுஷம்", "Anurādhā"],
+    ["ज्येष्ठा", "തൃക്കേട്ട", "கேட்டை", "Jyeṣṭhā"],
+    ["मूल", "മൂലം", "மூலம்", "Mūla"],
+    ["पूर्वाषाढ़ा", "പൂരാടം", "பூராடம்", "Pūrvāṣāḍhā"],
+    ["उत्तराषाढ़ा", "ഉത്രാടം", "உத்திராடம்", "Uttarāṣāḍhā"],
+    ["श्रवण", "തിരുവോണം", "திருவோணம்", "Śravaṇa"],
+    ["धनिष्ठा", "അവിട്ടം", "அவிட்டம்", "Dhaniṣṭhā"],
+    ["शतभिषा", "ചതയം", "சதயம்", "Śatabhiṣā"],
+    ["पूर्व भाद्रपद", "പൂരുരുട്ടാതി", "பூரட்டாதி", "Pūrvabhādrapadā"],
+    ["उत्तर भाद्रपद", "ഉത്രട്ടാതി", "உத்திரட்டாதி", "Uttarabhādrapadā"],
+    ["रेवती", "രേവതി", "ரேவதி", "Revatī"],
+];
+
+/// Hindi, Malayalam, Tamil and Sanskrit names of the nine grahas, Sun to Ketu in the
+/// weekday order.
+const GRAHAS: [(CelestialBody, [&str; 4]); 9] = [
+    (CelestialBody::Sun, ["सूर्य", "സൂര്യൻ", "சூரியன்", "Sūrya"]),
+    (CelestialBody::Moon, ["चन्द्र", "ചന്ദ്രൻ", "சந்திரன்", "Candra"]),
+    (CelestialBody::Mars, ["मंगल", "ചൊവ്വ", "செவ்வாய்", "Maṅgala"]),
+    (CelestialBody::Mercury, ["बुध", "ബുധൻ", "புதன்", "Budha"]),
+    (CelestialBody::Jupiter, ["गुरु", "വ്യാഴം", "குரு", "Guru"]),
+    (CelestialBody::Venus, ["शुक्र", "ശുക്രൻ", "சுக்கிரன்", "Śukra"]),
+    (CelestialBody::Saturn, ["शनि", "ശനി", "சனி", "Śani"]),
+    (CelestialBody::Rahu, ["राहु", "രാഹു", "ராகு", "Rāhu"]),
+    (CelestialBody::Ketu, ["केतु", "കേതു", "கேது", "Ketu"]),
+];
+
+/// Hindi, Malayalam, Tamil and Sanskrit names, Sunday first.
+const WEEKDAYS: [[&str; 4]; 7] = [
+    ["रविवार", "ഞായർ", "ஞாயிறு", "Ravivāra"],
+    ["सोमवार", "തിങ്കൾ", "திங்கள்", "Somavāra"],
+    ["मंगलवार", "ചൊവ്വ", "செவ்வாய்", "Maṅgalavāra"],
+    ["बुधवार", "ബുധൻ", "புதன்", "Budhavāra"],
+    ["गुरुवार", "വ്യാഴം", "வியாழன்", "Guruvāra"],
+    ["शुक्रवार", "വെള്ളി", "வெள்ளி", "Śukravāra"],
+    ["शनिवार", "ശനി", "சனி", "Śanivāra"],
+];
+
+/// Hindi, Malayalam, Tamil and Sanskrit names, Mangala first.
+const YOGINIS: [[&str; 4]; 8] = [
+    ["मंगला", "മംഗള", "மங்களா", "Maṅgalā"],
+    ["पिंगला", "പിംഗള", "பிங்களா", "Piṅgalā"],
+    ["धान्या", "ധാന്യ", "தான்யா", "Dhānyā"],
+    ["भ्रामरी", "ഭ്രാമരി", "பிராமரி", "Bhrāmarī"],
+    ["भद्रिका", "ഭദ്രിക", "பத்ரிகா", "Bhadrikā"],
+    ["उल्का", "ഉൽക്ക", "உல்கா", "Ulkā"],
+    ["सिद्धा", "സിദ്ധ", "சித்தா", "Siddhā"],
+    ["संकटा", "സങ്കട", "சங்கடா", "Saṅkaṭā"],
+];
+
+impl Localized for ZodiacSign {
+    fn name_in(&self, locale: Locale) -> String {
+        match locale.column() {
+            Some(column) => SIGNS[*self as usize][column].to_string(),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl Localized for Nakshatra {
+    fn name_in(&self, locale: Locale) -> String {
+        match locale.column() {
+            Some(column) => NAKSHATRAS[*self as usize][column].to_string(),
+            None => format!("{:?}", self),
+        }
+    }
+}
+
+/// The outer planets and asteroids have no traditional names and stay in English.
+impl Localized for CelestialBody {
+    fn name_in(&self, locale: Locale) -> String {
+        let names = GRAHAS
+            .iter()
+            .find(|(body, _)| body == self)
+            .map(|(_, names)| names);
+        match (locale.column(), names) {
+            (Some(column), Some(names)) => names[column].to_string(),
+            _ => format!("{:?}", self),
+        }
+    }
+}
+
+impl Localized for Vara {
+    fn name_in(&self, locale: Locale) -> String {
+        match locale.column() {
+            Some(column) => WEEKDAYS[*self as usize][column].to_string(),
+            None => [
+                "Sunday",
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+            ][*self as usize]
+                .to_string(),
+        }
+    }
+}
+
+impl Localized for Yogini {
+    fn name_in(&self, locale: Locale) -> String {
+        match locale.column() {
+            Some(column) => YOGINIS[*self as usize][column].to_string(),
+            None => format!("{:?}", self),
+        }
+    }
+}
+
+impl Localized for DashaLord {
+    fn name_in(&self, locale: Locale) -> String {
+        match self {
+            DashaLord::Planet(planet) => planet.name_in(locale),
+            DashaLord::Yogini(yogini) => yogini.name_in(locale),
+            DashaLord::Sign(sign) => sign.name_in(locale),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_follow_the_locale() {
+        assert_eq!(ZodiacSign::Leo.name_in(Locale::English), "Leo");
+        assert_eq!(ZodiacSign::Leo.name_in(Locale::Malayalam), "ചിങ്ങം");
+        assert_eq!(Nakshatra::Shravana.name_in(Locale::Tamil), "திருவோணம்");
+        assert_eq!(Nakshatra::Revati.name_in(Locale::Sanskrit), "Revatī");
+        assert_eq!(Vara::Guruvara.name_in(Locale::English), "Thursday");
+        assert_eq!(
+            DashaLord::Planet(CelestialBody::Jupiter).name_in(Locale::Hindi),
+            "गुरु"
+        );
+        assert_eq!(CelestialBody::Pluto.name_in(Locale::Hindi), "Pluto");
+        assert_eq!(
+            DashaLord::Yogini(Yogini::Sankata).name_in(Locale::Sanskrit),
+            "Saṅkaṭā"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// A point of the zodiac in degrees, always in `0..360`. Adding or subtracting degrees
+/// wraps around 0° Aries.
+///
+/// Serializes as a bare number of degrees. Displays as the degree, minute and second within
+/// the sign, `23°45'12" Taurus`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Longitude(f64);
+
+impl Longitude {
+    pub fn new(degrees: f64) -> Self {
+        // rem_euclid can round a tiny negative angle up to exactly 360
+        let degrees = degrees.rem_euclid(360.0);
+        Longitude(if degrees < 360.0 { degrees } else { 0.0 })
+    }
+
+    /// Degrees from 0° Aries.
+    pub fn degrees(self) -> f64 {
+        self.0
+    }
+
+    pub fn sign(self) -> ZodiacSign {
+        ZodiacSign::from_longitude(self.0)
+    }
+
+    /// Degrees from the start of the sign, in `0..30`.
+    pub fn degree_in_sign(self) -> f64 {
+        self.0 % 30.0
+    }
+
+    pub fn nakshatra(self) -> NakshatraInfo {
+        NakshatraInfo::from_longitude(self.0)
+    }
+
+    /// Sign, degree, minute and second, rounded to the nearest second. A longitude a
+    /// fraction of a second short of a sign boundary rounds into the next sign.
+    pub fn dms(self) -> (ZodiacSign, u8, u8, u8) {
+        let seconds = (self.0 * 3600.0).round() as u32 % (360 * 3600);
+        let sign = ZodiacSign::from_longitude(f64::from(seconds / 108_000) * 30.0);
+        let within = seconds % 108_000;
+        (
+            sign,
+            (within / 3600) as u8,
+            (within / 60 % 60) as u8,
+            (within % 60) as u8,
+        )
+    }
+
+    /// Degrees `self` is ahead of `other` going forward through the zodiac, in `0..360`.
+    pub fn arc_from(self, other: Longitude) -> f64 {
+        (self.0 - other.0).rem_euclid(360.0)
+    }
+
+    /// The shorter way round between the two, in `0..=180`.
+    pub fn separation(self, other: Longitude) -> f64 {
+        let arc = self.arc_from(other);
+        arc.min(360.0 - arc)
+    }
+}
+
+impl fmt::Display for Longitude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (sign, degrees, minutes, seconds) = self.dms();
+        f.pad(&format!(
+            "{}°{:02}'{:02}\" {}",
+            degrees, minutes, seconds, sign
+        ))
+    }
+}
+
+impl From<f64> for Longitude {
+    fn from(degrees: f64) -> Self {
+        Longitude::new(degrees)
+    }
+}
+
+impl From<Longitude> for f64 {
+    fn from(longitude: Longitude) -> Self {
+        longitude.0
+    }
+}
+
+impl Add<f64> for Longitude {
+    type Output = Longitude;
+
+    fn add(self, degrees: f64) -> Longitude {
+        Longitude::new(self.0 + degrees)
+    }
+}
+
+impl Sub<f64> for Longitude {
+    type Output = Longitude;
+
+    fn sub(self, degrees: f64) -> Longitude {
+        Longitude::new(self.0 - degrees)
+    }
+}
+
+/// The forward arc from `other` to `self`, as [`Longitude::arc_from`].
+impl Sub for Longitude {
+    type Output = f64;
+
+    fn sub(self, other: Longitude) -> f64 {
+        self.arc_from(other)
+    }
+}
+
+impl AddAssign<f64> for Longitude {
+    fn add_assign(&mut self, degrees: f64) {
+        *self = *self + degrees;
+    }
+}
+
+impl SubAssign<f64> for Longitude {
+    fn sub_assign(&mut self, degrees: f64) {
+        *self = *self - degrees;
+    }
+}
@@ -0,0 +1,184 @@
+use super::*;
+
+/// A lunar month of the Indian lunisolar calendar.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LunarMonth {
+    Chaitra,
+    Vaishakha,
+    Jyeshtha,
+    Ashadha,
+    Shravana,
+    Bhadrapada,
+    Ashwin,
+    Kartika,
+    Margashirsha,
+    Pausha,
+    Magha,
+    Phalguna,
+}
+
+impl LunarMonth {
+    const ALL: [LunarMonth; 12] = [
+        LunarMonth::Chaitra,
+        LunarMonth::Vaishakha,
+        LunarMonth::Jyeshtha,
+        LunarMonth::Ashadha,
+        LunarMonth::Shravana,
+        LunarMonth::Bhadrapada,
+        LunarMonth::Ashwin,
+        LunarMonth::Kartika,
+        LunarMonth::Margashirsha,
+        LunarMonth::Pausha,
+        LunarMonth::Magha,
+        LunarMonth::Phalguna,
+    ];
+
+    /// The amanta month beginning at a new Moon with the Sun in `sign`: the month in which
+    /// the Sun goes on into the next sign, Chaitra being the month of Mesha Sankranti.
+    pub fn from_sun_sign(sign: ZodiacSign) -> Self {
+        LunarMonth::ALL[(sign as usize + 1) % 12]
+    }
+
+    pub fn next(&self) -> Self {
+        LunarMonth::ALL[(*self as usize + 1) % 12]
+    }
+}
+
+impl fmt::Display for LunarMonth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A moment in the Indian lunisolar calendar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LunisolarDate {
+    /// The month counted from new Moon to new Moon (amanta), as in the south and west.
+    pub month: LunarMonth,
+    /// An intercalary month, in which the Sun enters no sign. It takes the name of the
+    /// month that follows it.
+    pub adhika: bool,
+    /// The month counted from full Moon to full Moon (purnimanta), as in the north, which
+    /// runs half a month ahead of the amanta one through the dark half.
+    pub purnimanta_month: LunarMonth,
+    pub paksha: Paksha,
+    pub tithi: Tithi,
+    /// Year of the Saka era, begun with Chaitra.
+    pub saka_year: i32,
+    /// Year of the Vikram Samvat, 135 years ahead of the Saka, also counted from Chaitra.
+    pub vikram_samvat: i32,
+    /// New Moons on which the amanta month begins and ends.
+    pub month_start: DateTime<Utc>,
+    pub month_end: DateTime<Utc>,
+}
+
+impl LunisolarDate {
+    /// The month name with "Adhika" before it for an intercalary month.
+    pub fn month_name(&self) -> String {
+        if self.adhika {
+            format!("Adhika {}", self.month)
+        } else {
+            self.month.to_string()
+        }
+    }
+}
+
+/// Years between the start of the Saka era and the Gregorian year in which its year
+/// begins.
+#[cfg(not(feature = "model-only"))]
+const SAKA_OFFSET: i32 = 78;
+
+/// Years the Vikram Samvat runs ahead of the Saka era.
+#[cfg(not(feature = "model-only"))]
+const VIKRAM_OFFSET: i32 = 135;
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The lunisolar date at `date_time`, with the Sun placed on the engine's ayanamsa.
+    ///
+    /// A month is adhika when the Sun enters no sign between its new Moons. The rare kshaya
+    /// month, in which it enters two, is not detected; the month takes the name of the
+    /// first.
+    pub fn lunisolar_date(
+        &self,
+        date_time: DateTime<Utc>,
+    ) -> Result<LunisolarDate, AstrologyError> {
+        let phase = self.lunar_phase(date_time)?;
+        // The mean Moon gains on the Sun by 12.19° a day; the margin keeps the search
+        // from landing on the new Moon after this one
+        let days_since_new_moon = phase.elongation / 12.19 + 2.0;
+        let month_start = self.next_new_moon(
+            date_time - ChronoDuration::minutes((days_since_new_moon * 1440.0) as i64),
+        )?;
+        let month_end = self.next_new_moon(month_start + ChronoDuration::days(1))?;
+
+        let sun_sign = |moment: DateTime<Utc>| -> Result<ZodiacSign, AstrologyError> {
+            let longitude =
+                self.sidereal_longitude(CelestialBody::Sun, date_to_julian_day(moment))?;
+            Ok(ZodiacSign::from_longitude(longitude))
+        };
+        let start_sign = sun_sign(month_start)?;
+        let month = LunarMonth::from_sun_sign(start_sign);
+        let adhika = sun_sign(month_end)? == start_sign;
+        let purnimanta_month = match phase.paksha {
+            Paksha::Krishna if !adhika => month.next(),
+            _ => month,
+        };
+
+        // The year turns with Chaitra, which can begin as late as April
+        let year = date_time.year();
+        let before_chaitra =
+            date_time.month() <= 4 && (month as usize) >= LunarMonth::Margashirsha as usize;
+        let saka_year = year - SAKA_OFFSET - i32::from(before_chaitra);
+
+        Ok(LunisolarDate {
+            month,
+            adhika,
+            purnimanta_month,
+            paksha: phase.paksha,
+            tithi: phase.tithi,
+            saka_year,
+            vikram_samvat: saka_year + VIKRAM_OFFSET,
+            month_start,
+            month_end,
+        })
+    }
+}
+
+#[cfg(all(test, not(feature = "model-only")))]
+mod tests {
+    use super::*;
+
+    fn lunisolar(year: i32, month: u32, day: u32) -> LunisolarDate {
+        let ephemeris = SwissEph::new().unwrap();
+        let noon = Utc.with_ymd_and_hms(year, month, day, 6, 30, 0).unwrap();
+        ephemeris.lunisolar_date(noon).unwrap()
+    }
+
+    #[test]
+    fn months_years_and_adhika_masa() {
+        // Diwali, the new Moon ending Ashwin amanta, fell on 1 November 2024; the day
+        // before was Kartika in the purnimanta reckoning
+        let diwali_eve = lunisolar(2024, 10, 31);
+        assert_eq!(diwali_eve.month, LunarMonth::Ashwin);
+        assert_eq!(diwali_eve.purnimanta_month, LunarMonth::Kartika);
+        assert_eq!(diwali_eve.paksha, Paksha::Krishna);
+        assert_eq!(diwali_eve.saka_year, 1946);
+        assert_eq!(diwali_eve.vikram_samvat, 2081);
+
+        // Makara Sankranti 2024 came in Pausha, before the year turned
+        let january = lunisolar(2024, 1, 15);
+        assert_eq!(january.month, LunarMonth::Pausha);
+        assert_eq!(january.saka_year, 1945);
+
+        // 2023 had an adhika Shravana from 18 July to 16 August
+        let adhika = lunisolar(2023, 8, 1);
+        assert!(adhika.adhika);
+        assert_eq!(adhika.month_name(), "Adhika Shravana");
+        let nija = lunisolar(2023, 8, 25);
+        assert!(!nija.adhika);
+        assert_eq!(nija.month, LunarMonth::Shravana);
+    }
+}
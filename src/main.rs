@@ -1,17 +1,462 @@
-use aztro_core::{   AstronomicalResult, BirthInfo, CelestialBody, CoordinateSystem, Gender, Location, Report};
-use chrono::{TimeZone, Utc};
- 
+//! The `aztro` command line. Run it without arguments for usage.
 
+#[cfg(not(feature = "model-only"))]
+use aztro_core::{
+    AstrologyError, BirthInfo, CelestialBody, ChartInfo, ChartOptions, CompatibilityInfo,
+    DashaInfo, HouseSystem, Longitude, Report, SavedChart, SwissEph, TransitInfo,
+};
+#[cfg(not(feature = "model-only"))]
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(not(feature = "model-only"))]
+use serde::Serialize;
+#[cfg(not(feature = "model-only"))]
+use std::collections::HashMap;
+#[cfg(not(feature = "model-only"))]
+use std::fmt::Write;
+#[cfg(not(feature = "model-only"))]
+use std::process::ExitCode;
+
+#[cfg(not(feature = "model-only"))]
+const USAGE: &str = "\
+Usage:
+  aztro chart    BIRTH [--house-system SYSTEM] [--outer-planets] [--asteroids]
+                       [--format json|text|svg]
+  aztro report   BIRTH [--format json|text]
+  aztro dasha    BIRTH [--as-of TIME] [--format json|text]
+  aztro transits --from TIME --to TIME [--planets Sun,Mars,...] [--format json|text]
+  aztro match    FIRST SECOND [--format json|text]
+
+BIRTH is either --date \"1991-06-18 07:10 +05:30\" --lat 11.2588 --lon 75.7804, or
+--input FILE with birth info in JSON or as \"1991-06-18 07:10 +05:30 @ 11.2588,75.7804\".
+FIRST and SECOND are files holding birth info, a saved chart or a chart from
+`aztro chart --format json`.
+A FILE of - is read from standard input. TIME is RFC 3339 or a date, YYYY-MM-DD, in UTC.
+SYSTEM is a house system such as Placidus or WholeSign. The format defaults to text.";
+
+/// Options that stand alone; every other option takes a value.
+#[cfg(not(feature = "model-only"))]
+const FLAGS: [&str; 2] = ["outer-planets", "asteroids"];
+
+#[cfg(feature = "model-only")]
 fn main() {
+    eprintln!("aztro_core was built with `model-only`; there is no engine to calculate with.");
+}
+
+#[cfg(not(feature = "model-only"))]
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let arguments = match Arguments::parse(&arguments) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&arguments) {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A subcommand with its positional arguments, its options and the flags given.
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Default, PartialEq)]
+struct Arguments {
+    command: String,
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl Arguments {
+    fn parse(arguments: &[String]) -> Result<Arguments, String> {
+        let mut arguments = arguments.iter();
+        let mut parsed = Arguments {
+            command: arguments.next().ok_or("No command given")?.clone(),
+            ..Arguments::default()
+        };
+        while let Some(argument) = arguments.next() {
+            match argument.strip_prefix("--") {
+                Some(name) if FLAGS.contains(&name) => {
+                    parsed.options.insert(name.to_string(), "true".to_string());
+                }
+                Some(name) => {
+                    let value = arguments
+                        .next()
+                        .ok_or_else(|| format!("--{} needs a value", name))?;
+                    parsed.options.insert(name.to_string(), value.clone());
+                }
+                None => parsed.positional.push(argument.clone()),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn required(&self, name: &str) -> Result<&str, AstrologyError> {
+        self.option(name)
+            .ok_or_else(|| invalid(format!("--{} is required", name)))
+    }
+
+    fn format(&self) -> Format {
+        match self.option("format") {
+            Some("json") => Format::Json,
+            Some("svg") => Format::Svg,
+            _ => Format::Text,
+        }
+    }
+
+    /// The birth from `--input`, or else from `--date`, `--lat` and `--lon`.
+    fn birth_info(&self) -> Result<BirthInfo, AstrologyError> {
+        if let Some(path) = self.option("input") {
+            return birth_info(&read_input(path)?);
+        }
+        Ok(BirthInfo::parse(&format!(
+            "{} @ {},{}",
+            self.required("date")?,
+            self.required("lat")?,
+            self.required("lon")?
+        ))?)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Format {
+    Json,
+    Text,
+    Svg,
+}
+
+#[cfg(not(feature = "model-only"))]
+fn run(arguments: &Arguments) -> Result<String, AstrologyError> {
+    let ephemeris = SwissEph::new()?;
+    let format = arguments.format();
+    if format == Format::Svg && arguments.command != "chart" {
+        return Err(invalid("Only charts can be drawn as SVG".to_string()));
+    }
+
+    match arguments.command.as_str() {
+        "chart" => {
+            let mut options = ChartOptions::default();
+            if let Some(system) = arguments.option("house-system") {
+                options.house_system = serde_json::from_value::<HouseSystem>(system.into())
+                    .map_err(|_| invalid(format!("Unknown house system '{}'", system)))?;
+            }
+            options.include_outer_planets = arguments.option("outer-planets").is_some();
+            options.include_asteroids = arguments.option("asteroids").is_some();
+            let chart =
+                ephemeris.calculate_chart_with_options(&arguments.birth_info()?, &options)?;
+            match format {
+                Format::Json => to_json(&chart),
+                Format::Text => Ok(chart_text(&chart)),
+                Format::Svg => Ok(chart.to_svg()),
+            }
+        }
+        "report" => {
+            let birth_info = arguments.birth_info()?;
+            let name = birth_info
+                .person
+                .as_ref()
+                .map_or(String::new(), |person| person.name.clone());
+            let gender = birth_info.gender();
+            let report = Report::calculate_with(&ephemeris, name, birth_info, gender)?;
+            match format {
+                Format::Json => to_json(&report),
+                _ => Ok(report.to_string()),
+            }
+        }
+        "dasha" => {
+            let as_of = match arguments.option("as-of") {
+                Some(time) => parse_time(time)?,
+                None => Utc::now(),
+            };
+            let dasha = ephemeris.calculate_dasha(&arguments.birth_info()?, as_of)?;
+            match format {
+                Format::Json => to_json(&dasha),
+                _ => Ok(dasha_text(&dasha)),
+            }
+        }
+        "transits" => {
+            let from = parse_time(arguments.required("from")?)?;
+            let to = parse_time(arguments.required("to")?)?;
+            let bodies = match arguments.option("planets") {
+                Some(planets) => planets
+                    .split(',')
+                    .map(|planet| {
+                        serde_json::from_value::<CelestialBody>(planet.trim().into())
+                            .map_err(|_| invalid(format!("Unknown planet '{}'", planet)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                // The Moon changes sign every two or three days and would crowd out the rest
+                None => vec![
+                    CelestialBody::Sun,
+                    CelestialBody::Mercury,
+                    CelestialBody::Venus,
+                    CelestialBody::Mars,
+                    CelestialBody::Jupiter,
+                    CelestialBody::Saturn,
+                    CelestialBody::Rahu,
+                    CelestialBody::Ketu,
+                ],
+            };
+            let transits = ephemeris.calculate_transits(&bodies, from, to)?;
+            match format {
+                Format::Json => to_json(&transits),
+                _ => Ok(transits_text(&transits)),
+            }
+        }
+        "match" => {
+            let [first, second] = arguments.positional.as_slice() else {
+                return Err(invalid("match needs two files".to_string()));
+            };
+            let compatibility = match (match_input(first)?, match_input(second)?) {
+                (MatchInput::Birth(first), MatchInput::Birth(second)) => {
+                    ephemeris.calculate_marriage_match(&first, &second)?
+                }
+                (first, second) => ephemeris.calculate_compatibility(
+                    &first.chart(&ephemeris)?,
+                    &second.chart(&ephemeris)?,
+                )?,
+            };
+            match format {
+                Format::Json => to_json(&compatibility),
+                _ => Ok(match_text(&compatibility)),
+            }
+        }
+        command => Err(invalid(format!(
+            "Unknown command '{}'\n\n{}",
+            command, USAGE
+        ))),
+    }
+}
+
+/// One side of a match: a chart already cast, or a birth to cast one for.
+#[cfg(not(feature = "model-only"))]
+enum MatchInput {
+    Chart(Box<ChartInfo>),
+    Birth(BirthInfo),
+}
+
+#[cfg(not(feature = "model-only"))]
+impl MatchInput {
+    fn chart(self, ephemeris: &SwissEph) -> Result<ChartInfo, AstrologyError> {
+        match self {
+            MatchInput::Chart(chart) => Ok(*chart),
+            MatchInput::Birth(birth) => {
+                ephemeris.calculate_chart(&birth, ChartOptions::default().house_system)
+            }
+        }
+    }
+}
 
- 
-    let name = "Aghil Mohan";
+#[cfg(not(feature = "model-only"))]
+fn match_input(path: &str) -> Result<MatchInput, AstrologyError> {
+    let text = read_input(path)?;
+    if let Ok(saved) = SavedChart::from_json(&text) {
+        return Ok(MatchInput::Chart(Box::new(saved.chart)));
+    }
+    match serde_json::from_str::<ChartInfo>(&text) {
+        Ok(chart) => Ok(MatchInput::Chart(Box::new(chart))),
+        Err(_) => Ok(MatchInput::Birth(birth_info(&text)?)),
+    }
+}
+
+/// Contents of the file at `path`, or of standard input for `-`.
+#[cfg(not(feature = "model-only"))]
+fn read_input(path: &str) -> Result<String, AstrologyError> {
+    let read = if path == "-" {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    };
+    read.map_err(|error| invalid(format!("Cannot read {}: {}", path, error)))
+}
 
-    let gender = Gender::Male;
+/// A [`BirthInfo`] in JSON or a birth record in the form [`BirthInfo::parse`] takes.
+#[cfg(not(feature = "model-only"))]
+fn birth_info(text: &str) -> Result<BirthInfo, AstrologyError> {
+    if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|error| invalid(error.to_string()))
+    } else {
+        Ok(BirthInfo::parse(text)?)
+    }
+}
+
+/// An RFC 3339 time, or midnight UTC at the start of a `YYYY-MM-DD` date.
+#[cfg(not(feature = "model-only"))]
+fn parse_time(time: &str) -> Result<DateTime<Utc>, AstrologyError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| invalid(format!("Invalid time '{}'", time)))
+}
+
+#[cfg(not(feature = "model-only"))]
+fn invalid(message: String) -> AstrologyError {
+    AstrologyError::InvalidInput(message)
+}
+
+#[cfg(not(feature = "model-only"))]
+fn to_json(value: &impl Serialize) -> Result<String, AstrologyError> {
+    serde_json::to_string_pretty(value)
+        .map(|json| json + "\n")
+        .map_err(|error| AstrologyError::Calculation(error.to_string()))
+}
+
+#[cfg(not(feature = "model-only"))]
+fn chart_text(chart: &ChartInfo) -> String {
+    let mut text = format!(
+        "{:<10} {}\n\n{:<8} {:<22} {:<2} {:<9} {:<16} Pada\n",
+        "Ascendant",
+        Longitude::new(chart.ascendant.longitude()),
+        "Planet",
+        "Longitude",
+        "R",
+        "House",
+        "Nakshatra"
+    );
+    for planet in &chart.planets {
+        let _ = writeln!(
+            text,
+            "{:<8} {:<22} {:<2} {:<9} {:<16} {}",
+            format!("{:?}", planet.planet),
+            planet.longitude,
+            if planet.retrograde { "R" } else { "" },
+            format!("{:?}", planet.house),
+            format!("{:?}", planet.nakshatra.nakshatra),
+            planet.nakshatra.pada
+        );
+    }
+    text
+}
+
+#[cfg(not(feature = "model-only"))]
+fn dasha_text(dasha: &DashaInfo) -> String {
+    [
+        (
+            "Maha",
+            dasha.maha_dasha,
+            dasha.maha_dasha_start,
+            dasha.maha_dasha_end,
+        ),
+        (
+            "Antar",
+            dasha.antar_dasha,
+            dasha.antar_dasha_start,
+            dasha.antar_dasha_end,
+        ),
+        (
+            "Pratyantar",
+            dasha.pratyantar_dasha,
+            dasha.pratyantar_dasha_start,
+            dasha.pratyantar_dasha_end,
+        ),
+    ]
+    .iter()
+    .map(|(level, lord, start, end)| {
+        format!(
+            "{:<10} {:<8} {} to {}\n",
+            level,
+            lord,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d")
+        )
+    })
+    .collect()
+}
+
+#[cfg(not(feature = "model-only"))]
+fn transits_text(transits: &[TransitInfo]) -> String {
+    transits
+        .iter()
+        .map(|transit| {
+            format!(
+                "{} {:<8} {} to {}{}\n",
+                transit.date.format("%Y-%m-%d %H:%M"),
+                format!("{:?}", transit.planet),
+                transit.from_sign,
+                transit.to_sign,
+                if transit.retrograde { " (R)" } else { "" }
+            )
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "model-only"))]
+fn match_text(compatibility: &CompatibilityInfo) -> String {
+    let kutas = &compatibility.kutas;
+    let mut text = String::new();
+    for (name, kuta) in [
+        ("Varna", &kutas.varna),
+        ("Vasya", &kutas.vasya),
+        ("Tara", &kutas.tara),
+        ("Yoni", &kutas.yoni),
+        ("Graha Maitri", &kutas.graha_maitri),
+        ("Gana", &kutas.gana),
+        ("Bhakut", &kutas.bhakut),
+        ("Nadi", &kutas.nadi),
+    ] {
+        let _ = writeln!(text, "{:<13} {:>4} / {}", name, kuta.score, kuta.max);
+    }
+    let total = kutas.total();
+    let _ = writeln!(
+        text,
+        "{:<13} {:>4} / {} ({:?})",
+        "Total",
+        total.score,
+        total.max,
+        total.verdict()
+    );
+    for (side, dosha) in [
+        ("first", &compatibility.mangal_dosha1),
+        ("second", &compatibility.mangal_dosha2),
+    ] {
+        let _ = writeln!(
+            text,
+            "Mangal Dosha of the {}: {}",
+            side,
+            if dosha.is_formed() { "yes" } else { "no" }
+        );
+    }
+    text
+}
 
-    let birth_info = Location::kozhikode().born_at(1991, 6, 18, 7, 10, 0);
+#[cfg(all(test, not(feature = "model-only")))]
+mod tests {
+    use super::*;
 
-    let report = Report::calculate(name, birth_info, gender).unwrap();
+    #[test]
+    fn options_take_values_and_flags_stand_alone() {
+        let arguments: Vec<String> = "chart --outer-planets --lat 11.2 --format svg extra"
+            .split(' ')
+            .map(String::from)
+            .collect();
+        let parsed = Arguments::parse(&arguments).unwrap();
+        assert_eq!(parsed.command, "chart");
+        assert_eq!(parsed.option("lat"), Some("11.2"));
+        assert_eq!(parsed.option("outer-planets"), Some("true"));
+        assert_eq!(parsed.format(), Format::Svg);
+        assert_eq!(parsed.positional, vec!["extra".to_string()]);
 
-    report.pretty_print();
+        let dangling = vec!["dasha".to_string(), "--as-of".to_string()];
+        assert!(Arguments::parse(&dangling).is_err());
+        assert_eq!(
+            parse_time("2024-03-01").unwrap().to_rfc3339(),
+            "2024-03-01T00:00:00+00:00"
+        );
+    }
 }
@@ -0,0 +1,162 @@
+use super::*;
+
+/// Degrees from a midpoint within which a planet or the ascendant is taken to stand on it.
+pub const MIDPOINT_ORB: f64 = 1.5;
+
+/// The point halfway between two planets, and what stands on it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Midpoint {
+    pub planet1: CelestialBody,
+    pub planet2: CelestialBody,
+    /// The nearer midpoint, halfway along the shorter arc between the two.
+    pub longitude: Longitude,
+    /// Planets and the ascendant on the midpoint or on the point opposite it, which
+    /// together make its axis, closest first. `distance` is to the nearer end of the axis.
+    pub contacts: Vec<NearbyPoint>,
+}
+
+impl Midpoint {
+    /// The far end of the midpoint axis.
+    pub fn opposite(&self) -> Longitude {
+        self.longitude + 180.0
+    }
+}
+
+impl ChartInfo {
+    /// Midpoints of every pair of the chart's planets, the first planet listed first, with
+    /// the planets and the ascendant within `orb` of each axis.
+    ///
+    /// Rahu and Ketu always stand opposite each other, so their midpoint is left out.
+    pub fn midpoints(&self, orb: f64) -> Vec<Midpoint> {
+        let mut points: Vec<(ChartPoint, Longitude)> = self
+            .planets
+            .iter()
+            .map(|planet| (ChartPoint::Planet(planet.planet), planet.longitude))
+            .collect();
+        points.push((
+            ChartPoint::Ascendant,
+            Longitude::new(self.ascendant.longitude()),
+        ));
+
+        let mut midpoints = Vec::new();
+        for (i, first) in self.planets.iter().enumerate() {
+            for second in self.planets.iter().skip(i + 1) {
+                let nodes = [first.planet, second.planet];
+                if nodes.contains(&CelestialBody::Rahu) && nodes.contains(&CelestialBody::Ketu) {
+                    continue;
+                }
+                let arc = second.longitude - first.longitude;
+                let longitude = if arc <= 180.0 {
+                    first.longitude + arc / 2.0
+                } else {
+                    second.longitude + (360.0 - arc) / 2.0
+                };
+
+                let mut contacts: Vec<NearbyPoint> = points
+                    .iter()
+                    .filter(|(point, _)| {
+                        !matches!(point, ChartPoint::Planet(planet) if nodes.contains(planet))
+                    })
+                    .map(|&(point, point_longitude)| {
+                        let distance = point_longitude.separation(longitude);
+                        NearbyPoint {
+                            point,
+                            longitude: point_longitude.degrees(),
+                            distance: distance.min(180.0 - distance),
+                        }
+                    })
+                    .filter(|contact| contact.distance <= orb)
+                    .collect();
+                contacts.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+                midpoints.push(Midpoint {
+                    planet1: first.planet,
+                    planet2: second.planet,
+                    longitude,
+                    contacts,
+                });
+            }
+        }
+        midpoints
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Midpoints of the chart's planets with what stands on them within [`MIDPOINT_ORB`].
+    pub fn calculate_midpoints(&self, chart: &ChartInfo) -> Vec<Midpoint> {
+        chart.midpoints(MIDPOINT_ORB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+    }
+
+    #[test]
+    fn midpoints_take_the_shorter_arc_and_find_their_contacts() {
+        let chart = ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Cancer,
+                degree: 0.5,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                planet(CelestialBody::Sun, 350.0),
+                planet(CelestialBody::Moon, 30.0),
+                planet(CelestialBody::Mars, 190.5),
+                planet(CelestialBody::Rahu, 100.0),
+                planet(CelestialBody::Ketu, 280.0),
+            ],
+            warnings: Vec::new(),
+        };
+        let midpoints = chart.midpoints(MIDPOINT_ORB);
+        // Ten pairs of five planets, less the nodes
+        assert_eq!(midpoints.len(), 9);
+
+        // Across 0° Aries, with Mars on the far end of the axis
+        let sun_moon = &midpoints[0];
+        assert_eq!(
+            (sun_moon.planet1, sun_moon.planet2),
+            (CelestialBody::Sun, CelestialBody::Moon)
+        );
+        assert!((sun_moon.longitude.degrees() - 10.0).abs() < 1e-9);
+        assert_eq!(
+            sun_moon.contacts[0].point,
+            ChartPoint::Planet(CelestialBody::Mars)
+        );
+        assert!((sun_moon.contacts[0].distance - 0.5).abs() < 1e-9);
+
+        // Nothing stands on 65°, between the Moon at 30° and Rahu at 100°
+        let moon_rahu = midpoints
+            .iter()
+            .find(|midpoint| {
+                (midpoint.planet1, midpoint.planet2) == (CelestialBody::Moon, CelestialBody::Rahu)
+            })
+            .unwrap();
+        assert!((moon_rahu.longitude.degrees() - 65.0).abs() < 1e-9);
+        assert!(moon_rahu.contacts.is_empty());
+        // The shorter arc from the Sun at 350° to Mars runs back through 270°, and the
+        // ascendant is on the far end of that axis
+        let sun_mars = &midpoints[1];
+        assert_eq!(sun_mars.planet2, CelestialBody::Mars);
+        assert_eq!(sun_mars.contacts[0].point, ChartPoint::Ascendant);
+    }
+}
@@ -0,0 +1,47 @@
+//! Plain data types: charts, positions, signs, nakshatras, dashas and the results of every
+//! calculation. Nothing here touches the Swiss Ephemeris, so this module is also available
+//! with the `model-only` feature.
+
+pub use crate::{
+    bhakut_kuta, declination_aspects, gana_kuta, graha_maitri_kuta, hadda_lord,
+    kp_horary_longitude, nadi_kuta, pancha_vargeeya_bala, quick_match, sputa_drishti, tara_kuta,
+    temporal_relationship, varna_kuta, vasya_kuta, vasya_of, yoni_kuta, Activity, Angle,
+    AnglePoint, Angles, AshtakavargaContributor, AshtakavargaReport, Ashtakoota, AshtottariDasha,
+    Aspect, AspectConfig, AspectEvent, AspectInfo, AspectPhase, AspectTarget, AstrologyError,
+    AstronomicalResult, AvasthaInfo, Ayanamsa, AyanamsaInfo, BhavaChalitChart, BhavaChalitHouse,
+    BhavaChalitMethod, BhavaChalitPlanet, BhavaInfo, Bhinnashtakavarga, BirthInfo,
+    BirthInfoBuilder, BirthInfoError, CalculationFlag, CalendarSystem, CareerSummary,
+    CelestialBody, CelestialCoordinates, Chandrabala, CharaKaraka, CharaKarakaInfo, ChartInfo,
+    ChartOptions, ChartOverlay, ChartPoint, ChartStyle, ChartType, Choghadiya, CombustionOrb,
+    CombustionOrbs, CompatibilityInfo, CompoundRelationship, Condition, ConditionalDasha,
+    Conventions, CoordinateSystem, Dasha, DashaChange, DashaInfo, DashaLevel, DashaLord,
+    DashaPeriod, DashaRegistry, DashaSystem, DashaSystemKind, DashaTable, DashaTableEntry,
+    DashamsaDeity, DayPeriods, DeclinationAspect, DeclinationAspectInfo, DeeptadiAvastha, Dignity,
+    DignityInfo, DivisionalChart, DwisaptatiSamaDasha, Effects, EphemerisEntry, EphemerisMode,
+    EphemerisRow, EquatorialPosition, FixedOrbs, FixedStar, FixedStarConjunction,
+    FixedStarPosition, FriendshipMatrix, Gana, Gender, GrahaDrishtiInfo, House, HouseCusp,
+    HouseSystem, Houses, Impact, InauspiciousPeriod, InterpretationFragment, InterpretationKey,
+    InterpretationPack, InterpretationSection, InterpretationTopic, IshtaKashta, JagradadiAvastha,
+    JaiminiReport, JulianDay, KakshaInfo, KalachakraDasha, KarakaScheme, Karana, KpChart, KpCusp,
+    KpLords, KpPlanet, KutaScore, KutaWeights, Locale, Localized, Location, Longitude,
+    LordCondition, LunarMonth, LunarPhase, LunisolarDate, MangalDosha, MatchVerdict, Midpoint,
+    MoonPhase, MoonProfile, Motion, MuddaPeriod, MuhurtaWindow, MundaneChart, MundaneEvent, Muntha,
+    Nadi, Nakshatra, NakshatraDetails, NakshatraInfo, NakshatraNature, NakshatraTransit,
+    NakshatraVarna, NearbyPoint, NiryanaShoolaDasha, NityaYoga, NodeType, Observer, Office,
+    OfficeBearer, On, OrbStrategy, Paksha, Panchanga, PanchangaLimb, ParentsSummary, PeriodQuality,
+    Person, PlanetOverlay, PlanetPosition, PlanetReturn, PlanetStateSet, PlanetaryState,
+    PlanetaryWar, PositionCacheSettings, PositionCacheStats, PrashnaChart, PrashnaMoon,
+    ProgressedChart, ProgressedIngress, ProgressedMoonChange, QuickMatch, RectificationScorer,
+    RectificationWindow, Reference, Relationship, RemedialMeasure, Report, ReportOptions,
+    ReportSettings, ReturnChart, SadeSatiPhase, Saham, SahamInfo, SahamPoint, Sankranti,
+    SaturnAffliction, SaturnTransitPeriod, SavedChart, SensitivePoint, ShoolaDasha, SolarMonth,
+    SpecialLagna, StrengthInfo, StrengthScaledOrbs, Subject, Synastry, TajikaAspect, TajikaYoga,
+    Tara, Tarabala, Tattva, TemplatePack, Tithi, TithiPravesha, Tradition, Trait, TransitAspect,
+    TransitInfo, Upagraha, UpagrahaInfo, Vara, VargaFlags, Varna, VarshaphalReport, Vasya,
+    VimshottariDasha, WarSide, Warning, WarningKind, Yoga, YogaCategory, YogaInfo, YogaRule,
+    Yogini, YoginiDasha, Yoni, YoniGender, ZodiacSign, ASCENDANT_BOUNDARY_ORB, BHAVA_SANDHI_ORB,
+    CHART_FILE_VERSION, DASHA_LIFESPAN_YEARS, DASHA_SANDHI_WINDOW_HOURS, DECLINATION_ORB,
+    DEEP_EXALTATION_ORB, JULIAN_YEAR_DAYS, KP_HORARY_NUMBERS, MIDPOINT_ORB,
+    OUT_OF_BOUNDS_DECLINATION, OVERLAY_CUSP_ORB, PLANETARY_WAR_ORB, PROGRESSION_ORB,
+    SAVANA_YEAR_DAYS, SIDEREAL_YEAR_DAYS, TIMEZONE_MISMATCH_HOURS, TROPICAL_YEAR_DAYS,
+};
@@ -0,0 +1,115 @@
+use super::*;
+
+/// How fast a planet moves against its mean daily motion, whichever way it is going.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Motion {
+    /// Under a tenth of its mean motion, turning retrograde or direct.
+    Stationary,
+    /// Under half.
+    VerySlow,
+    /// Under nine tenths.
+    Slow,
+    /// Within a tenth of the mean.
+    Average,
+    /// Over a tenth faster.
+    Fast,
+}
+
+impl Motion {
+    /// The motion of a planet moving at `ratio` times its mean daily motion. A negative
+    /// ratio is retrograde and judged by its size.
+    pub fn from_speed_ratio(ratio: f64) -> Self {
+        match ratio.abs() {
+            r if r < 0.1 => Motion::Stationary,
+            r if r < 0.5 => Motion::VerySlow,
+            r if r < 0.9 => Motion::Slow,
+            r if r <= 1.1 => Motion::Average,
+            _ => Motion::Fast,
+        }
+    }
+}
+
+impl CelestialBody {
+    /// Mean motion in longitude in degrees a day, as seen from the Earth. The nodes move
+    /// backwards at this rate. None for the asteroids.
+    pub fn mean_daily_motion(&self) -> Option<f64> {
+        match self {
+            CelestialBody::Sun | CelestialBody::Mercury | CelestialBody::Venus => Some(0.9856),
+            CelestialBody::Moon => Some(13.1764),
+            CelestialBody::Mars => Some(0.524),
+            CelestialBody::Jupiter => Some(0.0831),
+            CelestialBody::Saturn => Some(0.0335),
+            CelestialBody::Rahu | CelestialBody::Ketu => Some(0.0529),
+            CelestialBody::Uranus => Some(0.0117),
+            CelestialBody::Neptune => Some(0.006),
+            CelestialBody::Pluto => Some(0.004),
+            _ => None,
+        }
+    }
+}
+
+impl PlanetPosition {
+    /// Speed as a multiple of the planet's mean daily motion, negative when retrograde. The
+    /// nodes, which always move backwards on average, count that as forward.
+    pub fn speed_ratio(&self) -> Option<f64> {
+        let ratio = self.speed / self.planet.mean_daily_motion()?;
+        Some(match self.planet {
+            CelestialBody::Rahu | CelestialBody::Ketu => -ratio,
+            _ => ratio,
+        })
+    }
+
+    /// How fast the planet is moving, for reading its strength or timing a transit near a
+    /// station. None for the asteroids.
+    pub fn motion(&self) -> Option<Motion> {
+        self.speed_ratio().map(Motion::from_speed_ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moving(planet: CelestialBody, speed: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet,
+            longitude: Longitude::new(0.0),
+            latitude: 0.0,
+            speed,
+            sign: ZodiacSign::Aries,
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(0.0),
+            retrograde: speed < 0.0,
+        }
+    }
+
+    #[test]
+    fn motion_is_judged_against_the_mean() {
+        assert_eq!(
+            moving(CelestialBody::Moon, 15.0).motion(),
+            Some(Motion::Fast)
+        );
+        assert_eq!(
+            moving(CelestialBody::Moon, 13.0).motion(),
+            Some(Motion::Average)
+        );
+        assert_eq!(
+            moving(CelestialBody::Mercury, -0.05).motion(),
+            Some(Motion::Stationary)
+        );
+        assert_eq!(
+            moving(CelestialBody::Mars, -0.4).motion(),
+            Some(Motion::Slow)
+        );
+        assert_eq!(
+            moving(CelestialBody::Saturn, 0.01).motion(),
+            Some(Motion::VerySlow)
+        );
+        assert!(moving(CelestialBody::Ceres, 0.2).motion().is_none());
+
+        let rahu = moving(CelestialBody::Rahu, -0.053);
+        assert!(rahu.speed_ratio().unwrap() > 0.0);
+        assert_eq!(rahu.motion(), Some(Motion::Average));
+    }
+}
@@ -0,0 +1,383 @@
+use super::*;
+
+/// Undertakings a muhurta can be chosen for. Each has its own favoured tithis, nakshatras,
+/// weekdays and rising signs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Activity {
+    Marriage,
+    Travel,
+    BusinessStart,
+}
+
+impl Activity {
+    pub fn favourable_nakshatras(&self) -> &'static [Nakshatra] {
+        use Nakshatra::*;
+        match self {
+            Activity::Marriage => &[
+                Rohini,
+                Mrigashira,
+                Magha,
+                UttaraPhalguni,
+                Hasta,
+                Swati,
+                Anuradha,
+                Moola,
+                UttaraAshadha,
+                UttaraBhadrapada,
+                Revati,
+            ],
+            Activity::Travel => &[
+                Ashwini, Mrigashira, Punarvasu, Pushya, Hasta, Anuradha, Shravana, Dhanishta,
+                Revati,
+            ],
+            Activity::BusinessStart => &[
+                Ashwini,
+                Rohini,
+                Pushya,
+                UttaraPhalguni,
+                Hasta,
+                Chitra,
+                Anuradha,
+                UttaraAshadha,
+                Shravana,
+                UttaraBhadrapada,
+                Revati,
+            ],
+        }
+    }
+
+    pub fn favourable_varas(&self) -> &'static [Vara] {
+        match self {
+            Activity::Marriage | Activity::Travel => &[
+                Vara::Somavara,
+                Vara::Budhavara,
+                Vara::Guruvara,
+                Vara::Shukravara,
+            ],
+            Activity::BusinessStart => &[Vara::Budhavara, Vara::Guruvara, Vara::Shukravara],
+        }
+    }
+
+    pub fn unfavourable_varas(&self) -> &'static [Vara] {
+        match self {
+            Activity::Marriage | Activity::BusinessStart => &[Vara::Mangalavara, Vara::Shanivara],
+            Activity::Travel => &[Vara::Mangalavara],
+        }
+    }
+
+    /// Marriage wants a dual or benefic sign rising, travel a movable one and a new
+    /// business a fixed one.
+    pub fn favourable_lagnas(&self) -> &'static [ZodiacSign] {
+        use ZodiacSign::*;
+        match self {
+            Activity::Marriage => &[Gemini, Virgo, Libra],
+            Activity::Travel => &[Aries, Cancer, Libra, Capricorn],
+            Activity::BusinessStart => &[Taurus, Leo, Scorpio, Aquarius],
+        }
+    }
+}
+
+/// A stretch of time over which tithi, nakshatra, vara, karana and lagna all stay the same,
+/// and which lies wholly inside or outside the vishaghati and amritaghati, with the score
+/// they earn for an activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MuhurtaWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub score: i32,
+    /// Why the window scored as it did, one entry per factor that counted.
+    pub reasons: Vec<String>,
+}
+
+/// Scores the factors in force for `activity` at `date_time`. Rikta tithis, the new Moon,
+/// Vishti karana, the vishaghati and the 3rd, 5th and 7th taras are faults that cost more
+/// than a favour earns.
+#[cfg(not(feature = "model-only"))]
+fn score_muhurta(
+    activity: Activity,
+    panchanga: &Panchanga,
+    date_time: DateTime<Utc>,
+    lagna: ZodiacSign,
+    birth_nakshatra: Option<Nakshatra>,
+) -> (i32, Vec<String>) {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    let tithi = panchanga.tithi.value;
+    match tithi {
+        Tithi::Chaturthi | Tithi::Navami | Tithi::Chaturdashi => {
+            score -= 2;
+            reasons.push(format!("{:?} is a rikta tithi", tithi));
+        }
+        Tithi::Amavasya => {
+            score -= 2;
+            reasons.push("Amavasya is avoided for new undertakings".to_string());
+        }
+        Tithi::Dwitiya
+        | Tithi::Tritiya
+        | Tithi::Panchami
+        | Tithi::Saptami
+        | Tithi::Dashami
+        | Tithi::Ekadashi
+        | Tithi::Trayodashi => {
+            score += 1;
+            reasons.push(format!("{:?} is an auspicious tithi", tithi));
+        }
+        _ => {}
+    }
+    if activity == Activity::Marriage && panchanga.paksha == Paksha::Shukla {
+        score += 1;
+        reasons.push("Shukla paksha, the waxing Moon".to_string());
+    }
+
+    let nakshatra = panchanga.nakshatra.value;
+    if activity.favourable_nakshatras().contains(&nakshatra) {
+        score += 2;
+        reasons.push(format!("{:?} nakshatra favours {:?}", nakshatra, activity));
+    } else if matches!(
+        nakshatra,
+        Nakshatra::Bharani | Nakshatra::Ardra | Nakshatra::Ashlesha | Nakshatra::Jyeshtha
+    ) {
+        score -= 2;
+        reasons.push(format!("{:?} is a harsh nakshatra", nakshatra));
+    }
+
+    let vara = panchanga.vara.value;
+    if activity.favourable_varas().contains(&vara) {
+        score += 1;
+        reasons.push(format!("{:?} favours {:?}", vara, activity));
+    } else if activity.unfavourable_varas().contains(&vara) {
+        score -= 1;
+        reasons.push(format!("{:?} is unfavourable for {:?}", vara, activity));
+    }
+
+    if panchanga.karana.value == Karana::Vishti {
+        score -= 2;
+        reasons.push("Vishti (Bhadra) karana".to_string());
+    }
+
+    if panchanga.vishaghati.contains(date_time) {
+        score -= 2;
+        reasons.push(format!("Vishaghati of {:?}", nakshatra));
+    } else if panchanga.amritaghati.contains(date_time) {
+        score += 1;
+        reasons.push(format!("Amritaghati of {:?}", nakshatra));
+    }
+
+    if activity.favourable_lagnas().contains(&lagna) {
+        score += 1;
+        reasons.push(format!("{} rising favours {:?}", lagna, activity));
+    }
+
+    if let Some(birth_nakshatra) = birth_nakshatra {
+        let tara = crate::core::tara_index(birth_nakshatra as usize, nakshatra as usize) + 1;
+        match tara {
+            3 | 5 | 7 => {
+                score -= 2;
+                reasons.push(format!("Tara {} from the birth star is inauspicious", tara));
+            }
+            2 | 4 | 6 | 8 | 9 => {
+                score += 1;
+                reasons.push(format!("Tara {} from the birth star is auspicious", tara));
+            }
+            _ => {}
+        }
+    }
+
+    (score, reasons)
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Windows between `start` and `end` at `location` suited to `activity`, best first.
+    /// Only windows with a positive score are returned. With `birth_nakshatra`, the tara
+    /// of each window counted from that star is scored as well.
+    pub fn find_muhurtas(
+        &self,
+        activity: Activity,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        location: &Location,
+        birth_nakshatra: Option<Nakshatra>,
+    ) -> Result<Vec<MuhurtaWindow>, AstrologyError> {
+        let mut windows = self.muhurta_windows(activity, start, end, location, birth_nakshatra)?;
+        windows.retain(|window| window.score > 0);
+        windows.sort_by(|a, b| b.score.cmp(&a.score).then(a.start.cmp(&b.start)));
+        Ok(windows)
+    }
+
+    /// Every window from `start` to `end` in order, whatever its score.
+    fn muhurta_windows(
+        &self,
+        activity: Activity,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        location: &Location,
+        birth_nakshatra: Option<Nakshatra>,
+    ) -> Result<Vec<MuhurtaWindow>, AstrologyError> {
+        let mut windows = Vec::new();
+        let mut current = start;
+
+        while current < end {
+            let panchanga = self.calculate_panchanga(current, location)?;
+            let (lagna, lagna_end) = self.lagna_until(current, end, location)?;
+            let window_end = [
+                panchanga.tithi.end,
+                panchanga.nakshatra.end,
+                panchanga.vara.end,
+                panchanga.karana.end,
+                lagna_end,
+                end,
+            ]
+            .into_iter()
+            .chain(panchanga.vishaghati.next_boundary(current))
+            .chain(panchanga.amritaghati.next_boundary(current))
+            .min()
+            .unwrap_or(end)
+            .max(current + ChronoDuration::minutes(1));
+
+            let (score, reasons) =
+                score_muhurta(activity, &panchanga, current, lagna, birth_nakshatra);
+            windows.push(MuhurtaWindow {
+                start: current,
+                end: window_end,
+                score,
+                reasons,
+            });
+            current = window_end;
+        }
+        Ok(windows)
+    }
+
+    /// The rising sign at `date_time` and when it gives way to the next, searched no
+    /// further than `limit`.
+    fn lagna_until(
+        &self,
+        date_time: DateTime<Utc>,
+        limit: DateTime<Utc>,
+        location: &Location,
+    ) -> Result<(ZodiacSign, DateTime<Utc>), AstrologyError> {
+        let lagna = |julian_day| {
+            self.calculate_ascendant(
+                CoordinateSystem::Sidereal,
+                julian_day,
+                location.latitude,
+                location.longitude,
+                HouseSystem::default(),
+            )
+            .map(|ascendant| ascendant.sign)
+        };
+
+        let step = 10.0 / 1440.0;
+        let limit_jd = date_to_julian_day(limit);
+        let mut low = date_to_julian_day(date_time);
+        let sign = lagna(low)?;
+        let mut high = low + step;
+        while lagna(high)? == sign {
+            if high >= limit_jd {
+                return Ok((sign, limit));
+            }
+            low = high;
+            high += step;
+        }
+
+        while high - low > 1.0 / 1440.0 {
+            let middle = (low + high) / 2.0;
+            if lagna(middle)? == sign {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Ok((sign, julian_day_to_date(high)))
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    fn limb<T>(value: T, start: DateTime<Utc>, hours: i64) -> PanchangaLimb<T> {
+        PanchangaLimb {
+            value,
+            start,
+            end: start + ChronoDuration::hours(hours),
+        }
+    }
+
+    #[test]
+    fn faults_outweigh_favours() {
+        // A rikta tithi in Vishti karana, Punarvasu the 7th tara from Ashwini
+        let start = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+        let ghatis = limb(Nakshatra::Punarvasu, start + ChronoDuration::hours(12), 2);
+        let panchanga = Panchanga {
+            tithi: limb(Tithi::Chaturthi, start, 24),
+            paksha: Paksha::Krishna,
+            vara: limb(Vara::Ravivara, start, 24),
+            nakshatra: limb(Nakshatra::Punarvasu, start, 24),
+            yoga: limb(NityaYoga::Vishkambha, start, 24),
+            karana: limb(Karana::Vishti, start, 12),
+            vishaghati: ghatis.clone(),
+            amritaghati: limb(Nakshatra::Punarvasu, start + ChronoDuration::hours(20), 2),
+        };
+        let at = start + ChronoDuration::hours(1);
+
+        let (score, reasons) = score_muhurta(
+            Activity::Marriage,
+            &panchanga,
+            at,
+            ZodiacSign::Gemini,
+            Some(Nakshatra::Ashwini),
+        );
+        assert!(score < 0, "{} {:?}", score, reasons);
+        assert_eq!(score, -2 - 2 + 1 - 2);
+        assert!(reasons.iter().any(|reason| reason.starts_with("Tara 7")));
+
+        // The same moment without the birth star, and then inside the vishaghati
+        let (without_tara, _) =
+            score_muhurta(Activity::Marriage, &panchanga, at, ZodiacSign::Gemini, None);
+        assert_eq!(without_tara, score + 2);
+        let (in_vishaghati, _) = score_muhurta(
+            Activity::Marriage,
+            &panchanga,
+            ghatis.start,
+            ZodiacSign::Gemini,
+            None,
+        );
+        assert_eq!(in_vishaghati, without_tara - 2);
+    }
+
+    #[test]
+    fn windows_cover_the_range_and_come_best_first() {
+        let ephemeris = SwissEph::new().unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+        let end = start + ChronoDuration::days(2);
+        let location = Location::kozhikode();
+
+        let windows = ephemeris
+            .muhurta_windows(Activity::Travel, start, end, &location, None)
+            .unwrap();
+        assert_eq!(windows.first().unwrap().start, start);
+        assert_eq!(windows.last().unwrap().end, end);
+        for pair in windows.windows(2) {
+            assert!(pair[0].start < pair[0].end);
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(windows
+            .iter()
+            .any(|window| window.reasons.iter().any(|reason| reason.contains("ghati"))));
+
+        let best = ephemeris
+            .find_muhurtas(Activity::Travel, start, end, &location, None)
+            .unwrap();
+        assert!(!best.is_empty());
+        assert!(best.iter().all(|window| window.score > 0));
+        assert!(best.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        let mut by_start = best.clone();
+        by_start.sort_by_key(|window| window.start);
+        assert!(by_start.windows(2).all(|pair| pair[0].end <= pair[1].start));
+    }
+}
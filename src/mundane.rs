@@ -0,0 +1,182 @@
+use super::*;
+
+/// The moment a mundane chart is cast for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum MundaneEvent {
+    /// Whatever moment was asked for, such as the present.
+    Moment,
+    /// The Sun entering a sign, in the zodiac given.
+    Ingress {
+        sign: ZodiacSign,
+        zodiac: CoordinateSystem,
+    },
+    NewMoon,
+    FullMoon,
+}
+
+/// A chart cast for a place rather than a person: a country's capital at the Sun's entry
+/// into Aries, a city at a new Moon, or simply the sky overhead now.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MundaneChart {
+    pub event: MundaneEvent,
+    pub date_time: DateTime<Utc>,
+    pub location: Location,
+    pub chart: ChartInfo,
+}
+
+/// Mean daily motion of the Sun in longitude.
+#[cfg(not(feature = "model-only"))]
+const SUN_MEAN_MOTION: f64 = 0.9856;
+
+#[cfg(not(feature = "model-only"))]
+impl ChartInfo {
+    /// The sky over `location` at this moment, on the default engine.
+    pub fn now(location: &Location) -> Result<ChartInfo, AstrologyError> {
+        let ephemeris = SwissEph::new()?;
+        Ok(ephemeris.mundane_chart(location, Utc::now())?.chart)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The chart of `location` at `date_time`, on the engine's house system.
+    pub fn mundane_chart(
+        &self,
+        location: &Location,
+        date_time: DateTime<Utc>,
+    ) -> Result<MundaneChart, AstrologyError> {
+        self.cast_mundane(MundaneEvent::Moment, location, date_time)
+    }
+
+    /// The chart of `location` for the Sun's next entry into `sign` after `after`. With
+    /// [`CoordinateSystem::Tropical`] and Aries this is the vernal equinox; with
+    /// [`CoordinateSystem::Sidereal`] it is the sankranti on the engine's ayanamsa, Mesha
+    /// Sankranti for Aries.
+    pub fn ingress_chart(
+        &self,
+        sign: ZodiacSign,
+        zodiac: CoordinateSystem,
+        location: &Location,
+        after: DateTime<Utc>,
+    ) -> Result<MundaneChart, AstrologyError> {
+        let julian_day = next_solar_longitude(
+            date_to_julian_day(after),
+            sign as usize as f64 * 30.0,
+            |julian_day| self.sun_longitude(zodiac, julian_day),
+        )?;
+        let event = MundaneEvent::Ingress { sign, zodiac };
+        self.cast_mundane(event, location, julian_day_to_date(julian_day))
+    }
+
+    /// The chart of `location` for the first new Moon after `after`.
+    pub fn new_moon_chart(
+        &self,
+        location: &Location,
+        after: DateTime<Utc>,
+    ) -> Result<MundaneChart, AstrologyError> {
+        let date_time = self.next_new_moon(after)?;
+        self.cast_mundane(MundaneEvent::NewMoon, location, date_time)
+    }
+
+    /// The chart of `location` for the first full Moon after `after`.
+    pub fn full_moon_chart(
+        &self,
+        location: &Location,
+        after: DateTime<Utc>,
+    ) -> Result<MundaneChart, AstrologyError> {
+        let date_time = self.next_full_moon(after)?;
+        self.cast_mundane(MundaneEvent::FullMoon, location, date_time)
+    }
+
+    fn cast_mundane(
+        &self,
+        event: MundaneEvent,
+        location: &Location,
+        date_time: DateTime<Utc>,
+    ) -> Result<MundaneChart, AstrologyError> {
+        let moment = BirthInfo {
+            date_time,
+            location: location.clone(),
+            person: None,
+        };
+        Ok(MundaneChart {
+            event,
+            date_time,
+            location: location.clone(),
+            chart: self.calculate_chart(&moment, self.conventions.house_system)?,
+        })
+    }
+
+    pub(crate) fn sun_longitude(
+        &self,
+        zodiac: CoordinateSystem,
+        julian_day: JulianDay,
+    ) -> Result<f64, AstrologyError> {
+        match self.calculate(zodiac, julian_day, CelestialBody::Sun, &[], None)? {
+            AstronomicalResult::CelestialBody(info) => Ok(info.longitude),
+            _ => Err(AstrologyError::Ephemeris {
+                code: -1,
+                message: "Failed to calculate Sun position".to_string(),
+            }),
+        }
+    }
+}
+
+/// The first moment after `julian_day` at which the Sun, placed by `sun`, reaches
+/// `target` degrees of longitude.
+#[cfg(not(feature = "model-only"))]
+pub(crate) fn next_solar_longitude(
+    julian_day: JulianDay,
+    target: f64,
+    sun: impl Fn(JulianDay) -> Result<f64, AstrologyError>,
+) -> Result<JulianDay, AstrologyError> {
+    let mut ahead = (target - sun(julian_day)?).rem_euclid(360.0);
+    if ahead < 1e-6 {
+        ahead = 360.0;
+    }
+    let mut found = julian_day + ahead / SUN_MEAN_MOTION;
+    for _ in 0..20 {
+        let difference = signed_arc(target, sun(found)?);
+        if difference.abs() < 1e-6 {
+            break;
+        }
+        found += difference / SUN_MEAN_MOTION;
+    }
+    Ok(found)
+}
+
+#[cfg(all(test, not(feature = "model-only")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ingresses_fall_at_the_equinox_and_the_sankranti() {
+        let ephemeris = SwissEph::new().unwrap();
+        let delhi = Location::new(28.6139, 77.2090, 5.5);
+        let after = DateTime::from_timestamp(1_704_067_200, 0).unwrap(); // 2024-01-01
+
+        // The March equinox of 2024 fell at 03:06 UT on the 20th
+        let equinox = ephemeris
+            .ingress_chart(ZodiacSign::Aries, CoordinateSystem::Tropical, &delhi, after)
+            .unwrap();
+        let expected = DateTime::from_timestamp(1_710_903_960, 0).unwrap();
+        assert!((equinox.date_time - expected).num_minutes().abs() <= 2);
+
+        // Mesha Sankranti on the Lahiri ayanamsa came on 13 April
+        let sankranti = ephemeris
+            .ingress_chart(ZodiacSign::Aries, CoordinateSystem::Sidereal, &delhi, after)
+            .unwrap();
+        assert_eq!(
+            sankranti.date_time.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 4, 13).unwrap()
+        );
+        let sun = sankranti.chart.planet(CelestialBody::Sun).unwrap();
+        assert!(sun.longitude.degrees() < 0.001 || sun.longitude.degrees() > 359.999);
+
+        let new_moon = ephemeris.new_moon_chart(&delhi, after).unwrap();
+        assert_eq!(new_moon.event, MundaneEvent::NewMoon);
+        assert!(new_moon.date_time > after);
+    }
+}
@@ -0,0 +1,426 @@
+use super::*;
+
+// ---------------------------
+// ## Nakshatra Attributes
+// ---------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Yoni {
+    Horse,
+    Elephant,
+    Sheep,
+    Serpent,
+    Dog,
+    Cat,
+    Rat,
+    Cow,
+    Buffalo,
+    Tiger,
+    Deer,
+    Monkey,
+    Mongoose,
+    Lion,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Gana {
+    Deva,
+    Manushya,
+    Rakshasa,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Nadi {
+    Aadi,
+    Madhya,
+    Antya,
+}
+
+impl Nakshatra {
+    pub fn yoni(&self) -> Yoni {
+        match self {
+            Nakshatra::Ashwini | Nakshatra::Shatabhisha => Yoni::Horse,
+            Nakshatra::Bharani | Nakshatra::Revati => Yoni::Elephant,
+            Nakshatra::Krittika | Nakshatra::Pushya => Yoni::Sheep,
+            Nakshatra::Rohini | Nakshatra::Mrigashira => Yoni::Serpent,
+            Nakshatra::Ardra | Nakshatra::Moola => Yoni::Dog,
+            Nakshatra::Punarvasu | Nakshatra::Ashlesha => Yoni::Cat,
+            Nakshatra::Magha | Nakshatra::PurvaPhalguni => Yoni::Rat,
+            Nakshatra::UttaraPhalguni | Nakshatra::UttaraBhadrapada => Yoni::Cow,
+            Nakshatra::Hasta | Nakshatra::Swati => Yoni::Buffalo,
+            Nakshatra::Chitra | Nakshatra::Vishakha => Yoni::Tiger,
+            Nakshatra::Anuradha | Nakshatra::Jyeshtha => Yoni::Deer,
+            Nakshatra::PurvaAshadha | Nakshatra::Shravana => Yoni::Monkey,
+            Nakshatra::UttaraAshadha => Yoni::Mongoose,
+            Nakshatra::Dhanishta | Nakshatra::PurvaBhadrapada => Yoni::Lion,
+        }
+    }
+
+    pub fn gana(&self) -> Gana {
+        match self {
+            Nakshatra::Ashwini
+            | Nakshatra::Mrigashira
+            | Nakshatra::Punarvasu
+            | Nakshatra::Pushya
+            | Nakshatra::Hasta
+            | Nakshatra::Swati
+            | Nakshatra::Anuradha
+            | Nakshatra::Shravana
+            | Nakshatra::Revati => Gana::Deva,
+            Nakshatra::Bharani
+            | Nakshatra::Rohini
+            | Nakshatra::Ardra
+            | Nakshatra::PurvaPhalguni
+            | Nakshatra::UttaraPhalguni
+            | Nakshatra::PurvaAshadha
+            | Nakshatra::UttaraAshadha
+            | Nakshatra::PurvaBhadrapada
+            | Nakshatra::UttaraBhadrapada => Gana::Manushya,
+            Nakshatra::Krittika
+            | Nakshatra::Ashlesha
+            | Nakshatra::Magha
+            | Nakshatra::Chitra
+            | Nakshatra::Vishakha
+            | Nakshatra::Jyeshtha
+            | Nakshatra::Moola
+            | Nakshatra::Dhanishta
+            | Nakshatra::Shatabhisha => Gana::Rakshasa,
+        }
+    }
+
+    /// The nadis run Aadi, Madhya, Antya and back again, six stars to a round.
+    pub fn nadi(&self) -> Nadi {
+        match crate::core::nakshatra_nadi(*self as usize) {
+            0 => Nadi::Aadi,
+            1 => Nadi::Madhya,
+            _ => Nadi::Antya,
+        }
+    }
+}
+
+/// Sex of a nakshatra's yoni animal. Each animal but the mongoose has one nakshatra of
+/// each.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum YoniGender {
+    Male,
+    Female,
+}
+
+/// The element of a nakshatra: the first five are earth, the next six water, six fire,
+/// five air and the last five ether.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Tattva {
+    Prithvi,
+    Jala,
+    Agni,
+    Vayu,
+    Akasha,
+}
+
+/// A nakshatra's caste, which adds farmers, butchers and outcastes to the four varnas.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum NakshatraVarna {
+    Brahmin,
+    Kshatriya,
+    Vaishya,
+    Shudra,
+    Farmer,
+    Butcher,
+    Outcaste,
+}
+
+/// The seven natures by which muhurta texts sort the nakshatras, each suiting its own
+/// kind of work.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum NakshatraNature {
+    /// Fixed: foundations, buildings, planting and anything meant to last.
+    Dhruva,
+    /// Movable: travel, vehicles and changes of place.
+    Chara,
+    /// Fierce: confrontation, demolition and work with fire or weapons.
+    Ugra,
+    /// Mixed: routine duties and work with metal and fire.
+    Mishra,
+    /// Swift: trade, medicine, learning and short journeys.
+    Kshipra,
+    /// Soft: arts, friendship, romance, clothes and ornaments.
+    Mridu,
+    /// Sharp: severance, exorcism and dealing with enemies.
+    Tikshna,
+}
+
+/// Everything known about a nakshatra, in one value for display.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NakshatraDetails {
+    pub nakshatra: Nakshatra,
+    pub lord: CelestialBody,
+    pub deity: String,
+    pub symbol: String,
+    pub gana: Gana,
+    pub yoni: Yoni,
+    pub yoni_gender: YoniGender,
+    pub nadi: Nadi,
+    pub varna: NakshatraVarna,
+    pub tattva: Tattva,
+    pub nature: NakshatraNature,
+    /// Undertakings among [`Activity`] the nakshatra favours.
+    pub favourable_activities: Vec<Activity>,
+    /// First syllable of a name for a birth in each pada, the first pada first.
+    pub syllables: Vec<String>,
+}
+
+/// Ruling deity of each nakshatra, Ashwini first.
+const DEITIES: [&str; 27] = [
+    "Ashvini Kumaras",
+    "Yama",
+    "Agni",
+    "Prajapati",
+    "Soma",
+    "Rudra",
+    "Aditi",
+    "Brihaspati",
+    "Sarpas",
+    "Pitris",
+    "Bhaga",
+    "Aryaman",
+    "Savitr",
+    "Tvashtr",
+    "Vayu",
+    "Indragni",
+    "Mitra",
+    "Indra",
+    "Nirriti",
+    "Apas",
+    "Vishvedevas",
+    "Vishnu",
+    "Vasus",
+    "Varuna",
+    "Aja Ekapada",
+    "Ahir Budhnya",
+    "Pushan",
+];
+
+const SYMBOLS: [&str; 27] = [
+    "Horse's head",
+    "Yoni",
+    "Razor",
+    "Ox cart",
+    "Deer's head",
+    "Teardrop",
+    "Quiver of arrows",
+    "Cow's udder",
+    "Coiled serpent",
+    "Royal throne",
+    "Front legs of a bed",
+    "Back legs of a bed",
+    "Hand",
+    "Pearl",
+    "Shoot blown by the wind",
+    "Triumphal arch",
+    "Lotus",
+    "Earring",
+    "Bundle of roots",
+    "Elephant tusk",
+    "Planks of a bed",
+    "Ear",
+    "Drum",
+    "Empty circle",
+    "Front legs of a funeral cot",
+    "Back legs of a funeral cot",
+    "Fish",
+];
+
+/// Namakshara of each pada, Ashwini first. Some traditions name Shravana's Ju, Je, Jo and
+/// Gha, the syllables others give to Abhijit.
+const SYLLABLES: [[&str; 4]; 27] = [
+    ["Chu", "Che", "Cho", "La"],
+    ["Li", "Lu", "Le", "Lo"],
+    ["A", "I", "U", "E"],
+    ["O", "Va", "Vi", "Vu"],
+    ["Ve", "Vo", "Ka", "Ki"],
+    ["Ku", "Gha", "Na", "Chha"],
+    ["Ke", "Ko", "Ha", "Hi"],
+    ["Hu", "He", "Ho", "Da"],
+    ["Di", "Du", "De", "Do"],
+    ["Ma", "Mi", "Mu", "Me"],
+    ["Mo", "Ta", "Ti", "Tu"],
+    ["Te", "To", "Pa", "Pi"],
+    ["Pu", "Sha", "Na", "Tha"],
+    ["Pe", "Po", "Ra", "Ri"],
+    ["Ru", "Re", "Ro", "Ta"],
+    ["Ti", "Tu", "Te", "To"],
+    ["Na", "Ni", "Nu", "Ne"],
+    ["No", "Ya", "Yi", "Yu"],
+    ["Ye", "Yo", "Bha", "Bhi"],
+    ["Bhu", "Dha", "Pha", "Dha"],
+    ["Bhe", "Bho", "Ja", "Ji"],
+    ["Khi", "Khu", "Khe", "Kho"],
+    ["Ga", "Gi", "Gu", "Ge"],
+    ["Go", "Sa", "Si", "Su"],
+    ["Se", "So", "Da", "Di"],
+    ["Du", "Tha", "Jha", "Na"],
+    ["De", "Do", "Cha", "Chi"],
+];
+
+impl Nakshatra {
+    /// The nakshatras from Ashwini to Revati.
+    pub fn all() -> impl Iterator<Item = Nakshatra> {
+        (0..27).map(|index| Nakshatra::from_longitude((index as f64 + 0.5) * 360.0 / 27.0))
+    }
+
+    /// Planet ruling the nakshatra, which also rules its Vimshottari dasha.
+    pub fn lord(&self) -> CelestialBody {
+        NakshatraInfo::get_nakshatra_lord(*self)
+    }
+
+    pub fn deity(&self) -> &'static str {
+        DEITIES[*self as usize]
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        SYMBOLS[*self as usize]
+    }
+
+    /// The syllable a name starts with for a birth in `pada`, from 1 to 4.
+    pub fn syllable(&self, pada: u8) -> Option<&'static str> {
+        let index = usize::from(pada).checked_sub(1)?;
+        SYLLABLES[*self as usize].get(index).copied()
+    }
+
+    pub fn yoni_gender(&self) -> YoniGender {
+        match self {
+            Nakshatra::Ashwini
+            | Nakshatra::Bharani
+            | Nakshatra::Rohini
+            | Nakshatra::Pushya
+            | Nakshatra::Ashlesha
+            | Nakshatra::Magha
+            | Nakshatra::UttaraPhalguni
+            | Nakshatra::Swati
+            | Nakshatra::Vishakha
+            | Nakshatra::Jyeshtha
+            | Nakshatra::Moola
+            | Nakshatra::PurvaAshadha
+            | Nakshatra::UttaraAshadha
+            | Nakshatra::PurvaBhadrapada => YoniGender::Male,
+            _ => YoniGender::Female,
+        }
+    }
+
+    pub fn tattva(&self) -> Tattva {
+        match *self as usize {
+            0..=4 => Tattva::Prithvi,
+            5..=10 => Tattva::Jala,
+            11..=16 => Tattva::Agni,
+            17..=21 => Tattva::Vayu,
+            _ => Tattva::Akasha,
+        }
+    }
+
+    pub fn varna(&self) -> NakshatraVarna {
+        use Nakshatra::*;
+        match self {
+            Krittika | PurvaPhalguni | PurvaAshadha | PurvaBhadrapada => NakshatraVarna::Brahmin,
+            Pushya | UttaraPhalguni | UttaraAshadha | UttaraBhadrapada => NakshatraVarna::Kshatriya,
+            Ashwini | Punarvasu | Hasta => NakshatraVarna::Vaishya,
+            Rohini | Magha | Anuradha | Revati => NakshatraVarna::Shudra,
+            Mrigashira | Chitra | Jyeshtha | Dhanishta => NakshatraVarna::Farmer,
+            Ardra | Swati | Moola | Shatabhisha => NakshatraVarna::Butcher,
+            Bharani | Ashlesha | Vishakha | Shravana => NakshatraVarna::Outcaste,
+        }
+    }
+
+    pub fn nature(&self) -> NakshatraNature {
+        use Nakshatra::*;
+        match self {
+            Rohini | UttaraPhalguni | UttaraAshadha | UttaraBhadrapada => NakshatraNature::Dhruva,
+            Punarvasu | Swati | Shravana | Dhanishta | Shatabhisha => NakshatraNature::Chara,
+            Bharani | Magha | PurvaPhalguni | PurvaAshadha | PurvaBhadrapada => {
+                NakshatraNature::Ugra
+            }
+            Krittika | Vishakha => NakshatraNature::Mishra,
+            Ashwini | Pushya | Hasta => NakshatraNature::Kshipra,
+            Mrigashira | Chitra | Anuradha | Revati => NakshatraNature::Mridu,
+            Ardra | Ashlesha | Jyeshtha | Moola => NakshatraNature::Tikshna,
+        }
+    }
+
+    /// Undertakings whose muhurta favours the nakshatra.
+    pub fn favourable_activities(&self) -> Vec<Activity> {
+        [
+            Activity::Marriage,
+            Activity::Travel,
+            Activity::BusinessStart,
+        ]
+        .into_iter()
+        .filter(|activity| activity.favourable_nakshatras().contains(self))
+        .collect()
+    }
+
+    pub fn details(&self) -> NakshatraDetails {
+        NakshatraDetails {
+            nakshatra: *self,
+            lord: self.lord(),
+            deity: self.deity().to_string(),
+            symbol: self.symbol().to_string(),
+            gana: self.gana(),
+            yoni: self.yoni(),
+            yoni_gender: self.yoni_gender(),
+            nadi: self.nadi(),
+            varna: self.varna(),
+            tattva: self.tattva(),
+            nature: self.nature(),
+            favourable_activities: self.favourable_activities(),
+            syllables: SYLLABLES[*self as usize]
+                .iter()
+                .map(|syllable| syllable.to_string())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_yoni_but_the_mongoose_has_a_male_and_a_female() {
+        let mut sexes: HashMap<Yoni, Vec<YoniGender>> = HashMap::new();
+        for nakshatra in Nakshatra::all() {
+            sexes
+                .entry(nakshatra.yoni())
+                .or_default()
+                .push(nakshatra.yoni_gender());
+        }
+        for (yoni, sexes) in sexes {
+            match yoni {
+                Yoni::Mongoose => assert_eq!(sexes, vec![YoniGender::Male]),
+                _ => {
+                    assert_eq!(sexes.len(), 2, "{:?}", yoni);
+                    assert_ne!(sexes[0], sexes[1], "{:?}", yoni);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn details_follow_the_tables() {
+        let rohini = Nakshatra::Rohini.details();
+        assert_eq!(rohini.lord, CelestialBody::Moon);
+        assert_eq!(rohini.deity, "Prajapati");
+        assert_eq!(rohini.nature, NakshatraNature::Dhruva);
+        assert_eq!(rohini.syllables, ["O", "Va", "Vi", "Vu"]);
+        assert!(rohini.favourable_activities.contains(&Activity::Marriage));
+        assert_eq!(Nakshatra::Revati.tattva(), Tattva::Akasha);
+        assert_eq!(Nakshatra::Revati.syllable(5), None);
+        assert_eq!(Nakshatra::all().count(), 27);
+    }
+}
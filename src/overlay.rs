@@ -0,0 +1,181 @@
+use super::*;
+
+/// Degrees within which a transiting planet is taken to be on a natal cusp.
+pub const OVERLAY_CUSP_ORB: f64 = 2.0;
+
+/// One transiting planet read against the natal chart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PlanetOverlay {
+    pub planet: CelestialBody,
+    pub longitude: f64,
+    pub retrograde: bool,
+    /// Natal house the planet is passing through.
+    pub house: House,
+    /// Aspects to the natal planets, the transiting planet as `planet1`.
+    pub aspects: Vec<AspectInfo>,
+    /// Conjunctions to natal cusps, with the cusp as `target`.
+    pub cusp_conjunctions: Vec<AspectInfo>,
+}
+
+/// A transit chart laid over a natal one: where each transiting planet falls in the natal
+/// houses and what it touches there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ChartOverlay {
+    /// In the order of the transit chart's planets.
+    pub planets: Vec<PlanetOverlay>,
+}
+
+impl ChartOverlay {
+    /// The overlay with the default fixed orbs, and [`OVERLAY_CUSP_ORB`] for the cusps.
+    pub fn new(natal: &ChartInfo, transit_chart: &ChartInfo) -> Self {
+        Self::with_orbs(
+            natal,
+            transit_chart,
+            &FixedOrbs::default(),
+            OVERLAY_CUSP_ORB,
+        )
+    }
+
+    /// The overlay with aspects found under `orbs` and cusps within `cusp_orb`. Natal
+    /// planets stand still, so an aspect applies or separates by the transiting planet's
+    /// motion alone.
+    pub fn with_orbs(
+        natal: &ChartInfo,
+        transit_chart: &ChartInfo,
+        orbs: &dyn OrbStrategy,
+        cusp_orb: f64,
+    ) -> Self {
+        let planets = transit_chart
+            .planets
+            .iter()
+            .map(|transiting| {
+                let fixed = |natal: &PlanetPosition| PlanetPosition {
+                    speed: 0.0,
+                    ..natal.clone()
+                };
+                let aspects = natal
+                    .planets
+                    .iter()
+                    .filter_map(|natal| closest_aspect(transiting, &fixed(natal), orbs))
+                    .collect();
+
+                let cusp_conjunctions = natal
+                    .houses
+                    .iter()
+                    .filter_map(|cusp| {
+                        let longitude = Longitude::new(cusp.longitude());
+                        let orb = transiting.longitude.separation(longitude);
+                        let on_cusp = PlanetPosition {
+                            longitude,
+                            speed: 0.0,
+                            ..transiting.clone()
+                        };
+                        (orb <= cusp_orb).then(|| AspectInfo {
+                            aspect: Aspect::Conjunction,
+                            planet1: transiting.planet,
+                            planet2: transiting.planet,
+                            orb,
+                            phase: aspect_phase(0.0, transiting, &on_cusp),
+                            target: AspectTarget::Cusp(cusp.house),
+                        })
+                    })
+                    .collect();
+
+                PlanetOverlay {
+                    planet: transiting.planet,
+                    longitude: transiting.longitude.degrees(),
+                    retrograde: transiting.retrograde,
+                    house: natal.house_of(transiting.longitude.degrees()),
+                    aspects,
+                    cusp_conjunctions,
+                }
+            })
+            .collect();
+        ChartOverlay { planets }
+    }
+
+    /// The overlay of one transiting planet.
+    pub fn planet(&self, planet: CelestialBody) -> Option<&PlanetOverlay> {
+        self.planets.iter().find(|overlay| overlay.planet == planet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(body: CelestialBody, longitude: f64, speed: f64) -> PlanetPosition {
+        PlanetPosition {
+            planet: body,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: speed < 0.0,
+        }
+    }
+
+    fn chart(ascendant: f64, planets: Vec<PlanetPosition>) -> ChartInfo {
+        let cusp = |house: House| {
+            let longitude = (ascendant + (house as usize - 1) as f64 * 30.0) % 360.0;
+            HouseCusp {
+                house,
+                sign: ZodiacSign::from_longitude(longitude),
+                degree: longitude % 30.0,
+            }
+        };
+        ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::Equal,
+            ascendant: cusp(House::First),
+            houses: House::all().map(cusp).collect(),
+            planets,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn transits_fall_in_natal_houses_and_touch_natal_points() {
+        let natal = chart(
+            100.0,
+            vec![
+                planet(CelestialBody::Sun, 40.0, 1.0),
+                planet(CelestialBody::Moon, 200.0, 13.0),
+            ],
+        );
+        let transits = chart(
+            0.0,
+            vec![
+                planet(CelestialBody::Saturn, 339.0, -0.05),
+                planet(CelestialBody::Jupiter, 78.0, 0.2),
+            ],
+        );
+        let overlay = ChartOverlay::new(&natal, &transits);
+        assert_eq!(overlay.planets.len(), 2);
+
+        // Saturn at 339° is still in the natal 8th, retrograding away from the 9th cusp at 340°
+        let saturn = overlay.planet(CelestialBody::Saturn).unwrap();
+        assert_eq!(saturn.house, House::Eighth);
+        assert_eq!(saturn.cusp_conjunctions.len(), 1);
+        let on_cusp = &saturn.cusp_conjunctions[0];
+        assert_eq!(on_cusp.target, AspectTarget::Cusp(House::Ninth));
+        assert_eq!(on_cusp.phase, Some(AspectPhase::Separating));
+
+        // Jupiter in the natal 12th closes on a trine to the natal Moon
+        let jupiter = overlay.planet(CelestialBody::Jupiter).unwrap();
+        assert_eq!(jupiter.house, House::Twelfth);
+        let trine = jupiter
+            .aspects
+            .iter()
+            .find(|aspect| aspect.planet2 == CelestialBody::Moon)
+            .unwrap();
+        assert_eq!(trine.aspect, Aspect::Trine);
+        assert_eq!(trine.target, AspectTarget::Planet(CelestialBody::Moon));
+        assert_eq!(trine.phase, Some(AspectPhase::Applying));
+        assert!(jupiter.cusp_conjunctions.is_empty());
+    }
+}
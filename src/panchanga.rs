@@ -0,0 +1,704 @@
+use super::*;
+
+#[cfg(not(feature = "model-only"))]
+const NAKSHATRA_SPAN: f64 = 360.0 / 27.0;
+#[cfg(not(feature = "model-only"))]
+const TITHI_SPAN: f64 = 12.0;
+#[cfg(not(feature = "model-only"))]
+const KARANA_SPAN: f64 = 6.0;
+/// Mean length of the month from new Moon to new Moon, in days.
+#[cfg(not(feature = "model-only"))]
+const SYNODIC_MONTH: f64 = 29.530589;
+
+// ---------------------------
+// ## Panchanga Limbs
+// ---------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Paksha {
+    Shukla,
+    Krishna,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Tithi {
+    Pratipada,
+    Dwitiya,
+    Tritiya,
+    Chaturthi,
+    Panchami,
+    Shashthi,
+    Saptami,
+    Ashtami,
+    Navami,
+    Dashami,
+    Ekadashi,
+    Dwadashi,
+    Trayodashi,
+    Chaturdashi,
+    Purnima,
+    Amavasya,
+}
+
+impl Tithi {
+    /// Maps a tithi number (0-29, counted from the new Moon) to its name and paksha.
+    pub fn from_index(index: usize) -> (Tithi, Paksha) {
+        const TITHIS: [Tithi; 14] = [
+            Tithi::Pratipada,
+            Tithi::Dwitiya,
+            Tithi::Tritiya,
+            Tithi::Chaturthi,
+            Tithi::Panchami,
+            Tithi::Shashthi,
+            Tithi::Saptami,
+            Tithi::Ashtami,
+            Tithi::Navami,
+            Tithi::Dashami,
+            Tithi::Ekadashi,
+            Tithi::Dwadashi,
+            Tithi::Trayodashi,
+            Tithi::Chaturdashi,
+        ];
+        match index % 30 {
+            14 => (Tithi::Purnima, Paksha::Shukla),
+            29 => (Tithi::Amavasya, Paksha::Krishna),
+            i if i < 14 => (TITHIS[i], Paksha::Shukla),
+            i => (TITHIS[i - 15], Paksha::Krishna),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Vara {
+    Ravivara,
+    Somavara,
+    Mangalavara,
+    Budhavara,
+    Guruvara,
+    Shukravara,
+    Shanivara,
+}
+
+impl Vara {
+    pub fn from_weekday(weekday: chrono::Weekday) -> Vara {
+        match weekday {
+            chrono::Weekday::Sun => Vara::Ravivara,
+            chrono::Weekday::Mon => Vara::Somavara,
+            chrono::Weekday::Tue => Vara::Mangalavara,
+            chrono::Weekday::Wed => Vara::Budhavara,
+            chrono::Weekday::Thu => Vara::Guruvara,
+            chrono::Weekday::Fri => Vara::Shukravara,
+            chrono::Weekday::Sat => Vara::Shanivara,
+        }
+    }
+
+    pub fn lord(&self) -> CelestialBody {
+        match self {
+            Vara::Ravivara => CelestialBody::Sun,
+            Vara::Somavara => CelestialBody::Moon,
+            Vara::Mangalavara => CelestialBody::Mars,
+            Vara::Budhavara => CelestialBody::Mercury,
+            Vara::Guruvara => CelestialBody::Jupiter,
+            Vara::Shukravara => CelestialBody::Venus,
+            Vara::Shanivara => CelestialBody::Saturn,
+        }
+    }
+}
+
+/// The 27 soli-lunar yogas, not to be confused with the planetary combinations in `Yoga`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum NityaYoga {
+    Vishkambha,
+    Priti,
+    Ayushman,
+    Saubhagya,
+    Shobhana,
+    Atiganda,
+    Sukarma,
+    Dhriti,
+    Shula,
+    Ganda,
+    Vriddhi,
+    Dhruva,
+    Vyaghata,
+    Harshana,
+    Vajra,
+    Siddhi,
+    Vyatipata,
+    Variyan,
+    Parigha,
+    Shiva,
+    Siddha,
+    Sadhya,
+    Shubha,
+    Shukla,
+    Brahma,
+    Indra,
+    Vaidhriti,
+}
+
+impl NityaYoga {
+    pub fn from_index(index: usize) -> NityaYoga {
+        const YOGAS: [NityaYoga; 27] = [
+            NityaYoga::Vishkambha,
+            NityaYoga::Priti,
+            NityaYoga::Ayushman,
+            NityaYoga::Saubhagya,
+            NityaYoga::Shobhana,
+            NityaYoga::Atiganda,
+            NityaYoga::Sukarma,
+            NityaYoga::Dhriti,
+            NityaYoga::Shula,
+            NityaYoga::Ganda,
+            NityaYoga::Vriddhi,
+            NityaYoga::Dhruva,
+            NityaYoga::Vyaghata,
+            NityaYoga::Harshana,
+            NityaYoga::Vajra,
+            NityaYoga::Siddhi,
+            NityaYoga::Vyatipata,
+            NityaYoga::Variyan,
+            NityaYoga::Parigha,
+            NityaYoga::Shiva,
+            NityaYoga::Siddha,
+            NityaYoga::Sadhya,
+            NityaYoga::Shubha,
+            NityaYoga::Shukla,
+            NityaYoga::Brahma,
+            NityaYoga::Indra,
+            NityaYoga::Vaidhriti,
+        ];
+        YOGAS[index % 27]
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Karana {
+    Bava,
+    Balava,
+    Kaulava,
+    Taitila,
+    Gara,
+    Vanija,
+    Vishti,
+    Shakuni,
+    Chatushpada,
+    Naga,
+    Kimstughna,
+}
+
+impl Karana {
+    /// Maps a half-tithi number (0-59, counted from the new Moon) to its karana. The first
+    /// and last three half-tithis hold the four fixed karanas; the seven movable ones cycle
+    /// through the rest.
+    pub fn from_index(index: usize) -> Karana {
+        const MOVABLE: [Karana; 7] = [
+            Karana::Bava,
+            Karana::Balava,
+            Karana::Kaulava,
+            Karana::Taitila,
+            Karana::Gara,
+            Karana::Vanija,
+            Karana::Vishti,
+        ];
+        match index % 60 {
+            0 => Karana::Kimstughna,
+            57 => Karana::Shakuni,
+            58 => Karana::Chatushpada,
+            59 => Karana::Naga,
+            i => MOVABLE[(i - 1) % 7],
+        }
+    }
+}
+
+/// A panchanga limb together with the moments it begins and ends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PanchangaLimb<T> {
+    pub value: T,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl<T> PanchangaLimb<T> {
+    pub fn contains(&self, date_time: DateTime<Utc>) -> bool {
+        self.start <= date_time && date_time < self.end
+    }
+
+    /// The first of the limb's start and end still to come after `date_time`.
+    pub fn next_boundary(&self, date_time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        [self.start, self.end]
+            .into_iter()
+            .find(|&moment| moment > date_time)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Panchanga {
+    pub tithi: PanchangaLimb<Tithi>,
+    pub paksha: Paksha,
+    pub vara: PanchangaLimb<Vara>,
+    pub nakshatra: PanchangaLimb<Nakshatra>,
+    pub yoga: PanchangaLimb<NityaYoga>,
+    pub karana: PanchangaLimb<Karana>,
+    /// Vishaghati of the nakshatra in force; it may already have passed or be still to come.
+    pub vishaghati: PanchangaLimb<Nakshatra>,
+    /// Amritaghati of the nakshatra in force.
+    pub amritaghati: PanchangaLimb<Nakshatra>,
+}
+
+/// How the Moon looks from the Earth, by the tithi in force.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum MoonPhase {
+    /// The last tithi of the dark half, ending at the new Moon.
+    Amavasya,
+    Waxing,
+    /// The last tithi of the bright half, ending at the full Moon.
+    Purnima,
+    Waning,
+}
+
+/// The Moon's phase at one moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LunarPhase {
+    /// Moon minus Sun in degrees, 0-360, counted from the new Moon.
+    pub elongation: f64,
+    pub phase: MoonPhase,
+    /// Share of the disc lit, in percent.
+    pub illumination: f64,
+    /// Tithi number, 0-29, counted from the new Moon.
+    pub tithi_index: usize,
+    pub tithi: Tithi,
+    pub paksha: Paksha,
+}
+
+impl LunarPhase {
+    /// The phase for an elongation of the Moon from the Sun, in degrees.
+    pub fn from_elongation(elongation: f64) -> Self {
+        let elongation = elongation.rem_euclid(360.0);
+        let tithi_index = ((elongation / 12.0).floor() as usize).min(29);
+        let (tithi, paksha) = Tithi::from_index(tithi_index);
+        let phase = match (tithi, paksha) {
+            (Tithi::Amavasya, _) => MoonPhase::Amavasya,
+            (Tithi::Purnima, _) => MoonPhase::Purnima,
+            (_, Paksha::Shukla) => MoonPhase::Waxing,
+            (_, Paksha::Krishna) => MoonPhase::Waning,
+        };
+        LunarPhase {
+            elongation,
+            phase,
+            illumination: (1.0 - elongation.to_radians().cos()) * 50.0,
+            tithi_index,
+            tithi,
+            paksha,
+        }
+    }
+}
+
+// ---------------------------
+// ## Vishaghati and Amritaghati
+// ---------------------------
+
+/// Both segments last four of the nakshatra's sixty ghatis.
+const GHATI_SEGMENT_LENGTH: f64 = 4.0;
+
+/// Ghati at which each nakshatra's vishaghati begins, Ashwini first, after Muhurta
+/// Chintamani. Some texts give 11 for Ardra and 56 for Moola.
+const VISHAGHATI_START: [f64; 27] = [
+    50.0, 24.0, 30.0, 40.0, 14.0, 21.0, 30.0, 20.0, 32.0, 30.0, 20.0, 18.0, 21.0, 20.0, 14.0, 14.0,
+    10.0, 14.0, 20.0, 24.0, 20.0, 10.0, 10.0, 18.0, 16.0, 24.0, 30.0,
+];
+
+/// Ghati at which each nakshatra's amritaghati begins, Ashwini first.
+const AMRITAGHATI_START: [f64; 27] = [
+    42.0, 48.0, 54.0, 52.0, 38.0, 35.0, 54.0, 44.0, 56.0, 54.0, 44.0, 42.0, 45.0, 44.0, 38.0, 38.0,
+    34.0, 38.0, 44.0, 48.0, 44.0, 34.0, 34.0, 42.0, 40.0, 48.0, 54.0,
+];
+
+impl Nakshatra {
+    /// Ghati, counted from the start of the nakshatra, at which its vishaghati begins.
+    pub fn vishaghati_start(&self) -> f64 {
+        VISHAGHATI_START[*self as usize]
+    }
+
+    /// Ghati, counted from the start of the nakshatra, at which its amritaghati begins.
+    pub fn amritaghati_start(&self) -> f64 {
+        AMRITAGHATI_START[*self as usize]
+    }
+}
+
+impl PanchangaLimb<Nakshatra> {
+    /// The inauspicious vishaghati within this nakshatra. Ghatis are scaled to the
+    /// nakshatra's actual duration, so each is a sixtieth of it.
+    pub fn vishaghati(&self) -> PanchangaLimb<Nakshatra> {
+        self.ghati_segment(self.value.vishaghati_start())
+    }
+
+    /// The auspicious amritaghati within this nakshatra.
+    pub fn amritaghati(&self) -> PanchangaLimb<Nakshatra> {
+        self.ghati_segment(self.value.amritaghati_start())
+    }
+
+    fn ghati_segment(&self, start_ghati: f64) -> PanchangaLimb<Nakshatra> {
+        let ghati_ms = (self.end - self.start).num_milliseconds() as f64 / 60.0;
+        let at = |ghati: f64| self.start + ChronoDuration::milliseconds((ghati * ghati_ms) as i64);
+        PanchangaLimb {
+            value: self.value,
+            start: at(start_ghati),
+            end: at(start_ghati + GHATI_SEGMENT_LENGTH),
+        }
+    }
+}
+
+// ---------------------------
+// ## Panchanga Calculation
+// ---------------------------
+
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Copy, Clone)]
+enum LunarAngle {
+    /// Moon minus Sun; drives tithi and karana.
+    Elongation,
+    /// Moon plus Sun; drives the nitya yoga.
+    YogaSum,
+    /// Moon alone; drives the nakshatra.
+    Moon,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Computes tithi, vara, nakshatra, yoga and karana in force at `date_time`.
+    ///
+    /// The vara runs from sunrise to sunrise. Where the Sun does not rise or set, it falls
+    /// back to the civil day at the location, from local midnight to midnight.
+    pub fn calculate_panchanga(
+        &self,
+        date_time: DateTime<Utc>,
+        location: &Location,
+    ) -> Result<Panchanga, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+
+        let elongation = self.lunar_angle(julian_day, LunarAngle::Elongation)?.0;
+        let tithi_index = (elongation / TITHI_SPAN).floor() as usize;
+        let (tithi, paksha) = Tithi::from_index(tithi_index);
+        let tithi = self.panchanga_limb(julian_day, LunarAngle::Elongation, TITHI_SPAN, tithi)?;
+
+        let karana_index = (elongation / KARANA_SPAN).floor() as usize;
+        let karana = Karana::from_index(karana_index);
+        let karana =
+            self.panchanga_limb(julian_day, LunarAngle::Elongation, KARANA_SPAN, karana)?;
+
+        let nakshatra = self.calculate_nakshatra_limb(date_time)?;
+
+        let yoga_sum = self.lunar_angle(julian_day, LunarAngle::YogaSum)?.0;
+        let yoga = NityaYoga::from_index((yoga_sum / NAKSHATRA_SPAN).floor() as usize);
+        let yoga = self.panchanga_limb(julian_day, LunarAngle::YogaSum, NAKSHATRA_SPAN, yoga)?;
+
+        let vara = match self.sunrise_vara(date_time, location) {
+            Ok(vara) => vara,
+            Err(_) => civil_vara(date_time, location),
+        };
+
+        Ok(Panchanga {
+            tithi,
+            paksha,
+            vara,
+            vishaghati: nakshatra.vishaghati(),
+            amritaghati: nakshatra.amritaghati(),
+            nakshatra,
+            yoga,
+            karana,
+        })
+    }
+
+    /// The Moon's nakshatra at `date_time` with the moments it begins and ends. Needs no
+    /// location; use [`PanchangaLimb::vishaghati`] and [`PanchangaLimb::amritaghati`] on the
+    /// result for the segments within it.
+    pub fn calculate_nakshatra_limb(
+        &self,
+        date_time: DateTime<Utc>,
+    ) -> Result<PanchangaLimb<Nakshatra>, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let moon = self.lunar_angle(julian_day, LunarAngle::Moon)?.0;
+        let nakshatra = Nakshatra::from_longitude(moon);
+        self.panchanga_limb(julian_day, LunarAngle::Moon, NAKSHATRA_SPAN, nakshatra)
+    }
+
+    /// The Moon's sidereal sign at `date_time` with the moments it enters and leaves it.
+    pub fn calculate_moon_sign_limb(
+        &self,
+        date_time: DateTime<Utc>,
+    ) -> Result<PanchangaLimb<ZodiacSign>, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let moon = self.lunar_angle(julian_day, LunarAngle::Moon)?.0;
+        let sign = ZodiacSign::from_longitude(moon);
+        self.panchanga_limb(julian_day, LunarAngle::Moon, 30.0, sign)
+    }
+
+    /// The Moon's elongation from the Sun at `date_time`, with the phase, tithi and paksha
+    /// it gives.
+    pub fn lunar_phase(&self, date_time: DateTime<Utc>) -> Result<LunarPhase, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let elongation = self.lunar_angle(julian_day, LunarAngle::Elongation)?.0;
+        Ok(LunarPhase::from_elongation(elongation))
+    }
+
+    /// The first full Moon after `date_time`.
+    pub fn next_full_moon(
+        &self,
+        date_time: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        self.next_elongation(date_time, 180.0)
+    }
+
+    /// The first new Moon after `date_time`.
+    pub fn next_new_moon(&self, date_time: DateTime<Utc>) -> Result<DateTime<Utc>, AstrologyError> {
+        self.next_elongation(date_time, 0.0)
+    }
+
+    /// The first moment after `date_time` the Moon is `target` degrees ahead of the Sun.
+    fn next_elongation(
+        &self,
+        date_time: DateTime<Utc>,
+        target: f64,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        let julian_day = date_to_julian_day(date_time);
+        let elongation = self.lunar_angle(julian_day, LunarAngle::Elongation)?.0;
+        // Start from the mean motion so the search settles on the crossing ahead
+        let mut ahead = (target - elongation).rem_euclid(360.0);
+        if ahead < 1e-6 {
+            ahead = 360.0;
+        }
+        let guess = julian_day + ahead / 360.0 * SYNODIC_MONTH;
+        let mut found = self.find_lunar_angle(guess, LunarAngle::Elongation, target)?;
+        if found <= julian_day {
+            found = self.find_lunar_angle(found + SYNODIC_MONTH, LunarAngle::Elongation, target)?;
+        }
+        Ok(julian_day_to_date(found))
+    }
+
+    fn sunrise_vara(
+        &self,
+        date_time: DateTime<Utc>,
+        location: &Location,
+    ) -> Result<PanchangaLimb<Vara>, AstrologyError> {
+        let local_date = date_time
+            .with_timezone(&location_offset(location))
+            .date_naive();
+        let sunrise = self.sunrise(local_date, location)?;
+
+        let (day, start, end) = if date_time < sunrise {
+            let previous_day = local_date.pred_opt().unwrap_or(local_date);
+            (previous_day, self.sunrise(previous_day, location)?, sunrise)
+        } else {
+            let next_day = local_date.succ_opt().unwrap_or(local_date);
+            (local_date, sunrise, self.sunrise(next_day, location)?)
+        };
+
+        Ok(PanchangaLimb {
+            value: Vara::from_weekday(day.weekday()),
+            start,
+            end,
+        })
+    }
+
+    /// The angle (0-360) and its daily rate of change at `julian_day`.
+    fn lunar_angle(
+        &self,
+        julian_day: JulianDay,
+        angle: LunarAngle,
+    ) -> Result<(f64, f64), AstrologyError> {
+        let position = |body| match self.calculate(
+            CoordinateSystem::Sidereal,
+            julian_day,
+            body,
+            &[CalculationFlag::Speed],
+            None,
+        )? {
+            AstronomicalResult::CelestialBody(info) => Ok((info.longitude, info.speed_longitude)),
+            _ => Err(AstrologyError::Ephemeris {
+                code: -1,
+                message: format!("Failed to calculate {:?} position", body),
+            }),
+        };
+
+        let (moon, moon_speed) = position(CelestialBody::Moon)?;
+        let (value, rate) = match angle {
+            LunarAngle::Moon => (moon, moon_speed),
+            LunarAngle::Elongation => {
+                let (sun, sun_speed) = position(CelestialBody::Sun)?;
+                (moon - sun, moon_speed - sun_speed)
+            }
+            LunarAngle::YogaSum => {
+                let (sun, sun_speed) = position(CelestialBody::Sun)?;
+                (moon + sun, moon_speed + sun_speed)
+            }
+        };
+        Ok((value.rem_euclid(360.0), rate))
+    }
+
+    /// Finds when `angle` crosses the boundaries of the `span`-wide segment it occupies at
+    /// `julian_day`.
+    fn panchanga_limb<T>(
+        &self,
+        julian_day: JulianDay,
+        angle: LunarAngle,
+        span: f64,
+        value: T,
+    ) -> Result<PanchangaLimb<T>, AstrologyError> {
+        let current = self.lunar_angle(julian_day, angle)?.0;
+        let segment_start = (current / span).floor() * span;
+
+        let start = self.find_lunar_angle(julian_day, angle, segment_start)?;
+        let end = self.find_lunar_angle(julian_day, angle, segment_start + span)?;
+
+        Ok(PanchangaLimb {
+            value,
+            start: julian_day_to_date(start),
+            end: julian_day_to_date(end),
+        })
+    }
+
+    /// Newton iteration from `julian_day` towards the moment `angle` equals `target`.
+    fn find_lunar_angle(
+        &self,
+        julian_day: JulianDay,
+        angle: LunarAngle,
+        target: f64,
+    ) -> Result<JulianDay, AstrologyError> {
+        let mut jd = julian_day;
+        for _ in 0..20 {
+            let (value, rate) = self.lunar_angle(jd, angle)?;
+            let difference = (target - value + 540.0).rem_euclid(360.0) - 180.0;
+            if difference.abs() < 1e-6 || rate.abs() < f64::EPSILON {
+                break;
+            }
+            jd += difference / rate;
+        }
+        Ok(jd)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+fn location_offset(location: &Location) -> FixedOffset {
+    FixedOffset::east_opt((location.timezone * 3600.0).round() as i32)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+}
+
+#[cfg(not(feature = "model-only"))]
+fn civil_vara(date_time: DateTime<Utc>, location: &Location) -> PanchangaLimb<Vara> {
+    let offset = location_offset(location);
+    let local_date = date_time.with_timezone(&offset).date_naive();
+    let day_start = offset
+        .from_local_datetime(&local_date.and_time(NaiveTime::MIN))
+        .single()
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or(date_time);
+
+    PanchangaLimb {
+        value: Vara::from_weekday(local_date.weekday()),
+        start: day_start,
+        end: day_start + ChronoDuration::days(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "model-only"))]
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn phases_follow_the_elongation() {
+        let new = LunarPhase::from_elongation(355.0);
+        assert_eq!(new.phase, MoonPhase::Amavasya);
+        assert_eq!(new.tithi_index, 29);
+        assert!(new.illumination < 1.0);
+
+        let full = LunarPhase::from_elongation(-181.0);
+        assert_eq!(full.phase, MoonPhase::Purnima);
+        assert_eq!(full.paksha, Paksha::Shukla);
+        assert!(full.illumination > 99.9);
+
+        let quarter = LunarPhase::from_elongation(270.0);
+        assert_eq!(quarter.phase, MoonPhase::Waning);
+        assert_eq!(quarter.tithi, Tithi::Ashtami);
+        assert!((quarter.illumination - 50.0).abs() < 1e-9);
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn finds_the_next_full_and_new_moons() {
+        let ephemeris = SwissEph::new().unwrap();
+        let after = utc("2024-10-16T01:00:00Z");
+        assert_eq!(
+            ephemeris.lunar_phase(after).unwrap().tithi,
+            Tithi::Chaturdashi
+        );
+
+        // Full Moon at 11:26 UT on 17 October 2024, new Moon at 12:47 UT on 1 November
+        let full = ephemeris.next_full_moon(after).unwrap();
+        let expected = utc("2024-10-17T11:26:00Z");
+        assert!((full - expected).num_minutes().abs() <= 2);
+        let new = ephemeris.next_new_moon(after).unwrap();
+        let expected = utc("2024-11-01T12:47:00Z");
+        assert!((new - expected).num_minutes().abs() <= 2);
+
+        assert!(ephemeris.next_full_moon(full).unwrap() - full > ChronoDuration::days(29));
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn limbs_begin_and_end_when_the_almanacs_have_them() {
+        let ephemeris = SwissEph::new().unwrap();
+        let delhi = Location::new(28.6139, 77.2090, 5.5);
+        // Sharad Purnima, the morning of 17 October 2024 in New Delhi
+        let panchanga = ephemeris
+            .calculate_panchanga(utc("2024-10-17T08:00:00+05:30"), &delhi)
+            .unwrap();
+        let near = |moment: DateTime<Utc>, expected: &str| {
+            let off = (moment - utc(expected)).num_seconds().abs();
+            assert!(off <= 120, "{} is not {}", moment, expected);
+        };
+
+        // As the published panchangas have them, to the minute
+        assert_eq!(panchanga.tithi.value, Tithi::Purnima);
+        near(panchanga.tithi.start, "2024-10-16T20:40:00+05:30");
+        near(panchanga.tithi.end, "2024-10-17T16:55:00+05:30");
+        assert_eq!(panchanga.nakshatra.value, Nakshatra::Revati);
+        near(panchanga.nakshatra.end, "2024-10-17T16:20:00+05:30");
+        assert_eq!(panchanga.yoga.value, NityaYoga::Harshana);
+        near(panchanga.yoga.end, "2024-10-18T01:42:00+05:30");
+        // Thursday, from sunrise to sunrise
+        assert_eq!(panchanga.vara.value, Vara::Guruvara);
+        near(panchanga.vara.start, "2024-10-17T06:23:00+05:30");
+        near(panchanga.vara.end, "2024-10-18T06:24:00+05:30");
+
+        // The tithi's second karana, which ends with it
+        assert_eq!(panchanga.karana.value, Karana::Bava);
+        assert_eq!(panchanga.karana.end, panchanga.tithi.end);
+        let half = panchanga.tithi.start + (panchanga.tithi.end - panchanga.tithi.start) / 2;
+        near(panchanga.karana.start, &half.to_rfc3339());
+
+        // The next tithi starts where this one ends
+        let next = ephemeris
+            .calculate_panchanga(panchanga.tithi.end + ChronoDuration::minutes(1), &delhi)
+            .unwrap();
+        assert_eq!(next.tithi.value, Tithi::Pratipada);
+        assert_eq!(next.paksha, Paksha::Krishna);
+        assert!((next.tithi.start - panchanga.tithi.end).num_seconds().abs() <= 1);
+    }
+}
@@ -0,0 +1,247 @@
+use super::*;
+#[cfg(not(feature = "model-only"))]
+use std::sync::Mutex;
+
+/// Size and resolution of an engine's cache of body positions.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PositionCacheSettings {
+    /// Positions kept before the least recently used are dropped. Zero turns the cache off.
+    pub capacity: usize,
+    /// Julian days closer than this share one entry, so a cached position can be up to
+    /// this far from the moment asked for. The default, a millionth of a day, is under a
+    /// tenth of a second, in which the Moon moves about 0.05″.
+    pub precision: f64,
+}
+
+impl Default for PositionCacheSettings {
+    fn default() -> Self {
+        PositionCacheSettings {
+            capacity: 32768,
+            precision: 1e-6,
+        }
+    }
+}
+
+/// How well an engine's position cache has served so far.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PositionCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Positions held now.
+    pub entries: usize,
+}
+
+impl PositionCacheStats {
+    /// Share of lookups answered from the cache, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+/// Julian day in units of the precision, the body's Swiss Ephemeris number and the flags.
+#[cfg(not(feature = "model-only"))]
+type PositionKey = (i64, c_int, c_int);
+
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Default)]
+struct CacheEntries {
+    /// Each position with the tick of its last use.
+    positions: HashMap<PositionKey, (CelestialCoordinates, u64)>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// Positions an engine has already asked Swiss Ephemeris for. Transit scans, return
+/// searches and dashas come back to the same moments again and again.
+#[cfg(not(feature = "model-only"))]
+#[derive(Debug, Default)]
+pub(crate) struct PositionCache {
+    settings: PositionCacheSettings,
+    entries: Mutex<CacheEntries>,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl PositionCache {
+    pub(crate) fn new(settings: PositionCacheSettings) -> Self {
+        PositionCache {
+            settings,
+            entries: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn settings(&self) -> PositionCacheSettings {
+        self.settings
+    }
+
+    fn key(&self, julian_day: JulianDay, body_code: c_int, iflag: c_int) -> PositionKey {
+        (
+            (julian_day / self.settings.precision).round() as i64,
+            body_code,
+            iflag,
+        )
+    }
+
+    /// The position for `julian_day`, from the cache or else from `calculate`, which is
+    /// kept if it succeeds. Topocentric positions depend on an observer the key does not
+    /// hold, so they always go to `calculate`.
+    pub(crate) fn get_or_calculate(
+        &self,
+        julian_day: JulianDay,
+        body_code: c_int,
+        iflag: c_int,
+        calculate: impl FnOnce() -> Result<CelestialCoordinates, AstrologyError>,
+    ) -> Result<CelestialCoordinates, AstrologyError> {
+        if self.settings.capacity == 0 || iflag & SEFLG_TOPOCTR != 0 {
+            return calculate();
+        }
+        let key = self.key(julian_day, body_code, iflag);
+        {
+            let mut entries = self.lock();
+            entries.tick += 1;
+            let tick = entries.tick;
+            if let Some((position, used)) = entries.positions.get_mut(&key) {
+                *used = tick;
+                let position = *position;
+                entries.hits += 1;
+                return Ok(position);
+            }
+            entries.misses += 1;
+        }
+
+        // The lock is not held while Swiss Ephemeris works, so other threads sharing the
+        // engine are not held up.
+        let position = calculate()?;
+        let mut entries = self.lock();
+        if entries.positions.len() >= self.settings.capacity {
+            // Dropping the least recently used eighth at once keeps evictions rare
+            let mut ticks: Vec<u64> = entries.positions.values().map(|(_, used)| *used).collect();
+            let evicted = (ticks.len() / 8).max(1);
+            let (_, &mut last_evicted, _) = ticks.select_nth_unstable(evicted - 1);
+            entries
+                .positions
+                .retain(|_, (_, used)| *used > last_evicted);
+        }
+        let tick = entries.tick;
+        entries.positions.insert(key, (position, tick));
+        Ok(position)
+    }
+
+    pub(crate) fn stats(&self) -> PositionCacheStats {
+        let entries = self.lock();
+        PositionCacheStats {
+            hits: entries.hits,
+            misses: entries.misses,
+            entries: entries.positions.len(),
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.lock().positions.clear();
+    }
+
+    /// A panic while the lock was held cannot leave a half-written entry, so a poisoned
+    /// lock is used as it is.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Hits and misses of the engine's position cache since it was built or last cleared.
+    pub fn position_cache_stats(&self) -> PositionCacheStats {
+        self.position_cache.stats()
+    }
+
+    /// Forgets every cached position. The hit and miss counts are kept.
+    pub fn clear_position_cache(&self) {
+        self.position_cache.clear();
+    }
+}
+
+#[cfg(all(test, not(feature = "model-only")))]
+mod tests {
+    use super::*;
+
+    fn coordinates(longitude: f64) -> CelestialCoordinates {
+        CelestialCoordinates {
+            longitude,
+            latitude: 0.0,
+            distance: 1.0,
+            speed_longitude: 1.0,
+            speed_latitude: 0.0,
+            speed_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn nearby_days_share_an_entry() {
+        let cache = PositionCache::new(PositionCacheSettings::default());
+        let first = cache.get_or_calculate(2451545.0, 0, 0, || Ok(coordinates(10.0)));
+        let second = cache.get_or_calculate(2451545.0 + 1e-8, 0, 0, || Ok(coordinates(20.0)));
+        assert_eq!(first, second);
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn the_least_recently_used_are_dropped() {
+        let cache = PositionCache::new(PositionCacheSettings {
+            capacity: 8,
+            precision: 1.0,
+        });
+        for day in 0..8 {
+            cache
+                .get_or_calculate(day as f64, 0, 0, || Ok(coordinates(day as f64)))
+                .unwrap();
+        }
+        // Day 0 is used again, so day 1 is now the oldest
+        cache
+            .get_or_calculate(0.0, 0, 0, || Ok(coordinates(-1.0)))
+            .unwrap();
+        cache
+            .get_or_calculate(8.0, 0, 0, || Ok(coordinates(8.0)))
+            .unwrap();
+
+        assert_eq!(cache.stats().entries, 8);
+        let day_zero = cache.get_or_calculate(0.0, 0, 0, || Ok(coordinates(-1.0)));
+        assert_eq!(day_zero.unwrap().longitude, 0.0);
+        let day_one = cache.get_or_calculate(1.0, 0, 0, || Ok(coordinates(-1.0)));
+        assert_eq!(day_one.unwrap().longitude, -1.0);
+    }
+
+    #[test]
+    fn a_precision_that_is_not_positive_fails_the_build() {
+        for precision in [0.0, -1e-6, f64::NAN, f64::INFINITY] {
+            let engine = SwissEph::builder()
+                .ephemeris_mode(EphemerisMode::Moshier)
+                .position_cache(PositionCacheSettings {
+                    capacity: 8,
+                    precision,
+                })
+                .build();
+            assert!(
+                matches!(engine, Err(AstrologyError::InvalidInput(_))),
+                "{}",
+                precision
+            );
+        }
+        // Only the capacity turns the cache off
+        let uncached = SwissEph::builder()
+            .ephemeris_mode(EphemerisMode::Moshier)
+            .position_cache(PositionCacheSettings {
+                capacity: 0,
+                ..Default::default()
+            })
+            .build();
+        assert!(uncached.is_ok());
+    }
+}
@@ -0,0 +1,308 @@
+use super::*;
+
+/// Highest number a querent can choose in KP horary.
+pub const KP_HORARY_NUMBERS: u16 = 249;
+
+/// Longitude at which KP horary number `number` (1 to 249) begins. The zodiac is cut at
+/// every sub of every nakshatra and at every sign boundary, giving 249 parts; number 1
+/// starts at 0° Aries.
+pub fn kp_horary_longitude(number: u16) -> Option<f64> {
+    let index = usize::from(number.checked_sub(1)?);
+    kp_horary_starts().get(index).copied()
+}
+
+fn kp_horary_starts() -> Vec<f64> {
+    let span = 360.0 / 27.0;
+    let sequence = Dasha::sequence();
+    let mut starts = Vec::with_capacity(usize::from(KP_HORARY_NUMBERS));
+
+    for index in 0..27 {
+        let nakshatra_start = index as f64 * span;
+        let lord = NakshatraInfo::from_longitude(nakshatra_start + span / 2.0).lord;
+        let first = sequence
+            .iter()
+            .position(|dasha| dasha.lord() == lord)
+            .unwrap_or(0);
+        let mut offset = 0.0;
+        for i in 0..sequence.len() {
+            starts.push(nakshatra_start + offset);
+            offset += span * sequence[(first + i) % sequence.len()].years() / 120.0;
+        }
+    }
+    for sign in 0..12 {
+        let boundary = sign as f64 * 30.0;
+        if !starts.iter().any(|start| (start - boundary).abs() < 1e-6) {
+            starts.push(boundary);
+        }
+    }
+
+    starts.sort_by(f64::total_cmp);
+    starts
+}
+
+/// The Moon of a horary chart, the first thing read for the state of the querent's mind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PrashnaMoon {
+    pub longitude: f64,
+    pub sign: ZodiacSign,
+    pub house: House,
+    pub nakshatra: NakshatraInfo,
+    pub lords: KpLords,
+    /// Degrees the Moon is ahead of the Sun, 0 at the new Moon and 180 at the full.
+    pub elongation: f64,
+    pub paksha: Paksha,
+    pub dignity: DignityInfo,
+    /// Houses the Moon is a KP significator of.
+    pub signifies: Vec<House>,
+}
+
+/// A horary chart, cast for the moment a question is asked rather than for a birth.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PrashnaChart {
+    pub date_time: DateTime<Utc>,
+    pub location: Location,
+    /// The querent's KP number, when the lagna was fixed by it rather than by the time.
+    pub number: Option<u16>,
+    /// The first cusp with its KP lords.
+    pub lagna: KpCusp,
+    /// Cusps, planets and house significators, on the KP ayanamsa and Placidus cusps.
+    pub chart: KpChart,
+    pub moon: PrashnaMoon,
+    /// KP ruling planets, strongest first: the star and sign lords of the lagna, the star
+    /// and sign lords of the Moon, and the lord of the weekday.
+    pub ruling_planets: Vec<CelestialBody>,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl PrashnaChart {
+    /// The chart of a question asked now at `location`.
+    pub fn for_now(location: &Location) -> Result<PrashnaChart, AstrologyError> {
+        SwissEph::new()?.calculate_prashna(Utc::now(), location, None)
+    }
+
+    /// The chart of a question asked now at `location`, with the lagna fixed by the
+    /// querent's KP number from 1 to 249.
+    pub fn for_number(number: u16, location: &Location) -> Result<PrashnaChart, AstrologyError> {
+        SwissEph::new()?.calculate_prashna(Utc::now(), location, Some(number))
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Casts the horary chart of a question asked at `date_time` and `location`. Planets
+    /// stand where they are at that moment. With a KP `number`, the cusps are those of the
+    /// next moment the number's degree rises there, so the lagna is the querent's choice.
+    pub fn calculate_prashna(
+        &self,
+        date_time: DateTime<Utc>,
+        location: &Location,
+        number: Option<u16>,
+    ) -> Result<PrashnaChart, AstrologyError> {
+        let kp = self.kp_engine();
+        let birth = BirthInfo {
+            date_time,
+            location: location.clone(),
+            person: None,
+        };
+        let chart = kp.calculate_chart(&birth, HouseSystem::Krishnamurti)?;
+
+        let houses = match number {
+            None => chart.houses.clone(),
+            Some(number) => {
+                let lagna = kp_horary_longitude(number).ok_or_else(|| {
+                    AstrologyError::InvalidInput(format!(
+                        "KP horary numbers run from 1 to {}, not {}",
+                        KP_HORARY_NUMBERS, number
+                    ))
+                })?;
+                let rising = kp.next_rising(lagna, date_time, location)?;
+                kp.calculate_houses(
+                    CoordinateSystem::Sidereal,
+                    date_to_julian_day(rising),
+                    location.latitude,
+                    location.longitude,
+                    HouseSystem::Krishnamurti,
+                )?
+                .cusps
+            }
+        };
+
+        let cusps: Vec<KpCusp> = houses.iter().map(KpCusp::from_cusp).collect();
+        let planets = chart
+            .planets
+            .iter()
+            .map(|planet| KpPlanet {
+                house: house_from_cusps(&houses, planet.longitude.degrees()),
+                ..KpPlanet::from_position(planet)
+            })
+            .collect();
+        let kp_chart = KpChart::from_cusps_and_planets(cusps, planets);
+
+        let lagna = kp_chart.cusps[0].clone();
+        let moon = self.prashna_moon(&chart, &kp_chart)?;
+        let vara = self.calculate_panchanga(date_time, location)?.vara.value;
+
+        let mut ruling_planets = Vec::new();
+        for planet in [
+            lagna.lords.star_lord,
+            lagna.lords.sign_lord,
+            moon.lords.star_lord,
+            moon.lords.sign_lord,
+            vara.lord(),
+        ] {
+            if !ruling_planets.contains(&planet) {
+                ruling_planets.push(planet);
+            }
+        }
+
+        Ok(PrashnaChart {
+            date_time,
+            location: location.clone(),
+            number,
+            lagna,
+            chart: kp_chart,
+            moon,
+            ruling_planets,
+        })
+    }
+
+    fn prashna_moon(
+        &self,
+        chart: &ChartInfo,
+        kp_chart: &KpChart,
+    ) -> Result<PrashnaMoon, AstrologyError> {
+        let position = |body: CelestialBody| {
+            chart
+                .planets
+                .iter()
+                .find(|planet| planet.planet == body)
+                .ok_or(AstrologyError::MissingBody(body))
+        };
+        let moon = position(CelestialBody::Moon)?;
+        let sun = position(CelestialBody::Sun)?;
+        let kp_moon = kp_chart
+            .planets
+            .iter()
+            .find(|planet| planet.planet == CelestialBody::Moon)
+            .ok_or(AstrologyError::MissingBody(CelestialBody::Moon))?;
+
+        let elongation = moon.longitude - sun.longitude;
+        let signifies = kp_chart
+            .cusps
+            .iter()
+            .map(|cusp| cusp.house)
+            .filter(|&house| {
+                kp_chart
+                    .significators_of(house)
+                    .contains(&CelestialBody::Moon)
+            })
+            .collect();
+
+        Ok(PrashnaMoon {
+            longitude: moon.longitude.degrees(),
+            sign: moon.sign,
+            house: kp_moon.house,
+            nakshatra: moon.nakshatra.clone(),
+            lords: kp_moon.lords,
+            elongation,
+            paksha: if elongation < 180.0 {
+                Paksha::Shukla
+            } else {
+                Paksha::Krishna
+            },
+            dignity: self.calculate_dignity(moon),
+            signifies,
+        })
+    }
+
+    /// First moment from `after` at which sidereal longitude `lagna` rises at `location`.
+    fn next_rising(
+        &self,
+        lagna: f64,
+        after: DateTime<Utc>,
+        location: &Location,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        let ascendant = |julian_day| {
+            self.calculate_ascendant(
+                CoordinateSystem::Sidereal,
+                julian_day,
+                location.latitude,
+                location.longitude,
+                HouseSystem::Krishnamurti,
+            )
+            .map(|cusp| cusp.longitude())
+        };
+        // Arc still to rise before `lagna`, seen from an ascendant at `from`
+        let ahead = |from: f64| (lagna - from).rem_euclid(360.0);
+
+        let step = 10.0 / 1440.0;
+        let start = date_to_julian_day(after);
+        let mut low = start;
+        let mut low_ascendant = ascendant(low)?;
+        while low - start < 1.1 {
+            let high = low + step;
+            let high_ascendant = ascendant(high)?;
+            let moved = (high_ascendant - low_ascendant).rem_euclid(360.0);
+            if moved >= ahead(low_ascendant) {
+                let (mut low, mut high) = (low, high);
+                while high - low > 1.0 / 86400.0 {
+                    let middle = (low + high) / 2.0;
+                    let middle_ascendant = ascendant(middle)?;
+                    if (middle_ascendant - low_ascendant).rem_euclid(360.0) < ahead(low_ascendant) {
+                        low = middle;
+                        low_ascendant = middle_ascendant;
+                    } else {
+                        high = middle;
+                    }
+                }
+                return Ok(julian_day_to_date(high));
+            }
+            low = high;
+            low_ascendant = high_ascendant;
+        }
+
+        Err(AstrologyError::NoRiseOrSet(format!(
+            "{:.2}° does not rise within a day at latitude {:.2}",
+            lagna, location.latitude
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn horary_numbers_start_where_the_kp_table_has_them() {
+        let starts = kp_horary_starts();
+        assert_eq!(starts.len(), usize::from(KP_HORARY_NUMBERS));
+        assert!(starts.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let close = |number: u16, degrees: f64| {
+            let start = kp_horary_longitude(number).unwrap();
+            assert!((start - degrees).abs() < 1e-9, "{}: {}", number, start);
+        };
+        close(1, 0.0);
+        // The last of Aries is the Rahu sub of Krittika, cut at the sign boundary: number
+        // 23 is its part in Taurus and 24 the Jupiter sub
+        close(22, 29.0 + 13.0 / 60.0 + 20.0 / 3600.0);
+        close(23, 30.0);
+        close(24, 31.0 + 13.0 / 60.0 + 20.0 / 3600.0);
+        // The Saturn sub of Mrigashira ends on the boundary, so Gemini starts number 42
+        // with the Mercury sub and no extra cut
+        close(41, 57.0 + 53.0 / 60.0 + 20.0 / 3600.0);
+        close(42, 60.0);
+        let gemini = KpLords::from_longitude(60.0);
+        assert_eq!(
+            (gemini.star_lord, gemini.sub_lord),
+            (CelestialBody::Mars, CelestialBody::Mercury)
+        );
+        // The Saturn sub of Revati
+        close(249, 357.0 + 53.0 / 60.0 + 20.0 / 3600.0);
+
+        assert_eq!(kp_horary_longitude(0), None);
+        assert_eq!(kp_horary_longitude(250), None);
+    }
+}
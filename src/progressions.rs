@@ -0,0 +1,195 @@
+use super::*;
+
+/// Orb within which a progressed planet aspects a natal one.
+pub const PROGRESSION_ORB: f64 = 1.0;
+
+/// What the progressed Moon moved into.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ProgressedIngress {
+    Sign(ZodiacSign),
+    /// A house of the birth chart.
+    House(House),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ProgressedMoonChange {
+    /// Calendar date on which the change falls in the native's life.
+    pub date: DateTime<Utc>,
+    pub ingress: ProgressedIngress,
+}
+
+/// Secondary progressions and solar arc directions for one date in the native's life.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ProgressedChart {
+    pub target_date: DateTime<Utc>,
+    /// Moment whose sky stands for `target_date`: one day after birth for every year of
+    /// life.
+    pub progressed_date: DateTime<Utc>,
+    /// Planets and houses cast for `progressed_date` at the birthplace.
+    pub chart: ChartInfo,
+    /// Aspects from progressed planets (`planet1`) to natal ones (`planet2`) within
+    /// [`PROGRESSION_ORB`].
+    pub aspects_to_natal: Vec<AspectInfo>,
+    /// Every sign and natal-house ingress of the progressed Moon from birth to
+    /// `target_date`; the last of each kind began the Moon's current placement.
+    pub moon_changes: Vec<ProgressedMoonChange>,
+    /// Distance the progressed Sun has moved from the natal Sun.
+    pub solar_arc: f64,
+    /// Natal planets moved forward by `solar_arc`, placed in the natal houses. Speeds and
+    /// retrograde flags are the natal ones.
+    pub solar_arc_planets: Vec<PlanetPosition>,
+    pub solar_arc_ascendant: HouseCusp,
+}
+
+/// Julian day whose sky stands for `julian_day` in a life begun at `birth_jd`.
+#[cfg(not(feature = "model-only"))]
+fn progressed_julian_day(birth_jd: JulianDay, julian_day: JulianDay) -> JulianDay {
+    birth_jd + (julian_day - birth_jd) / TROPICAL_YEAR_DAYS
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Secondary progressions (a day for a year) and solar arc directions for
+    /// `target_date`.
+    pub fn progressed_chart(
+        &self,
+        birth_info: &BirthInfo,
+        target_date: DateTime<Utc>,
+    ) -> Result<ProgressedChart, AstrologyError> {
+        let house_system = self.conventions.house_system;
+        let natal = self.calculate_chart(birth_info, house_system)?;
+
+        let birth_jd = date_to_julian_day(birth_info.date_time);
+        let progressed_date = julian_day_to_date(progressed_julian_day(
+            birth_jd,
+            date_to_julian_day(target_date),
+        ));
+        let chart = self.calculate_chart(
+            &BirthInfo {
+                date_time: progressed_date,
+                location: birth_info.location.clone(),
+                person: None,
+            },
+            house_system,
+        )?;
+
+        let orbs = FixedOrbs {
+            conjunction: PROGRESSION_ORB,
+            opposition: PROGRESSION_ORB,
+            trine: PROGRESSION_ORB,
+            square: PROGRESSION_ORB,
+            sextile: PROGRESSION_ORB,
+        };
+        // The nodes barely progress and always oppose each other, so node-to-node
+        // aspects say nothing
+        let node = |planet: &PlanetPosition| {
+            matches!(planet.planet, CelestialBody::Rahu | CelestialBody::Ketu)
+        };
+        let aspects_to_natal = chart
+            .planets
+            .iter()
+            .flat_map(|progressed| {
+                natal
+                    .planets
+                    .iter()
+                    .filter(|planet| !(node(progressed) && node(planet)))
+                    .filter_map(|planet| closest_aspect(progressed, planet, &orbs))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let sun = |chart: &ChartInfo| {
+            chart
+                .planets
+                .iter()
+                .find(|planet| planet.planet == CelestialBody::Sun)
+                .map(|planet| planet.longitude)
+                .ok_or(AstrologyError::MissingBody(CelestialBody::Sun))
+        };
+        let solar_arc = sun(&chart)? - sun(&natal)?;
+        let solar_arc_planets = natal
+            .planets
+            .iter()
+            .map(|planet| {
+                let longitude = planet.longitude + solar_arc;
+                PlanetPosition {
+                    longitude,
+                    sign: longitude.sign(),
+                    house: natal.house_of(longitude.degrees()),
+                    nakshatra: longitude.nakshatra(),
+                    ..planet.clone()
+                }
+            })
+            .collect();
+        let ascendant = Longitude::new(natal.ascendant.longitude()) + solar_arc;
+        let solar_arc_ascendant = HouseCusp {
+            house: House::First,
+            sign: ascendant.sign(),
+            degree: ascendant.degree_in_sign(),
+        };
+
+        Ok(ProgressedChart {
+            target_date,
+            progressed_date,
+            aspects_to_natal,
+            moon_changes: self.progressed_moon_changes(
+                birth_info,
+                birth_info.date_time,
+                target_date,
+            )?,
+            chart,
+            solar_arc,
+            solar_arc_planets,
+            solar_arc_ascendant,
+        })
+    }
+
+    /// Sign and natal-house ingresses of the progressed Moon falling between `start` and
+    /// `end` in the native's life, in date order. Dates are good to about six hours.
+    pub fn progressed_moon_changes(
+        &self,
+        birth_info: &BirthInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ProgressedMoonChange>, AstrologyError> {
+        let natal = self.calculate_chart(birth_info, self.conventions.house_system)?;
+        let birth_jd = date_to_julian_day(birth_info.date_time);
+        let progressed = |date: DateTime<Utc>| {
+            julian_day_to_date(progressed_julian_day(birth_jd, date_to_julian_day(date)))
+        };
+
+        let targets: Vec<f64> = (0..12)
+            .map(|sign| sign as f64 * 30.0)
+            .chain(natal.houses.iter().map(HouseCusp::longitude))
+            .collect();
+        let crossings = self.find_crossings(
+            CelestialBody::Moon,
+            progressed(start),
+            progressed(end),
+            &targets,
+        )?;
+
+        let mut changes: Vec<ProgressedMoonChange> = crossings
+            .into_iter()
+            .map(|crossing| {
+                let ingress = match crossing.target {
+                    sign @ 0..12 => {
+                        ProgressedIngress::Sign(ZodiacSign::from_longitude(sign as f64 * 30.0))
+                    }
+                    cusp => ProgressedIngress::House(natal.houses[cusp - 12].house),
+                };
+                ProgressedMoonChange {
+                    date: julian_day_to_date(
+                        birth_jd + (crossing.julian_day - birth_jd) * TROPICAL_YEAR_DAYS,
+                    ),
+                    ingress,
+                }
+            })
+            .collect();
+        changes.sort_by_key(|change| change.date);
+        Ok(changes)
+    }
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+/// Any point of a natal chart that has a zodiacal longitude.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ChartPoint {
+    Planet(CelestialBody),
+    Ascendant,
+    Cusp(House),
+    Upagraha(Upagraha),
+    SpecialLagna(SpecialLagna),
+}
+
+/// A chart point found within the orb of a searched degree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NearbyPoint {
+    pub point: ChartPoint,
+    pub longitude: f64,
+    /// Shortest arc to the searched degree, between 0 and 180.
+    pub distance: f64,
+}
+
+/// Shortest arc between two longitudes.
+#[cfg(not(feature = "model-only"))]
+fn arc_distance(a: f64, b: f64) -> f64 {
+    let difference = (a - b).rem_euclid(360.0);
+    difference.min(360.0 - difference)
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Every planet, cusp, upagraha and special lagna of `chart` within `orb` degrees of
+    /// `longitude`, closest first. Useful for checking an eclipse degree, a fixed star or a
+    /// transit against the whole natal chart.
+    pub fn points_near(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+        longitude: f64,
+        orb: f64,
+    ) -> Result<Vec<NearbyPoint>, AstrologyError> {
+        let mut points: Vec<(ChartPoint, f64)> = chart
+            .planets
+            .iter()
+            .map(|planet| {
+                (
+                    ChartPoint::Planet(planet.planet),
+                    planet.longitude.degrees(),
+                )
+            })
+            .collect();
+        points.push((ChartPoint::Ascendant, chart.ascendant.longitude()));
+        points.extend(
+            chart
+                .houses
+                .iter()
+                .map(|cusp| (ChartPoint::Cusp(cusp.house), cusp.longitude())),
+        );
+        points.extend(
+            self.calculate_upagrahas(birth_info, chart)?
+                .into_values()
+                .map(|info| (ChartPoint::Upagraha(info.upagraha), info.longitude)),
+        );
+        points.extend(
+            self.calculate_special_lagnas(chart)?
+                .into_iter()
+                .map(|(lagna, longitude)| (ChartPoint::SpecialLagna(lagna), longitude.degrees())),
+        );
+
+        let mut nearby: Vec<NearbyPoint> = points
+            .into_iter()
+            .map(|(point, point_longitude)| NearbyPoint {
+                point,
+                longitude: point_longitude.rem_euclid(360.0),
+                distance: arc_distance(point_longitude, longitude),
+            })
+            .filter(|point| point.distance <= orb)
+            .collect();
+        nearby.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(nearby)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_are_found_closest_first() {
+        let ephemeris = SwissEph::new().unwrap();
+        let birth = crate::examples::native().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&birth, HouseSystem::WholeSign)
+            .unwrap();
+        let sun = chart
+            .planet(CelestialBody::Sun)
+            .unwrap()
+            .longitude
+            .degrees();
+
+        let nearby = ephemeris.points_near(&birth, &chart, sun, 5.0).unwrap();
+        assert_eq!(nearby[0].point, ChartPoint::Planet(CelestialBody::Sun));
+        assert!(nearby[0].distance < 1e-9);
+        assert!(nearby
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+        assert!(nearby.iter().all(|point| point.distance <= 5.0));
+
+        // A degree given past 360 or below 0 is the same degree
+        let beyond = ephemeris
+            .points_near(&birth, &chart, sun + 360.0, 5.0)
+            .unwrap();
+        assert_eq!(beyond.len(), nearby.len());
+        for (one, other) in beyond.iter().zip(&nearby) {
+            assert_eq!(one.point, other.point);
+            assert!((one.distance - other.distance).abs() < 1e-9);
+        }
+        let wrapped = ephemeris
+            .points_near(&birth, &chart, sun - 359.5, 1.0)
+            .unwrap();
+        assert_eq!(wrapped[0].point, ChartPoint::Planet(CelestialBody::Sun));
+        assert!((wrapped[0].distance - 0.5).abs() < 1e-9);
+
+        // The whole-sign cusp at 0° Aries is found from just behind it
+        let cusps: Vec<ChartPoint> = ephemeris
+            .points_near(&birth, &chart, 359.9, 0.2)
+            .unwrap()
+            .into_iter()
+            .map(|point| point.point)
+            .filter(|point| matches!(point, ChartPoint::Cusp(_)))
+            .collect();
+        assert_eq!(cusps.len(), 1);
+    }
+
+    #[test]
+    fn a_half_circle_orb_finds_every_point() {
+        let ephemeris = SwissEph::new().unwrap();
+        let birth = crate::examples::native().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&birth, HouseSystem::WholeSign)
+            .unwrap();
+
+        let everything = ephemeris.points_near(&birth, &chart, 123.4, 180.0).unwrap();
+        let upagrahas = ephemeris.calculate_upagrahas(&birth, &chart).unwrap();
+        let lagnas = ephemeris.calculate_special_lagnas(&chart).unwrap();
+        assert_eq!(
+            everything.len(),
+            chart.planets.len() + 1 + chart.houses.len() + upagrahas.len() + lagnas.len()
+        );
+        assert!(everything
+            .iter()
+            .all(|point| (0.0..360.0).contains(&point.longitude)));
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+/// A stretch of possible birth times over which the lagna, the navamsa lagna and the Moon's
+/// nakshatra pada all stay the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct RectificationWindow {
+    pub start: DateTime<Utc>,
+    /// First moment something changes, or the end of the range searched.
+    pub end: DateTime<Utc>,
+    pub lagna: ZodiacSign,
+    pub navamsa_lagna: ZodiacSign,
+    pub moon_nakshatra: Nakshatra,
+    pub moon_pada: u8,
+    /// Sum of the scores given by the rectifier's scorers; 0 without any.
+    pub score: f64,
+}
+
+/// Rates a candidate birth time, usually by how well the chart accounts for known events in
+/// the native's life. Higher is better.
+///
+/// `chart` is cast for the middle of `window`. Closures taking the same arguments are
+/// scorers too.
+pub trait RectificationScorer {
+    fn score(&self, window: &RectificationWindow, chart: &ChartInfo) -> f64;
+}
+
+impl<F: Fn(&RectificationWindow, &ChartInfo) -> f64> RectificationScorer for F {
+    fn score(&self, window: &RectificationWindow, chart: &ChartInfo) -> f64 {
+        self(window, chart)
+    }
+}
+
+/// Candidate windows are searched in steps this long. The navamsa lagna never holds for
+/// less than about six minutes.
+#[cfg(not(feature = "model-only"))]
+const RECTIFICATION_STEP: f64 = 2.0 / 1440.0;
+/// Window boundaries are refined until they are known to within a second.
+#[cfg(not(feature = "model-only"))]
+const RECTIFICATION_PRECISION: f64 = 1.0 / 86400.0;
+
+/// What a window keeps fixed: lagna, navamsa lagna, Moon's nakshatra and pada.
+#[cfg(not(feature = "model-only"))]
+type Markers = (ZodiacSign, ZodiacSign, Nakshatra, u8);
+
+/// Splits the range of possible birth times around an approximate one into
+/// [`RectificationWindow`]s, from [`SwissEph::rectifier`].
+///
+/// ```no_run
+/// use aztro_core::*;
+///
+/// let ephemeris = SwissEph::new()?;
+/// let birth = Location::kozhikode().born_at(1991, 6, 18, 7, 10, 0)?;
+/// let windows = ephemeris
+///     .rectifier(&birth, chrono::Duration::minutes(30))
+///     // Married young: favour a Venus in the seventh house
+///     .score_with(|_: &RectificationWindow, chart: &ChartInfo| {
+///         let venus = chart.planets.iter().find(|p| p.planet == CelestialBody::Venus);
+///         if venus.is_some_and(|venus| venus.house == House::Seventh) { 1.0 } else { 0.0 }
+///     })
+///     .windows()?;
+/// # Ok::<(), aztro_core::AstrologyError>(())
+/// ```
+#[cfg(not(feature = "model-only"))]
+pub struct BirthTimeRectifier<'a> {
+    ephemeris: &'a SwissEph,
+    birth_info: BirthInfo,
+    tolerance: ChronoDuration,
+    scorers: Vec<Box<dyn RectificationScorer + 'a>>,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl<'a> BirthTimeRectifier<'a> {
+    /// Adds a scorer; each window's score is the sum over all of them.
+    pub fn score_with(mut self, scorer: impl RectificationScorer + 'a) -> Self {
+        self.scorers.push(Box::new(scorer));
+        self
+    }
+
+    /// Every window from the approximate birth time less the tolerance to the birth time
+    /// plus the tolerance, in time order.
+    pub fn windows(&self) -> Result<Vec<RectificationWindow>, AstrologyError> {
+        let birth_jd = date_to_julian_day(self.birth_info.date_time);
+        let tolerance = self.tolerance.num_seconds().abs() as f64 / 86400.0;
+        let (start, end) = (birth_jd - tolerance, birth_jd + tolerance);
+
+        let mut windows = Vec::new();
+        let mut window_start = start;
+        let mut low = start;
+        let mut markers = self.markers(start)?;
+        while low < end {
+            let high = (low + RECTIFICATION_STEP).min(end);
+            if self.markers(high)? == markers {
+                low = high;
+                continue;
+            }
+
+            let (mut low_bound, mut high_bound) = (low, high);
+            while high_bound - low_bound > RECTIFICATION_PRECISION {
+                let middle = (low_bound + high_bound) / 2.0;
+                if self.markers(middle)? == markers {
+                    low_bound = middle;
+                } else {
+                    high_bound = middle;
+                }
+            }
+            windows.push(self.window(window_start, high_bound, markers)?);
+            // Carry on from the change itself, so a second change within the step is caught
+            window_start = high_bound;
+            low = high_bound;
+            markers = self.markers(high_bound)?;
+        }
+        windows.push(self.window(window_start, end, markers)?);
+
+        Ok(windows)
+    }
+
+    fn markers(&self, julian_day: JulianDay) -> Result<Markers, AstrologyError> {
+        let location = &self.birth_info.location;
+        let lagna = self
+            .ephemeris
+            .calculate_ascendant(
+                CoordinateSystem::Sidereal,
+                julian_day,
+                location.latitude,
+                location.longitude,
+                self.ephemeris.conventions.house_system,
+            )?
+            .longitude();
+        let moon = NakshatraInfo::from_longitude(
+            self.ephemeris
+                .sidereal_longitude(CelestialBody::Moon, julian_day)?,
+        );
+        Ok((
+            ZodiacSign::from_longitude(lagna),
+            ZodiacSign::from_longitude(navamsa_longitude(lagna)),
+            moon.nakshatra,
+            moon.pada,
+        ))
+    }
+
+    fn window(
+        &self,
+        start: JulianDay,
+        end: JulianDay,
+        (lagna, navamsa_lagna, moon_nakshatra, moon_pada): Markers,
+    ) -> Result<RectificationWindow, AstrologyError> {
+        let mut window = RectificationWindow {
+            start: julian_day_to_date(start),
+            end: julian_day_to_date(end),
+            lagna,
+            navamsa_lagna,
+            moon_nakshatra,
+            moon_pada,
+            score: 0.0,
+        };
+        if !self.scorers.is_empty() {
+            let chart = self.ephemeris.calculate_chart(
+                &BirthInfo {
+                    date_time: julian_day_to_date((start + end) / 2.0),
+                    location: self.birth_info.location.clone(),
+                    person: None,
+                },
+                self.ephemeris.conventions.house_system,
+            )?;
+            window.score = self
+                .scorers
+                .iter()
+                .map(|scorer| scorer.score(&window, &chart))
+                .sum();
+        }
+        Ok(window)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// A rectifier for a birth time known to within `tolerance` either side of
+    /// `birth_info`'s. Cusps use the engine's house system.
+    pub fn rectifier(
+        &self,
+        birth_info: &BirthInfo,
+        tolerance: ChronoDuration,
+    ) -> BirthTimeRectifier<'_> {
+        BirthTimeRectifier {
+            ephemeris: self,
+            birth_info: birth_info.clone(),
+            tolerance,
+            scorers: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::*;
+
+/// A body coming back to the longitude it held at birth.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PlanetReturn {
+    pub planet: CelestialBody,
+    pub natal_longitude: Longitude,
+    pub date: DateTime<Utc>,
+    /// Whether the body was moving backwards over its natal place, as on the middle pass
+    /// of a Saturn return that comes three times.
+    pub retrograde: bool,
+}
+
+/// A return with the chart cast for it at the birthplace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ReturnChart {
+    pub planet_return: PlanetReturn,
+    pub chart: ChartInfo,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The moment nearest `around_date` at which sidereal `planet` stands exactly at
+    /// `natal_longitude`, to the minute.
+    ///
+    /// The Sun and Moon, and the mean nodes, move at a steady enough rate to step straight
+    /// to the return from their mean motion. Every other body is searched for within half
+    /// its mean cycle either side of `around_date`, so each pass of a retrograde loop is
+    /// found and the one nearest the date given is returned. Bodies without a mean motion,
+    /// the asteroids, are not supported.
+    pub fn find_planet_return(
+        &self,
+        planet: CelestialBody,
+        natal_longitude: Longitude,
+        around_date: DateTime<Utc>,
+    ) -> Result<PlanetReturn, AstrologyError> {
+        let (julian_day, retrograde) = self.return_julian_day(
+            planet,
+            natal_longitude.degrees(),
+            date_to_julian_day(around_date),
+        )?;
+        Ok(PlanetReturn {
+            planet,
+            natal_longitude,
+            date: julian_day_to_date(julian_day),
+            retrograde,
+        })
+    }
+
+    /// The return of `planet` to its birth longitude nearest `around_date`, with the chart
+    /// for that moment at the birthplace.
+    pub fn calculate_return_chart(
+        &self,
+        birth_info: &BirthInfo,
+        planet: CelestialBody,
+        around_date: DateTime<Utc>,
+    ) -> Result<ReturnChart, AstrologyError> {
+        let natal_longitude =
+            self.sidereal_longitude(planet, date_to_julian_day(birth_info.date_time))?;
+        let planet_return =
+            self.find_planet_return(planet, Longitude::new(natal_longitude), around_date)?;
+        let chart = self.calculate_chart(
+            &BirthInfo {
+                date_time: planet_return.date,
+                location: birth_info.location.clone(),
+                person: None,
+            },
+            self.conventions.house_system,
+        )?;
+        Ok(ReturnChart {
+            planet_return,
+            chart,
+        })
+    }
+
+    /// Julian day of the return nearest `around`, and whether the body was retrograde.
+    pub(crate) fn return_julian_day(
+        &self,
+        planet: CelestialBody,
+        natal_longitude: f64,
+        around: JulianDay,
+    ) -> Result<(JulianDay, bool), AstrologyError> {
+        let mean_motion = planet.mean_daily_motion().ok_or_else(|| {
+            AstrologyError::InvalidInput(format!("No mean motion to time a return of {:?}", planet))
+        })?;
+        let steady = match planet {
+            CelestialBody::Sun | CelestialBody::Moon => Some(mean_motion),
+            CelestialBody::Rahu | CelestialBody::Ketu
+                if self.conventions.node_type == NodeType::Mean =>
+            {
+                Some(-mean_motion)
+            }
+            _ => None,
+        };
+
+        if let Some(speed) = steady {
+            let mut julian_day = around;
+            for _ in 0..10 {
+                let arc = signed_arc(
+                    self.sidereal_longitude(planet, julian_day)?,
+                    natal_longitude,
+                );
+                if arc.abs() < 1e-7 {
+                    break;
+                }
+                julian_day -= arc / speed;
+            }
+            return Ok((julian_day, speed < 0.0));
+        }
+
+        let half_cycle = 180.0 / mean_motion;
+        self.find_crossings(
+            planet,
+            julian_day_to_date(around - half_cycle),
+            julian_day_to_date(around + half_cycle),
+            &[natal_longitude],
+        )?
+        .into_iter()
+        .min_by(|a, b| {
+            (a.julian_day - around)
+                .abs()
+                .total_cmp(&(b.julian_day - around).abs())
+        })
+        .map(|crossing| (crossing.julian_day, crossing.retrograde))
+        .ok_or_else(|| {
+            AstrologyError::Calculation(format!(
+                "{:?} does not return to {:.2}° within half a cycle",
+                planet, natal_longitude
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "model-only"))]
+    use super::*;
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn returns_are_found_for_slow_planets_and_the_nodes() {
+        let ephemeris = SwissEph::new().unwrap();
+        let date = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap();
+        let at = |planet, date: DateTime<Utc>| {
+            ephemeris
+                .sidereal_longitude(planet, date_to_julian_day(date))
+                .unwrap()
+        };
+
+        // Saturn's place at the start of 1995 comes back around the end of 2024
+        let natal = Longitude::new(at(CelestialBody::Saturn, date(1995, 1, 1)));
+        let saturn = ephemeris
+            .find_planet_return(CelestialBody::Saturn, natal, date(2024, 6, 1))
+            .unwrap();
+        assert!((2023..=2025).contains(&saturn.date.year()));
+        assert!(natal.separation(Longitude::new(at(CelestialBody::Saturn, saturn.date))) < 0.01);
+
+        // The mean node goes round backwards in about 18.6 years
+        let mean = SwissEph::new().unwrap().with_conventions(Conventions {
+            node_type: NodeType::Mean,
+            ..*ephemeris.conventions()
+        });
+        let birth = date(2000, 1, 1);
+        let natal = Longitude::new(
+            mean.sidereal_longitude(CelestialBody::Rahu, date_to_julian_day(birth))
+                .unwrap(),
+        );
+        let rahu = mean
+            .find_planet_return(CelestialBody::Rahu, natal, date(2018, 8, 1))
+            .unwrap();
+        assert!(rahu.retrograde);
+        let days = (rahu.date - birth).num_days();
+        assert!((6780..6800).contains(&days), "{}", days);
+
+        assert!(ephemeris
+            .find_planet_return(CelestialBody::Ceres, natal, date(2018, 8, 1))
+            .is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+impl SwissEph {
+    /// First sunrise after local midnight of `date` at `location`.
+    pub fn sunrise(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        self.rise_or_set(date, location, CelestialBody::Sun, SE_CALC_RISE)
+    }
+
+    /// First sunset after local midnight of `date` at `location`.
+    pub fn sunset(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        self.rise_or_set(date, location, CelestialBody::Sun, SE_CALC_SET)
+    }
+
+    /// First moonrise after local midnight of `date`. The Moon rises about 50 minutes later
+    /// each day, so on some dates this falls on the following day.
+    pub fn moonrise(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        self.rise_or_set(date, location, CelestialBody::Moon, SE_CALC_RISE)
+    }
+
+    /// First moonset after local midnight of `date`; may fall on the following day.
+    pub fn moonset(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        self.rise_or_set(date, location, CelestialBody::Moon, SE_CALC_SET)
+    }
+
+    /// Time from sunrise to sunset on `date`.
+    pub fn day_length(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<ChronoDuration, AstrologyError> {
+        let sunrise = self.sunrise(date, location)?;
+        let sunset = self.rise_or_set_after(sunrise, location, CelestialBody::Sun, SE_CALC_SET)?;
+        Ok(sunset - sunrise)
+    }
+
+    /// Time from sunset on `date` to the following sunrise.
+    pub fn night_length(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+    ) -> Result<ChronoDuration, AstrologyError> {
+        let sunrise = self.sunrise(date, location)?;
+        let sunset = self.rise_or_set_after(sunrise, location, CelestialBody::Sun, SE_CALC_SET)?;
+        let next_sunrise =
+            self.rise_or_set_after(sunset, location, CelestialBody::Sun, SE_CALC_RISE)?;
+        Ok(next_sunrise - sunset)
+    }
+
+    fn rise_or_set(
+        &self,
+        date: NaiveDate,
+        location: &Location,
+        body: CelestialBody,
+        event: c_int,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        let offset = FixedOffset::east_opt((location.timezone * 3600.0).round() as i32)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+        let local_midnight = offset
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .single()
+            .ok_or_else(|| AstrologyError::InvalidInput(format!("Invalid local date {}", date)))?;
+        self.rise_or_set_after(local_midnight.with_timezone(&Utc), location, body, event)
+    }
+
+    /// Next rise or set of `body` after `after`, for the upper limb with standard refraction.
+    fn rise_or_set_after(
+        &self,
+        after: DateTime<Utc>,
+        location: &Location,
+        body: CelestialBody,
+        event: c_int,
+    ) -> Result<DateTime<Utc>, AstrologyError> {
+        let mut geopos: [c_double; 3] = [location.longitude, location.latitude, location.altitude];
+        let mut tret: c_double = 0.0;
+        let mut error: [c_char; 256] = [0; 256];
+
+        let _timed = Timed::start(
+            TimedWork::EphemerisCall,
+            tracing::trace_span!("swe_rise_trans", body = ?body, event),
+        );
+        let result = unsafe {
+            swe_rise_trans(
+                date_to_julian_day(after),
+                body as c_int,
+                std::ptr::null_mut(),
+                self.ephemeris_flags(0),
+                event,
+                geopos.as_mut_ptr(),
+                0.0,
+                0.0,
+                &mut tret,
+                error.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => Ok(julian_day_to_date(tret)),
+            -2 => Err(AstrologyError::NoRiseOrSet(format!(
+                "{:?} does not {} at latitude {:.2}",
+                body,
+                if event == SE_CALC_RISE { "rise" } else { "set" },
+                location.latitude
+            ))),
+            code => Err(AstrologyError::Ephemeris {
+                code,
+                message: unsafe { CStr::from_ptr(error.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+/// The three signs of Sade Sati, Saturn's seven and a half years around the natal Moon.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SadeSatiPhase {
+    /// Saturn in the 12th sign from the Moon.
+    Rising,
+    /// Saturn over the Moon's own sign.
+    Peak,
+    /// Saturn in the 2nd sign from the Moon.
+    Setting,
+}
+
+/// A troubled placement of transiting Saturn counted from the natal Moon's sign.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SaturnAffliction {
+    SadeSati(SadeSatiPhase),
+    /// Saturn in the 4th, 7th or 10th from the Moon.
+    KantakaShani,
+    /// Saturn in the 8th from the Moon.
+    AshtamaShani,
+}
+
+/// One unbroken stay of transiting Saturn in an afflicting sign. A retrograde Saturn that
+/// backs out of a sign and returns gives two periods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SaturnTransitPeriod {
+    pub affliction: SaturnAffliction,
+    pub sign: ZodiacSign,
+    /// Saturn's sign counted from the natal Moon's.
+    pub house_from_moon: House,
+    /// Saturn's ingress into `sign`, even if it falls before the range searched.
+    pub start: DateTime<Utc>,
+    /// Saturn's egress from `sign`, even if it falls after the range searched.
+    pub end: DateTime<Utc>,
+}
+
+/// Saturn never stays in one sign this long, retrograde loops included, so searching this
+/// far outside a range finds the ingress and egress of every stay overlapping it.
+#[cfg(not(feature = "model-only"))]
+const SATURN_LONGEST_STAY_DAYS: f64 = 3.5 * JULIAN_YEAR_DAYS;
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Phases of Sade Sati overlapping `start` to `end`, in date order.
+    pub fn calculate_sade_sati(
+        &self,
+        birth_info: &BirthInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SaturnTransitPeriod>, AstrologyError> {
+        self.saturn_afflictions(birth_info, start, end, |house| match house {
+            House::Twelfth => Some(SaturnAffliction::SadeSati(SadeSatiPhase::Rising)),
+            House::First => Some(SaturnAffliction::SadeSati(SadeSatiPhase::Peak)),
+            House::Second => Some(SaturnAffliction::SadeSati(SadeSatiPhase::Setting)),
+            _ => None,
+        })
+    }
+
+    /// Periods of Kantaka Shani overlapping `start` to `end`, in date order.
+    pub fn calculate_kantaka_shani(
+        &self,
+        birth_info: &BirthInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SaturnTransitPeriod>, AstrologyError> {
+        self.saturn_afflictions(birth_info, start, end, |house| match house {
+            House::Fourth | House::Seventh | House::Tenth => Some(SaturnAffliction::KantakaShani),
+            _ => None,
+        })
+    }
+
+    /// Periods of Ashtama Shani overlapping `start` to `end`, in date order.
+    pub fn calculate_ashtama_shani(
+        &self,
+        birth_info: &BirthInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SaturnTransitPeriod>, AstrologyError> {
+        self.saturn_afflictions(birth_info, start, end, |house| match house {
+            House::Eighth => Some(SaturnAffliction::AshtamaShani),
+            _ => None,
+        })
+    }
+
+    /// Every stay of Saturn overlapping `start` to `end` in a sign to which `affliction`,
+    /// given the sign counted from the natal Moon, assigns an affliction.
+    fn saturn_afflictions(
+        &self,
+        birth_info: &BirthInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        affliction: impl Fn(House) -> Option<SaturnAffliction>,
+    ) -> Result<Vec<SaturnTransitPeriod>, AstrologyError> {
+        let moon = ZodiacSign::from_longitude(self.sidereal_longitude(
+            CelestialBody::Moon,
+            date_to_julian_day(birth_info.date_time),
+        )?);
+        let search_start = date_to_julian_day(start) - SATURN_LONGEST_STAY_DAYS;
+        let search_end = date_to_julian_day(end) + SATURN_LONGEST_STAY_DAYS;
+
+        // Saturn's stays in each sign, as (sign, ingress, egress)
+        let mut stays = Vec::new();
+        let mut sign = ZodiacSign::from_longitude(
+            self.sidereal_longitude(CelestialBody::Saturn, search_start)?,
+        );
+        let mut ingress = julian_day_to_date(search_start);
+        for transit in self.calculate_transits(
+            &[CelestialBody::Saturn],
+            ingress,
+            julian_day_to_date(search_end),
+        )? {
+            stays.push((sign, ingress, transit.date));
+            sign = transit.to_sign;
+            ingress = transit.date;
+        }
+        stays.push((sign, ingress, julian_day_to_date(search_end)));
+
+        Ok(stays
+            .into_iter()
+            .filter(|&(_, ingress, egress)| ingress < end && egress > start)
+            .filter_map(|(sign, ingress, egress)| {
+                let house_from_moon =
+                    House::from_index((sign as usize + 12 - moon as usize) % 12 + 1)?;
+                Some(SaturnTransitPeriod {
+                    affliction: affliction(house_from_moon)?,
+                    sign,
+                    house_from_moon,
+                    start: ingress,
+                    end: egress,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+/// A solar month of the Indian calendars, named for the sidereal sign the Sun passes
+/// through during it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SolarMonth {
+    Mesha,
+    Vrishabha,
+    Mithuna,
+    Karka,
+    Simha,
+    Kanya,
+    Tula,
+    Vrishchika,
+    Dhanu,
+    Makara,
+    Kumbha,
+    Meena,
+}
+
+impl SolarMonth {
+    /// The month the Sun spends in `sign`.
+    pub fn from_sign(sign: ZodiacSign) -> Self {
+        const MONTHS: [SolarMonth; 12] = [
+            SolarMonth::Mesha,
+            SolarMonth::Vrishabha,
+            SolarMonth::Mithuna,
+            SolarMonth::Karka,
+            SolarMonth::Simha,
+            SolarMonth::Kanya,
+            SolarMonth::Tula,
+            SolarMonth::Vrishchika,
+            SolarMonth::Dhanu,
+            SolarMonth::Makara,
+            SolarMonth::Kumbha,
+            SolarMonth::Meena,
+        ];
+        MONTHS[sign as usize]
+    }
+
+    pub fn sign(&self) -> ZodiacSign {
+        ZodiacSign::from_longitude(*self as usize as f64 * 30.0)
+    }
+}
+
+impl fmt::Display for SolarMonth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// The Sun's entry into a sidereal sign, which begins the solar month of that sign.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Sankranti {
+    pub sign: ZodiacSign,
+    pub month: SolarMonth,
+    pub date_time: DateTime<Utc>,
+}
+
+impl Sankranti {
+    /// The festival name, such as "Makara Sankranti".
+    pub fn name(&self) -> String {
+        format!("{} Sankranti", self.month)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The twelve sankrantis falling in the Gregorian `year`, counted in UT, on `ayanamsa`
+    /// rather than the engine's own, in date order from Makara Sankranti in January.
+    pub fn sankranti_dates(
+        &self,
+        year: i32,
+        ayanamsa: Ayanamsa,
+    ) -> Result<Vec<Sankranti>, AstrologyError> {
+        let year_start = |year| {
+            Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+                .single()
+                .map(date_to_julian_day)
+                .ok_or_else(|| AstrologyError::InvalidInput(format!("No year {}", year)))
+        };
+        let (start, end) = (year_start(year)?, year_start(year + 1)?);
+        let sun = |julian_day| {
+            let tropical = self.sun_longitude(CoordinateSystem::Tropical, julian_day)?;
+            let ayanamsa = ayanamsa_at(ayanamsa, julian_day).ayanamsa_value;
+            Ok((tropical - ayanamsa).rem_euclid(360.0))
+        };
+
+        let mut sankrantis = Vec::new();
+        let mut boundary = (sun(start)? / 30.0).floor() * 30.0;
+        let mut julian_day = start;
+        loop {
+            boundary = (boundary + 30.0) % 360.0;
+            julian_day = next_solar_longitude(julian_day, boundary, sun)?;
+            if julian_day >= end {
+                break;
+            }
+            let sign = ZodiacSign::from_longitude(boundary);
+            sankrantis.push(Sankranti {
+                sign,
+                month: SolarMonth::from_sign(sign),
+                date_time: julian_day_to_date(julian_day),
+            });
+            // Well clear of the boundary just found, so the search moves on to the next
+            julian_day += 1.0;
+        }
+        Ok(sankrantis)
+    }
+}
+
+#[cfg(all(test, not(feature = "model-only")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twelve_sankrantis_fall_in_a_year() {
+        let ephemeris = SwissEph::new().unwrap();
+        let sankrantis = ephemeris.sankranti_dates(2024, Ayanamsa::Lahiri).unwrap();
+        assert_eq!(sankrantis.len(), 12);
+
+        // Makara Sankranti came at 02:42 IST on 15 January 2024
+        let makara = &sankrantis[0];
+        assert_eq!(makara.name(), "Makara Sankranti");
+        let expected = Utc.with_ymd_and_hms(2024, 1, 14, 21, 12, 0).unwrap();
+        assert!((makara.date_time - expected).num_minutes().abs() <= 5);
+
+        let mesha = &sankrantis[3];
+        assert_eq!(mesha.month, SolarMonth::Mesha);
+        assert_eq!(mesha.month.sign(), ZodiacSign::Aries);
+        assert_eq!(
+            mesha.date_time.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 4, 13).unwrap()
+        );
+        assert!(sankrantis
+            .windows(2)
+            .all(|pair| pair[0].date_time < pair[1].date_time));
+    }
+}
@@ -0,0 +1,289 @@
+use super::*;
+
+/// A planet of one chart placed in the houses of another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HouseOverlay {
+    pub planet: CelestialBody,
+    pub longitude: f64,
+    /// House of the host chart the planet falls in.
+    pub house: House,
+}
+
+/// Two charts read against each other, as opposed to the Kuta scoring of
+/// [`SwissEph::calculate_compatibility`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Synastry {
+    /// Aspects from each planet of the first chart (`planet1`) to each planet of the
+    /// second (`planet2`).
+    pub aspects: Vec<AspectInfo>,
+    /// Planets of the second chart in the houses of the first.
+    pub second_in_first: Vec<HouseOverlay>,
+    /// Planets of the first chart in the houses of the second.
+    pub first_in_second: Vec<HouseOverlay>,
+}
+
+/// Midpoint of two longitudes along the shorter arc between them.
+#[cfg(not(feature = "model-only"))]
+fn near_midpoint(a: f64, b: f64) -> f64 {
+    let arc = (b - a).rem_euclid(360.0);
+    if arc <= 180.0 {
+        (a + arc / 2.0).rem_euclid(360.0)
+    } else {
+        (a - (360.0 - arc) / 2.0).rem_euclid(360.0)
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+fn cusp_at(house: House, longitude: f64) -> HouseCusp {
+    HouseCusp {
+        house,
+        sign: ZodiacSign::from_longitude(longitude),
+        degree: longitude.rem_euclid(30.0),
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Aspects and house overlays between two charts, using the default fixed orbs.
+    pub fn calculate_synastry(&self, first: &ChartInfo, second: &ChartInfo) -> Synastry {
+        self.calculate_synastry_with(first, second, &FixedOrbs::default())
+    }
+
+    /// Aspects and house overlays between two charts, taking orbs from `orbs`.
+    pub fn calculate_synastry_with(
+        &self,
+        first: &ChartInfo,
+        second: &ChartInfo,
+        orbs: &dyn OrbStrategy,
+    ) -> Synastry {
+        let aspects = first
+            .planets
+            .iter()
+            .flat_map(|planet1| {
+                second
+                    .planets
+                    .iter()
+                    .filter_map(move |planet2| closest_aspect(planet1, planet2, orbs))
+            })
+            .collect();
+
+        Synastry {
+            aspects,
+            second_in_first: self.house_overlays(first, second),
+            first_in_second: self.house_overlays(second, first),
+        }
+    }
+
+    /// Houses of `host` occupied by each planet of `guest`.
+    pub fn house_overlays(&self, host: &ChartInfo, guest: &ChartInfo) -> Vec<HouseOverlay> {
+        guest
+            .planets
+            .iter()
+            .map(|planet| HouseOverlay {
+                planet: planet.planet,
+                longitude: planet.longitude.degrees(),
+                house: host.house_of(planet.longitude.degrees()),
+            })
+            .collect()
+    }
+
+    /// Composite chart of the midpoints of two charts. Each planet and cusp sits at the
+    /// nearer midpoint of its two placements. A cusp whose nearer midpoint would fall on the
+    /// wrong side of the composite ascendant takes the opposite point instead, so the
+    /// houses stay in order.
+    pub fn composite_chart(&self, first: &ChartInfo, second: &ChartInfo) -> ChartInfo {
+        let ascendant_longitude =
+            near_midpoint(first.ascendant.longitude(), second.ascendant.longitude());
+
+        let houses: Vec<HouseCusp> = first
+            .houses
+            .iter()
+            .zip(&second.houses)
+            .enumerate()
+            .map(|(i, (cusp1, cusp2))| {
+                let expected = ascendant_longitude + i as f64 * 30.0;
+                let mut longitude = near_midpoint(cusp1.longitude(), cusp2.longitude());
+                let distance = (longitude - expected).rem_euclid(360.0);
+                if distance > 90.0 && distance < 270.0 {
+                    longitude = (longitude + 180.0).rem_euclid(360.0);
+                }
+                cusp_at(cusp1.house, longitude)
+            })
+            .collect();
+
+        let planets = first
+            .planets
+            .iter()
+            .filter_map(|planet1| {
+                let planet2 = second.planets.iter().find(|p| p.planet == planet1.planet)?;
+                let longitude =
+                    near_midpoint(planet1.longitude.degrees(), planet2.longitude.degrees());
+                let speed = (planet1.speed + planet2.speed) / 2.0;
+                Some(PlanetPosition {
+                    planet: planet1.planet,
+                    longitude: Longitude::new(longitude),
+                    latitude: (planet1.latitude + planet2.latitude) / 2.0,
+                    speed,
+                    sign: ZodiacSign::from_longitude(longitude),
+                    house: house_from_cusps(&houses, longitude),
+                    nakshatra: NakshatraInfo::from_longitude(longitude),
+                    retrograde: speed < 0.0,
+                })
+            })
+            .collect();
+
+        let mut warnings = first.warnings.clone();
+        for warning in &second.warnings {
+            if !warnings.contains(warning) {
+                warnings.push(warning.clone());
+            }
+        }
+
+        ChartInfo {
+            chart_type: first.chart_type,
+            house_system: first.house_system,
+            ascendant: cusp_at(House::First, ascendant_longitude),
+            houses,
+            planets,
+            warnings,
+        }
+    }
+
+    /// Davison relationship chart: a real chart cast for the moment halfway between two
+    /// births, at the point halfway between the two birthplaces.
+    pub fn davison_chart(
+        &self,
+        first: &BirthInfo,
+        second: &BirthInfo,
+        house_system: HouseSystem,
+    ) -> Result<ChartInfo, AstrologyError> {
+        let date_time = first.date_time + (second.date_time - first.date_time) / 2;
+        let longitude = near_midpoint(
+            first.location.longitude.rem_euclid(360.0),
+            second.location.longitude.rem_euclid(360.0),
+        );
+        let location = Location::new(
+            (first.location.latitude + second.location.latitude) / 2.0,
+            if longitude > 180.0 {
+                longitude - 360.0
+            } else {
+                longitude
+            },
+            (first.location.timezone + second.location.timezone) / 2.0,
+        );
+
+        self.calculate_chart(
+            &BirthInfo {
+                date_time,
+                location,
+                person: None,
+            },
+            house_system,
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    fn separation(a: f64, b: f64) -> f64 {
+        let difference = (a - b).rem_euclid(360.0);
+        difference.min(360.0 - difference)
+    }
+
+    fn charts(ephemeris: &SwissEph) -> (ChartInfo, ChartInfo) {
+        let chart = |birth: BirthInfo| {
+            ephemeris
+                .calculate_chart(&birth, HouseSystem::WholeSign)
+                .unwrap()
+        };
+        (
+            chart(crate::examples::native().unwrap()),
+            chart(crate::examples::partner().unwrap()),
+        )
+    }
+
+    #[test]
+    fn the_near_midpoint_takes_the_shorter_arc() {
+        assert!(separation(near_midpoint(350.0, 10.0), 0.0) < 1e-9);
+        assert!(separation(near_midpoint(10.0, 350.0), 0.0) < 1e-9);
+        assert!(separation(near_midpoint(100.0, 200.0), 150.0) < 1e-9);
+        assert!(separation(near_midpoint(0.0, 240.0), 300.0) < 1e-9);
+    }
+
+    #[test]
+    fn composite_planets_sit_halfway_between_their_placements() {
+        let ephemeris = SwissEph::new().unwrap();
+        let (first, second) = charts(&ephemeris);
+        let composite = ephemeris.composite_chart(&first, &second);
+
+        assert_eq!(composite.planets.len(), first.planets.len());
+        for planet in &composite.planets {
+            let one = first.planet(planet.planet).unwrap().longitude.degrees();
+            let other = second.planet(planet.planet).unwrap().longitude.degrees();
+            let at = planet.longitude.degrees();
+            let half = separation(one, other) / 2.0;
+
+            assert!(
+                (separation(at, one) - half).abs() < 1e-9,
+                "{:?}",
+                planet.planet
+            );
+            assert!(
+                (separation(at, other) - half).abs() < 1e-9,
+                "{:?}",
+                planet.planet
+            );
+            assert_eq!(planet.sign, ZodiacSign::from_longitude(at));
+        }
+
+        let ascendant = composite.ascendant.longitude();
+        let half = separation(first.ascendant.longitude(), second.ascendant.longitude()) / 2.0;
+        assert!((separation(ascendant, first.ascendant.longitude()) - half).abs() < 1e-9);
+
+        // The cusps follow the composite ascendant round the zodiac in order
+        for (i, cusp) in composite.houses.iter().enumerate() {
+            let distance = (cusp.longitude() - ascendant - i as f64 * 30.0).rem_euclid(360.0);
+            assert!(!(90.0..270.0).contains(&distance), "{:?}", cusp.house);
+        }
+    }
+
+    #[test]
+    fn the_davison_chart_is_cast_halfway_in_time_and_space() {
+        let ephemeris = SwissEph::new().unwrap();
+        let first = crate::examples::native().unwrap();
+        let second = crate::examples::partner().unwrap();
+        let davison = ephemeris
+            .davison_chart(&first, &second, HouseSystem::WholeSign)
+            .unwrap();
+
+        // 18 Jun 1991 01:40 UTC and 4 Feb 1993 15:55 UTC, between Kozhikode and Thrissur
+        let midway = BirthInfo {
+            date_time: Utc.with_ymd_and_hms(1992, 4, 11, 20, 47, 30).unwrap(),
+            location: Location::new((11.2588 + 10.522) / 2.0, (75.7804 + 76.2100) / 2.0, 5.5),
+            person: None,
+        };
+        let expected = ephemeris
+            .calculate_chart(&midway, HouseSystem::WholeSign)
+            .unwrap();
+
+        assert!(
+            separation(
+                davison.ascendant.longitude(),
+                expected.ascendant.longitude()
+            ) < 1e-6
+        );
+        for planet in &expected.planets {
+            let cast = davison.planet(planet.planet).unwrap();
+            assert!(
+                separation(cast.longitude.degrees(), planet.longitude.degrees()) < 1e-6,
+                "{:?}",
+                planet.planet
+            );
+        }
+    }
+}
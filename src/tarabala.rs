@@ -0,0 +1,140 @@
+use super::*;
+
+/// The nine taras, counted from the birth nakshatra to the one of the day and round again
+/// every nine stars.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Tara {
+    Janma,
+    Sampat,
+    Vipat,
+    Kshema,
+    Pratyak,
+    Sadhana,
+    Naidhana,
+    Mitra,
+    ParamaMitra,
+}
+
+impl Tara {
+    /// Tara of `to` counted from `from`, which is itself the Janma tara.
+    pub fn between(from: Nakshatra, to: Nakshatra) -> Tara {
+        match crate::core::tara_index(from as usize, to as usize) {
+            0 => Tara::Janma,
+            1 => Tara::Sampat,
+            2 => Tara::Vipat,
+            3 => Tara::Kshema,
+            4 => Tara::Pratyak,
+            5 => Tara::Sadhana,
+            6 => Tara::Naidhana,
+            7 => Tara::Mitra,
+            _ => Tara::ParamaMitra,
+        }
+    }
+
+    /// All but Vipat, Pratyak and Naidhana. Janma is counted favourable, as in the tara
+    /// kuta, though some avoid it for new undertakings.
+    pub fn is_favourable(&self) -> bool {
+        !matches!(self, Tara::Vipat | Tara::Pratyak | Tara::Naidhana)
+    }
+}
+
+/// Houses from the natal Moon whose transit by the Moon gives chandrabala.
+#[cfg(not(feature = "model-only"))]
+const CHANDRABALA_HOUSES: [House; 6] = [
+    House::First,
+    House::Third,
+    House::Sixth,
+    House::Seventh,
+    House::Tenth,
+    House::Eleventh,
+];
+
+/// The day's nakshatra judged against the birth nakshatra.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Tarabala {
+    /// The Moon's nakshatra and when it begins and ends.
+    pub nakshatra: PanchangaLimb<Nakshatra>,
+    pub tara: Tara,
+    pub favourable: bool,
+}
+
+/// The Moon's sign judged against the natal Moon's.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Chandrabala {
+    /// The Moon's sign and when it enters and leaves it.
+    pub moon_sign: PanchangaLimb<ZodiacSign>,
+    /// The Moon's sign counted from the natal Moon's.
+    pub house_from_moon: House,
+    /// The Moon is in the 1st, 3rd, 6th, 7th, 10th or 11th from the natal Moon.
+    pub favourable: bool,
+}
+
+/// Sign of `to` counted from `from`.
+#[cfg(not(feature = "model-only"))]
+fn house_from(from: ZodiacSign, to: ZodiacSign) -> House {
+    House::from_index((to as usize + 12 - from as usize) % 12 + 1).unwrap_or(House::First)
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Tarabala at `date_time` for a native born under `natal_moon_nakshatra`.
+    pub fn calculate_tarabala(
+        &self,
+        natal_moon_nakshatra: Nakshatra,
+        date_time: DateTime<Utc>,
+    ) -> Result<Tarabala, AstrologyError> {
+        let nakshatra = self.calculate_nakshatra_limb(date_time)?;
+        let tara = Tara::between(natal_moon_nakshatra, nakshatra.value);
+        Ok(Tarabala {
+            nakshatra,
+            tara,
+            favourable: tara.is_favourable(),
+        })
+    }
+
+    /// Chandrabala at `date_time` for a native whose Moon is in `natal_moon_sign`.
+    pub fn calculate_chandrabala(
+        &self,
+        natal_moon_sign: ZodiacSign,
+        date_time: DateTime<Utc>,
+    ) -> Result<Chandrabala, AstrologyError> {
+        let moon_sign = self.calculate_moon_sign_limb(date_time)?;
+        let house_from_moon = house_from(natal_moon_sign, moon_sign.value);
+        Ok(Chandrabala {
+            moon_sign,
+            house_from_moon,
+            favourable: CHANDRABALA_HOUSES.contains(&house_from_moon),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taras_repeat_every_nine_stars() {
+        assert_eq!(
+            Tara::between(Nakshatra::Rohini, Nakshatra::Rohini),
+            Tara::Janma
+        );
+        assert_eq!(
+            Tara::between(Nakshatra::Rohini, Nakshatra::Ardra),
+            Tara::Vipat
+        );
+        assert_eq!(
+            Tara::between(Nakshatra::Rohini, Nakshatra::Hasta),
+            Tara::Janma
+        );
+        // Counting wraps from Revati to Ashwini
+        assert_eq!(
+            Tara::between(Nakshatra::Revati, Nakshatra::Krittika),
+            Tara::Kshema
+        );
+        assert!(!Tara::Naidhana.is_favourable());
+        assert!(Tara::ParamaMitra.is_favourable());
+    }
+}
@@ -0,0 +1,143 @@
+use super::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Work the engine keeps time for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TimedWork {
+    /// A body's position or rise and set asked of Swiss Ephemeris, not answered from the
+    /// position cache.
+    EphemerisCall,
+    /// Houses and angles asked of Swiss Ephemeris.
+    HouseCall,
+    /// One body stepped through a date range for sign, nakshatra or aspect crossings.
+    TransitScan,
+    /// One [`Report`] worked out in full.
+    Report,
+    /// A whole [`Report::calculate_batch`].
+    BatchReport,
+}
+
+impl TimedWork {
+    const ALL: [TimedWork; 5] = [
+        TimedWork::EphemerisCall,
+        TimedWork::HouseCall,
+        TimedWork::TransitScan,
+        TimedWork::Report,
+        TimedWork::BatchReport,
+    ];
+}
+
+/// How often one kind of work has been done and how long it took altogether.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct WorkTiming {
+    pub count: u64,
+    pub total_seconds: f64,
+}
+
+impl WorkTiming {
+    pub fn mean_seconds(&self) -> f64 {
+        match self.count {
+            0 => 0.0,
+            count => self.total_seconds / count as f64,
+        }
+    }
+}
+
+/// Time spent in the engine since the process started or the counts were last reset,
+/// across every engine and thread.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TimingStats {
+    pub work: HashMap<TimedWork, WorkTiming>,
+}
+
+impl TimingStats {
+    pub fn get(&self, work: TimedWork) -> WorkTiming {
+        self.work.get(&work).copied().unwrap_or_default()
+    }
+}
+
+static COUNTS: [AtomicU64; 5] = [const { AtomicU64::new(0) }; 5];
+static NANOSECONDS: [AtomicU64; 5] = [const { AtomicU64::new(0) }; 5];
+
+/// Counts and times of the work the engine has done. The same work is also reported as
+/// `tracing` spans, for applications with a subscriber installed.
+pub fn timing_stats() -> TimingStats {
+    let work = TimedWork::ALL
+        .iter()
+        .map(|&work| {
+            let index = work as usize;
+            let timing = WorkTiming {
+                count: COUNTS[index].load(Ordering::Relaxed),
+                total_seconds: NANOSECONDS[index].load(Ordering::Relaxed) as f64 * 1e-9,
+            };
+            (work, timing)
+        })
+        .collect();
+    TimingStats { work }
+}
+
+/// Sets every count and time back to zero.
+pub fn reset_timing_stats() {
+    for index in 0..TimedWork::ALL.len() {
+        COUNTS[index].store(0, Ordering::Relaxed);
+        NANOSECONDS[index].store(0, Ordering::Relaxed);
+    }
+}
+
+/// Work under way: inside its span until dropped, when its time is added to the counts.
+pub(crate) struct Timed {
+    work: TimedWork,
+    start: Instant,
+    _span: tracing::span::EnteredSpan,
+}
+
+impl Timed {
+    pub(crate) fn start(work: TimedWork, span: tracing::Span) -> Self {
+        Timed {
+            work,
+            start: Instant::now(),
+            _span: span.entered(),
+        }
+    }
+}
+
+impl Drop for Timed {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let index = self.work as usize;
+        COUNTS[index].fetch_add(1, Ordering::Relaxed);
+        NANOSECONDS[index].fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        tracing::trace!(elapsed_us = elapsed.as_micros() as u64, "done");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engine_work_is_counted_and_timed() {
+        let ephemeris = SwissEph::new().unwrap();
+        let before = timing_stats();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        ephemeris
+            .calculate_transits(
+                &[CelestialBody::Mars],
+                start,
+                start + ChronoDuration::days(30),
+            )
+            .unwrap();
+        let after = timing_stats();
+
+        // Other tests run alongside, so the counts can only be said to have grown
+        let scans = after.get(TimedWork::TransitScan);
+        assert!(scans.count > before.get(TimedWork::TransitScan).count);
+        assert!(scans.total_seconds > 0.0);
+        assert!(scans.mean_seconds() <= scans.total_seconds);
+        assert!(after.get(TimedWork::EphemerisCall).count >= 1);
+    }
+}
@@ -0,0 +1,334 @@
+use super::*;
+
+/// Regional schools of practice, each with its own defaults for casting and reading a
+/// chart. See [`Tradition::conventions`] for what each one sets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Tradition {
+    NorthIndian,
+    SouthIndian,
+    Kerala,
+    /// Krishnamurti Paddhati.
+    Kp,
+    Western,
+}
+
+/// Sidereal zodiac used for sidereal positions.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Ayanamsa {
+    #[default]
+    Lahiri,
+    Raman,
+    Krishnamurti,
+    FaganBradley,
+    DeLuce,
+    Ushashashi,
+    DjwhalKhul,
+    /// Sri Yukteshwar's, from *The Holy Science*.
+    Yukteshwar,
+    JnBhasin,
+    /// The Surya Siddhanta's, zero in 499 AD.
+    SuryaSiddhanta,
+    Aryabhata,
+    /// Spica held at 180°, moving with the star rather than by a precession rate.
+    TrueCitra,
+    /// Zeta Piscium held at 359°50'.
+    TrueRevati,
+    /// Delta Cancri held at 106°.
+    TruePushya,
+    /// Lambda Scorpii held at 240°, the start of Moola.
+    TrueMula,
+    /// The galactic centre held at 0° Sagittarius.
+    GalacticCenter,
+}
+
+impl Ayanamsa {
+    /// Every supported ayanamsa.
+    pub fn all() -> [Ayanamsa; 16] {
+        use Ayanamsa::*;
+        [
+            Lahiri,
+            Raman,
+            Krishnamurti,
+            FaganBradley,
+            DeLuce,
+            Ushashashi,
+            DjwhalKhul,
+            Yukteshwar,
+            JnBhasin,
+            SuryaSiddhanta,
+            Aryabhata,
+            TrueCitra,
+            TrueRevati,
+            TruePushya,
+            TrueMula,
+            GalacticCenter,
+        ]
+    }
+
+    /// The Swiss Ephemeris sidereal mode for this ayanamsa.
+    pub fn sid_mode(&self) -> c_int {
+        match self {
+            Ayanamsa::FaganBradley => 0,
+            Ayanamsa::Lahiri => 1,
+            Ayanamsa::DeLuce => 2,
+            Ayanamsa::Raman => 3,
+            Ayanamsa::Ushashashi => 4,
+            Ayanamsa::Krishnamurti => 5,
+            Ayanamsa::DjwhalKhul => 6,
+            Ayanamsa::Yukteshwar => 7,
+            Ayanamsa::JnBhasin => 8,
+            Ayanamsa::GalacticCenter => 17,
+            Ayanamsa::SuryaSiddhanta => 21,
+            Ayanamsa::Aryabhata => 23,
+            Ayanamsa::TrueCitra => 27,
+            Ayanamsa::TrueRevati => 28,
+            Ayanamsa::TruePushya => 29,
+            Ayanamsa::TrueMula => 35,
+        }
+    }
+}
+
+/// Which lunar node Rahu (and so Ketu) follows.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum NodeType {
+    Mean,
+    #[default]
+    True,
+}
+
+impl NodeType {
+    /// The Swiss Ephemeris body number of this node.
+    pub fn body_code(&self) -> c_int {
+        match self {
+            NodeType::Mean => 10,
+            NodeType::True => 11,
+        }
+    }
+}
+
+/// How a chart is drawn. The crate does not render charts; this tells a front end which
+/// layout its users expect.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ChartStyle {
+    /// Diamond layout with fixed houses.
+    #[default]
+    NorthIndian,
+    /// Square layout with fixed signs.
+    SouthIndian,
+    EastIndian,
+    /// Circular wheel.
+    Wheel,
+}
+
+/// Points each of the eight kutas is worth in a match. The classical weights add up to 36.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KutaWeights {
+    pub varna: f64,
+    pub vasya: f64,
+    pub tara: f64,
+    pub yoni: f64,
+    pub graha_maitri: f64,
+    pub gana: f64,
+    pub bhakut: f64,
+    pub nadi: f64,
+}
+
+impl KutaWeights {
+    pub fn total(&self) -> f64 {
+        self.varna
+            + self.vasya
+            + self.tara
+            + self.yoni
+            + self.graha_maitri
+            + self.gana
+            + self.bhakut
+            + self.nadi
+    }
+}
+
+impl Default for KutaWeights {
+    fn default() -> Self {
+        KutaWeights {
+            varna: 1.0,
+            vasya: 2.0,
+            tara: 3.0,
+            yoni: 4.0,
+            graha_maitri: 5.0,
+            gana: 6.0,
+            bhakut: 7.0,
+            nadi: 8.0,
+        }
+    }
+}
+
+/// Days in a Julian year, the dasha year most software uses.
+pub const JULIAN_YEAR_DAYS: f64 = 365.25;
+/// Days in the sidereal solar year, the Sun's return to the same star.
+pub const SIDEREAL_YEAR_DAYS: f64 = 365.256363;
+/// Days in the savana year of twelve 30-day months.
+pub const SAVANA_YEAR_DAYS: f64 = 360.0;
+/// Days in the tropical year, the Sun's return to the equinox.
+pub const TROPICAL_YEAR_DAYS: f64 = 365.242_19;
+
+/// Every setting a [`Tradition`] decides. Start from a tradition and change single fields
+/// to suit a particular astrologer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Conventions {
+    /// Zodiac the tradition reckons in; pass it as the coordinate system of calculations.
+    pub zodiac: CoordinateSystem,
+    pub ayanamsa: Ayanamsa,
+    pub house_system: HouseSystem,
+    pub node_type: NodeType,
+    pub chart_style: ChartStyle,
+    pub kuta_weights: KutaWeights,
+    /// Length of a dasha year in days.
+    pub dasha_year_days: f64,
+    #[serde(default)]
+    pub combustion_orbs: CombustionOrbs,
+}
+
+impl Default for Conventions {
+    /// Lahiri sidereal positions, Placidus houses and the true node.
+    fn default() -> Self {
+        Conventions {
+            zodiac: CoordinateSystem::Sidereal,
+            ayanamsa: Ayanamsa::default(),
+            house_system: HouseSystem::default(),
+            node_type: NodeType::default(),
+            chart_style: ChartStyle::default(),
+            kuta_weights: KutaWeights::default(),
+            dasha_year_days: JULIAN_YEAR_DAYS,
+            combustion_orbs: CombustionOrbs::default(),
+        }
+    }
+}
+
+impl Tradition {
+    pub fn conventions(&self) -> Conventions {
+        let indian = Conventions {
+            zodiac: CoordinateSystem::Sidereal,
+            ayanamsa: Ayanamsa::Lahiri,
+            house_system: HouseSystem::WholeSign,
+            node_type: NodeType::Mean,
+            chart_style: ChartStyle::NorthIndian,
+            kuta_weights: KutaWeights::default(),
+            dasha_year_days: JULIAN_YEAR_DAYS,
+            combustion_orbs: CombustionOrbs::default(),
+        };
+        match self {
+            Tradition::NorthIndian => indian,
+            Tradition::SouthIndian => Conventions {
+                house_system: HouseSystem::Sripati,
+                chart_style: ChartStyle::SouthIndian,
+                ..indian
+            },
+            Tradition::Kerala => Conventions {
+                house_system: HouseSystem::Sripati,
+                node_type: NodeType::True,
+                chart_style: ChartStyle::SouthIndian,
+                dasha_year_days: SIDEREAL_YEAR_DAYS,
+                ..indian
+            },
+            Tradition::Kp => Conventions {
+                ayanamsa: Ayanamsa::Krishnamurti,
+                house_system: HouseSystem::Krishnamurti,
+                chart_style: ChartStyle::SouthIndian,
+                ..indian
+            },
+            Tradition::Western => Conventions {
+                zodiac: CoordinateSystem::Tropical,
+                house_system: HouseSystem::Placidus,
+                node_type: NodeType::True,
+                chart_style: ChartStyle::Wheel,
+                ..indian
+            },
+        }
+    }
+}
+
+impl From<Tradition> for Conventions {
+    fn from(tradition: Tradition) -> Self {
+        tradition.conventions()
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Uses the defaults of `tradition` for the ayanamsa, Rahu's node, kuta weights and
+    /// the length of a dasha year.
+    pub fn with_tradition(self, tradition: Tradition) -> Self {
+        self.with_conventions(tradition.conventions())
+    }
+
+    /// Also empties the position cache, since the ayanamsa and the node may change.
+    pub fn with_conventions(mut self, conventions: Conventions) -> Self {
+        self.conventions = conventions;
+        self.position_cache.clear();
+        self
+    }
+
+    pub fn conventions(&self) -> &Conventions {
+        &self.conventions
+    }
+
+    /// Points Swiss Ephemeris at the configured ayanamsa before a sidereal calculation.
+    /// Setting the mode flushes the library's position cache, so it is only passed on when
+    /// it differs from the mode last set.
+    pub(crate) fn set_sidereal_mode(&self) {
+        set_sidereal_mode(self.conventions.ayanamsa);
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+pub(crate) fn set_sidereal_mode(ayanamsa: Ayanamsa) {
+    let mode = ayanamsa.sid_mode();
+    SIDEREAL_MODE.with(|current| {
+        if std::mem::replace(current, mode) != mode {
+            unsafe {
+                swe_set_sid_mode(mode, 0.0, 0.0);
+            }
+        }
+    });
+}
+
+/// Sidereal mode last handed to Swiss Ephemeris.
+#[cfg(not(feature = "model-only"))]
+static SIDEREAL_MODE: LibrarySetting<c_int> = LibrarySetting {
+    per_thread: &SIDEREAL_MODE_ON_THREAD,
+    per_process: std::sync::Mutex::new(-1),
+};
+
+#[cfg(not(feature = "model-only"))]
+thread_local! {
+    static SIDEREAL_MODE_ON_THREAD: std::cell::RefCell<c_int> = const { std::cell::RefCell::new(-1) };
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ayanamsas_are_compared_at_one_instant() {
+        let values = AyanamsaInfo::compare_all(2_451_545.0);
+        assert_eq!(values.len(), Ayanamsa::all().len());
+        let value = |name: &str| {
+            values
+                .iter()
+                .find(|info| info.ayanamsa_name == name)
+                .unwrap()
+                .ayanamsa_value
+        };
+        // Fagan-Bradley as swetest gives it, 24°44'11.15"
+        assert!((value("FaganBradley") - 24.736430).abs() < 1e-5);
+        assert!((value("Lahiri") - 23.853).abs() < 1e-3);
+        // True Citra follows Spica, which Lahiri was meant to hold at 180°
+        assert!((value("TrueCitra") - value("Lahiri")).abs() < 0.05);
+    }
+}
@@ -0,0 +1,434 @@
+use super::*;
+
+/// A body crossing into a new nakshatra.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NakshatraTransit {
+    pub planet: CelestialBody,
+    pub from_nakshatra: Nakshatra,
+    pub to_nakshatra: Nakshatra,
+    pub date: DateTime<Utc>,
+    pub retrograde: bool,
+}
+
+/// A transiting body reaching an exact aspect to a natal planet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TransitAspect {
+    pub planet: CelestialBody,
+    pub natal_planet: CelestialBody,
+    pub aspect: Aspect,
+    pub date: DateTime<Utc>,
+    pub retrograde: bool,
+}
+
+/// A transiting body perfecting an aspect to a fixed natal point.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AspectEvent {
+    pub planet: CelestialBody,
+    pub aspect: Aspect,
+    pub natal_point: Longitude,
+    /// Where the transiting body stood when the aspect was exact.
+    pub longitude: Longitude,
+    pub date: DateTime<Utc>,
+    pub retrograde: bool,
+}
+
+/// Transit times are refined until they are known to within a minute.
+#[cfg(not(feature = "model-only"))]
+const TRANSIT_PRECISION: f64 = 1.0 / 1440.0;
+
+/// The moment a body passes a target longitude.
+#[cfg(not(feature = "model-only"))]
+pub(crate) struct Crossing {
+    pub(crate) julian_day: JulianDay,
+    /// Index into the targets searched for.
+    pub(crate) target: usize,
+    pub(crate) retrograde: bool,
+}
+
+/// Arc from `target` to `longitude`, in `-180..180`.
+#[cfg(not(feature = "model-only"))]
+pub(crate) fn signed_arc(longitude: f64, target: f64) -> f64 {
+    (longitude - target + 180.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Every sign ingress of `bodies` between `start` and `end`, in date order. Each
+    /// ingress is timed to the minute, and a body that retrogrades back over a sign
+    /// boundary and re-enters it is reported every time it crosses.
+    pub fn calculate_transits(
+        &self,
+        bodies: &[CelestialBody],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<TransitInfo>, AstrologyError> {
+        let boundaries: Vec<f64> = (0..12).map(|sign| sign as f64 * 30.0).collect();
+        let mut transits = Vec::new();
+        for &planet in bodies {
+            for crossing in self.find_crossings(planet, start, end, &boundaries)? {
+                let (before, after) = around(boundaries[crossing.target], 30.0);
+                let (from, to) = if crossing.retrograde {
+                    (after, before)
+                } else {
+                    (before, after)
+                };
+                transits.push(TransitInfo {
+                    planet,
+                    from_sign: ZodiacSign::from_longitude(from),
+                    to_sign: ZodiacSign::from_longitude(to),
+                    date: julian_day_to_date(crossing.julian_day),
+                    retrograde: crossing.retrograde,
+                });
+            }
+        }
+        transits.sort_by_key(|transit| transit.date);
+        Ok(transits)
+    }
+
+    /// Every nakshatra ingress of `bodies` between `start` and `end`, in date order.
+    pub fn calculate_nakshatra_transits(
+        &self,
+        bodies: &[CelestialBody],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<NakshatraTransit>, AstrologyError> {
+        let span = 360.0 / 27.0;
+        let boundaries: Vec<f64> = (0..27).map(|index| index as f64 * span).collect();
+        let mut transits = Vec::new();
+        for &planet in bodies {
+            for crossing in self.find_crossings(planet, start, end, &boundaries)? {
+                let (before, after) = around(boundaries[crossing.target], span);
+                let (from, to) = if crossing.retrograde {
+                    (after, before)
+                } else {
+                    (before, after)
+                };
+                transits.push(NakshatraTransit {
+                    planet,
+                    from_nakshatra: Nakshatra::from_longitude(from),
+                    to_nakshatra: Nakshatra::from_longitude(to),
+                    date: julian_day_to_date(crossing.julian_day),
+                    retrograde: crossing.retrograde,
+                });
+            }
+        }
+        transits.sort_by_key(|transit| transit.date);
+        Ok(transits)
+    }
+
+    /// Every moment between `start` and `end` at which one of `bodies` makes an exact
+    /// aspect to a planet of `natal`, in date order. A retrograde body can hit the same
+    /// point three times and each hit is reported.
+    pub fn calculate_transit_aspects(
+        &self,
+        bodies: &[CelestialBody],
+        natal: &ChartInfo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<TransitAspect>, AstrologyError> {
+        let mut points = Vec::new();
+        for natal_planet in &natal.planets {
+            for aspect in Aspect::all() {
+                let angle = aspect.angle();
+                points.push((natal_planet.planet, aspect, natal_planet.longitude + angle));
+                if angle != 0.0 && angle != 180.0 {
+                    points.push((natal_planet.planet, aspect, natal_planet.longitude - angle));
+                }
+            }
+        }
+        let targets: Vec<f64> = points
+            .iter()
+            .map(|&(_, _, target): &(_, _, Longitude)| target.degrees())
+            .collect();
+
+        let mut hits = Vec::new();
+        for &planet in bodies {
+            for crossing in self.find_crossings(planet, start, end, &targets)? {
+                let (natal_planet, aspect, _) = &points[crossing.target];
+                hits.push(TransitAspect {
+                    planet,
+                    natal_planet: *natal_planet,
+                    aspect: *aspect,
+                    date: julian_day_to_date(crossing.julian_day),
+                    retrograde: crossing.retrograde,
+                });
+            }
+        }
+        hits.sort_by_key(|hit| hit.date);
+        Ok(hits)
+    }
+
+    /// Every moment within `range` at which `transiting_planet` makes `aspect` exact to
+    /// `natal_point`, on either side of it, in date order and timed to the minute. A body
+    /// that retrogrades back over the aspect point is reported each time it crosses.
+    pub fn find_aspect_events(
+        &self,
+        natal_point: Longitude,
+        transiting_planet: CelestialBody,
+        aspect: Aspect,
+        range: std::ops::Range<DateTime<Utc>>,
+    ) -> Result<Vec<AspectEvent>, AstrologyError> {
+        let angle = aspect.angle();
+        let mut targets = vec![natal_point + angle];
+        if angle != 0.0 && angle != 180.0 {
+            targets.push(natal_point - angle);
+        }
+        let degrees: Vec<f64> = targets.iter().map(|target| target.degrees()).collect();
+
+        let mut events: Vec<AspectEvent> = self
+            .find_crossings(transiting_planet, range.start, range.end, &degrees)?
+            .into_iter()
+            .map(|crossing| AspectEvent {
+                planet: transiting_planet,
+                aspect,
+                natal_point,
+                longitude: targets[crossing.target],
+                date: julian_day_to_date(crossing.julian_day),
+                retrograde: crossing.retrograde,
+            })
+            .collect();
+        events.sort_by_key(|event| event.date);
+        Ok(events)
+    }
+
+    /// Steps `body` through the range and bisects every step over which it passes one of
+    /// `targets`. Steps are short enough that a body cannot pass the same target twice
+    /// within one, so retrograde loops are caught as separate crossings.
+    pub(crate) fn find_crossings(
+        &self,
+        body: CelestialBody,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        targets: &[f64],
+    ) -> Result<Vec<Crossing>, AstrologyError> {
+        let _timed = Timed::start(
+            TimedWork::TransitScan,
+            tracing::debug_span!("transit_scan", body = ?body, %start, %end, targets = targets.len()),
+        );
+        let step = match body {
+            CelestialBody::Moon => 0.25,
+            _ => 1.0,
+        };
+        let end_jd = date_to_julian_day(end);
+        let mut julian_day = date_to_julian_day(start);
+        let mut longitude = self.sidereal_longitude(body, julian_day)?;
+        let mut crossings = Vec::new();
+
+        while julian_day < end_jd {
+            let next_jd = (julian_day + step).min(end_jd);
+            let next_longitude = self.sidereal_longitude(body, next_jd)?;
+
+            for (index, &target) in targets.iter().enumerate() {
+                let before = signed_arc(longitude, target);
+                let after = signed_arc(next_longitude, target);
+                // A jump of half the zodiac is the far side of the target, not a crossing.
+                if (before < 0.0) == (after < 0.0) || (after - before).abs() >= 180.0 {
+                    continue;
+                }
+
+                let (mut low, mut high) = (julian_day, next_jd);
+                while high - low > TRANSIT_PRECISION {
+                    let middle = (low + high) / 2.0;
+                    let arc = signed_arc(self.sidereal_longitude(body, middle)?, target);
+                    if (arc < 0.0) == (before < 0.0) {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                crossings.push(Crossing {
+                    julian_day: high,
+                    target: index,
+                    retrograde: after < before,
+                });
+            }
+
+            julian_day = next_jd;
+            longitude = next_longitude;
+        }
+
+        Ok(crossings)
+    }
+
+    pub(crate) fn sidereal_longitude(
+        &self,
+        body: CelestialBody,
+        julian_day: JulianDay,
+    ) -> Result<f64, AstrologyError> {
+        match self.calculate(CoordinateSystem::Sidereal, julian_day, body, &[], None)? {
+            AstronomicalResult::CelestialBody(info) => Ok(info.longitude),
+            _ => Err(AstrologyError::Ephemeris {
+                code: -1,
+                message: format!("Failed to calculate {:?} position", body),
+            }),
+        }
+    }
+}
+
+/// Longitudes just before and just after a boundary between segments of `span` degrees.
+#[cfg(not(feature = "model-only"))]
+fn around(boundary: f64, span: f64) -> (f64, f64) {
+    (boundary - span / 2.0, boundary + span / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "model-only"))]
+    use super::*;
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn a_retrograde_planet_perfects_an_aspect_three_times() {
+        let ephemeris = SwissEph::new().unwrap();
+        let date = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap();
+        // Jupiter stationed retrograde in late Taurus in October 2024 and direct in mid
+        // Taurus in February 2025, passing 52° on the way out, back and out again
+        let natal_point = Longitude::new(52.0);
+        let range = date(2024, 6, 1)..date(2025, 6, 1);
+        let conjunctions = ephemeris
+            .find_aspect_events(
+                natal_point,
+                CelestialBody::Jupiter,
+                Aspect::Conjunction,
+                range.clone(),
+            )
+            .unwrap();
+        assert_eq!(conjunctions.len(), 3);
+        assert_eq!(
+            conjunctions
+                .iter()
+                .map(|event| event.retrograde)
+                .collect::<Vec<_>>(),
+            [false, true, false]
+        );
+        for event in &conjunctions {
+            let longitude = ephemeris
+                .sidereal_longitude(CelestialBody::Jupiter, date_to_julian_day(event.date))
+                .unwrap();
+            assert!(Longitude::new(longitude).separation(natal_point) < 0.01);
+        }
+
+        // A trine from 292° lands on the same place, and from 172° on the other side of
+        // it where Jupiter never goes this year
+        let trines = ephemeris
+            .find_aspect_events(
+                Longitude::new(292.0),
+                CelestialBody::Jupiter,
+                Aspect::Trine,
+                range,
+            )
+            .unwrap();
+        assert_eq!(trines.len(), 3);
+        assert!(trines.iter().all(|event| event.longitude == natal_point));
+        assert_eq!(trines[1].date, conjunctions[1].date);
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn sign_ingresses_are_timed_and_retrograde_ones_reported() {
+        let ephemeris = SwissEph::new().unwrap();
+        let date = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap();
+        let transits = ephemeris
+            .calculate_transits(
+                &[CelestialBody::Sun, CelestialBody::Mercury],
+                date(2024, 1, 1),
+                date(2025, 1, 1),
+            )
+            .unwrap();
+        assert!(transits.windows(2).all(|pair| pair[0].date <= pair[1].date));
+
+        for transit in &transits {
+            let longitude = ephemeris
+                .sidereal_longitude(transit.planet, date_to_julian_day(transit.date))
+                .unwrap();
+            // Just past the boundary in the direction of travel
+            let step = if transit.retrograde { -0.01 } else { 0.01 };
+            assert!(signed_arc(longitude, (longitude / 30.0).round() * 30.0).abs() < 0.01);
+            assert_eq!(
+                ZodiacSign::from_longitude(longitude + step),
+                transit.to_sign
+            );
+        }
+
+        // The Sun enters every sign once and never turns back; Makar Sankranti fell on
+        // the night of 14 January by UTC
+        let sun: Vec<&TransitInfo> = transits
+            .iter()
+            .filter(|transit| transit.planet == CelestialBody::Sun)
+            .collect();
+        assert_eq!(sun.len(), 12);
+        assert!(sun.iter().all(|transit| !transit.retrograde));
+        assert!(sun
+            .windows(2)
+            .all(|pair| pair[0].to_sign == pair[1].from_sign));
+        assert_eq!(sun[0].to_sign, ZodiacSign::Capricorn);
+        assert_eq!(
+            sun[0].date.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 1, 14).unwrap()
+        );
+
+        // Mercury retrograded back into Pisces on 9 April and into Cancer on 22 August
+        let backwards: Vec<(ZodiacSign, NaiveDate)> = transits
+            .iter()
+            .filter(|transit| transit.retrograde)
+            .map(|transit| (transit.to_sign, transit.date.date_naive()))
+            .collect();
+        assert_eq!(
+            backwards,
+            [
+                (
+                    ZodiacSign::Pisces,
+                    NaiveDate::from_ymd_opt(2024, 4, 9).unwrap()
+                ),
+                (
+                    ZodiacSign::Cancer,
+                    NaiveDate::from_ymd_opt(2024, 8, 22).unwrap()
+                ),
+            ]
+        );
+        assert!(transits
+            .iter()
+            .filter(|transit| transit.retrograde)
+            .all(|transit| transit.planet == CelestialBody::Mercury));
+    }
+
+    #[cfg(not(feature = "model-only"))]
+    #[test]
+    fn the_moon_enters_each_nakshatra_in_turn() {
+        let ephemeris = SwissEph::new().unwrap();
+        let date = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap();
+        let span = 360.0 / 27.0;
+        let transits = ephemeris
+            .calculate_nakshatra_transits(
+                &[CelestialBody::Moon],
+                date(2024, 1, 1),
+                date(2024, 1, 15),
+            )
+            .unwrap();
+
+        // About one a day, from Magha into Purva Phalguni onwards
+        assert_eq!(transits.len(), 14);
+        assert_eq!(transits[0].from_nakshatra, Nakshatra::Magha);
+        assert_eq!(transits[0].to_nakshatra, Nakshatra::PurvaPhalguni);
+        assert!(transits
+            .windows(2)
+            .all(|pair| pair[0].to_nakshatra == pair[1].from_nakshatra
+                && pair[0].date < pair[1].date));
+
+        for transit in &transits {
+            assert!(!transit.retrograde);
+            let longitude = ephemeris
+                .sidereal_longitude(CelestialBody::Moon, date_to_julian_day(transit.date))
+                .unwrap();
+            // The Moon covers about half a minute of arc in the minute the search allows
+            assert!(signed_arc(longitude, (longitude / span).round() * span).abs() < 0.01);
+            assert_eq!(
+                Nakshatra::from_longitude(longitude + 0.01),
+                transit.to_nakshatra
+            );
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct UpagrahaInfo {
+    pub upagraha: Upagraha,
+    pub longitude: f64,
+    pub sign: ZodiacSign,
+    pub house: House,
+}
+
+/// Saturn's place among the weekday lords, counted from the Sun as 0.
+#[cfg(not(feature = "model-only"))]
+const SATURN_DAY: usize = 6;
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// Calculates the Sun-based upagrahas (Dhuma, Vyatipata, Parivesha, Indrachaapa,
+    /// Upaketu) and the time-based ones (Kala, Mrityu, Artha Prahara, Yama Ghantaka).
+    ///
+    /// The time-based upagrahas need sunrise and sunset; where the Sun does not rise or set
+    /// on the birth day they are left out.
+    pub fn calculate_upagrahas(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+    ) -> Result<HashMap<Upagraha, UpagrahaInfo>, AstrologyError> {
+        let mut longitudes = self.sun_upagraha_longitudes(chart)?;
+        match self.time_upagraha_longitudes(birth_info, chart.house_system) {
+            Ok(time_based) => longitudes.extend(time_based),
+            Err(AstrologyError::NoRiseOrSet(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(longitudes
+            .into_iter()
+            .map(|(upagraha, longitude)| {
+                let info = UpagrahaInfo {
+                    upagraha,
+                    longitude,
+                    sign: ZodiacSign::from_longitude(longitude),
+                    house: chart.house_of(longitude),
+                };
+                (upagraha, info)
+            })
+            .collect())
+    }
+
+    /// Dhuma is the Sun plus 133°20'; each of the others follows from the previous one.
+    fn sun_upagraha_longitudes(
+        &self,
+        chart: &ChartInfo,
+    ) -> Result<Vec<(Upagraha, f64)>, AstrologyError> {
+        let sun = chart
+            .planets
+            .iter()
+            .find(|p| p.planet == CelestialBody::Sun)
+            .ok_or(AstrologyError::MissingBody(CelestialBody::Sun))?;
+
+        let dhuma = (sun.longitude + 133.0 + 20.0 / 60.0).degrees();
+        let vyatipata = (360.0 - dhuma).rem_euclid(360.0);
+        let parivesha = (vyatipata + 180.0).rem_euclid(360.0);
+        let indrachaapa = (360.0 - parivesha).rem_euclid(360.0);
+        let upaketu = (indrachaapa + 16.0 + 40.0 / 60.0).rem_euclid(360.0);
+
+        Ok(vec![
+            (Upagraha::Dhuma, dhuma),
+            (Upagraha::Vyatipata, vyatipata),
+            (Upagraha::Parivesha, parivesha),
+            (Upagraha::Indrachaapa, indrachaapa),
+            (Upagraha::Upaketu, upaketu),
+        ])
+    }
+
+    /// Gulika and Mandi, the ascendant at the start and at the middle of Saturn's part of the
+    /// day or night of birth; see [`SwissEph::calculate_upagrahas`] for the parts. Where the
+    /// Sun does not rise or set on the birth day there are none.
+    pub fn calculate_sensitive_points(
+        &self,
+        birth_info: &BirthInfo,
+        chart: &ChartInfo,
+    ) -> Result<HashMap<SensitivePoint, Longitude>, AstrologyError> {
+        let (start, part, first_lord) = match self.birth_day_parts(birth_info) {
+            Ok(parts) => parts,
+            Err(AstrologyError::NoRiseOrSet(_)) => return Ok(HashMap::new()),
+            Err(err) => return Err(err),
+        };
+        let saturn = start + part * ((SATURN_DAY + 7 - first_lord) % 7) as i32;
+        [
+            (SensitivePoint::Gulika, saturn),
+            (SensitivePoint::Mandi, saturn + part / 2),
+        ]
+        .into_iter()
+        .map(|(point, moment)| {
+            let ascendant = self.ascendant_at(moment, birth_info, chart.house_system)?;
+            Ok((point, Longitude::new(ascendant)))
+        })
+        .collect()
+    }
+
+    /// The day (or night) of birth is split into eight parts ruled in weekday order, starting
+    /// from the weekday lord by day and from the fifth lord from it by night; the eighth part
+    /// has no lord. Each upagraha is the ascendant at the start of its lord's part.
+    fn time_upagraha_longitudes(
+        &self,
+        birth_info: &BirthInfo,
+        house_system: HouseSystem,
+    ) -> Result<Vec<(Upagraha, f64)>, AstrologyError> {
+        let (start, part, first_lord) = self.birth_day_parts(birth_info)?;
+
+        // Weekday lords in order: Sun, Moon, Mars, Mercury, Jupiter, Venus, Saturn.
+        let rulers = [
+            (Upagraha::Kala, 0),
+            (Upagraha::Mrityu, 2),
+            (Upagraha::ArthaPrahara, 3),
+            (Upagraha::YamaGhantaka, 4),
+        ];
+
+        rulers
+            .into_iter()
+            .map(|(upagraha, lord)| {
+                let index = (lord + 7 - first_lord) % 7;
+                let moment = start + part * index as i32;
+                let ascendant = self.ascendant_at(moment, birth_info, house_system)?;
+                Ok((upagraha, ascendant))
+            })
+            .collect()
+    }
+
+    /// Start and length of the eight parts of the day or night of birth, and the weekday
+    /// lord of the first, counted from the Sun as 0.
+    fn birth_day_parts(
+        &self,
+        birth_info: &BirthInfo,
+    ) -> Result<(DateTime<Utc>, ChronoDuration, usize), AstrologyError> {
+        let birth = birth_info.date_time;
+        let location = &birth_info.location;
+        let local_date = birth_info.local_date_time().date_naive();
+
+        // The Vedic day starts at sunrise, so a birth before sunrise belongs to the day before.
+        let mut day = local_date;
+        let mut sunrise = self.sunrise(day, location)?;
+        if birth < sunrise {
+            day = day.pred_opt().unwrap_or(day);
+            sunrise = self.sunrise(day, location)?;
+        }
+        let sunset = self.sunset(day, location)?;
+        let next_sunrise = self.sunrise(day.succ_opt().unwrap_or(day), location)?;
+
+        let weekday = day.weekday().num_days_from_sunday() as usize;
+        let (start, end, first_lord) = if birth < sunset {
+            (sunrise, sunset, weekday)
+        } else {
+            (sunset, next_sunrise, (weekday + 4) % 7)
+        };
+        Ok((start, (end - start) / 8, first_lord))
+    }
+
+    fn ascendant_at(
+        &self,
+        moment: DateTime<Utc>,
+        birth_info: &BirthInfo,
+        house_system: HouseSystem,
+    ) -> Result<f64, AstrologyError> {
+        let ascendant = self.calculate_ascendant(
+            CoordinateSystem::Sidereal,
+            date_to_julian_day(moment),
+            birth_info.location.latitude,
+            birth_info.location.longitude,
+            house_system,
+        )?;
+        Ok(ascendant.longitude())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "model-only"))]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        let difference = (a - b).rem_euclid(360.0);
+        difference.min(360.0 - difference) < 1e-6
+    }
+
+    #[test]
+    fn the_sun_upagrahas_follow_one_from_another() {
+        let ephemeris = SwissEph::new().unwrap();
+        let birth = crate::examples::native().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&birth, HouseSystem::WholeSign)
+            .unwrap();
+        let upagrahas = ephemeris.calculate_upagrahas(&birth, &chart).unwrap();
+        let at = |upagraha| upagrahas[&upagraha].longitude;
+        let sun = chart
+            .planet(CelestialBody::Sun)
+            .unwrap()
+            .longitude
+            .degrees();
+
+        assert!(close(at(Upagraha::Dhuma), sun + 133.0 + 20.0 / 60.0));
+        assert!(close(at(Upagraha::Vyatipata), -at(Upagraha::Dhuma)));
+        assert!(close(
+            at(Upagraha::Parivesha),
+            at(Upagraha::Vyatipata) + 180.0
+        ));
+        assert!(close(at(Upagraha::Indrachaapa), -at(Upagraha::Parivesha)));
+        assert!(close(
+            at(Upagraha::Upaketu),
+            at(Upagraha::Indrachaapa) + 16.0 + 40.0 / 60.0
+        ));
+        // The chain closes a sign behind the Sun
+        assert!(close(at(Upagraha::Upaketu) + 30.0, sun));
+
+        let dhuma = &upagrahas[&Upagraha::Dhuma];
+        assert_eq!(dhuma.sign, ZodiacSign::from_longitude(dhuma.longitude));
+        assert_eq!(dhuma.house, chart.house_of(dhuma.longitude));
+    }
+
+    #[test]
+    fn gulika_rises_at_the_start_of_saturns_part() {
+        let ephemeris = SwissEph::new().unwrap();
+        let ascendant = |moment: DateTime<Utc>, birth: &BirthInfo| {
+            ephemeris
+                .ascendant_at(moment, birth, HouseSystem::WholeSign)
+                .unwrap()
+        };
+
+        // Born on a Tuesday morning: Saturn's part of the day is Gulika Kalam, the fifth
+        let native = crate::examples::native().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&native, HouseSystem::WholeSign)
+            .unwrap();
+        let points = ephemeris
+            .calculate_sensitive_points(&native, &chart)
+            .unwrap();
+        let day = native.local_date_time().date_naive();
+        let gulika_kalam = ephemeris
+            .inauspicious_periods(day, &native.location)
+            .unwrap()
+            .into_iter()
+            .find(|period| period.value == InauspiciousPeriod::GulikaKalam)
+            .unwrap();
+        let middle = gulika_kalam.start + (gulika_kalam.end - gulika_kalam.start) / 2;
+        assert!(close(
+            points[&SensitivePoint::Gulika].degrees(),
+            ascendant(gulika_kalam.start, &native)
+        ));
+        assert!(close(
+            points[&SensitivePoint::Mandi].degrees(),
+            ascendant(middle, &native)
+        ));
+
+        // Born on a Thursday night, when Saturn rules the sixth part of the night
+        let partner = crate::examples::partner().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&partner, HouseSystem::WholeSign)
+            .unwrap();
+        let points = ephemeris
+            .calculate_sensitive_points(&partner, &chart)
+            .unwrap();
+        let day = partner.local_date_time().date_naive();
+        let sunset = ephemeris.sunset(day, &partner.location).unwrap();
+        let next_sunrise = ephemeris
+            .sunrise(day.succ_opt().unwrap(), &partner.location)
+            .unwrap();
+        let sixth = sunset + (next_sunrise - sunset) / 8 * 5;
+        assert!(close(
+            points[&SensitivePoint::Gulika].degrees(),
+            ascendant(sixth, &partner)
+        ));
+    }
+}
@@ -0,0 +1,564 @@
+use super::*;
+
+fn is_odd_sign(sign: usize) -> bool {
+    // Aries, the first sign, has index 0.
+    sign.is_multiple_of(2)
+}
+
+/// Navamsa (D9) longitude. Each sign is cut into nine parts of 3°20', counted from the
+/// movable sign of its element: Aries for fire signs, Capricorn for earth, Libra for air
+/// and Cancer for water.
+pub fn navamsa_longitude(longitude: f64) -> f64 {
+    crate::core::navamsa_longitude(longitude)
+}
+
+#[cfg(not(feature = "model-only"))]
+/// Whole-sign house of `sign` counted from `ascendant`.
+fn house_from(ascendant: ZodiacSign, sign: ZodiacSign) -> House {
+    House::from_index((sign as usize + 12 - ascendant as usize) % 12 + 1).unwrap()
+}
+
+/// Presiding deities of the ten Dashamsa divisions. Odd signs run Indra to Ananta; even
+/// signs run the same list in reverse.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DashamsaDeity {
+    Indra,
+    Agni,
+    Yama,
+    Rakshasa,
+    Varuna,
+    Vayu,
+    Kubera,
+    Ishana,
+    Brahma,
+    Ananta,
+}
+
+impl DashamsaDeity {
+    pub fn from_longitude(longitude: f64) -> DashamsaDeity {
+        const DEITIES: [DashamsaDeity; 10] = [
+            DashamsaDeity::Indra,
+            DashamsaDeity::Agni,
+            DashamsaDeity::Yama,
+            DashamsaDeity::Rakshasa,
+            DashamsaDeity::Varuna,
+            DashamsaDeity::Vayu,
+            DashamsaDeity::Kubera,
+            DashamsaDeity::Ishana,
+            DashamsaDeity::Brahma,
+            DashamsaDeity::Ananta,
+        ];
+        let longitude = longitude.rem_euclid(360.0);
+        let sign = (longitude / 30.0).floor() as usize % 12;
+        let part = (((longitude % 30.0) / 3.0).floor() as usize).min(9);
+        if is_odd_sign(sign) {
+            DEITIES[part]
+        } else {
+            DEITIES[9 - part]
+        }
+    }
+}
+
+/// Career-focused reading of the Dashamsa.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CareerSummary {
+    pub tenth_lord: CelestialBody,
+    pub tenth_lord_house: Option<House>,
+    pub lagna_lord: CelestialBody,
+    pub lagna_lord_house: Option<House>,
+    pub strongest_planet: Option<CelestialBody>,
+}
+
+/// Parent-focused reading of the Dwadasamsa. The Sun signifies the father and the Moon
+/// the mother; the 4th and 9th houses stand for mother and father respectively.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ParentsSummary {
+    pub sun: Option<PlanetPosition>,
+    pub moon: Option<PlanetPosition>,
+    pub fourth_lord: CelestialBody,
+    pub fourth_lord_house: Option<House>,
+    pub ninth_lord: CelestialBody,
+    pub ninth_lord_house: Option<House>,
+}
+
+/// A planet this close to its degree of deepest exaltation is deeply exalted.
+pub const DEEP_EXALTATION_ORB: f64 = 1.0;
+
+/// The Pushkara bhaga of each sign from Aries: the degree within it, counted from one, that
+/// strengthens a planet occupying it.
+const PUSHKARA_BHAGA: [u8; 12] = [21, 14, 18, 8, 19, 9, 24, 11, 23, 14, 19, 9];
+
+/// Standing of a planet read across the rasi and navamsa, so it need not be worked out
+/// again from the divisional charts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VargaFlags {
+    /// Same sign in the rasi and the navamsa.
+    pub vargottama: bool,
+    /// In one of the two Pushkara navamsas of its sign, which fall in Taurus, Cancer,
+    /// Virgo, Libra, Sagittarius or Pisces.
+    pub pushkara_navamsa: bool,
+    /// In the Pushkara bhaga of its sign.
+    pub pushkara_bhaga: bool,
+    /// Degrees from the planet's point of deepest exaltation, for the seven planets.
+    pub deep_exaltation_distance: Option<f64>,
+    /// Within [`DEEP_EXALTATION_ORB`] of the point of deepest exaltation.
+    pub deeply_exalted: bool,
+}
+
+impl PlanetPosition {
+    /// Vargottama, Pushkara and deep exaltation flags of the planet's sidereal longitude.
+    pub fn varga_flags(&self) -> VargaFlags {
+        let longitude = self.longitude.degrees();
+        let sign = self.sign as usize;
+        let degree = self.longitude.degree_in_sign();
+        let navamsa = ZodiacSign::from_longitude(navamsa_longitude(longitude));
+
+        // Each element has two Pushkara navamsas, counted from one within the sign
+        let pushkara_parts = match sign % 4 {
+            0 => [7, 9],
+            1 => [3, 5],
+            2 => [6, 8],
+            _ => [1, 3],
+        };
+        let part = ((degree / (30.0 / 9.0)).floor() as usize).min(8) + 1;
+        let bhaga = PUSHKARA_BHAGA[sign] as f64;
+        let deep_exaltation_distance = self
+            .planet
+            .exaltation_degree()
+            .map(|exaltation| self.longitude.separation(Longitude::new(exaltation)));
+
+        VargaFlags {
+            vargottama: navamsa == self.sign,
+            pushkara_navamsa: pushkara_parts.contains(&part),
+            pushkara_bhaga: degree >= bhaga - 1.0 && degree < bhaga,
+            deep_exaltation_distance,
+            deeply_exalted: deep_exaltation_distance
+                .is_some_and(|distance| distance <= DEEP_EXALTATION_ORB),
+        }
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    pub fn calculate_navamsa(&self, longitude: f64) -> f64 {
+        navamsa_longitude(longitude)
+    }
+
+    pub fn calculate_d9(&self, chart: &ChartInfo) -> DivisionalChart {
+        self.divisional_chart(chart, ChartType::Navamsa, navamsa_longitude)
+    }
+
+    /// Saptamsa (D7) longitude. Odd signs count their seven parts from the sign itself,
+    /// even signs from the sign seventh to it.
+    pub fn calculate_saptamsa(&self, longitude: f64) -> f64 {
+        crate::core::varga_longitude(longitude, 7, |sign| {
+            if is_odd_sign(sign) {
+                sign
+            } else {
+                (sign + 6) % 12
+            }
+        })
+    }
+
+    pub fn calculate_d7(&self, chart: &ChartInfo) -> DivisionalChart {
+        self.divisional_chart(chart, ChartType::Saptamsa, |longitude| {
+            self.calculate_saptamsa(longitude)
+        })
+    }
+
+    /// Dashamsa (D10) longitude. Odd signs count their ten parts from the sign itself,
+    /// even signs from the sign ninth to it.
+    pub fn calculate_dashamsa(&self, longitude: f64) -> f64 {
+        crate::core::varga_longitude(longitude, 10, |sign| {
+            if is_odd_sign(sign) {
+                sign
+            } else {
+                (sign + 8) % 12
+            }
+        })
+    }
+
+    pub fn calculate_d10(&self, chart: &ChartInfo) -> DivisionalChart {
+        self.divisional_chart(chart, ChartType::Dashamsa, |longitude| {
+            self.calculate_dashamsa(longitude)
+        })
+    }
+
+    /// Dashamsa deity of each planet, from its natal longitude.
+    pub fn dashamsa_deities(&self, chart: &ChartInfo) -> HashMap<CelestialBody, DashamsaDeity> {
+        chart
+            .planets
+            .iter()
+            .map(|planet| {
+                (
+                    planet.planet,
+                    DashamsaDeity::from_longitude(planet.longitude.degrees()),
+                )
+            })
+            .collect()
+    }
+
+    /// Gathers the D10 tenth lord, the D10 lagna lord and the strongest D10 planet.
+    ///
+    /// Strength here is positional: dignity in the D10 sign (exalted, moolatrikona, own
+    /// sign, debilitated) plus a bonus for kendras and trikonas and a penalty for dusthanas.
+    pub fn career_summary(&self, d10: &DivisionalChart) -> CareerSummary {
+        let house_of = |body: CelestialBody| {
+            d10.planets
+                .iter()
+                .find(|p| p.planet == body)
+                .map(|p| p.house)
+        };
+
+        let score = |planet: &PlanetPosition| {
+            let dignity = self.calculate_dignity(planet);
+            let mut score = 0;
+            if dignity.exalted {
+                score += 4;
+            } else if dignity.moolatrikona {
+                score += 3;
+            } else if dignity.own_sign {
+                score += 2;
+            } else if dignity.debilitated {
+                score -= 2;
+            }
+            score += match planet.house {
+                House::First | House::Fourth | House::Seventh | House::Tenth => 2,
+                House::Fifth | House::Ninth => 1,
+                House::Sixth | House::Eighth | House::Twelfth => -1,
+                _ => 0,
+            };
+            score
+        };
+
+        let tenth_lord = d10.houses[9].lord();
+        let lagna_lord = d10.ascendant.lord();
+        let strongest_planet = d10
+            .planets
+            .iter()
+            .filter(|p| !matches!(p.planet, CelestialBody::Rahu | CelestialBody::Ketu))
+            .max_by_key(|p| score(p))
+            .map(|p| p.planet);
+
+        CareerSummary {
+            tenth_lord,
+            tenth_lord_house: house_of(tenth_lord),
+            lagna_lord,
+            lagna_lord_house: house_of(lagna_lord),
+            strongest_planet,
+        }
+    }
+
+    /// Dwadasamsa (D12) longitude. Every sign counts its twelve parts from itself.
+    pub fn calculate_dwadasamsa(&self, longitude: f64) -> f64 {
+        crate::core::varga_longitude(longitude, 12, |sign| sign)
+    }
+
+    pub fn calculate_d12(&self, chart: &ChartInfo) -> DivisionalChart {
+        self.divisional_chart(chart, ChartType::Dwadasamsa, |longitude| {
+            self.calculate_dwadasamsa(longitude)
+        })
+    }
+
+    pub fn parents_summary(&self, d12: &DivisionalChart) -> ParentsSummary {
+        let position = |body: CelestialBody| d12.planets.iter().find(|p| p.planet == body);
+        let fourth_lord = d12.houses[3].lord();
+        let ninth_lord = d12.houses[8].lord();
+
+        ParentsSummary {
+            sun: position(CelestialBody::Sun).cloned(),
+            moon: position(CelestialBody::Moon).cloned(),
+            fourth_lord,
+            fourth_lord_house: position(fourth_lord).map(|p| p.house),
+            ninth_lord,
+            ninth_lord_house: position(ninth_lord).map(|p| p.house),
+        }
+    }
+
+    /// Builds a divisional chart with whole-sign houses from the varga ascendant.
+    fn divisional_chart(
+        &self,
+        chart: &ChartInfo,
+        chart_type: ChartType,
+        to_varga: impl Fn(f64) -> f64,
+    ) -> DivisionalChart {
+        let ascendant_longitude = chart.ascendant.longitude();
+        let ascendant = ZodiacSign::from_longitude(to_varga(ascendant_longitude));
+
+        let houses = std::array::from_fn(|i| {
+            ZodiacSign::from_longitude((ascendant as usize + i) as f64 * 30.0)
+        });
+
+        let planets = chart
+            .planets
+            .iter()
+            .map(|planet| {
+                let longitude = to_varga(planet.longitude.degrees());
+                let sign = ZodiacSign::from_longitude(longitude);
+                PlanetPosition {
+                    longitude: Longitude::new(longitude),
+                    sign,
+                    house: house_from(ascendant, sign),
+                    nakshatra: NakshatraInfo::from_longitude(longitude),
+                    ..planet.clone()
+                }
+            })
+            .collect();
+
+        DivisionalChart {
+            chart_type,
+            ascendant,
+            houses,
+            planets,
+        }
+    }
+
+    /// Reads the Saptamsa for children: the D7 lagna lord, Jupiter as significator of
+    /// progeny, and the fifth house of the D7.
+    pub fn children_indications(&self, d7: &DivisionalChart) -> Vec<String> {
+        let mut indications = Vec::new();
+        let house_of = |body: CelestialBody| {
+            d7.planets
+                .iter()
+                .find(|p| p.planet == body)
+                .map(|p| p.house)
+        };
+        let is_dusthana =
+            |house: House| matches!(house, House::Sixth | House::Eighth | House::Twelfth);
+        let is_kendra_or_trikona = |house: House| {
+            matches!(
+                house,
+                House::First
+                    | House::Fourth
+                    | House::Seventh
+                    | House::Tenth
+                    | House::Fifth
+                    | House::Ninth
+            )
+        };
+
+        let lagna_lord = d7.ascendant.lord();
+        if let Some(house) = house_of(lagna_lord) {
+            if is_kendra_or_trikona(house) {
+                indications.push(format!(
+                    "D7 lagna lord {:?} in the {:?} house supports progeny",
+                    lagna_lord, house
+                ));
+            } else if is_dusthana(house) {
+                indications.push(format!(
+                    "D7 lagna lord {:?} in the {:?} house may delay children",
+                    lagna_lord, house
+                ));
+            }
+        }
+
+        if let Some(house) = house_of(CelestialBody::Jupiter) {
+            if is_dusthana(house) {
+                indications.push(format!(
+                    "Jupiter, significator of children, is weak in the D7 {:?} house",
+                    house
+                ));
+            } else if is_kendra_or_trikona(house) {
+                indications.push(format!(
+                    "Jupiter, significator of children, is well placed in the D7 {:?} house",
+                    house
+                ));
+            }
+        }
+
+        let fifth_sign = d7.houses[4];
+        indications.push(format!(
+            "Fifth house of the D7 is {} ruled by {:?}",
+            fifth_sign,
+            fifth_sign.lord()
+        ));
+        for planet in d7.planets.iter().filter(|p| p.house == House::Fifth) {
+            let nature = match planet.planet {
+                CelestialBody::Jupiter
+                | CelestialBody::Venus
+                | CelestialBody::Mercury
+                | CelestialBody::Moon => "blesses",
+                _ => "afflicts",
+            };
+            indications.push(format!(
+                "{:?} in the D7 fifth house {} matters of children",
+                planet.planet, nature
+            ));
+        }
+
+        indications
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART: f64 = 30.0 / 9.0;
+
+    fn navamsa_sign(longitude: f64) -> ZodiacSign {
+        ZodiacSign::from_longitude(navamsa_longitude(longitude))
+    }
+
+    #[test]
+    fn navamsa_starts_from_the_movable_sign_of_each_element() {
+        use ZodiacSign::*;
+        let starts = [
+            Aries, Capricorn, Libra, Cancer, Aries, Capricorn, Libra, Cancer, Aries, Capricorn,
+            Libra, Cancer,
+        ];
+        for (sign, start) in starts.into_iter().enumerate() {
+            assert_eq!(
+                navamsa_sign(sign as f64 * 30.0 + 1.0),
+                start,
+                "sign {}",
+                sign
+            );
+        }
+    }
+
+    #[test]
+    fn navamsa_of_every_part_follows_on_around_the_zodiac() {
+        // The 108 navamsas run through the zodiac nine times in order, so the n-th part
+        // from 0° Aries falls in sign n mod 12.
+        for part in 0..108 {
+            let middle = (part as f64 + 0.5) * PART;
+            assert_eq!(
+                navamsa_sign(middle) as usize,
+                part % 12,
+                "part {} at {:.4}°",
+                part,
+                middle
+            );
+        }
+    }
+
+    #[test]
+    fn navamsa_boundaries_fall_at_three_degrees_twenty_minutes() {
+        use ZodiacSign::*;
+        assert_eq!(navamsa_sign(0.0), Aries);
+        assert_eq!(navamsa_sign(PART - 1e-9), Aries);
+        assert_eq!(navamsa_sign(PART + 1e-9), Taurus);
+        assert_eq!(navamsa_sign(30.0 - 1e-9), Sagittarius);
+        assert_eq!(navamsa_sign(30.0), Capricorn);
+        assert_eq!(navamsa_sign(360.0 - 1e-9), Pisces);
+    }
+
+    #[test]
+    fn navamsa_spreads_each_part_over_a_whole_sign() {
+        assert!((navamsa_longitude(PART / 2.0) - 15.0).abs() < 1e-9);
+        // 5° Taurus is 1°40' into its second part, which is Aquarius.
+        assert!((navamsa_longitude(35.0) - 315.0).abs() < 1e-9);
+        // 29° Pisces is 2°20' into its last part, which is Pisces.
+        assert!((navamsa_longitude(359.0) - (330.0 + 21.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn navamsa_normalises_longitude() {
+        assert_eq!(navamsa_longitude(-1.0), navamsa_longitude(359.0));
+        assert_eq!(navamsa_longitude(725.0), navamsa_longitude(5.0));
+    }
+
+    #[test]
+    fn vargottama_degrees_are_the_first_middle_and_last_parts() {
+        use ZodiacSign::*;
+        // Movable signs are vargottama in their first navamsa, fixed signs in their fifth
+        // and dual signs in their ninth.
+        for sign in [Aries, Cancer, Libra, Capricorn] {
+            assert_eq!(navamsa_sign(sign as usize as f64 * 30.0 + 1.0), sign);
+        }
+        for sign in [Taurus, Leo, Scorpio, Aquarius] {
+            assert_eq!(navamsa_sign(sign as usize as f64 * 30.0 + 15.0), sign);
+        }
+        for sign in [Gemini, Virgo, Sagittarius, Pisces] {
+            assert_eq!(navamsa_sign(sign as usize as f64 * 30.0 + 29.0), sign);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "model-only"))]
+    fn navamsa_matches_nakshatra_padas_in_a_known_chart() {
+        // Each pada of a nakshatra is one navamsa, so the D9 sign of every planet in the
+        // native's chart can be read off its nakshatra and pada as well.
+        let ephemeris = SwissEph::new().unwrap();
+        let chart = ephemeris
+            .calculate_chart(&crate::examples::native().unwrap(), HouseSystem::default())
+            .unwrap();
+        let d9 = ephemeris.calculate_d9(&chart);
+
+        for (natal, varga) in chart.planets.iter().zip(&d9.planets) {
+            let nakshatra = natal.nakshatra.nakshatra as usize;
+            let pada = natal.nakshatra.pada as usize;
+            assert_eq!(
+                varga.sign as usize,
+                (nakshatra * 4 + pada - 1) % 12,
+                "{:?} at {:.4}°",
+                natal.planet,
+                natal.longitude.degrees()
+            );
+        }
+        assert_eq!(
+            d9.ascendant,
+            navamsa_sign(chart.ascendant.longitude()),
+            "ascendant"
+        );
+    }
+
+    fn flags(planet: CelestialBody, longitude: f64) -> VargaFlags {
+        PlanetPosition {
+            planet,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        }
+        .varga_flags()
+    }
+
+    #[test]
+    fn pushkara_navamsas_fall_in_benefic_signs() {
+        for part in 0..108 {
+            let middle = (part as f64 + 0.5) * PART;
+            if flags(CelestialBody::Sun, middle).pushkara_navamsa {
+                assert!(
+                    matches!(
+                        navamsa_sign(middle),
+                        ZodiacSign::Taurus
+                            | ZodiacSign::Cancer
+                            | ZodiacSign::Virgo
+                            | ZodiacSign::Libra
+                            | ZodiacSign::Sagittarius
+                            | ZodiacSign::Pisces
+                    ),
+                    "part {}",
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn varga_flags_of_known_degrees() {
+        // First navamsa of Aries is Aries itself
+        assert!(flags(CelestialBody::Mars, 1.0).vargottama);
+        assert!(!flags(CelestialBody::Mars, 5.0).vargottama);
+        // 20°30' Aries lies in its 21st degree
+        assert!(flags(CelestialBody::Mars, 20.5).pushkara_bhaga);
+        assert!(!flags(CelestialBody::Mars, 21.5).pushkara_bhaga);
+
+        let sun = flags(CelestialBody::Sun, 10.4);
+        assert!(sun.deeply_exalted);
+        assert!((sun.deep_exaltation_distance.unwrap() - 0.4).abs() < 1e-9);
+        assert_eq!(
+            flags(CelestialBody::Rahu, 10.0).deep_exaltation_distance,
+            None
+        );
+    }
+}
@@ -0,0 +1,779 @@
+use super::*;
+
+// ---------------------------
+// ## Tajika Strength
+// ---------------------------
+
+/// Egyptian terms (hadda) of each sign from Aries: the lord of each term and the degrees
+/// it spans, in order from 0°.
+const HADDAS: [[(CelestialBody, f64); 5]; 12] = {
+    use CelestialBody::*;
+    [
+        [
+            (Jupiter, 6.0),
+            (Venus, 6.0),
+            (Mercury, 8.0),
+            (Mars, 5.0),
+            (Saturn, 5.0),
+        ],
+        [
+            (Venus, 8.0),
+            (Mercury, 6.0),
+            (Jupiter, 8.0),
+            (Saturn, 5.0),
+            (Mars, 3.0),
+        ],
+        [
+            (Mercury, 6.0),
+            (Jupiter, 6.0),
+            (Venus, 5.0),
+            (Mars, 7.0),
+            (Saturn, 6.0),
+        ],
+        [
+            (Mars, 7.0),
+            (Venus, 6.0),
+            (Mercury, 6.0),
+            (Jupiter, 7.0),
+            (Saturn, 4.0),
+        ],
+        [
+            (Jupiter, 6.0),
+            (Venus, 5.0),
+            (Saturn, 7.0),
+            (Mercury, 6.0),
+            (Mars, 6.0),
+        ],
+        [
+            (Mercury, 7.0),
+            (Venus, 10.0),
+            (Jupiter, 4.0),
+            (Mars, 7.0),
+            (Saturn, 2.0),
+        ],
+        [
+            (Saturn, 6.0),
+            (Mercury, 8.0),
+            (Jupiter, 7.0),
+            (Venus, 7.0),
+            (Mars, 2.0),
+        ],
+        [
+            (Mars, 7.0),
+            (Venus, 4.0),
+            (Mercury, 8.0),
+            (Jupiter, 5.0),
+            (Saturn, 6.0),
+        ],
+        [
+            (Jupiter, 12.0),
+            (Venus, 5.0),
+            (Mercury, 4.0),
+            (Saturn, 5.0),
+            (Mars, 4.0),
+        ],
+        [
+            (Mercury, 7.0),
+            (Jupiter, 7.0),
+            (Venus, 8.0),
+            (Saturn, 4.0),
+            (Mars, 4.0),
+        ],
+        [
+            (Mercury, 7.0),
+            (Venus, 6.0),
+            (Jupiter, 7.0),
+            (Mars, 5.0),
+            (Saturn, 5.0),
+        ],
+        [
+            (Venus, 12.0),
+            (Jupiter, 4.0),
+            (Mercury, 3.0),
+            (Mars, 9.0),
+            (Saturn, 2.0),
+        ],
+    ]
+};
+
+/// Lord of the Egyptian term holding `longitude`.
+pub fn hadda_lord(longitude: f64) -> CelestialBody {
+    let longitude = longitude.rem_euclid(360.0);
+    let terms = &HADDAS[(longitude / 30.0) as usize % 12];
+    let mut end = 0.0;
+    for &(lord, span) in terms {
+        end += span;
+        if longitude % 30.0 < end {
+            return lord;
+        }
+    }
+    terms[4].0
+}
+
+/// Pancha-vargeeya bala, the Tajika strength of one of the seven planets out of 20:
+/// a quarter of its strength by sign (out of 30), exaltation (20), hadda (15), drekkana
+/// (10) and navamsa (5). In each varga a planet takes the full share in its own
+/// division, half in a friend's, a quarter in a neutral's and an eighth in an enemy's.
+/// The nodes score nothing.
+pub fn pancha_vargeeya_bala(planet: &PlanetPosition, friendships: &FriendshipMatrix) -> f64 {
+    let body = planet.planet;
+    let Some(exaltation) = body.exaltation_degree() else {
+        return 0.0;
+    };
+    let share = |lord: CelestialBody, full: f64| {
+        if lord == body {
+            return full;
+        }
+        match friendships.relationship(body, lord) {
+            Relationship::Friend => full / 2.0,
+            Relationship::Neutral => full / 4.0,
+            Relationship::Enemy => full / 8.0,
+        }
+    };
+
+    let longitude = planet.longitude.degrees();
+    let sign = (longitude / 30.0) as usize % 12;
+    let drekkana = (sign + 4 * ((longitude % 30.0) / 10.0) as usize) % 12;
+    let distance = (longitude - exaltation).rem_euclid(360.0);
+
+    let kshetra = share(ZodiacSign::from_longitude(longitude).lord(), 30.0);
+    let uchcha = (180.0 - distance.min(360.0 - distance)) / 9.0;
+    let hadda = share(hadda_lord(longitude), 15.0);
+    let drekkana = share(
+        ZodiacSign::from_longitude(drekkana as f64 * 30.0).lord(),
+        10.0,
+    );
+    let navamsa = share(
+        ZodiacSign::from_longitude(navamsa_longitude(longitude)).lord(),
+        5.0,
+    );
+    (kshetra + uchcha + hadda + drekkana + navamsa) / 4.0
+}
+
+/// Tajika aspect between two signs, by how many signs the second lies from the first.
+/// Signs 2, 6, 8 and 12 apart do not see each other.
+#[cfg(not(feature = "model-only"))]
+fn tajika_aspect(from: ZodiacSign, to: ZodiacSign) -> Option<Aspect> {
+    match (to as usize + 12 - from as usize) % 12 + 1 {
+        1 => Some(Aspect::Conjunction),
+        3 | 11 => Some(Aspect::Sextile),
+        4 | 10 => Some(Aspect::Square),
+        5 | 9 => Some(Aspect::Trine),
+        7 => Some(Aspect::Opposition),
+        _ => None,
+    }
+}
+
+/// Deeptamsha, the orb of a planet's light in Tajika.
+#[cfg(not(feature = "model-only"))]
+fn deeptamsha(body: CelestialBody) -> Option<f64> {
+    match body {
+        CelestialBody::Sun => Some(15.0),
+        CelestialBody::Moon => Some(12.0),
+        CelestialBody::Mars => Some(8.0),
+        CelestialBody::Mercury => Some(7.0),
+        CelestialBody::Jupiter => Some(9.0),
+        CelestialBody::Venus => Some(7.0),
+        CelestialBody::Saturn => Some(9.0),
+        _ => None,
+    }
+}
+
+// ---------------------------
+// ## Report Types
+// ---------------------------
+
+/// The progressed point of the annual chart: the natal lagna sign moved on one sign for
+/// every completed year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Muntha {
+    pub sign: ZodiacSign,
+    /// House of the annual chart, counted in whole signs from its lagna.
+    pub house: House,
+    pub lord: CelestialBody,
+}
+
+/// The five Panchadhikari, the office-bearers from whom the year lord is chosen.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Office {
+    MunthaLord,
+    BirthLagnaLord,
+    YearLagnaLord,
+    /// Lord of the triplicity of the annual lagna, by day or by night.
+    TriRashiLord,
+    /// Lord of the Sun's sign for a day return, of the Moon's for a night one.
+    DinaRatriLord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct OfficeBearer {
+    pub office: Office,
+    pub planet: CelestialBody,
+    /// Pancha-vargeeya bala out of 20.
+    pub strength: f64,
+    pub aspects_lagna: bool,
+}
+
+/// How two planets in Tajika aspect are moving relative to each other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TajikaYoga {
+    /// Applying: the gap between their degrees is closing. Promises the matter.
+    Ithasala,
+    /// Separating: the gap is opening. The matter is past or slipping away.
+    Ishrafa,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TajikaAspect {
+    pub planet1: CelestialBody,
+    pub planet2: CelestialBody,
+    /// Sextiles and trines are friendly; conjunctions, squares and oppositions are open
+    /// or hidden enmity.
+    pub aspect: Aspect,
+    pub yoga: TajikaYoga,
+    /// Gap between the two planets' degrees within their signs.
+    pub orb: f64,
+}
+
+/// Sensitive points of the annual chart. Each is `a - b + c`; see [`Saham::formula`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Saham {
+    Punya,
+    Vidya,
+    Yashas,
+    Mitra,
+    Mahatmya,
+    Asha,
+    Samartha,
+    Bhratri,
+    Gaurava,
+    Pitri,
+    Matri,
+    Putra,
+    Vivaha,
+    Karma,
+    Roga,
+    Mrityu,
+    Paradesa,
+    Artha,
+}
+
+/// A term of a saham formula.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SahamPoint {
+    Lagna,
+    Body(CelestialBody),
+    Cusp(House),
+    LordOf(House),
+    Saham(Saham),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SahamInfo {
+    pub saham: Saham,
+    pub longitude: f64,
+    pub sign: ZodiacSign,
+    pub house: House,
+}
+
+/// A period of the Mudda dasha, Vimshottari compressed into one year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MuddaPeriod {
+    pub lord: Dasha,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// The Tajika annual chart and what is read from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct VarshaphalReport {
+    pub year: i32,
+    /// Completed years of age at the return.
+    pub age: u32,
+    /// Moment the Sun comes back to its sidereal birth longitude.
+    pub return_time: DateTime<Utc>,
+    /// Start of the next year, when the following return falls.
+    pub next_return_time: DateTime<Utc>,
+    /// Cast for the return at the birthplace.
+    pub chart: ChartInfo,
+    /// The Sun is above the horizon at the return.
+    pub day: bool,
+    pub muntha: Muntha,
+    pub office_bearers: Vec<OfficeBearer>,
+    /// Varsheshwara, the lord of the year.
+    pub year_lord: CelestialBody,
+    pub tajika_aspects: Vec<TajikaAspect>,
+    pub sahams: Vec<SahamInfo>,
+    pub mudda_dasha: Vec<MuddaPeriod>,
+}
+
+/// A Tithi Pravesha chart: the lunar analogue of the solar return, cast when the Moon is
+/// as far ahead of the Sun as at birth with the Sun back near its birth longitude.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TithiPravesha {
+    pub year: i32,
+    /// Completed years of age at the solar return the pravesha falls nearest.
+    pub age: u32,
+    pub pravesha_time: DateTime<Utc>,
+    /// Degrees the Moon is ahead of the Sun, the same as at birth.
+    pub elongation: f64,
+    pub tithi: Tithi,
+    pub paksha: Paksha,
+    /// Weekday of the pravesha, counted from sunrise.
+    pub vara: Vara,
+    /// Lord of the year: the lord of the weekday.
+    pub year_lord: CelestialBody,
+    /// Cast for the pravesha at the birthplace.
+    pub chart: ChartInfo,
+}
+
+impl Saham {
+    pub fn all() -> [Saham; 18] {
+        use Saham::*;
+        [
+            Punya, Vidya, Yashas, Mitra, Mahatmya, Asha, Samartha, Bhratri, Gaurava, Pitri, Matri,
+            Putra, Vivaha, Karma, Roga, Mrityu, Paradesa, Artha,
+        ]
+    }
+
+    /// The saham's `(a, b, c)` for `a - b + c` in a day chart, and whether `a` and `b`
+    /// swap places in a night chart.
+    pub fn formula(&self) -> (SahamPoint, SahamPoint, SahamPoint, bool) {
+        use CelestialBody::*;
+        use SahamPoint::{Body, Cusp, Lagna, LordOf};
+        match self {
+            Saham::Punya => (Body(Moon), Body(Sun), Lagna, true),
+            Saham::Vidya => (Body(Sun), Body(Moon), Lagna, true),
+            Saham::Yashas => (Body(Jupiter), SahamPoint::Saham(Saham::Punya), Lagna, true),
+            Saham::Mitra => (
+                Body(Jupiter),
+                SahamPoint::Saham(Saham::Punya),
+                Body(Venus),
+                true,
+            ),
+            Saham::Mahatmya => (SahamPoint::Saham(Saham::Punya), Body(Mars), Lagna, true),
+            Saham::Asha => (Body(Saturn), Body(Mars), Lagna, true),
+            Saham::Samartha => (Body(Mars), LordOf(House::First), Lagna, true),
+            Saham::Bhratri => (Body(Jupiter), Body(Saturn), Lagna, false),
+            Saham::Gaurava => (Body(Jupiter), Body(Moon), Body(Sun), true),
+            Saham::Pitri => (Body(Saturn), Body(Sun), Lagna, true),
+            Saham::Matri => (Body(Moon), Body(Venus), Lagna, true),
+            Saham::Putra => (Body(Jupiter), Body(Moon), Lagna, true),
+            Saham::Vivaha => (Body(Venus), Body(Saturn), Lagna, true),
+            Saham::Karma => (Body(Mars), Body(Mercury), Lagna, true),
+            Saham::Roga => (Lagna, Body(Moon), Lagna, false),
+            Saham::Mrityu => (Cusp(House::Eighth), Body(Moon), Body(Saturn), false),
+            Saham::Paradesa => (Cusp(House::Ninth), LordOf(House::Ninth), Lagna, false),
+            Saham::Artha => (Cusp(House::Second), LordOf(House::Second), Lagna, false),
+        }
+    }
+}
+
+/// Tri-rashi pati of each lagna sign from Aries, by day and by night, after the Tajika
+/// Neelakanthi.
+#[cfg(not(feature = "model-only"))]
+fn tri_rashi_lord(lagna: ZodiacSign, day: bool) -> CelestialBody {
+    use CelestialBody::*;
+    const LORDS: [(CelestialBody, CelestialBody); 12] = [
+        (Sun, Jupiter),
+        (Venus, Moon),
+        (Saturn, Mercury),
+        (Venus, Mars),
+        (Jupiter, Sun),
+        (Moon, Venus),
+        (Mercury, Saturn),
+        (Mars, Venus),
+        (Saturn, Saturn),
+        (Mars, Moon),
+        (Jupiter, Jupiter),
+        (Moon, Mars),
+    ];
+    let (by_day, by_night) = LORDS[lagna as usize];
+    if day {
+        by_day
+    } else {
+        by_night
+    }
+}
+
+// ---------------------------
+// ## Calculation
+// ---------------------------
+
+/// Reads sahams off an annual chart, resolving sahams built on other sahams.
+#[cfg(not(feature = "model-only"))]
+struct SahamChart<'a> {
+    chart: &'a ChartInfo,
+    day: bool,
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SahamChart<'_> {
+    fn point(&self, point: SahamPoint) -> Option<f64> {
+        let cusp = |house: House| self.chart.houses.get(house as usize - 1);
+        match point {
+            SahamPoint::Lagna => Some(self.chart.ascendant.longitude()),
+            SahamPoint::Body(body) => self
+                .chart
+                .planets
+                .iter()
+                .find(|planet| planet.planet == body)
+                .map(|planet| planet.longitude.degrees()),
+            SahamPoint::Cusp(house) => cusp(house).map(HouseCusp::longitude),
+            SahamPoint::LordOf(house) => {
+                let lord = cusp(house)?.sign.lord();
+                self.point(SahamPoint::Body(lord))
+            }
+            SahamPoint::Saham(saham) => self.longitude(saham),
+        }
+    }
+
+    /// `a - b + c`, with 30° more when `c` does not fall between `b` and `a` counting
+    /// forward from `b`.
+    fn longitude(&self, saham: Saham) -> Option<f64> {
+        let (mut a, mut b, c, reverses) = saham.formula();
+        if saham == Saham::Samartha && self.chart.ascendant.sign.lord() == CelestialBody::Mars {
+            // With Mars ruling the lagna, Jupiter stands in for the lagna lord
+            b = SahamPoint::Body(CelestialBody::Jupiter);
+        }
+        if reverses && !self.day {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let (a, b, c) = (self.point(a)?, self.point(b)?, self.point(c)?);
+        let correction = if (c - b).rem_euclid(360.0) > (a - b).rem_euclid(360.0) {
+            30.0
+        } else {
+            0.0
+        };
+        Some((a - b + c + correction).rem_euclid(360.0))
+    }
+}
+
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The Tajika annual chart for the year of life starting in calendar year `year`,
+    /// cast at the birthplace for the moment the sidereal Sun returns to its birth
+    /// longitude.
+    pub fn calculate_varshaphal(
+        &self,
+        birth_info: &BirthInfo,
+        year: i32,
+    ) -> Result<VarshaphalReport, AstrologyError> {
+        let age = year - birth_info.date_time.year();
+        if age < 0 {
+            return Err(AstrologyError::DateOutOfRange(format!(
+                "{} is before the year of birth",
+                year
+            )));
+        }
+        let age = age as u32;
+
+        let house_system = self.conventions.house_system;
+        let natal = self.calculate_chart(birth_info, house_system)?;
+        let natal_sun =
+            self.sidereal_longitude(CelestialBody::Sun, date_to_julian_day(birth_info.date_time))?;
+        let return_jd = self.solar_return(birth_info, natal_sun, age)?;
+        let next_return_jd = self.solar_return(birth_info, natal_sun, age + 1)?;
+        let return_time = julian_day_to_date(return_jd);
+        let next_return_time = julian_day_to_date(next_return_jd);
+
+        let chart = self.calculate_chart(
+            &BirthInfo {
+                date_time: return_time,
+                location: birth_info.location.clone(),
+                person: None,
+            },
+            house_system,
+        )?;
+        let position = |body: CelestialBody| {
+            chart
+                .planets
+                .iter()
+                .find(|planet| planet.planet == body)
+                .ok_or(AstrologyError::MissingBody(body))
+        };
+        let sun = position(CelestialBody::Sun)?;
+        let moon = position(CelestialBody::Moon)?;
+        let day = (sun.house as usize) >= House::Seventh as usize;
+
+        let lagna = chart.ascendant.sign;
+        let muntha_sign =
+            ZodiacSign::from_longitude((natal.ascendant.sign as u32 + age) as f64 * 30.0);
+        let muntha = Muntha {
+            sign: muntha_sign,
+            house: House::from_index((muntha_sign as usize + 12 - lagna as usize) % 12 + 1)
+                .unwrap_or(House::First),
+            lord: muntha_sign.lord(),
+        };
+
+        let office_bearers = [
+            (Office::MunthaLord, muntha.lord),
+            (Office::BirthLagnaLord, natal.ascendant.sign.lord()),
+            (Office::YearLagnaLord, lagna.lord()),
+            (Office::TriRashiLord, tri_rashi_lord(lagna, day)),
+            (
+                Office::DinaRatriLord,
+                if day {
+                    sun.sign.lord()
+                } else {
+                    moon.sign.lord()
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|(office, planet)| {
+            let position = position(planet)?;
+            Ok(OfficeBearer {
+                office,
+                planet,
+                strength: pancha_vargeeya_bala(position, &self.friendships),
+                aspects_lagna: tajika_aspect(position.sign, lagna).is_some(),
+            })
+        })
+        .collect::<Result<Vec<_>, AstrologyError>>()?;
+
+        let sahams = {
+            let saham_chart = SahamChart { chart: &chart, day };
+            Saham::all()
+                .into_iter()
+                .filter_map(|saham| {
+                    let longitude = saham_chart.longitude(saham)?;
+                    Some(SahamInfo {
+                        saham,
+                        longitude,
+                        sign: ZodiacSign::from_longitude(longitude),
+                        house: chart.house_of(longitude),
+                    })
+                })
+                .collect()
+        };
+
+        let natal_moon = natal
+            .planets
+            .iter()
+            .find(|planet| planet.planet == CelestialBody::Moon)
+            .ok_or(AstrologyError::MissingBody(CelestialBody::Moon))?;
+
+        Ok(VarshaphalReport {
+            year,
+            age,
+            return_time,
+            next_return_time,
+            day,
+            muntha,
+            year_lord: year_lord(&office_bearers),
+            office_bearers,
+            tajika_aspects: tajika_aspects(&chart),
+            sahams,
+            mudda_dasha: mudda_dasha(
+                natal_moon.nakshatra.lord,
+                age,
+                return_time,
+                next_return_time,
+            ),
+            chart,
+        })
+    }
+
+    /// The Tithi Pravesha chart for the year of life starting in calendar year `year`:
+    /// the return of the birth tithi's exact Sun-Moon angle nearest the solar return, so
+    /// the Sun is within about fifteen degrees of its birth longitude.
+    pub fn calculate_tithi_pravesha(
+        &self,
+        birth_info: &BirthInfo,
+        year: i32,
+    ) -> Result<TithiPravesha, AstrologyError> {
+        let age = year - birth_info.date_time.year();
+        if age < 0 {
+            return Err(AstrologyError::DateOutOfRange(format!(
+                "{} is before the year of birth",
+                year
+            )));
+        }
+        let age = age as u32;
+
+        let birth_jd = date_to_julian_day(birth_info.date_time);
+        let elongation_at = |julian_day: JulianDay| -> Result<f64, AstrologyError> {
+            let sun = self.sidereal_longitude(CelestialBody::Sun, julian_day)?;
+            let moon = self.sidereal_longitude(CelestialBody::Moon, julian_day)?;
+            Ok((moon - sun).rem_euclid(360.0))
+        };
+        let elongation = elongation_at(birth_jd)?;
+        let natal_sun = self.sidereal_longitude(CelestialBody::Sun, birth_jd)?;
+
+        // From the solar return, step to the nearest recurrence of the elongation
+        let mut julian_day = self.solar_return(birth_info, natal_sun, age)?;
+        for _ in 0..20 {
+            let arc = signed_arc(elongation_at(julian_day)?, elongation);
+            if arc.abs() < 1e-7 {
+                break;
+            }
+            // The Moon gains on the Sun by about 12.19 degrees a day
+            julian_day -= arc / 12.19;
+        }
+        let pravesha_time = julian_day_to_date(julian_day);
+
+        let (tithi, paksha) = Tithi::from_index((elongation / 12.0).floor() as usize);
+        let vara = self
+            .calculate_panchanga(pravesha_time, &birth_info.location)?
+            .vara
+            .value;
+        let chart = self.calculate_chart(
+            &BirthInfo {
+                date_time: pravesha_time,
+                location: birth_info.location.clone(),
+                person: None,
+            },
+            self.conventions.house_system,
+        )?;
+
+        Ok(TithiPravesha {
+            year,
+            age,
+            pravesha_time,
+            elongation,
+            tithi,
+            paksha,
+            year_lord: vara.lord(),
+            vara,
+            chart,
+        })
+    }
+
+    /// Julian day on which the sidereal Sun is back at `natal_sun` after `age` years.
+    fn solar_return(
+        &self,
+        birth_info: &BirthInfo,
+        natal_sun: f64,
+        age: u32,
+    ) -> Result<JulianDay, AstrologyError> {
+        let around = date_to_julian_day(birth_info.date_time) + age as f64 * SIDEREAL_YEAR_DAYS;
+        let (julian_day, _) = self.return_julian_day(CelestialBody::Sun, natal_sun, around)?;
+        Ok(julian_day)
+    }
+}
+
+/// The strongest office-bearer that aspects the annual lagna, or the strongest of all
+/// when none does. The Moon rules the year only when no other candidate is left.
+#[cfg(not(feature = "model-only"))]
+fn year_lord(office_bearers: &[OfficeBearer]) -> CelestialBody {
+    let aspecting: Vec<&OfficeBearer> = office_bearers
+        .iter()
+        .filter(|bearer| bearer.aspects_lagna)
+        .collect();
+    let candidates = if aspecting.is_empty() {
+        office_bearers.iter().collect()
+    } else {
+        aspecting
+    };
+    let without_moon: Vec<&OfficeBearer> = candidates
+        .iter()
+        .copied()
+        .filter(|bearer| bearer.planet != CelestialBody::Moon)
+        .collect();
+    let candidates = if without_moon.is_empty() {
+        candidates
+    } else {
+        without_moon
+    };
+
+    candidates
+        .into_iter()
+        .reduce(|best, bearer| {
+            if bearer.strength > best.strength {
+                bearer
+            } else {
+                best
+            }
+        })
+        .map_or(CelestialBody::Sun, |bearer| bearer.planet)
+}
+
+/// Ithasala and Ishrafa between every pair of the seven planets in Tajika aspect whose
+/// degrees lie within their mean deeptamsha.
+#[cfg(not(feature = "model-only"))]
+fn tajika_aspects(chart: &ChartInfo) -> Vec<TajikaAspect> {
+    let planets: Vec<&PlanetPosition> = chart
+        .planets
+        .iter()
+        .filter(|planet| deeptamsha(planet.planet).is_some())
+        .collect();
+
+    let mut aspects = Vec::new();
+    for (i, first) in planets.iter().enumerate() {
+        for second in &planets[i + 1..] {
+            let Some(aspect) = tajika_aspect(first.sign, second.sign) else {
+                continue;
+            };
+            let orb = (deeptamsha(first.planet).unwrap_or(0.0)
+                + deeptamsha(second.planet).unwrap_or(0.0))
+                / 2.0;
+            let gap = first.longitude.degree_in_sign() - second.longitude.degree_in_sign();
+            if gap.abs() > orb {
+                continue;
+            }
+            let closing = gap * (first.speed - second.speed) < 0.0;
+            aspects.push(TajikaAspect {
+                planet1: first.planet,
+                planet2: second.planet,
+                aspect,
+                yoga: if closing {
+                    TajikaYoga::Ithasala
+                } else {
+                    TajikaYoga::Ishrafa
+                },
+                orb: gap.abs(),
+            });
+        }
+    }
+    aspects
+}
+
+/// Vimshottari periods in proportion over the year from `start` to `end`. The first
+/// lord is the birth star's lord moved on one place in the sequence for every completed
+/// year, the same as counting the birth star's number plus the age, less two, from the
+/// Sun.
+#[cfg(not(feature = "model-only"))]
+fn mudda_dasha(
+    birth_star_lord: CelestialBody,
+    age: u32,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<MuddaPeriod> {
+    let sequence = Dasha::sequence();
+    let first = sequence
+        .iter()
+        .position(|dasha| dasha.lord() == birth_star_lord)
+        .unwrap_or(0);
+    let duration = (end - start).num_seconds() as f64;
+
+    let mut period_start = start;
+    (0..sequence.len())
+        .map(|i| {
+            let lord = sequence[(first + age as usize + i) % sequence.len()];
+            let period_end = if i + 1 == sequence.len() {
+                end
+            } else {
+                period_start + ChronoDuration::seconds((duration * lord.years() / 120.0) as i64)
+            };
+            let period = MuddaPeriod {
+                lord,
+                start: period_start,
+                end: period_end,
+            };
+            period_start = period_end;
+            period
+        })
+        .collect()
+}
@@ -0,0 +1,437 @@
+use super::*;
+
+/// A chart with the positions the Swiss Ephemeris's own `swetest` gives for it, sidereal
+/// under Lahiri with the true node and Placidus houses: the engine's defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoldenChart {
+    /// The birth, as [`BirthInfo::parse`] reads it.
+    pub birth: &'static str,
+    /// Longitude of the ascendant in degrees.
+    pub ascendant: f64,
+    /// Longitude of each body in degrees.
+    pub positions: &'static [(CelestialBody, f64)],
+}
+
+/// Reference charts spread over a century and a half and both hemispheres of longitude.
+pub const GOLDEN_CHARTS: [GoldenChart; 4] = [
+    GoldenChart {
+        birth: "2000-01-01 12:00 Z @ 0,0",
+        ascendant: 347.5222842,
+        positions: &[
+            (CelestialBody::Sun, 256.5157004),
+            (CelestialBody::Moon, 199.4706024),
+            (CelestialBody::Mars, 304.1100833),
+            (CelestialBody::Mercury, 248.0360609),
+            (CelestialBody::Jupiter, 1.3998655),
+            (CelestialBody::Venus, 217.7125709),
+            (CelestialBody::Saturn, 16.5424409),
+            (CelestialBody::Rahu, 100.0996727),
+            (CelestialBody::Ketu, 280.0996727),
+            (CelestialBody::Uranus, 290.9559644),
+            (CelestialBody::Neptune, 279.3397895),
+            (CelestialBody::Pluto, 227.6015549),
+        ],
+    },
+    GoldenChart {
+        birth: "1991-06-18 07:10 +05:30 @ 11.2588,75.7804",
+        ascendant: 76.7763675,
+        positions: &[
+            (CelestialBody::Sun, 62.6132088),
+            (CelestialBody::Moon, 138.7347600),
+            (CelestialBody::Mars, 109.6066985),
+            (CelestialBody::Mercury, 63.7021730),
+            (CelestialBody::Jupiter, 108.1695541),
+            (CelestialBody::Venus, 107.9067171),
+            (CelestialBody::Saturn, 282.3053309),
+            (CelestialBody::Rahu, 265.3890456),
+            (CelestialBody::Ketu, 85.3890456),
+            (CelestialBody::Uranus, 258.7312310),
+            (CelestialBody::Neptune, 262.1639484),
+            (CelestialBody::Pluto, 204.2603414),
+        ],
+    },
+    GoldenChart {
+        birth: "1947-08-15 00:00 +05:30 @ 28.6139,77.2090",
+        ascendant: 37.7322700,
+        positions: &[
+            (CelestialBody::Sun, 117.9890172),
+            (CelestialBody::Moon, 93.9835382),
+            (CelestialBody::Mars, 67.4562377),
+            (CelestialBody::Mercury, 103.6742773),
+            (CelestialBody::Jupiter, 205.8776851),
+            (CelestialBody::Venus, 112.5611955),
+            (CelestialBody::Saturn, 110.4730506),
+            (CelestialBody::Rahu, 35.7426312),
+            (CelestialBody::Ketu, 215.7426312),
+            (CelestialBody::Uranus, 62.0534096),
+            (CelestialBody::Neptune, 165.7073230),
+            (CelestialBody::Pluto, 110.1079668),
+        ],
+    },
+    GoldenChart {
+        birth: "1879-03-14 10:50 Z @ 48.4,10.0",
+        ascendant: 79.4721642,
+        positions: &[
+            (CelestialBody::Sun, 331.3335064),
+            (CelestialBody::Moon, 232.3516936),
+            (CelestialBody::Mars, 274.7399786),
+            (CelestialBody::Mercury, 340.9696434),
+            (CelestialBody::Jupiter, 305.3098562),
+            (CelestialBody::Venus, 354.8107688),
+            (CelestialBody::Saturn, 342.0155876),
+            (CelestialBody::Rahu, 280.5516468),
+            (CelestialBody::Ketu, 100.5516468),
+            (CelestialBody::Uranus, 129.1143267),
+            (CelestialBody::Neptune, 15.6976379),
+            (CelestialBody::Pluto, 32.5513634),
+        ],
+    },
+];
+
+/// A calculated position further from its golden value than the tolerance allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deviation {
+    pub birth: &'static str,
+    /// The body, or `None` for the ascendant.
+    pub body: Option<CelestialBody>,
+    pub expected: f64,
+    /// The engine's longitude, or NaN when the body was missing from the chart.
+    pub actual: f64,
+}
+
+impl SwissEph {
+    /// Casts each of the [`GOLDEN_CHARTS`] and lists every position more than `tolerance`
+    /// degrees from its reference. The references assume the default conventions, so an
+    /// engine built with another ayanamsa or node will deviate everywhere.
+    pub fn check_golden_charts(&self, tolerance: f64) -> Result<Vec<Deviation>, AstrologyError> {
+        let options = ChartOptions {
+            house_system: HouseSystem::Placidus,
+            include_outer_planets: true,
+            include_asteroids: false,
+        };
+        let mut deviations = Vec::new();
+        for golden in &GOLDEN_CHARTS {
+            let birth = BirthInfo::parse(golden.birth).expect("golden births are well formed");
+            let chart = self.calculate_chart_with_options(&birth, &options)?;
+
+            let mut compare = |body: Option<CelestialBody>, expected: f64, actual: f64| {
+                let separation = Longitude::new(actual).separation(Longitude::new(expected));
+                if actual.is_nan() || separation > tolerance {
+                    deviations.push(Deviation {
+                        birth: golden.birth,
+                        body,
+                        expected,
+                        actual,
+                    });
+                }
+            };
+            compare(None, golden.ascendant, chart.ascendant.longitude());
+            for &(body, expected) in golden.positions {
+                let actual = chart
+                    .planets
+                    .iter()
+                    .find(|planet| planet.planet == body)
+                    .map_or(f64::NAN, |planet| planet.longitude.degrees());
+                compare(Some(body), expected, actual);
+            }
+        }
+        Ok(deviations)
+    }
+}
+
+/// What is wrong with `chart`, one line per fault: longitudes outside `0..360`, signs or
+/// houses that disagree with the longitude, cusps out of order, the nodes not opposite.
+/// Empty for a sound chart.
+pub fn chart_invariant_violations(chart: &ChartInfo) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    let ascendant = chart.ascendant.longitude();
+    if !(0.0..360.0).contains(&ascendant) {
+        violations.push(format!("ascendant at {}", ascendant));
+    }
+    if chart.houses.len() != 12 {
+        violations.push(format!("{} house cusps", chart.houses.len()));
+    }
+    for (index, cusp) in chart.houses.iter().enumerate() {
+        if cusp.house as usize != index + 1 {
+            violations.push(format!(
+                "cusp {} is for the {:?} house",
+                index + 1,
+                cusp.house
+            ));
+        }
+        if !(0.0..30.0).contains(&cusp.degree) {
+            violations.push(format!(
+                "{:?} cusp at {}° in its sign",
+                cusp.house, cusp.degree
+            ));
+        }
+    }
+
+    for planet in &chart.planets {
+        let longitude = planet.longitude.degrees();
+        if !(0.0..360.0).contains(&longitude) {
+            violations.push(format!("{:?} at {}", planet.planet, longitude));
+        }
+        if planet.sign != planet.longitude.sign() {
+            violations.push(format!(
+                "{:?} at {} is given as in {}",
+                planet.planet, longitude, planet.sign
+            ));
+        }
+        if !(1..=12).contains(&(planet.house as usize)) {
+            violations.push(format!(
+                "{:?} in house {}",
+                planet.planet, planet.house as usize
+            ));
+        }
+        if chart.houses.len() == 12 {
+            let house = house_from_cusps(&chart.houses, longitude);
+            if planet.house != house {
+                violations.push(format!(
+                    "{:?} at {} is given the {:?} house, not the {:?}",
+                    planet.planet, longitude, planet.house, house
+                ));
+            }
+        }
+    }
+
+    let node = |body| chart.planets.iter().find(|planet| planet.planet == body);
+    if let (Some(rahu), Some(ketu)) = (node(CelestialBody::Rahu), node(CelestialBody::Ketu)) {
+        let separation = rahu.longitude.separation(ketu.longitude);
+        if (separation - 180.0).abs() > 1e-9 {
+            violations.push(format!("the nodes are {}° apart", separation));
+        }
+    }
+    violations
+}
+
+/// What is wrong with the mahadashas `system` lays out from `birth`, and with their
+/// sub-periods: gaps or overlaps, a first period not running at birth, too short a span,
+/// or a round of the sequence not adding up to the system's years. Lengths may be out by
+/// the second each period is rounded to.
+pub fn dasha_invariant_violations(
+    system: &dyn DashaSystem,
+    moon_longitude: f64,
+    birth: DateTime<Utc>,
+    year_days: f64,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+    let year_seconds = year_days * 86400.0;
+    let periods = maha_periods(system, moon_longitude, birth, year_days);
+    let Some((first, last)) = periods.first().zip(periods.last()) else {
+        return vec!["no mahadashas".to_string()];
+    };
+
+    if !(first.start <= birth && birth < first.end) {
+        violations.push(format!(
+            "the first mahadasha runs {} to {}, not over the birth at {}",
+            first.start, first.end, birth
+        ));
+    }
+    violations.extend(contiguity_violations(&periods));
+
+    let covered = (last.end - birth).num_seconds() as f64 / year_seconds;
+    let slack = periods.len() as f64 / year_seconds;
+    if covered < DASHA_LIFESPAN_YEARS - slack {
+        violations.push(format!("the mahadashas cover only {} years", covered));
+    }
+
+    let (sequence, _) = system.maha_dashas(moon_longitude);
+    let cycle_years: f64 = sequence.iter().map(|&(_, years)| years).sum();
+    if let Some(round_end) = periods.get(sequence.len() - 1).map(|period| period.end) {
+        let round = (round_end - first.start).num_seconds() as f64 / year_seconds;
+        if (round - cycle_years).abs() > (sequence.len() + 1) as f64 / year_seconds {
+            violations.push(format!(
+                "a round of mahadashas lasts {} years, not {}",
+                round, cycle_years
+            ));
+        }
+    }
+
+    for maha in &periods {
+        let subs = split_period(system, moon_longitude, maha);
+        let fractions: f64 = system
+            .sub_periods(moon_longitude, maha.lord)
+            .iter()
+            .map(|&(_, fraction)| fraction)
+            .sum();
+        if (fractions - 1.0).abs() > 1e-9 {
+            violations.push(format!("{} sub-periods add up to {}", maha.lord, fractions));
+        }
+        violations.extend(contiguity_violations(&subs));
+        if let Some((sub_first, sub_last)) = subs.first().zip(subs.last()) {
+            let shortfall = (maha.end - sub_last.end).num_seconds();
+            if sub_first.start != maha.start || !(0..=subs.len() as i64).contains(&shortfall) {
+                violations.push(format!(
+                    "{} sub-periods run {} to {}, not {} to {}",
+                    maha.lord, sub_first.start, sub_last.end, maha.start, maha.end
+                ));
+            }
+        }
+    }
+    violations
+}
+
+fn contiguity_violations(periods: &[DashaPeriod]) -> Vec<String> {
+    let mut violations = Vec::new();
+    for period in periods {
+        if period.end <= period.start {
+            violations.push(format!(
+                "{} runs {} to {}",
+                period.lord, period.start, period.end
+            ));
+        }
+    }
+    for pair in periods.windows(2) {
+        if pair[0].end != pair[1].start {
+            violations.push(format!(
+                "{} ends {} but {} begins {}",
+                pair[0].lord, pair[0].end, pair[1].lord, pair[1].start
+            ));
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn birth(seconds: i64, latitude: f64, longitude: f64) -> BirthInfo {
+        BirthInfo {
+            date_time: DateTime::from_timestamp(seconds, 0).unwrap(),
+            location: Location::new(latitude, longitude, 0.0),
+            person: None,
+        }
+    }
+
+    // 1900 to 2100
+    const SECONDS: std::ops::Range<i64> = -2_208_988_800..4_102_444_800;
+
+    #[test]
+    fn charts_match_swetest() {
+        let deviations = SwissEph::new().unwrap().check_golden_charts(1e-4).unwrap();
+        assert!(deviations.is_empty(), "{:#?}", deviations);
+    }
+
+    proptest! {
+        #[test]
+        fn longitudes_are_normalized(degrees in -1e6..1e6f64) {
+            let longitude = Longitude::new(degrees).degrees();
+            prop_assert!((0.0..360.0).contains(&longitude));
+            let turns = (degrees - longitude) / 360.0;
+            prop_assert!((turns - turns.round()).abs() < 1e-9);
+        }
+
+        #[test]
+        fn dashas_are_contiguous(moon in 0.0..360.0f64, seconds in SECONDS) {
+            let birth = DateTime::from_timestamp(seconds, 0).unwrap();
+            let systems: [&dyn DashaSystem; 5] = [
+                &VimshottariDasha,
+                &YoginiDasha,
+                &AshtottariDasha,
+                &KalachakraDasha,
+                &DwisaptatiSamaDasha,
+            ];
+            for system in systems {
+                let violations = dasha_invariant_violations(system, moon, birth, 365.25);
+                prop_assert!(violations.is_empty(), "{:?}: {:#?}", system.kind(), violations);
+            }
+        }
+
+        #[test]
+        fn vimshottari_rounds_last_120_years(moon in 0.0..360.0f64, seconds in SECONDS) {
+            let birth = DateTime::from_timestamp(seconds, 0).unwrap();
+            let periods = maha_periods(&VimshottariDasha, moon, birth, 365.25);
+            let years = (periods[8].end - periods[0].start).num_seconds() as f64
+                / (365.25 * 86400.0);
+            prop_assert!((years - 120.0).abs() < 1e-6, "{}", years);
+        }
+    }
+
+    #[test]
+    fn placidus_falls_back_above_the_arctic_circle() {
+        let ephemeris = SwissEph::new().unwrap();
+        // Tromsø, at 69.6° north, on the winter solstice of 2000
+        let tromso = birth(977_400_000, 69.65, 18.96);
+        let chart = ephemeris
+            .calculate_chart(&tromso, HouseSystem::Placidus)
+            .unwrap();
+        assert_eq!(chart.house_system, HouseSystem::Porphyry);
+        assert_eq!(chart.warnings.len(), 1);
+        assert!(chart_invariant_violations(&chart).is_empty());
+
+        let delhi = ephemeris
+            .calculate_chart(&birth(977_400_000, 28.61, 77.21), HouseSystem::Placidus)
+            .unwrap();
+        assert_eq!(delhi.house_system, HouseSystem::Placidus);
+        assert!(delhi.warnings.is_empty());
+    }
+
+    #[test]
+    fn charts_need_a_valid_location() {
+        let ephemeris = SwissEph::new().unwrap();
+        for (latitude, longitude) in [(91.0, 0.0), (0.0, 180.5), (f64::NAN, 0.0)] {
+            let result =
+                ephemeris.calculate_chart(&birth(0, latitude, longitude), HouseSystem::WholeSign);
+            assert!(matches!(
+                result,
+                Err(AstrologyError::BirthInfo(
+                    BirthInfoError::InvalidCoordinates(_)
+                ))
+            ));
+        }
+        assert!(Location::try_new(28.61, 77.21, 5.5).is_ok());
+        assert!(Location::try_new(28.61, 77.21, 15.0).is_err());
+    }
+
+    #[test]
+    fn reports_do_not_depend_on_the_clock() {
+        let ephemeris = SwissEph::new().unwrap();
+        let options = ReportOptions::at(DateTime::from_timestamp(1_700_000_000, 0).unwrap());
+        let report = || {
+            let report = Report::calculate_with_options(
+                &ephemeris,
+                "Native",
+                birth(977_400_000, 28.61, 77.21),
+                Gender::Female,
+                options,
+            )
+            .unwrap();
+            serde_json::to_value(report).unwrap()
+        };
+        let first = report();
+        assert_eq!(first, report());
+        assert_eq!(
+            first["settings"]["reference_time"],
+            serde_json::to_value(options.reference_time).unwrap()
+        );
+        assert_eq!(first["settings"]["ayanamsa"], "Lahiri");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn charts_are_consistent(
+            seconds in SECONDS,
+            latitude in -89.9..89.9f64,
+            longitude in -180.0..180.0f64,
+            whole_sign in any::<bool>(),
+        ) {
+            let options = ChartOptions {
+                house_system: if whole_sign { HouseSystem::WholeSign } else { HouseSystem::Placidus },
+                include_outer_planets: true,
+                include_asteroids: false,
+            };
+            let chart = SwissEph::new()
+                .unwrap()
+                .calculate_chart_with_options(&birth(seconds, latitude, longitude), &options)
+                .unwrap();
+            let violations = chart_invariant_violations(&chart);
+            prop_assert!(violations.is_empty(), "{:#?}", violations);
+        }
+    }
+}
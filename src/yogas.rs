@@ -1,478 +1,1281 @@
-use std::sync::Arc;
-
 use super::*;
 
-impl SwissEph {
-    /// Identifies and calculates yogas based on Vedic astrology principles.
-    pub fn calculate_yogas(&self, chart_info: &ChartInfo) -> Vec<YogaInfo> {
-        let mut yogas = Vec::new();
-
-        // Helper function to determine if a planet is in Kendra (1, 4, 7, 10 houses)
-        let is_in_kendra = |house: House| {
-            matches!(
-                house,
-                House::First | House::Fourth | House::Seventh | House::Tenth
-            )
-        };
+// ---------------------------
+// ## Yoga Rules
+// ---------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum YogaCategory {
+    Mahapurusha,
+    Raja,
+    Dhana,
+    Daridra,
+    Chandra,
+    Surya,
+    Viparita,
+    Parivartana,
+    NeechaBhanga,
+    Nabhasa,
+    Arishta,
+    Other,
+}
 
-        // Helper function to determine if a planet is in own sign or exalted
-        let is_in_own_sign_or_exalted = |planet: CelestialBody, sign: ZodiacSign| {
-            let own_signs = match planet {
-                CelestialBody::Sun => vec![ZodiacSign::Leo],
-                CelestialBody::Moon => vec![ZodiacSign::Cancer],
-                CelestialBody::Mars => vec![ZodiacSign::Aries, ZodiacSign::Scorpio],
-                CelestialBody::Mercury => vec![ZodiacSign::Gemini, ZodiacSign::Virgo],
-                CelestialBody::Jupiter => vec![ZodiacSign::Sagittarius, ZodiacSign::Pisces],
-                CelestialBody::Venus => vec![ZodiacSign::Taurus, ZodiacSign::Libra],
-                CelestialBody::Saturn => vec![ZodiacSign::Capricorn, ZodiacSign::Aquarius],
-                _ => vec![],
-            };
-            let exalted_sign = match planet {
-                CelestialBody::Sun => ZodiacSign::Aries,
-                CelestialBody::Moon => ZodiacSign::Taurus,
-                CelestialBody::Mars => ZodiacSign::Capricorn,
-                CelestialBody::Mercury => ZodiacSign::Virgo,
-                CelestialBody::Jupiter => ZodiacSign::Cancer,
-                CelestialBody::Venus => ZodiacSign::Pisces,
-                CelestialBody::Saturn => ZodiacSign::Libra,
-                _ => ZodiacSign::Aries, // Default placeholder
-            };
-            own_signs.contains(&sign) || sign == exalted_sign
-        };
+/// A planet named outright, or found from the chart.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Subject {
+    Planet(CelestialBody),
+    /// Lord of the house, counted in whole signs from the lagna.
+    LordOf(u8),
+    /// Lord of the sign the planet occupies.
+    Dispositor(CelestialBody),
+}
 
-        // Gajakesari Yoga: Jupiter in Kendra from Moon
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            if let Some(jupiter) = chart_info
-                .planets
-                .iter()
-                .find(|p| p.planet == CelestialBody::Jupiter)
-            {
-                let moon_house_num = moon.house as u8;
-                let jupiter_house_num = jupiter.house as u8;
-                let diff = (12 + jupiter_house_num - moon_house_num) % 12;
-                if diff == 0 || diff == 4 || diff == 7 || diff == 10 {
-                    yogas.push(YogaInfo {
-                        yoga: Yoga::Gajakesari,
-                        strength: 1.0,
-                        involved_planets: vec![CelestialBody::Moon, CelestialBody::Jupiter],
-                    });
-                }
-            }
-        }
+/// Where houses are counted from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Reference {
+    Lagna,
+    Subject(Subject),
+}
 
-        // Budhaditya Yoga: Sun and Mercury in the same house
-        if let Some(sun) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Sun)
-        {
-            if let Some(mercury) = chart_info
-                .planets
-                .iter()
-                .find(|p| p.planet == CelestialBody::Mercury)
-            {
-                if sun.house == mercury.house {
-                    yogas.push(YogaInfo {
-                        yoga: Yoga::Budhaditya,
-                        strength: 1.0,
-                        involved_planets: vec![CelestialBody::Sun, CelestialBody::Mercury],
-                    });
-                }
-            }
-        }
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Dignity {
+    Exalted,
+    Moolatrikona,
+    OwnSign,
+    Debilitated,
+}
 
-        // Panch Mahapurusha Yogas
-        for planet in &[
-            CelestialBody::Mars,
-            CelestialBody::Mercury,
-            CelestialBody::Jupiter,
-            CelestialBody::Venus,
-            CelestialBody::Saturn,
-        ] {
-            if let Some(p) = chart_info.planets.iter().find(|p| p.planet == *planet) {
-                if is_in_kendra(p.house) && is_in_own_sign_or_exalted(*planet, p.sign) {
-                    let yoga = match planet {
-                        CelestialBody::Mars => Yoga::Ruchaka,
-                        CelestialBody::Mercury => Yoga::Bhadra,
-                        CelestialBody::Jupiter => Yoga::Hamsa,
-                        CelestialBody::Venus => Yoga::Malavya,
-                        CelestialBody::Saturn => Yoga::Shash,
-                        _ => continue,
-                    };
-                    yogas.push(YogaInfo {
-                        yoga,
-                        strength: 1.0,
-                        involved_planets: vec![*planet],
-                    });
-                }
-            }
-        }
+/// A yoga's condition written as data. Houses are counted in whole signs, so a rule reads
+/// the same whatever house system the chart was cast with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum YogaRule {
+    All(Vec<YogaRule>),
+    Any(Vec<YogaRule>),
+    Not(Box<YogaRule>),
+    /// Both subjects are the same planet, as when one planet lords two houses.
+    Same(Subject, Subject),
+    /// The subject occupies one of `houses` counted from `from`.
+    InHouse {
+        subject: Subject,
+        from: Reference,
+        houses: Vec<u8>,
+    },
+    HasDignity {
+        subject: Subject,
+        dignities: Vec<Dignity>,
+    },
+    /// Both in the same sign.
+    Conjunct(Subject, Subject),
+    /// Conjunct, in opposite signs and so in mutual aspect, or in each other's signs.
+    Associated(Subject, Subject),
+    /// Each in a sign ruled by the other.
+    Exchange(Subject, Subject),
+    /// At least one of `planets` occupies one of `houses` counted from `from`.
+    Occupied {
+        from: Reference,
+        houses: Vec<u8>,
+        planets: Vec<CelestialBody>,
+    },
+    /// Every one of `planets` occupies one of `houses` counted from `from`.
+    AllIn {
+        from: Reference,
+        houses: Vec<u8>,
+        planets: Vec<CelestialBody>,
+    },
+    /// Every one of `planets` occupies one of `signs`.
+    AllInSigns {
+        planets: Vec<CelestialBody>,
+        signs: Vec<ZodiacSign>,
+    },
+    /// The seven planets between them occupy exactly this many signs.
+    SignsOccupied(u8),
+    /// Some sign holds at least this many of the seven planets.
+    Cluster(u8),
+    /// The seven planets all lie on one side of the Rahu-Ketu axis.
+    HemmedByNodes,
+}
 
-        // Adhi Yoga: Benefic planets in 6th, 7th, and 8th houses from Moon
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            let benefics = [
-                CelestialBody::Jupiter,
-                CelestialBody::Venus,
-                CelestialBody::Mercury,
-            ];
-            let houses_from_moon = |offset: u8| ((moon.house as u8 + offset - 1) % 12) + 1;
-            let mut benefic_planets_in_positions = vec![];
-
-            for &benefic in &benefics {
-                if let Some(planet) = chart_info.planets.iter().find(|p| p.planet == benefic) {
-                    let planet_house_num = planet.house as u8;
-                    if planet_house_num == houses_from_moon(6)
-                        || planet_house_num == houses_from_moon(7)
-                        || planet_house_num == houses_from_moon(8)
-                    {
-                        benefic_planets_in_positions.push(benefic);
-                    }
-                }
-            }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Condition {
+    pub description: String,
+    pub rule: YogaRule,
+}
 
-            if !benefic_planets_in_positions.is_empty() {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Adhi,
-                    strength: 1.0,
-                    involved_planets: benefic_planets_in_positions,
-                });
-            }
-        }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Effects {
+    pub description: String,
+    pub impact: Impact,
+}
 
-        // Chandra Mangala Yoga: Moon and Mars conjunction or mutual aspect
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            if let Some(mars) = chart_info
-                .planets
-                .iter()
-                .find(|p| p.planet == CelestialBody::Mars)
-            {
-                if moon.house == mars.house {
-                    yogas.push(YogaInfo {
-                        yoga: Yoga::Chandra,
-                        strength: 1.0,
-                        involved_planets: vec![CelestialBody::Moon, CelestialBody::Mars],
-                    });
-                }
-            }
-        }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Yoga {
+    pub name: String,
+    pub category: YogaCategory,
+    pub condition: Condition,
+    /// When this also holds, the yoga is formed but gives no result.
+    pub cancellation: Option<Condition>,
+    pub effects: Effects,
+}
 
-        // Sunapha Yoga: Planets (excluding Sun) in the 2nd house from Moon
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            let second_house_num = ((moon.house as u8) % 12) + 1;
-            let mut planets_in_second = vec![];
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct YogaInfo {
+    pub yoga: Yoga,
+    /// From 0 to 1, by the dignity of the planets forming the yoga. A cancelled yoga has
+    /// no strength.
+    pub strength: f64,
+    pub involved_planets: Vec<CelestialBody>,
+    pub cancelled: bool,
+}
 
-            for planet in chart_info
-                .planets
-                .iter()
-                .filter(|p| p.planet != CelestialBody::Sun)
-            {
-                if planet.house as u8 == second_house_num {
-                    planets_in_second.push(planet.planet);
-                }
-            }
+// ---------------------------
+// ## Catalog
+// ---------------------------
+
+const SEVEN: [CelestialBody; 7] = [
+    CelestialBody::Sun,
+    CelestialBody::Moon,
+    CelestialBody::Mars,
+    CelestialBody::Mercury,
+    CelestialBody::Jupiter,
+    CelestialBody::Venus,
+    CelestialBody::Saturn,
+];
+/// The five star planets, which alone form the Moon's and Sun's flanking yogas.
+const TARA_GRAHAS: [CelestialBody; 5] = [
+    CelestialBody::Mars,
+    CelestialBody::Mercury,
+    CelestialBody::Jupiter,
+    CelestialBody::Venus,
+    CelestialBody::Saturn,
+];
+const BENEFICS: [CelestialBody; 3] = [
+    CelestialBody::Mercury,
+    CelestialBody::Jupiter,
+    CelestialBody::Venus,
+];
+const MALEFICS: [CelestialBody; 5] = [
+    CelestialBody::Sun,
+    CelestialBody::Mars,
+    CelestialBody::Saturn,
+    CelestialBody::Rahu,
+    CelestialBody::Ketu,
+];
+const KENDRAS: [u8; 4] = [1, 4, 7, 10];
+const DUSTHANAS: [u8; 3] = [6, 8, 12];
+
+fn planet(body: CelestialBody) -> Subject {
+    Subject::Planet(body)
+}
 
-            if !planets_in_second.is_empty() {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Sunapha,
-                    strength: 1.0,
-                    involved_planets: planets_in_second,
-                });
-            }
-        }
+fn from_planet(body: CelestialBody) -> Reference {
+    Reference::Subject(Subject::Planet(body))
+}
 
-        // Anapha Yoga: Planets (excluding Sun) in the 12th house from Moon
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            let twelfth_house_num = ((moon.house as u8 + 10) % 12) + 1;
-            let mut planets_in_twelfth = vec![];
+fn in_house(subject: Subject, from: Reference, houses: &[u8]) -> YogaRule {
+    YogaRule::InHouse {
+        subject,
+        from,
+        houses: houses.to_vec(),
+    }
+}
 
-            for planet in chart_info
-                .planets
-                .iter()
-                .filter(|p| p.planet != CelestialBody::Sun)
-            {
-                if planet.house as u8 == twelfth_house_num {
-                    planets_in_twelfth.push(planet.planet);
-                }
-            }
+fn has_dignity(subject: Subject, dignities: &[Dignity]) -> YogaRule {
+    YogaRule::HasDignity {
+        subject,
+        dignities: dignities.to_vec(),
+    }
+}
 
-            if !planets_in_twelfth.is_empty() {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Anapha,
-                    strength: 1.0,
-                    involved_planets: planets_in_twelfth,
-                });
-            }
-        }
+fn occupied(from: Reference, houses: &[u8], planets: &[CelestialBody]) -> YogaRule {
+    YogaRule::Occupied {
+        from,
+        houses: houses.to_vec(),
+        planets: planets.to_vec(),
+    }
+}
 
-        // Durudhara Yoga: Planets (excluding Sun) in both 2nd and 12th houses from Moon
-        if yogas.iter().any(|y| y.yoga == Yoga::Sunapha)
-            && yogas.iter().any(|y| y.yoga == Yoga::Anapha)
-        {
-            let involved_planets: Vec<_> = yogas
-                .iter()
-                .filter(|y| y.yoga == Yoga::Sunapha || y.yoga == Yoga::Anapha)
-                .flat_map(|y| y.involved_planets.clone())
-                .collect();
-
-            yogas.push(YogaInfo {
-                yoga: Yoga::Duradhara,
-                strength: 1.0,
-                involved_planets,
-            });
-        }
+fn all_in(from: Reference, houses: &[u8], planets: &[CelestialBody]) -> YogaRule {
+    YogaRule::AllIn {
+        from,
+        houses: houses.to_vec(),
+        planets: planets.to_vec(),
+    }
+}
 
-        // Kemadruma Yoga: No planets (excluding Sun, Rahu, Ketu) in 2nd and 12th houses from Moon
-        if let Some(moon) = chart_info
-            .planets
-            .iter()
-            .find(|p| p.planet == CelestialBody::Moon)
-        {
-            let second_house_num = ((moon.house as u8) % 12) + 1;
-            let twelfth_house_num = ((moon.house as u8 + 10) % 12) + 1;
+fn not(rule: YogaRule) -> YogaRule {
+    YogaRule::Not(Box::new(rule))
+}
 
-            let planets_in_second_or_twelfth = chart_info
-                .planets
-                .iter()
-                .filter(|p| {
-                    p.planet != CelestialBody::Sun
-                        && p.planet != CelestialBody::Rahu
-                        && p.planet != CelestialBody::Ketu
-                })
-                .any(|p| {
-                    let house_num = p.house as u8;
-                    house_num == second_house_num || house_num == twelfth_house_num
-                });
-
-            if !planets_in_second_or_twelfth {
-                yogas.push(YogaInfo {
-                    yoga: Yoga::Kemadruma,
-                    strength: 1.0,
-                    involved_planets: vec![CelestialBody::Moon],
-                });
-            }
-        }
+fn yoga(
+    name: impl Into<String>,
+    category: YogaCategory,
+    condition: &str,
+    rule: YogaRule,
+    effect: &str,
+    impact: Impact,
+) -> Yoga {
+    Yoga {
+        name: name.into(),
+        category,
+        condition: Condition {
+            description: condition.to_string(),
+            rule,
+        },
+        cancellation: None,
+        effects: Effects {
+            description: effect.to_string(),
+            impact,
+        },
+    }
+}
 
-        // Parvata Yoga: Benefic planets in Kendras and malefic planets in upachaya houses (3, 6, 10, 11)
-        let benefic_planets = [
-            CelestialBody::Jupiter,
-            CelestialBody::Venus,
-            CelestialBody::Mercury,
-        ];
-        let malefic_planets = [
-            CelestialBody::Saturn,
-            CelestialBody::Mars,
-            CelestialBody::Sun,
-        ];
-
-        let benefics_in_kendra = chart_info
-            .planets
-            .iter()
-            .filter(|p| benefic_planets.contains(&p.planet) && is_in_kendra(p.house))
-            .count();
+impl Yoga {
+    fn cancelled_when(mut self, description: &str, rule: YogaRule) -> Self {
+        self.cancellation = Some(Condition {
+            description: description.to_string(),
+            rule,
+        });
+        self
+    }
 
-        let malefics_in_upachaya = chart_info
-            .planets
-            .iter()
-            .filter(|p| {
-                malefic_planets.contains(&p.planet)
-                    && matches!(
-                        p.house,
-                        House::Third | House::Sixth | House::Tenth | House::Eleventh
-                    )
-            })
-            .count();
-
-        if benefics_in_kendra > 0 && malefics_in_upachaya > 0 {
-            yogas.push(YogaInfo {
-                yoga: Yoga::Parvata,
-                strength: 1.0,
-                involved_planets: vec![], // Specific planets can be added if needed
-            });
+    /// The classical yogas this crate looks for: the Pancha Mahapurusha, Chandra and Surya
+    /// yogas, Raja and Dhana yogas between house lords, all 66 Parivartanas, Viparita and
+    /// Neecha Bhanga Raja yogas, Nabhasa yogas and the principal afflictions.
+    pub fn catalog() -> Vec<Yoga> {
+        use CelestialBody::*;
+        use Impact::{Negative, Neutral, Positive};
+        use YogaCategory::*;
+
+        let strong = [Dignity::Exalted, Dignity::Moolatrikona, Dignity::OwnSign];
+        let mut catalog = Vec::new();
+
+        for (name, body, effect) in [
+            ("Ruchaka", Mars, "Courage, command and physical strength."),
+            ("Bhadra", Mercury, "Eloquence, learning and skill in trade."),
+            (
+                "Hamsa",
+                Jupiter,
+                "Wisdom, virtue and respect of the learned.",
+            ),
+            (
+                "Malavya",
+                Venus,
+                "Comfort, beauty, vehicles and refined tastes.",
+            ),
+            (
+                "Sasa",
+                Saturn,
+                "Authority over many and success through discipline.",
+            ),
+        ] {
+            catalog.push(yoga(
+                format!("{} Yoga", name),
+                Mahapurusha,
+                &format!("{:?} in a kendra in its own or exaltation sign", body),
+                YogaRule::All(vec![
+                    in_house(planet(body), Reference::Lagna, &KENDRAS),
+                    has_dignity(planet(body), &strong),
+                ]),
+                effect,
+                Positive(On::Oneself, Trait::Fame, 8.0),
+            ));
         }
 
-        // Neechabhanga Raja Yoga: Cancellation of debilitation
-        for planet in &chart_info.planets {
-            if let Some(debilitated_sign) = match planet.planet {
-                CelestialBody::Sun => Some(ZodiacSign::Libra),
-                CelestialBody::Moon => Some(ZodiacSign::Scorpio),
-                CelestialBody::Mars => Some(ZodiacSign::Cancer),
-                CelestialBody::Mercury => Some(ZodiacSign::Pisces),
-                CelestialBody::Jupiter => Some(ZodiacSign::Capricorn),
-                CelestialBody::Venus => Some(ZodiacSign::Virgo),
-                CelestialBody::Saturn => Some(ZodiacSign::Aries),
-                _ => None,
-            } {
-                if planet.sign == debilitated_sign {
-                    // Check for cancellation conditions
-                    let cancellation = chart_info.planets.iter().any(|p| {
-                        // Debilitation lord in Kendra from Lagna
-                        let debilitation_lord = match planet.sign {
-                            ZodiacSign::Libra => CelestialBody::Venus,
-                            ZodiacSign::Scorpio => CelestialBody::Mars,
-                            ZodiacSign::Cancer => CelestialBody::Moon,
-                            ZodiacSign::Pisces => CelestialBody::Jupiter,
-                            ZodiacSign::Capricorn => CelestialBody::Saturn,
-                            ZodiacSign::Virgo => CelestialBody::Mercury,
-                            ZodiacSign::Aries => CelestialBody::Mars,
-                            _ => return false,
-                        };
-                        p.planet == debilitation_lord && is_in_kendra(p.house)
-                    });
-
-                    if cancellation {
-                        yogas.push(YogaInfo {
-                            yoga: Yoga::Raj,
-                            strength: 1.0,
-                            involved_planets: vec![planet.planet],
-                        });
-                    }
-                }
+        let moon = from_planet(Moon);
+        catalog.extend([
+            yoga(
+                "Sunapha Yoga",
+                Chandra,
+                "A planet other than the Sun in the 2nd from the Moon",
+                occupied(moon, &[2], &TARA_GRAHAS),
+                "Self-earned wealth and a good name.",
+                Positive(On::Oneself, Trait::Wealth, 5.0),
+            ),
+            yoga(
+                "Anapha Yoga",
+                Chandra,
+                "A planet other than the Sun in the 12th from the Moon",
+                occupied(moon, &[12], &TARA_GRAHAS),
+                "Good health, a pleasing manner and renown.",
+                Positive(On::Oneself, Trait::Health, 5.0),
+            ),
+            yoga(
+                "Durudhara Yoga",
+                Chandra,
+                "Planets other than the Sun on both sides of the Moon",
+                YogaRule::All(vec![
+                    occupied(moon, &[2], &TARA_GRAHAS),
+                    occupied(moon, &[12], &TARA_GRAHAS),
+                ]),
+                "Wealth, vehicles and generosity.",
+                Positive(On::Oneself, Trait::Generosity, 6.0),
+            ),
+            yoga(
+                "Kemadruma Yoga",
+                Chandra,
+                "No planet other than the Sun in the 2nd or 12th from the Moon",
+                not(occupied(moon, &[2, 12], &TARA_GRAHAS)),
+                "Poverty and struggle despite effort.",
+                Negative(On::Oneself, Trait::Wealth, 6.0),
+            )
+            .cancelled_when(
+                "A planet in a kendra from the Moon, or the Moon in a kendra",
+                YogaRule::Any(vec![
+                    occupied(moon, &KENDRAS, &TARA_GRAHAS),
+                    in_house(planet(Moon), Reference::Lagna, &KENDRAS),
+                ]),
+            ),
+            yoga(
+                "Gajakesari Yoga",
+                Chandra,
+                "Jupiter in a kendra from the Moon",
+                in_house(planet(Jupiter), moon, &KENDRAS),
+                "Intelligence, lasting reputation and prosperity.",
+                Positive(On::Oneself, Trait::Wisdom, 7.0),
+            )
+            .cancelled_when(
+                "Jupiter debilitated",
+                has_dignity(planet(Jupiter), &[Dignity::Debilitated]),
+            ),
+            yoga(
+                "Chandra-Mangala Yoga",
+                Chandra,
+                "The Moon and Mars together",
+                YogaRule::Conjunct(planet(Moon), planet(Mars)),
+                "Earnings through enterprise and trade.",
+                Positive(On::Oneself, Trait::Wealth, 5.0),
+            ),
+            yoga(
+                "Adhi Yoga",
+                Chandra,
+                "Mercury, Jupiter and Venus in the 6th, 7th and 8th from the Moon",
+                all_in(moon, &[6, 7, 8], &BENEFICS),
+                "Leadership, comfort and victory over enemies.",
+                Positive(On::Oneself, Trait::Leadership, 7.0),
+            ),
+            yoga(
+                "Vasumati Yoga",
+                Chandra,
+                "Mercury, Jupiter and Venus in upachayas from the Moon",
+                all_in(moon, &[3, 6, 10, 11], &BENEFICS),
+                "Steady accumulation of wealth.",
+                Positive(On::Oneself, Trait::Wealth, 6.0),
+            ),
+            yoga(
+                "Sakata Yoga",
+                Chandra,
+                "Jupiter in the 6th, 8th or 12th from the Moon",
+                in_house(planet(Jupiter), moon, &DUSTHANAS),
+                "Fortunes that rise and fall like a cart's wheel.",
+                Negative(On::Oneself, Trait::Wealth, 4.0),
+            )
+            .cancelled_when(
+                "Jupiter in a kendra from the lagna",
+                in_house(planet(Jupiter), Reference::Lagna, &KENDRAS),
+            ),
+            yoga(
+                "Amala Yoga",
+                Other,
+                "Only benefics in the 10th from the lagna",
+                YogaRule::All(vec![
+                    occupied(Reference::Lagna, &[10], &BENEFICS),
+                    not(occupied(Reference::Lagna, &[10], &MALEFICS)),
+                ]),
+                "Spotless reputation and lasting fame.",
+                Positive(On::Oneself, Trait::Fame, 6.0),
+            ),
+        ]);
+
+        let sun = from_planet(Sun);
+        catalog.extend([
+            yoga(
+                "Vesi Yoga",
+                Surya,
+                "A planet other than the Moon in the 2nd from the Sun",
+                occupied(sun, &[2], &TARA_GRAHAS),
+                "Balanced outlook and truthful speech.",
+                Positive(On::Oneself, Trait::Honesty, 4.0),
+            ),
+            yoga(
+                "Vasi Yoga",
+                Surya,
+                "A planet other than the Moon in the 12th from the Sun",
+                occupied(sun, &[12], &TARA_GRAHAS),
+                "Prosperity and a charitable nature.",
+                Positive(On::Oneself, Trait::Generosity, 4.0),
+            ),
+            yoga(
+                "Ubhayachari Yoga",
+                Surya,
+                "Planets other than the Moon on both sides of the Sun",
+                YogaRule::All(vec![
+                    occupied(sun, &[2], &TARA_GRAHAS),
+                    occupied(sun, &[12], &TARA_GRAHAS),
+                ]),
+                "Eminence equal to a king's.",
+                Positive(On::Oneself, Trait::Power, 6.0),
+            ),
+            yoga(
+                "Budhaditya Yoga",
+                Surya,
+                "The Sun and Mercury together",
+                YogaRule::Conjunct(planet(Sun), planet(Mercury)),
+                "Sharp intellect and skill.",
+                Positive(On::Oneself, Trait::Intellect, 6.0),
+            ),
+        ]);
+
+        catalog.extend([
+            yoga(
+                "Guru-Chandala Yoga",
+                Arishta,
+                "Jupiter with Rahu",
+                YogaRule::Conjunct(planet(Jupiter), planet(Rahu)),
+                "Unorthodox views and trouble from advisers.",
+                Negative(On::Oneself, Trait::Wisdom, 5.0),
+            ),
+            yoga(
+                "Surya Grahana Yoga",
+                Arishta,
+                "The Sun with Rahu or Ketu",
+                YogaRule::Any(vec![
+                    YogaRule::Conjunct(planet(Sun), planet(Rahu)),
+                    YogaRule::Conjunct(planet(Sun), planet(Ketu)),
+                ]),
+                "Strain on vitality and on relations with the father.",
+                Negative(On::Father, Trait::Health, 5.0),
+            ),
+            yoga(
+                "Chandra Grahana Yoga",
+                Arishta,
+                "The Moon with Rahu or Ketu",
+                YogaRule::Any(vec![
+                    YogaRule::Conjunct(planet(Moon), planet(Rahu)),
+                    YogaRule::Conjunct(planet(Moon), planet(Ketu)),
+                ]),
+                "Restless mind and strain on the mother.",
+                Negative(On::Mother, Trait::Emotions, 5.0),
+            ),
+            yoga(
+                "Kala Sarpa Yoga",
+                Arishta,
+                "All seven planets on one side of the Rahu-Ketu axis",
+                YogaRule::HemmedByNodes,
+                "Obstacles and delays until the nodes are appeased.",
+                Negative(On::Oneself, Trait::Luck, 6.0),
+            ),
+        ]);
+
+        for (name, house) in [("Harsha", 6), ("Sarala", 8), ("Vimala", 12)] {
+            catalog.push(yoga(
+                format!("{} Yoga", name),
+                Viparita,
+                &format!("Lord of the {} in the 6th, 8th or 12th", ordinal(house)),
+                in_house(Subject::LordOf(house), Reference::Lagna, &DUSTHANAS),
+                "Rise through the misfortune of adversaries.",
+                Positive(On::Oneself, Trait::Resilience, 6.0),
+            ));
+        }
+        catalog.extend([
+            yoga(
+                "Daridra Yoga",
+                Daridra,
+                "Lord of the 11th in the 6th, 8th or 12th",
+                in_house(Subject::LordOf(11), Reference::Lagna, &DUSTHANAS),
+                "Gains that slip away and recurring debt.",
+                Negative(On::Oneself, Trait::Wealth, 6.0),
+            ),
+            yoga(
+                "Duryoga",
+                Daridra,
+                "Lord of the 10th in the 6th, 8th or 12th",
+                in_house(Subject::LordOf(10), Reference::Lagna, &DUSTHANAS),
+                "Effort that goes unrecognised in work.",
+                Negative(On::Oneself, Trait::Career, 5.0),
+            ),
+        ]);
+
+        let dhana_houses = [1, 2, 5, 9, 11];
+        for (i, &a) in dhana_houses.iter().enumerate() {
+            for &b in &dhana_houses[i + 1..] {
+                catalog.push(yoga(
+                    format!("Dhana Yoga ({} and {} lords)", ordinal(a), ordinal(b)),
+                    Dhana,
+                    &format!("Lords of the {} and {} associated", ordinal(a), ordinal(b)),
+                    lords_associated(a, b),
+                    "Wealth through the affairs of both houses.",
+                    Positive(On::Oneself, Trait::Wealth, 7.0),
+                ));
             }
         }
 
-        // Additional yogas can be calculated here following Vedic astrology principles
+        for kendra in KENDRAS {
+            for trikona in [1, 5, 9] {
+                if kendra == trikona {
+                    continue;
+                }
+                let name = if (kendra, trikona) == (10, 9) {
+                    "Dharma-Karmadhipati Yoga".to_string()
+                } else {
+                    format!(
+                        "Raja Yoga ({} and {} lords)",
+                        ordinal(kendra),
+                        ordinal(trikona)
+                    )
+                };
+                catalog.push(yoga(
+                    name,
+                    Raja,
+                    &format!(
+                        "Lords of the {} and {} associated",
+                        ordinal(kendra),
+                        ordinal(trikona)
+                    ),
+                    lords_associated(kendra, trikona),
+                    "Rise in status, authority and recognition.",
+                    Positive(On::Oneself, Trait::Power, 8.0),
+                ));
+            }
+        }
+        catalog.push(yoga(
+            "Yogakaraka",
+            Raja,
+            "One planet lords both a kendra and a trikona other than the lagna",
+            YogaRule::Any(
+                [4, 7, 10]
+                    .iter()
+                    .flat_map(|&kendra| {
+                        [5, 9].map(|trikona| {
+                            YogaRule::Same(Subject::LordOf(kendra), Subject::LordOf(trikona))
+                        })
+                    })
+                    .collect(),
+            ),
+            "A single planet that brings power and fortune in its periods.",
+            Positive(On::Oneself, Trait::Power, 7.0),
+        ));
+
+        for a in 1..=12u8 {
+            for b in a + 1..=12 {
+                let (kind, impact) = if DUSTHANAS.contains(&a) || DUSTHANAS.contains(&b) {
+                    ("Dainya", Negative(On::Oneself, Trait::Happiness, 5.0))
+                } else if a == 3 || b == 3 {
+                    ("Khala", Neutral(On::Oneself, Trait::Courage, 4.0))
+                } else {
+                    ("Maha", Positive(On::Oneself, Trait::Luck, 7.0))
+                };
+                catalog.push(yoga(
+                    format!(
+                        "{} Parivartana Yoga ({} and {})",
+                        kind,
+                        ordinal(a),
+                        ordinal(b)
+                    ),
+                    Parivartana,
+                    &format!(
+                        "Lords of the {} and {} in each other's signs",
+                        ordinal(a),
+                        ordinal(b)
+                    ),
+                    YogaRule::All(vec![
+                        not(YogaRule::Same(Subject::LordOf(a), Subject::LordOf(b))),
+                        YogaRule::Exchange(Subject::LordOf(a), Subject::LordOf(b)),
+                    ]),
+                    "The two houses lend each other their results.",
+                    impact,
+                ));
+            }
+        }
 
-        yogas
-    }
-}
+        for body in SEVEN {
+            let exaltation_lord = body
+                .exaltation_sign()
+                .map(|sign| sign.lord())
+                .unwrap_or(body);
+            catalog.push(yoga(
+                format!("Neecha Bhanga Raja Yoga ({:?})", body),
+                NeechaBhanga,
+                &format!(
+                    "{:?} debilitated, with its dispositor or exaltation lord in a kendra from \
+                     the lagna or the Moon",
+                    body
+                ),
+                YogaRule::All(vec![
+                    has_dignity(planet(body), &[Dignity::Debilitated]),
+                    YogaRule::Any(vec![
+                        in_house(Subject::Dispositor(body), Reference::Lagna, &KENDRAS),
+                        in_house(Subject::Dispositor(body), moon, &KENDRAS),
+                        in_house(planet(exaltation_lord), Reference::Lagna, &KENDRAS),
+                        in_house(planet(exaltation_lord), moon, &KENDRAS),
+                    ]),
+                ]),
+                "Success that comes after early setbacks.",
+                Positive(On::Oneself, Trait::Resilience, 7.0),
+            ));
+        }
 
+        catalog.extend([
+            yoga(
+                "Lakshmi Yoga",
+                Dhana,
+                "Lagna lord strong and the 9th lord in a kendra or trikona in its own or \
+                 exaltation sign",
+                YogaRule::All(vec![
+                    has_dignity(Subject::LordOf(1), &strong),
+                    has_dignity(Subject::LordOf(9), &strong),
+                    in_house(Subject::LordOf(9), Reference::Lagna, &[1, 4, 5, 7, 9, 10]),
+                ]),
+                "Wealth, nobility and the favour of fortune.",
+                Positive(On::Oneself, Trait::Wealth, 9.0),
+            ),
+            yoga(
+                "Saraswati Yoga",
+                Other,
+                "Mercury, Jupiter and Venus in kendras, trikonas or the 2nd",
+                all_in(Reference::Lagna, &[1, 2, 4, 5, 7, 9, 10], &BENEFICS),
+                "Learning, eloquence and skill in the arts.",
+                Positive(On::Oneself, Trait::Education, 8.0),
+            ),
+            yoga(
+                "Parvata Yoga",
+                Other,
+                "Benefics in kendras and no malefic in the 6th or 8th",
+                YogaRule::All(vec![
+                    occupied(Reference::Lagna, &KENDRAS, &BENEFICS),
+                    not(occupied(Reference::Lagna, &[6, 8], &MALEFICS)),
+                ]),
+                "Prosperity, charity and a commanding presence.",
+                Positive(On::Oneself, Trait::Fame, 6.0),
+            ),
+            yoga(
+                "Kahala Yoga",
+                Other,
+                "Lords of the 4th and 9th in kendras from each other and the lagna lord strong",
+                YogaRule::All(vec![
+                    in_house(
+                        Subject::LordOf(4),
+                        Reference::Subject(Subject::LordOf(9)),
+                        &KENDRAS,
+                    ),
+                    has_dignity(Subject::LordOf(1), &strong),
+                ]),
+                "Boldness and command of a following.",
+                Positive(On::Oneself, Trait::Courage, 6.0),
+            ),
+            yoga(
+                "Shubha Kartari Yoga",
+                Other,
+                "Benefics on both sides of the lagna",
+                YogaRule::All(vec![
+                    occupied(Reference::Lagna, &[2], &BENEFICS),
+                    occupied(Reference::Lagna, &[12], &BENEFICS),
+                ]),
+                "A protected, healthy and prosperous life.",
+                Positive(On::Oneself, Trait::Health, 6.0),
+            ),
+            yoga(
+                "Papa Kartari Yoga",
+                Arishta,
+                "Malefics on both sides of the lagna",
+                YogaRule::All(vec![
+                    occupied(Reference::Lagna, &[2], &MALEFICS),
+                    occupied(Reference::Lagna, &[12], &MALEFICS),
+                ]),
+                "A life hemmed in by pressures and obstacles.",
+                Negative(On::Oneself, Trait::Health, 6.0),
+            ),
+            yoga(
+                "Lagnadhi Yoga",
+                Other,
+                "Mercury, Jupiter and Venus in the 6th, 7th and 8th from the lagna",
+                all_in(Reference::Lagna, &[6, 7, 8], &BENEFICS),
+                "High office and a long, happy life.",
+                Positive(On::Oneself, Trait::Leadership, 7.0),
+            ),
+            yoga(
+                "Pravrajya Yoga",
+                Other,
+                "Four or more planets in one sign",
+                YogaRule::Cluster(4),
+                "Renunciation or a strong pull towards spiritual life.",
+                Positive(On::Oneself, Trait::Spirituality, 6.0),
+            ),
+        ]);
+
+        for (name, signs) in [
+            ("Vallaki", 7),
+            ("Dama", 6),
+            ("Pasha", 5),
+            ("Kedara", 4),
+            ("Shoola", 3),
+            ("Yuga", 2),
+            ("Gola", 1),
+        ] {
+            catalog.push(yoga(
+                format!("{} Yoga", name),
+                Nabhasa,
+                &format!("The seven planets in {} signs", signs),
+                YogaRule::SignsOccupied(signs),
+                "Shapes the general tenor of life.",
+                Neutral(On::Oneself, Trait::Adaptability, 3.0),
+            ));
+        }
 
-#[derive(Debug)]
-pub struct CalculationError {
-    pub message: String,
-}
-pub type CalculationResult<T> = Result<T, CalculationError>;
+        for (name, signs, nature) in [
+            (
+                "Rajju",
+                [
+                    ZodiacSign::Aries,
+                    ZodiacSign::Cancer,
+                    ZodiacSign::Libra,
+                    ZodiacSign::Capricorn,
+                ],
+                "movable",
+            ),
+            (
+                "Musala",
+                [
+                    ZodiacSign::Taurus,
+                    ZodiacSign::Leo,
+                    ZodiacSign::Scorpio,
+                    ZodiacSign::Aquarius,
+                ],
+                "fixed",
+            ),
+            (
+                "Nala",
+                [
+                    ZodiacSign::Gemini,
+                    ZodiacSign::Virgo,
+                    ZodiacSign::Sagittarius,
+                    ZodiacSign::Pisces,
+                ],
+                "dual",
+            ),
+        ] {
+            catalog.push(yoga(
+                format!("{} Yoga", name),
+                Nabhasa,
+                &format!("The seven planets in {} signs", nature),
+                YogaRule::AllInSigns {
+                    planets: SEVEN.to_vec(),
+                    signs: signs.to_vec(),
+                },
+                "Shapes the general tenor of life.",
+                Neutral(On::Oneself, Trait::Adaptability, 3.0),
+            ));
+        }
 
-impl CalculationError {
-    pub fn new(message: String) -> Self {
-        CalculationError { message }
+        catalog.extend([
+            yoga(
+                "Mala Yoga",
+                Nabhasa,
+                "Mercury, Jupiter and Venus all in kendras",
+                all_in(Reference::Lagna, &KENDRAS, &BENEFICS),
+                "Comforts, vehicles and a happy home.",
+                Positive(On::Oneself, Trait::Happiness, 5.0),
+            ),
+            yoga(
+                "Sarpa Yoga",
+                Nabhasa,
+                "The Sun, Mars and Saturn all in kendras",
+                all_in(Reference::Lagna, &KENDRAS, &[Sun, Mars, Saturn]),
+                "Hardship and dependence on others.",
+                Negative(On::Oneself, Trait::Happiness, 5.0),
+            ),
+        ]);
+
+        for (name, houses) in [
+            ("Yupa", [1, 2, 3, 4]),
+            ("Shara", [4, 5, 6, 7]),
+            ("Shakti", [7, 8, 9, 10]),
+            ("Danda", [10, 11, 12, 1]),
+            ("Kamala", KENDRAS),
+        ] {
+            catalog.push(yoga(
+                format!("{} Yoga", name),
+                Nabhasa,
+                &format!("The seven planets in houses {:?}", houses),
+                all_in(Reference::Lagna, &houses, &SEVEN),
+                "Shapes the general tenor of life.",
+                Neutral(On::Oneself, Trait::Adaptability, 3.0),
+            ));
+        }
+        catalog.extend([
+            yoga(
+                "Vapi Yoga",
+                Nabhasa,
+                "The seven planets in the succedent or the cadent houses",
+                YogaRule::Any(vec![
+                    all_in(Reference::Lagna, &[2, 5, 8, 11], &SEVEN),
+                    all_in(Reference::Lagna, &[3, 6, 9, 12], &SEVEN),
+                ]),
+                "Wealth that is saved rather than spent.",
+                Neutral(On::Oneself, Trait::Wealth, 3.0),
+            ),
+            yoga(
+                "Chakra Yoga",
+                Nabhasa,
+                "The seven planets in the six odd houses",
+                all_in(Reference::Lagna, &[1, 3, 5, 7, 9, 11], &SEVEN),
+                "Rule and command over others.",
+                Positive(On::Oneself, Trait::Power, 5.0),
+            ),
+            yoga(
+                "Samudra Yoga",
+                Nabhasa,
+                "The seven planets in the six even houses",
+                all_in(Reference::Lagna, &[2, 4, 6, 8, 10, 12], &SEVEN),
+                "Riches and enjoyment like an ocean's.",
+                Positive(On::Oneself, Trait::Wealth, 5.0),
+            ),
+        ]);
+
+        catalog
     }
 }
 
-impl std::fmt::Display for CalculationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
+fn lords_associated(a: u8, b: u8) -> YogaRule {
+    YogaRule::All(vec![
+        not(YogaRule::Same(Subject::LordOf(a), Subject::LordOf(b))),
+        YogaRule::Associated(Subject::LordOf(a), Subject::LordOf(b)),
+    ])
 }
 
-impl std::error::Error for CalculationError {
-    fn description(&self) -> &str {
-        &self.message
-    }
+pub(crate) fn ordinal(house: u8) -> String {
+    let suffix = match house {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", house, suffix)
 }
 
- 
+// ---------------------------
+// ## Evaluation
+// ---------------------------
 
-impl<T> Into<CalculationResult<T>> for CalculationError {
-    fn into(self) -> CalculationResult<T> {
-        Err(self)
-    }
+/// A chart seen through whole-sign houses from its lagna.
+struct YogaChart<'a> {
+    chart: &'a ChartInfo,
+    lagna: ZodiacSign,
 }
 
+impl YogaChart<'_> {
+    fn position(&self, body: CelestialBody) -> Option<&PlanetPosition> {
+        self.chart.planets.iter().find(|p| p.planet == body)
+    }
 
+    fn resolve(&self, subject: Subject) -> Option<CelestialBody> {
+        match subject {
+            Subject::Planet(body) => Some(body),
+            Subject::LordOf(house) => Some(self.sign_of_house(self.lagna, house).lord()),
+            Subject::Dispositor(body) => self.position(body).map(|p| p.sign.lord()),
+        }
+    }
 
+    fn sign(&self, subject: Subject) -> Option<ZodiacSign> {
+        self.resolve(subject)
+            .and_then(|body| self.position(body))
+            .map(|p| p.sign)
+    }
 
+    fn sign_of_house(&self, from: ZodiacSign, house: u8) -> ZodiacSign {
+        ZodiacSign::from_longitude((from as usize + house as usize - 1) as f64 * 30.0)
+    }
 
-pub type AstrologicalCalculation = Arc<dyn FnMut(&mut Report) -> CalculationResult<()>>;
-
-
-
+    fn reference_sign(&self, from: Reference) -> Option<ZodiacSign> {
+        match from {
+            Reference::Lagna => Some(self.lagna),
+            Reference::Subject(subject) => self.sign(subject),
+        }
+    }
 
-#[derive(Clone)]
-pub struct YogaDefinition {
-    pub name: String,
-    pub description: String, // explains when a yoga is considered to be active
-    pub reference: String,
-    pub calculation: AstrologicalCalculation,
-    pub impacts: Impacts,
-}
+    fn house_of(&self, from: Reference, sign: ZodiacSign) -> Option<u8> {
+        self.reference_sign(from)
+            .map(|start| ((sign as usize + 12 - start as usize) % 12) as u8 + 1)
+    }
 
-#[derive(Clone)]
-pub struct Impacts(Vec<Impact>);
+    fn in_houses(&self, body: CelestialBody, from: Reference, houses: &[u8]) -> bool {
+        self.position(body)
+            .and_then(|p| self.house_of(from, p.sign))
+            .is_some_and(|house| houses.contains(&house))
+    }
 
-#[derive(Clone)]
-pub struct Impact {
-    pub realm: Realm,
-    pub weight: i8, // -100 to 100
-    pub description: String,
-}
+    fn dignities(&self, body: CelestialBody) -> Vec<Dignity> {
+        let Some(position) = self.position(body) else {
+            return Vec::new();
+        };
+        let mut dignities = Vec::new();
+        if body.exaltation_sign() == Some(position.sign) {
+            dignities.push(Dignity::Exalted);
+        }
+        if body.debilitation_sign() == Some(position.sign) {
+            dignities.push(Dignity::Debilitated);
+        }
+        if body.moolatrikona_sign() == Some(position.sign) {
+            dignities.push(Dignity::Moolatrikona);
+        }
+        if !matches!(body, CelestialBody::Rahu | CelestialBody::Ketu)
+            && position.sign.lord() == body
+        {
+            dignities.push(Dignity::OwnSign);
+        }
+        dignities
+    }
 
- 
-
-
- macro_rules! yoga {
-     ($name:ident, $description:expr, $reference:expr, $calculation:expr) => {
-       const  $name: YogaDefinition =  YogaDefinition {
-             name: stringify!($name).to_string(),
-             description: $description.to_string(),
-             reference: $reference.to_string(),
-             calculation: $calculation,
-         }
-     };
- }
-
-
- yoga!(GajakesariYoga, 
-    "As Jupiter is in Kendra from Moon, it is considered to be in a state of joy and prosperity. This yoga is considered to be very auspicious and is associated with good fortune and success in life.",
-    "Brihat Jataka",
-    |report: &mut Report| {
-        let mut yogas = Vec::new();
-
-        if let Some(jupiter) = report.charts[0].planets.iter().find(|p| p.planet == CelestialBody::Jupiter) {
-            if let Some(moon) = report.charts[0].planets.iter().find(|p| p.planet == CelestialBody::Moon) {
-                if jupiter.house == moon.house {
-                    yogas.push(YogaInfo {
-                        yoga: Yoga::Gajakesari, 
-                        strength: 1.0,
-                        involved_planets: vec![CelestialBody::Jupiter, CelestialBody::Moon],
-                    });
+    fn holds(&self, rule: &YogaRule) -> bool {
+        match rule {
+            YogaRule::All(rules) => rules.iter().all(|rule| self.holds(rule)),
+            YogaRule::Any(rules) => rules.iter().any(|rule| self.holds(rule)),
+            YogaRule::Not(rule) => !self.holds(rule),
+            YogaRule::Same(a, b) => {
+                let a = self.resolve(*a);
+                a.is_some() && a == self.resolve(*b)
+            }
+            YogaRule::InHouse {
+                subject,
+                from,
+                houses,
+            } => self
+                .resolve(*subject)
+                .is_some_and(|body| self.in_houses(body, *from, houses)),
+            YogaRule::HasDignity { subject, dignities } => {
+                self.resolve(*subject).is_some_and(|body| {
+                    self.dignities(body)
+                        .iter()
+                        .any(|dignity| dignities.contains(dignity))
+                })
+            }
+            YogaRule::Conjunct(a, b) => match (self.sign(*a), self.sign(*b)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+            YogaRule::Associated(a, b) => match (self.sign(*a), self.sign(*b)) {
+                (Some(sign_a), Some(sign_b)) => {
+                    sign_a == sign_b
+                        || (sign_a as usize + 6) % 12 == sign_b as usize
+                        || self.holds(&YogaRule::Exchange(*a, *b))
                 }
+                _ => false,
+            },
+            YogaRule::Exchange(a, b) => match (
+                self.resolve(*a),
+                self.resolve(*b),
+                self.sign(*a),
+                self.sign(*b),
+            ) {
+                (Some(a), Some(b), Some(sign_a), Some(sign_b)) => {
+                    sign_a.lord() == b && sign_b.lord() == a
+                }
+                _ => false,
+            },
+            YogaRule::Occupied {
+                from,
+                houses,
+                planets,
+            } => planets
+                .iter()
+                .any(|&body| self.in_houses(body, *from, houses)),
+            YogaRule::AllIn {
+                from,
+                houses,
+                planets,
+            } => planets
+                .iter()
+                .all(|&body| self.in_houses(body, *from, houses)),
+            YogaRule::AllInSigns { planets, signs } => planets
+                .iter()
+                .all(|&body| self.position(body).is_some_and(|p| signs.contains(&p.sign))),
+            YogaRule::SignsOccupied(count) => {
+                let mut signs: Vec<ZodiacSign> = SEVEN
+                    .iter()
+                    .filter_map(|&body| self.position(body).map(|p| p.sign))
+                    .collect();
+                signs.sort_by_key(|&sign| sign as usize);
+                signs.dedup();
+                signs.len() == *count as usize
+            }
+            YogaRule::Cluster(count) => {
+                let mut per_sign = [0u8; 12];
+                for body in SEVEN {
+                    if let Some(p) = self.position(body) {
+                        per_sign[p.sign as usize] += 1;
+                    }
+                }
+                per_sign.iter().any(|&n| n >= *count)
             }
+            YogaRule::HemmedByNodes => match self.position(CelestialBody::Rahu) {
+                Some(rahu) => {
+                    let sides: Vec<bool> = SEVEN
+                        .iter()
+                        .filter_map(|&body| self.position(body))
+                        .map(|p| p.longitude - rahu.longitude < 180.0)
+                        .collect();
+                    sides.iter().all(|&side| side) || sides.iter().all(|&side| !side)
+                }
+                None => false,
+            },
         }
-
-        Ok(())
     }
-);
-
-
-
-
-
 
+    /// Planets a rule names or, for occupation rules, finds in place. Negated parts name
+    /// planets that must be absent, so they are not counted.
+    fn involved(&self, rule: &YogaRule, planets: &mut Vec<CelestialBody>) {
+        let mut add = |body: Option<CelestialBody>| {
+            if let Some(body) = body {
+                if !planets.contains(&body) {
+                    planets.push(body);
+                }
+            }
+        };
+        match rule {
+            YogaRule::All(rules) => rules.iter().for_each(|rule| self.involved(rule, planets)),
+            YogaRule::Any(rules) => rules
+                .iter()
+                .filter(|rule| self.holds(rule))
+                .for_each(|rule| self.involved(rule, planets)),
+            YogaRule::Not(_) => {}
+            YogaRule::Same(a, b)
+            | YogaRule::Conjunct(a, b)
+            | YogaRule::Associated(a, b)
+            | YogaRule::Exchange(a, b) => {
+                add(self.resolve(*a));
+                add(self.resolve(*b));
+            }
+            YogaRule::InHouse { subject, .. } | YogaRule::HasDignity { subject, .. } => {
+                add(self.resolve(*subject))
+            }
+            YogaRule::Occupied {
+                from,
+                houses,
+                planets: candidates,
+            } => candidates
+                .iter()
+                .filter(|&&body| self.in_houses(body, *from, houses))
+                .for_each(|&body| add(Some(body))),
+            YogaRule::AllIn {
+                planets: members, ..
+            }
+            | YogaRule::AllInSigns {
+                planets: members, ..
+            } => members.iter().for_each(|&body| add(Some(body))),
+            YogaRule::SignsOccupied(_) | YogaRule::Cluster(_) | YogaRule::HemmedByNodes => {}
+        }
+    }
 
+    /// Dignity of a planet as a share of full strength: exalted 1, moolatrikona 0.9, own
+    /// sign 0.8, debilitated 0.25 and anything else 0.5, halved when combust.
+    fn planet_strength(&self, body: CelestialBody) -> f64 {
+        let dignities = self.dignities(body);
+        let base = if dignities.contains(&Dignity::Exalted) {
+            1.0
+        } else if dignities.contains(&Dignity::Moolatrikona) {
+            0.9
+        } else if dignities.contains(&Dignity::OwnSign) {
+            0.8
+        } else if dignities.contains(&Dignity::Debilitated) {
+            0.25
+        } else {
+            0.5
+        };
 
-    
+        if self.chart.is_combust(body, &CombustionOrbs::default()) {
+            base * 0.5
+        } else {
+            base
+        }
+    }
+}
 
+impl ChartInfo {
+    /// Every yoga of [`Yoga::catalog`] formed in this chart. Cancelled yogas are kept,
+    /// flagged and with no strength, so their cancellation can be shown.
+    pub fn yogas(&self) -> Vec<YogaInfo> {
+        let chart = YogaChart {
+            chart: self,
+            lagna: self.ascendant.sign,
+        };
 
+        Yoga::catalog()
+            .into_iter()
+            .filter(|yoga| chart.holds(&yoga.condition.rule))
+            .map(|yoga| {
+                let mut involved_planets = Vec::new();
+                chart.involved(&yoga.condition.rule, &mut involved_planets);
+                let cancelled = yoga
+                    .cancellation
+                    .as_ref()
+                    .is_some_and(|cancellation| chart.holds(&cancellation.rule));
+                let strength = if cancelled || involved_planets.is_empty() {
+                    if cancelled {
+                        0.0
+                    } else {
+                        0.5
+                    }
+                } else {
+                    involved_planets
+                        .iter()
+                        .map(|&body| chart.planet_strength(body))
+                        .sum::<f64>()
+                        / involved_planets.len() as f64
+                };
+                YogaInfo {
+                    yoga,
+                    strength,
+                    involved_planets,
+                    cancelled,
+                }
+            })
+            .collect()
+    }
+}
 
+#[cfg(not(feature = "model-only"))]
+impl SwissEph {
+    /// The yogas formed in `chart`; see [`ChartInfo::yogas`].
+    pub fn calculate_yogas(&self, chart: &ChartInfo) -> Vec<YogaInfo> {
+        chart.yogas()
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CelestialBody::*;
+
+    /// Aries rising, with the Sun exalted in the 1st, Mars in Leo in the 5th, so the lords of
+    /// the 1st and 5th exchange signs, Venus in its own Libra in the 7th, and Jupiter
+    /// debilitated beside Saturn in its own Capricorn in the 10th.
+    fn chart() -> ChartInfo {
+        let position = |planet, longitude: f64| PlanetPosition {
+            planet,
+            longitude: Longitude::new(longitude),
+            latitude: 0.0,
+            speed: 1.0,
+            sign: ZodiacSign::from_longitude(longitude),
+            house: House::First,
+            nakshatra: NakshatraInfo::from_longitude(longitude),
+            retrograde: false,
+        };
+        ChartInfo {
+            chart_type: ChartType::Rasi,
+            house_system: HouseSystem::WholeSign,
+            ascendant: HouseCusp {
+                house: House::First,
+                sign: ZodiacSign::Aries,
+                degree: 5.0,
+            },
+            houses: Vec::new(),
+            planets: vec![
+                position(Sun, 10.0),
+                position(Moon, 20.0),
+                position(Mars, 130.0),
+                position(Mercury, 70.0),
+                position(Jupiter, 280.0),
+                position(Venus, 200.0),
+                position(Saturn, 290.0),
+                position(Rahu, 100.0),
+                position(Ketu, 280.0),
+            ],
+            warnings: Vec::new(),
+        }
+    }
 
+    fn find<'a>(yogas: &'a [YogaInfo], name: &str) -> Option<&'a YogaInfo> {
+        yogas.iter().find(|info| info.yoga.name == name)
+    }
 
+    #[test]
+    fn lords_of_houses_form_raja_yogas() {
+        let yogas = chart().yogas();
+
+        // Saturn lords the 10th and Jupiter the 9th, and they share Capricorn
+        let dharma_karma = find(&yogas, "Dharma-Karmadhipati Yoga").unwrap();
+        assert_eq!(dharma_karma.involved_planets, vec![Saturn, Jupiter]);
+        assert!(!dharma_karma.cancelled);
+        // Mars and the Sun in each other's signs are associated too
+        assert!(find(&yogas, "Raja Yoga (1st and 5th lords)").is_some());
+        // Venus lords the 7th but is with neither trikona lord
+        assert!(find(&yogas, "Raja Yoga (7th and 9th lords)").is_none());
+        // No planet lords both a kendra and a trikona for Aries rising
+        assert!(find(&yogas, "Yogakaraka").is_none());
+
+        assert!(find(&yogas, "Sasa Yoga").is_some());
+        assert!(find(&yogas, "Malavya Yoga").is_some());
+        assert!(find(&yogas, "Hamsa Yoga").is_none());
+    }
 
+    #[test]
+    fn an_exchange_forms_a_parivartana_for_each_pair_of_houses_it_lords() {
+        let yogas = chart().yogas();
+        let mut exchanges: Vec<&str> = yogas
+            .iter()
+            .filter(|info| info.yoga.category == YogaCategory::Parivartana)
+            .map(|info| info.yoga.name.as_str())
+            .collect();
+        exchanges.sort();
+        // Mars lords the 1st and the 8th, so its exchange with the Sun joins both to the 5th
+        assert_eq!(
+            exchanges,
+            [
+                "Dainya Parivartana Yoga (5th and 8th)",
+                "Maha Parivartana Yoga (1st and 5th)",
+            ]
+        );
+    }
 
+    #[test]
+    fn cancelled_yogas_are_kept_with_no_strength() {
+        let yogas = chart().yogas();
 
+        // Jupiter in the 10th from the Moon, but debilitated
+        let gajakesari = find(&yogas, "Gajakesari Yoga").unwrap();
+        assert!(gajakesari.cancelled);
+        assert_eq!(gajakesari.strength, 0.0);
 
+        // Nothing in Taurus or Pisces beside the Moon, but planets in kendras from it
+        let kemadruma = find(&yogas, "Kemadruma Yoga").unwrap();
+        assert!(kemadruma.cancelled);
 
+        // Jupiter's debility is itself cancelled by Saturn, its dispositor, in the 10th
+        let neecha_bhanga = find(&yogas, "Neecha Bhanga Raja Yoga (Jupiter)").unwrap();
+        assert!(!neecha_bhanga.cancelled);
+        assert!(find(&yogas, "Neecha Bhanga Raja Yoga (Saturn)").is_none());
+    }
 
-         
- 
\ No newline at end of file
+    #[test]
+    fn strength_is_the_mean_dignity_of_the_planets_involved() {
+        let yogas = chart().yogas();
+        let strength = |name| find(&yogas, name).unwrap().strength;
+
+        // Saturn in its own sign alone
+        assert_eq!(strength("Sasa Yoga"), 0.8);
+        // The exalted Sun and Mars in a neutral sign
+        assert_eq!(strength("Maha Parivartana Yoga (1st and 5th)"), 0.75);
+        // Saturn in its own sign and Jupiter debilitated
+        assert_eq!(strength("Dharma-Karmadhipati Yoga"), 0.525);
+
+        // Venus in Libra, its moolatrikona, counts for half when burnt up by the Sun
+        assert_eq!(strength("Malavya Yoga"), 0.9);
+        let mut combust = chart();
+        combust.planets[0] = PlanetPosition {
+            longitude: Longitude::new(195.0),
+            sign: ZodiacSign::Libra,
+            nakshatra: NakshatraInfo::from_longitude(195.0),
+            ..combust.planets[0].clone()
+        };
+        let yogas = combust.yogas();
+        assert_eq!(find(&yogas, "Malavya Yoga").unwrap().strength, 0.45);
+    }
+}